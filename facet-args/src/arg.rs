@@ -85,3 +85,21 @@ pub(crate) fn extract_subspan<'a>(subspan: &Subspan, arg: &'a str) -> &'a str {
     let end = core::cmp::min(subspan.offset + subspan.len, arg.len());
     &arg[subspan.offset..end]
 }
+
+/// Converts a field or variant name to the kebab-case used on the command line
+pub(crate) fn to_kebab(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (idx, c) in name.char_indices() {
+        if c == '_' {
+            out.push('-');
+        } else if c.is_uppercase() {
+            if idx > 0 && !out.ends_with('-') {
+                out.push('-');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
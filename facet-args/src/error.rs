@@ -0,0 +1,51 @@
+use alloc::string::String;
+use core::fmt;
+use facet_deserialize::DeserError;
+
+use crate::help::hint;
+
+/// Why a command line did not produce a value
+pub enum ArgsError<'input, 'shape> {
+    /// `--help` or `-h` was given: this is the help text of the command it was given to
+    Help(String),
+
+    /// `--version` was given: this is the program name followed by its version
+    Version(String),
+
+    /// The arguments could not be parsed
+    Invalid(DeserError<'input, 'shape>),
+}
+
+impl ArgsError<'_, '_> {
+    /// The status to exit with after printing this: 0 for help and version, 2 otherwise
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ArgsError::Help(_) | ArgsError::Version(_) => 0,
+            ArgsError::Invalid(_) => 2,
+        }
+    }
+}
+
+impl fmt::Display for ArgsError<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgsError::Help(help) => write!(f, "{help}"),
+            ArgsError::Version(version) => writeln!(f, "{version}"),
+            ArgsError::Invalid(err) => {
+                write!(f, "{err}")?;
+                if let Some(hint) = hint(&err.kind) {
+                    write!(f, "\n{hint}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ArgsError<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl core::error::Error for ArgsError<'_, '_> {}
@@ -1,23 +1,174 @@
 use alloc::borrow::Cow;
 use alloc::string::ToString;
-use facet_core::{FieldAttribute, Shape, Type, UserType};
+use facet_core::{
+    Def, EnumType, Field, FieldAttribute, Shape, StructKind, Type, UserType, Variant,
+};
 use facet_deserialize::{
     DeserErrorKind, Outcome, Raw, Scalar, Span, Spanned, Subspan, SubspanMeta,
 };
 use facet_reflect::Partial;
 
+use crate::arg::to_kebab;
+
+/// Whether a field has an arbitrary attribute, like `positional` or `subcommand`
+pub(crate) fn has_attr(field: &Field<'_>, name: &str) -> bool {
+    field
+        .attributes
+        .iter()
+        .any(|attr| matches!(attr, FieldAttribute::Arbitrary(a) if a.contains(name)))
+}
+
+/// The short flag of a field: `short = 'v'`, or its name if that's a single letter and the
+/// attribute is just `short`
+pub(crate) fn short_flag(field: &Field<'_>) -> Option<char> {
+    field.attributes.iter().find_map(|attr| {
+        let FieldAttribute::Arbitrary(a) = attr else {
+            return None;
+        };
+        let rest = a.strip_prefix("short")?.trim();
+        let value = match rest.strip_prefix('=') {
+            Some(value) => value.trim().trim_matches(|c| c == '\'' || c == '"'),
+            None if rest.is_empty() => field.name,
+            None => return None,
+        };
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    })
+}
+
+/// The fields that arguments are matched against: those of a struct, or those of the selected
+/// variant of a subcommand
+pub(crate) fn current_fields<'facet, 'shape>(
+    shape: &'shape Shape<'shape>,
+    wip: &Partial<'facet, 'shape>,
+) -> &'shape [Field<'shape>] {
+    match &shape.ty {
+        Type::User(UserType::Struct(st)) => st.fields,
+        Type::User(UserType::Enum(_)) => wip
+            .selected_variant()
+            .map(|variant| variant.data.fields)
+            .unwrap_or(&[]),
+        _ => &[],
+    }
+}
+
+/// The enum behind a subcommand field, which may be optional
+pub(crate) fn subcommand_enum<'shape>(shape: &'shape Shape<'shape>) -> Option<EnumType<'shape>> {
+    match (shape.def, shape.ty) {
+        (Def::Option(od), _) => subcommand_enum(od.t),
+        (_, Type::User(UserType::Enum(ed))) => Some(ed),
+        _ => None,
+    }
+}
+
+/// Finds the variant of a subcommand enum named by an argument, in kebab-case or as written
+pub(crate) fn find_command<'shape>(
+    arg: &str,
+    ed: EnumType<'shape>,
+) -> Option<&'shape Variant<'shape>> {
+    ed.variants
+        .iter()
+        .find(|variant| variant.name == arg || to_kebab(variant.name) == arg)
+}
+
+/// Finds the unset subcommand field that an argument names a command of
+pub(crate) fn find_subcommand_field<'facet, 'shape>(
+    arg: &str,
+    shape: &'shape Shape<'shape>,
+    wip: &Partial<'facet, 'shape>,
+) -> Option<&'shape str> {
+    current_fields(shape, wip)
+        .iter()
+        .enumerate()
+        .find(|(idx, field)| {
+            has_attr(field, "subcommand")
+                && !wip.is_field_set(*idx).unwrap_or(false)
+                && subcommand_enum(field.shape())
+                    .and_then(|ed| find_command(arg, ed))
+                    .is_some()
+        })
+        .map(|(_, field)| field.name)
+}
+
+/// Returns the variant named by the first argument of a subcommand
+pub(crate) fn find_command_variant<'shape>(
+    arg: &str,
+    shape: &'shape Shape<'shape>,
+) -> Result<&'shape str, DeserErrorKind<'shape>> {
+    subcommand_enum(shape)
+        .and_then(|ed| find_command(arg, ed))
+        .map(|variant| variant.name)
+        .ok_or_else(|| DeserErrorKind::NoSuchVariant {
+            name: arg.to_string(),
+            enum_shape: shape,
+        })
+}
+
+/// Whether the value being built is a subcommand whose variant hasn't been picked yet
+pub(crate) fn is_unselected_command<'facet, 'shape>(
+    shape: &'shape Shape<'shape>,
+    wip: &Partial<'facet, 'shape>,
+) -> bool {
+    matches!(shape.ty, Type::User(UserType::Enum(_))) && wip.selected_variant().is_none()
+}
+
+/// What the value of a subcommand starts with, and whether it uses up the current argument.
+///
+/// A unit variant is given by its name alone. Any other variant is an object, whose keys are
+/// the arguments that follow its name.
+pub(crate) fn subcommand_value<'facet, 'shape>(
+    shape: &'shape Shape<'shape>,
+    wip: &Partial<'facet, 'shape>,
+    arg_idx: usize,
+    args: &[&str],
+) -> Option<(Outcome<'shape>, bool)> {
+    match (subcommand_enum(shape), wip.selected_variant()) {
+        // The arguments of a struct variant
+        (Some(_), Some(variant)) => {
+            (variant.data.kind == StructKind::Struct).then_some((Outcome::ObjectStarted, false))
+        }
+        (Some(ed), None) => {
+            let variant = find_command(args.get(arg_idx)?, ed)?;
+            if variant.data.fields.is_empty() {
+                let name = Scalar::String(Cow::Borrowed(variant.name));
+                Some((Outcome::Scalar(name), true))
+            } else {
+                Some((Outcome::ObjectStarted, false))
+            }
+        }
+        // The arguments of a newtype variant, right after its name
+        (None, _) => {
+            let is_struct = matches!(
+                (shape.def, shape.ty),
+                (Def::Undefined, Type::User(UserType::Struct(st))) if st.kind == StructKind::Struct
+            );
+            let after_command = arg_idx
+                .checked_sub(1)
+                .and_then(|idx| args.get(idx))
+                .is_some_and(|arg| !arg.starts_with('-'));
+            (is_struct && after_command).then_some((Outcome::ObjectStarted, false))
+        }
+    }
+}
+
 pub(crate) fn validate_field<'facet, 'shape>(
     field_name: &str,
     shape: &'shape Shape<'shape>,
     wip: &Partial<'facet, 'shape>,
 ) -> Result<(), DeserErrorKind<'shape>> {
-    if let Type::User(UserType::Struct(_)) = &shape.ty {
-        if wip.field_index(field_name).is_none() {
-            return Err(DeserErrorKind::UnknownField {
-                field_name: field_name.to_string(),
-                shape,
-            });
-        }
+    let has_fields = match &shape.ty {
+        Type::User(UserType::Struct(_)) => true,
+        Type::User(UserType::Enum(_)) => wip.selected_variant().is_some(),
+        _ => false,
+    };
+    if has_fields && wip.field_index(field_name).is_none() {
+        return Err(DeserErrorKind::UnknownField {
+            field_name: field_name.to_string(),
+            shape,
+        });
     }
     Ok(())
 }
@@ -27,18 +178,12 @@ pub(crate) fn find_positional_field<'facet, 'shape>(
     shape: &'shape Shape<'shape>,
     wip: &Partial<'facet, 'shape>,
 ) -> Result<&'shape str, DeserErrorKind<'shape>> {
-    if let Type::User(UserType::Struct(st)) = &shape.ty {
-        for (idx, field) in st.fields.iter().enumerate() {
-            for attr in field.attributes.iter() {
-                if let FieldAttribute::Arbitrary(a) = attr {
-                    if a.contains("positional") {
-                        // Check if this field is already set
-                        let is_set = wip.is_field_set(idx).unwrap_or(false);
-                        if !is_set {
-                            return Ok(field.name);
-                        }
-                    }
-                }
+    for (idx, field) in current_fields(shape, wip).iter().enumerate() {
+        if has_attr(field, "positional") {
+            // Check if this field is already set
+            let is_set = wip.is_field_set(idx).unwrap_or(false);
+            if !is_set {
+                return Ok(field.name);
             }
         }
     }
@@ -53,11 +198,9 @@ pub(crate) fn find_unset_bool_field<'facet, 'shape>(
     shape: &'shape Shape<'shape>,
     wip: &Partial<'facet, 'shape>,
 ) -> Option<&'shape str> {
-    if let Type::User(UserType::Struct(st)) = &shape.ty {
-        for (idx, field) in st.fields.iter().enumerate() {
-            if !wip.is_field_set(idx).unwrap_or(false) && field.shape().is_type::<bool>() {
-                return Some(field.name);
-            }
+    for (idx, field) in current_fields(shape, wip).iter().enumerate() {
+        if !wip.is_field_set(idx).unwrap_or(false) && field.shape().is_type::<bool>() {
+            return Some(field.name);
         }
     }
     None
@@ -76,20 +219,20 @@ pub(crate) fn handle_unset_bool_field_error<'shape>(
     })
 }
 
-pub(crate) fn find_field_by_short_flag<'shape>(
+pub(crate) fn find_field_by_short_flag<'facet, 'shape>(
     key: &str,
     shape: &'shape Shape<'shape>,
+    wip: &Partial<'facet, 'shape>,
 ) -> Result<&'shape str, DeserErrorKind<'shape>> {
+    let mut chars = key.chars();
+    let key_char = match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    };
     match &shape.ty {
-        Type::User(UserType::Struct(st)) => st
-            .fields
+        Type::User(UserType::Struct(_) | UserType::Enum(_)) => current_fields(shape, wip)
             .iter()
-            .find(|field| {
-                field.attributes.iter().any(|attr| {
-                    matches!(attr, FieldAttribute::Arbitrary(a) if a.contains("short") &&
-                            (a.contains(key) || (key.len() == 1 && field.name == key)))
-                })
-            })
+            .find(|field| key_char.is_some() && short_flag(field) == key_char)
             .map(|field| field.name)
             .ok_or_else(|| DeserErrorKind::UnknownField {
                 field_name: key.to_string(),
@@ -1,5 +1,7 @@
 use crate::arg::{ArgType, extract_subspan};
+use crate::error::ArgsError;
 use crate::fields::*;
+use crate::help::find_request;
 use crate::parse::parse_scalar;
 use crate::results::*;
 use alloc::borrow::Cow;
@@ -44,6 +46,53 @@ where
     from_slice(Box::leak(args_str.into_boxed_slice()))
 }
 
+/// Parse command line arguments like [`from_slice`], answering `--help` and `-h` with the help
+/// of the (sub)command they're given to, and `--version` if a version is given.
+///
+/// `program` is the name of the program, shown in the help and version texts.
+pub fn from_slice_with_help<'input, 'facet, 'shape, T: Facet<'facet>>(
+    program: &str,
+    version: Option<&str>,
+    args: &'input [&'input str],
+) -> Result<T, ArgsError<'input, 'shape>>
+where
+    'input: 'facet + 'shape,
+{
+    if let Some(request) = find_request(T::SHAPE, program, version, args) {
+        return Err(request);
+    }
+    from_slice(args).map_err(ArgsError::Invalid)
+}
+
+/// Parse the arguments the program was started with into a Facet-compatible type.
+///
+/// Prints the help or the version and exits when they're asked for, and prints the error,
+/// with the valid flags, and exits with status 2 when the arguments are invalid.
+pub fn parse_std_args<T: Facet<'static>>(version: Option<&str>) -> T {
+    let mut args = std::env::args();
+    let program = args.next().unwrap_or_default();
+    let program = std::path::Path::new(&program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(&program)
+        .to_string();
+    let args_str: Vec<&'static str> = args
+        .map(|s| Box::leak(s.into_boxed_str()) as &str)
+        .collect();
+
+    match from_slice_with_help(&program, version, Box::leak(args_str.into_boxed_slice())) {
+        Ok(value) => value,
+        Err(err @ ArgsError::Invalid(_)) => {
+            eprintln!("{err}");
+            std::process::exit(err.exit_code());
+        }
+        Err(err) => {
+            print!("{err}");
+            std::process::exit(err.exit_code());
+        }
+    }
+}
+
 impl Format for Cli {
     type Input<'input> = [&'input str];
     type SpanType = Raw;
@@ -112,30 +161,40 @@ impl Format for Cli {
                         arg
                     };
 
-                    // Parse the argument type
-                    match ArgType::parse(effective_arg) {
-                        ArgType::LongFlag(key) => {
-                            // Validate field exists
-                            wrap_string_result(
-                                validate_field(&key, shape, &nd.wip).map(|_| key),
-                                if has_subspans { stay_put } else { span },
-                            )
-                        }
-                        ArgType::ShortFlag(key) => {
-                            // Convert short argument to field name via shape
-                            wrap_field_result(
-                                find_field_by_short_flag(key, shape),
-                                if has_subspans { stay_put } else { span },
-                            )
-                        }
-                        ArgType::Positional => {
-                            // Handle positional argument
-                            wrap_field_result(find_positional_field(shape, &nd.wip), stay_put)
-                        }
-                        ArgType::None => {
-                            // Handle empty argument (shouldn't happen normally)
-                            let err = create_unknown_field_error("empty argument", shape);
-                            Err(Spanned { node: err, span })
+                    if is_unselected_command(shape, &nd.wip) {
+                        // The first argument of a subcommand names its variant
+                        wrap_field_result(find_command_variant(effective_arg, shape), span)
+                    } else {
+                        // Parse the argument type
+                        match ArgType::parse(effective_arg) {
+                            ArgType::LongFlag(key) => {
+                                // Validate field exists
+                                wrap_string_result(
+                                    validate_field(&key, shape, &nd.wip).map(|_| key),
+                                    if has_subspans { stay_put } else { span },
+                                )
+                            }
+                            ArgType::ShortFlag(key) => {
+                                // Convert short argument to field name via shape
+                                wrap_field_result(
+                                    find_field_by_short_flag(key, shape, &nd.wip),
+                                    if has_subspans { stay_put } else { span },
+                                )
+                            }
+                            ArgType::Positional => {
+                                // A subcommand takes all the arguments that follow it
+                                let field =
+                                    match find_subcommand_field(effective_arg, shape, &nd.wip) {
+                                        Some(field) => Ok(field),
+                                        None => find_positional_field(shape, &nd.wip),
+                                    };
+                                wrap_field_result(field, stay_put)
+                            }
+                            ArgType::None => {
+                                // Handle empty argument (shouldn't happen normally)
+                                let err = create_unknown_field_error("empty argument", shape);
+                                Err(Spanned { node: err, span })
+                            }
                         }
                     }
                 } else {
@@ -146,8 +205,19 @@ impl Format for Cli {
 
             // Value for the current key
             Expectation::ObjectVal => {
+                let subcommand = if has_subspans {
+                    None
+                } else {
+                    subcommand_value(shape, &nd.wip, arg_idx, args)
+                };
+
                 // Determine what to do based on the type and available arguments
-                if shape.is_type::<bool>() {
+                if let Some((outcome, consumed)) = subcommand {
+                    Ok(Spanned {
+                        node: outcome,
+                        span: if consumed { step_forth } else { stay_put },
+                    })
+                } else if shape.is_type::<bool>() {
                     // Handle boolean values (true if we have an arg, false if EOF)
                     let has_arg = arg_idx < args.len();
                     wrap_result(handle_bool_value(has_arg), Outcome::Scalar, stay_put)
@@ -0,0 +1,327 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use facet_core::{Def, Facet, Field, FieldFlags, Shape, StructKind, Type, UserType, Variant};
+use facet_deserialize::DeserErrorKind;
+use facet_reflect::Partial;
+
+use crate::arg::{ArgType, to_kebab};
+use crate::error::ArgsError;
+use crate::fields::{find_command, has_attr, short_flag, subcommand_enum};
+
+/// Generates the `--help` text of a command line parsed into `T`.
+///
+/// The text is made of the doc comment of `T`, a usage line, and the positional arguments,
+/// options and subcommands of `T`, each described by its doc comment, type and default.
+/// `program` is the name shown in the usage line.
+pub fn help<'a, T: Facet<'a>>(program: &str) -> String {
+    Command::of_shape(program.to_string(), T::SHAPE).help(false)
+}
+
+/// Looks for `--help`, `-h` or `--version` in the arguments. Subcommands are followed, so that
+/// the help is that of the subcommand it was asked for.
+pub(crate) fn find_request<'input, 'shape, 's>(
+    shape: &'s Shape<'s>,
+    program: &str,
+    version: Option<&str>,
+    args: &[&str],
+) -> Option<ArgsError<'input, 'shape>> {
+    let mut command = Command::of_shape(program.to_string(), shape);
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--help" || (arg == "-h" && command.flag(arg).is_none()) {
+            return Some(ArgsError::Help(command.help(version.is_some())));
+        }
+        if let Some(version) = version {
+            if arg == "--version" || (arg == "-V" && command.flag(arg).is_none()) {
+                return Some(ArgsError::Version(format!("{program} {version}")));
+            }
+        }
+
+        if let ArgType::Positional = ArgType::parse(arg) {
+            if let Some(variant) = command.subcommand(arg) {
+                command = Command::of_variant(&command.name, variant);
+            }
+        } else if !arg.contains('=') && command.flag(arg).is_some_and(|f| !is_switch(f)) {
+            // Skip the value of the flag, which could be mistaken for a subcommand
+            args.next();
+        }
+    }
+    None
+}
+
+/// Lists what could have been given instead of a wrong argument, for error messages
+pub(crate) fn hint(kind: &DeserErrorKind<'_>) -> Option<String> {
+    let mut out = String::new();
+    match kind {
+        DeserErrorKind::NoSuchVariant { enum_shape, .. } => {
+            let ed = subcommand_enum(enum_shape)?;
+            let names: Vec<String> = ed.variants.iter().map(|v| to_kebab(v.name)).collect();
+            writeln!(out, "Possible values: {}", names.join(", ")).unwrap();
+        }
+        DeserErrorKind::UnknownField { shape, .. } => {
+            let Type::User(UserType::Struct(_)) = shape.ty else {
+                return None;
+            };
+            let command = Command::of_shape(String::new(), shape);
+            let flags: Vec<String> = command
+                .named()
+                .map(|field| match short_flag(field) {
+                    Some(c) => format!("-{c}/--{}", to_kebab(field.name)),
+                    None => format!("--{}", to_kebab(field.name)),
+                })
+                .chain(["-h/--help".to_string()])
+                .collect();
+            writeln!(out, "Valid flags: {}", flags.join(", ")).unwrap();
+            let commands = command.commands();
+            if !commands.is_empty() {
+                let names: Vec<&str> = commands.iter().map(|(name, _)| name.as_str()).collect();
+                writeln!(out, "Valid commands: {}", names.join(", ")).unwrap();
+            }
+        }
+        _ => return None,
+    }
+    out.push_str("For more information, try '--help'.");
+    Some(out)
+}
+
+/// A command, or a subcommand, and the arguments it takes
+struct Command<'shape> {
+    /// The program name, followed by the names of the subcommands leading to this one
+    name: String,
+    doc: &'shape [&'shape str],
+    fields: &'shape [Field<'shape>],
+}
+
+impl<'shape> Command<'shape> {
+    fn of_shape(name: String, shape: &'shape Shape<'shape>) -> Self {
+        let fields = match shape.ty {
+            Type::User(UserType::Struct(st)) => st.fields,
+            _ => &[],
+        };
+        Command {
+            name,
+            doc: shape.doc,
+            fields,
+        }
+    }
+
+    fn of_variant(parent: &str, variant: &'shape Variant<'shape>) -> Self {
+        let name = format!("{parent} {}", to_kebab(variant.name));
+        match variant.data.fields {
+            // The arguments of a newtype variant are those of the struct it holds
+            [field] if variant.data.kind == StructKind::Tuple => {
+                let mut command = Command::of_shape(name, field.shape());
+                if !variant.doc.is_empty() {
+                    command.doc = variant.doc;
+                }
+                command
+            }
+            fields => Command {
+                name,
+                doc: variant.doc,
+                fields,
+            },
+        }
+    }
+
+    fn positionals(&self) -> impl Iterator<Item = &'shape Field<'shape>> {
+        self.fields.iter().filter(|f| has_attr(f, "positional"))
+    }
+
+    fn named(&self) -> impl Iterator<Item = &'shape Field<'shape>> {
+        self.fields
+            .iter()
+            .filter(|f| !has_attr(f, "positional") && !has_attr(f, "subcommand"))
+    }
+
+    fn subcommand_field(&self) -> Option<&'shape Field<'shape>> {
+        self.fields.iter().find(|f| has_attr(f, "subcommand"))
+    }
+
+    /// Finds the field of a `--long` or `-s` flag
+    fn flag(&self, arg: &str) -> Option<&'shape Field<'shape>> {
+        match ArgType::parse(arg) {
+            ArgType::LongFlag(key) => self.fields.iter().find(|f| f.name == key),
+            ArgType::ShortFlag(key) => {
+                let mut chars = key.chars();
+                let c = chars.next().filter(|_| chars.next().is_none())?;
+                self.fields.iter().find(|f| short_flag(f) == Some(c))
+            }
+            _ => None,
+        }
+    }
+
+    /// Finds the variant of a subcommand named by an argument
+    fn subcommand(&self, arg: &str) -> Option<&'shape Variant<'shape>> {
+        let field = self.subcommand_field()?;
+        find_command(arg, subcommand_enum(field.shape())?)
+    }
+
+    /// The names and summaries of the subcommands
+    fn commands(&self) -> Vec<(String, String)> {
+        let Some(ed) = self
+            .subcommand_field()
+            .and_then(|field| subcommand_enum(field.shape()))
+        else {
+            return Vec::new();
+        };
+        ed.variants
+            .iter()
+            .map(|variant| {
+                let doc = Command::of_variant("", variant).doc;
+                (to_kebab(variant.name), summary(doc))
+            })
+            .collect()
+    }
+
+    fn help(&self, with_version: bool) -> String {
+        let mut out = String::new();
+        let about = about(self.doc);
+        if !about.is_empty() {
+            writeln!(out, "{about}\n").unwrap();
+        }
+
+        write!(out, "Usage: {} [OPTIONS]", self.name).unwrap();
+        for field in self.positionals() {
+            write!(out, " {}", positional_name(field)).unwrap();
+        }
+        if let Some(field) = self.subcommand_field() {
+            out.push_str(if is_optional(field) {
+                " [COMMAND]"
+            } else {
+                " <COMMAND>"
+            });
+        }
+        out.push('\n');
+
+        let arguments: Vec<(String, String)> = self
+            .positionals()
+            .map(|field| (positional_name(field), describe(field)))
+            .collect();
+        section(&mut out, "Arguments", &arguments);
+
+        let mut options: Vec<(String, String)> = self
+            .named()
+            .map(|field| (option_name(field), describe(field)))
+            .collect();
+        options.push(("-h, --help".to_string(), "Print help".to_string()));
+        if with_version {
+            options.push(("-V, --version".to_string(), "Print version".to_string()));
+        }
+        section(&mut out, "Options", &options);
+
+        section(&mut out, "Commands", &self.commands());
+        out
+    }
+}
+
+/// Writes a titled list of names and descriptions, with the descriptions aligned
+fn section(out: &mut String, title: &str, entries: &[(String, String)]) {
+    if entries.is_empty() {
+        return;
+    }
+    let width = entries
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    writeln!(out, "\n{title}:").unwrap();
+    for (name, description) in entries {
+        let line = format!("  {name:width$}  {description}");
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+}
+
+/// Bool fields are switches: they take no value
+fn is_switch(field: &Field<'_>) -> bool {
+    field.shape().is_type::<bool>()
+}
+
+fn is_optional(field: &Field<'_>) -> bool {
+    field.flags.contains(FieldFlags::DEFAULT) || matches!(field.shape().def, Def::Option(_))
+}
+
+/// `<PATH>` for a required positional argument, `[PATH]` for an optional one
+fn positional_name(field: &Field<'_>) -> String {
+    let name = field.name.to_uppercase();
+    let repeated = match field.shape().def {
+        Def::List(_) => "...",
+        _ => "",
+    };
+    if is_optional(field) {
+        format!("[{name}]{repeated}")
+    } else {
+        format!("<{name}>{repeated}")
+    }
+}
+
+/// `-j, --concurrency <usize>`, or `    --verbose` for a switch without a short flag
+fn option_name(field: &Field<'_>) -> String {
+    let long = format!("--{}", to_kebab(field.name));
+    let mut name = match short_flag(field) {
+        Some(c) => format!("-{c}, {long}"),
+        None => format!("    {long}"),
+    };
+    if !is_switch(field) {
+        write!(name, " {}", value_name(field.shape())).unwrap();
+    }
+    name
+}
+
+/// The type of value a flag takes
+fn value_name(shape: &Shape<'_>) -> String {
+    match shape.def {
+        Def::Option(od) => value_name(od.t),
+        Def::List(ld) => format!("{}...", value_name(ld.t())),
+        _ => format!("<{}>", shape.type_identifier),
+    }
+}
+
+/// The summary of a field, followed by its default value
+fn describe(field: &Field<'_>) -> String {
+    let mut description = summary(field.doc);
+    if field.flags.contains(FieldFlags::DEFAULT) && !is_switch(field) {
+        if let Some(default) = default_value(field).filter(|d| !d.is_empty()) {
+            if !description.is_empty() {
+                description.push(' ');
+            }
+            write!(description, "[default: {default}]").unwrap();
+        }
+    }
+    description
+}
+
+/// Formats the default value of a field, if its type can be displayed
+fn default_value(field: &Field<'_>) -> Option<String> {
+    let mut partial = Partial::alloc_shape(field.shape()).ok()?;
+    match field.vtable.default_fn {
+        Some(default_fn) => partial.set_field_default(default_fn).ok()?,
+        None => partial.set_default().ok()?,
+    };
+    let value = partial.build().ok()?;
+    value.shape().is_display().then(|| value.peek().to_string())
+}
+
+/// The first paragraph of a doc comment, on one line
+fn summary(doc: &[&str]) -> String {
+    let lines: Vec<&str> = doc
+        .iter()
+        .map(|line| line.trim())
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty())
+        .collect();
+    lines.join(" ")
+}
+
+/// A whole doc comment, without the space that follows `///`
+fn about(doc: &[&str]) -> String {
+    let lines: Vec<&str> = doc
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    lines.join("\n").trim().to_string()
+}
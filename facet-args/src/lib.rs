@@ -10,7 +10,9 @@ extern crate alloc;
 pub mod format;
 
 pub(crate) mod arg;
+pub(crate) mod error;
 pub(crate) mod fields;
+pub(crate) mod help;
 pub(crate) mod parse;
 pub(crate) mod results;
 
@@ -19,3 +21,7 @@ pub use format::from_slice;
 
 #[allow(unused)]
 pub use format::from_std_args;
+
+pub use error::ArgsError;
+pub use format::{from_slice_with_help, parse_std_args};
+pub use help::help;
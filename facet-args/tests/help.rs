@@ -0,0 +1,125 @@
+use facet::Facet;
+use facet_args::ArgsError;
+use facet_testhelpers::test;
+
+/// A tool that builds things
+#[derive(Facet)]
+struct Args {
+    /// Print more output
+    #[facet(named, short = 'v')]
+    verbose: bool,
+
+    /// Number of jobs to run at once
+    #[facet(named, short = 'j', default = 4)]
+    jobs: usize,
+
+    /// Where to write logs
+    #[facet(named, default)]
+    log_file: Option<String>,
+
+    /// What to do
+    #[facet(subcommand)]
+    command: Command,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Command {
+    /// Build a target
+    Build(BuildArgs),
+    /// Remove build artifacts
+    Clean,
+    /// Run the tests
+    RunTests {
+        #[facet(positional)]
+        filter: String,
+    },
+}
+
+#[derive(Facet)]
+struct BuildArgs {
+    /// The target to build
+    #[facet(positional)]
+    target: String,
+
+    /// Build with optimizations
+    #[facet(named, short = 'r')]
+    release: bool,
+}
+
+const HELP: &str = "A tool that builds things
+
+Usage: tool [OPTIONS] <COMMAND>
+
+Options:
+  -v, --verbose            Print more output
+  -j, --jobs <usize>       Number of jobs to run at once [default: 4]
+      --log-file <String>  Where to write logs
+  -h, --help               Print help
+  -V, --version            Print version
+
+Commands:
+  build      Build a target
+  clean      Remove build artifacts
+  run-tests  Run the tests
+";
+
+#[test]
+fn test_help() {
+    // Without a version, there's no `--version` flag
+    let expected = HELP.replace("  -V, --version            Print version\n", "");
+    assert_eq!(facet_args::help::<Args>("tool"), expected);
+}
+
+#[test]
+fn test_help_flag() {
+    for flag in ["--help", "-h"] {
+        let err = facet_args::from_slice_with_help::<Args>("tool", Some("1.2.3"), &["-v", flag])
+            .unwrap_err();
+        assert_eq!(err.exit_code(), 0);
+        match err {
+            ArgsError::Help(help) => assert_eq!(help, HELP),
+            other => panic!("expected help, got {other}"),
+        }
+    }
+}
+
+#[test]
+fn test_subcommand_help() {
+    let err = facet_args::from_slice_with_help::<Args>("tool", None, &["-j", "2", "build", "-h"])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Build a target
+
+Usage: tool build [OPTIONS] <TARGET>
+
+Arguments:
+  <TARGET>  The target to build
+
+Options:
+  -r, --release  Build with optimizations
+  -h, --help     Print help
+"
+    );
+}
+
+#[test]
+fn test_version() {
+    let err = facet_args::from_slice_with_help::<Args>("tool", Some("1.2.3"), &["--version"])
+        .unwrap_err();
+    assert!(matches!(&err, ArgsError::Version(version) if version == "tool 1.2.3"));
+    assert_eq!(err.to_string(), "tool 1.2.3\n");
+
+    // Without a version, `--version` is just an unknown flag
+    let err = facet_args::from_slice_with_help::<Args>("tool", None, &["--version"]).unwrap_err();
+    assert!(matches!(err, ArgsError::Invalid(_)));
+}
+
+#[test]
+fn test_help_after_double_dash_is_an_argument() {
+    let err =
+        facet_args::from_slice_with_help::<Args>("tool", None, &["--", "--help"]).unwrap_err();
+    assert!(matches!(err, ArgsError::Invalid(_)));
+}
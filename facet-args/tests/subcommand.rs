@@ -0,0 +1,119 @@
+use facet::Facet;
+use facet_args::ArgsError;
+use facet_testhelpers::test;
+
+/// A tool that builds things
+#[derive(Facet, Debug, PartialEq)]
+struct Args {
+    /// Print more output
+    #[facet(named, short = 'v')]
+    verbose: bool,
+
+    /// Number of jobs to run at once
+    #[facet(named, short = 'j', default = 4)]
+    jobs: usize,
+
+    /// Where to write logs
+    #[facet(named, default)]
+    log_file: Option<String>,
+
+    /// What to do
+    #[facet(subcommand)]
+    command: Command,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Command {
+    /// Build a target
+    Build(BuildArgs),
+    /// Remove build artifacts
+    Clean,
+    /// Run the tests
+    RunTests {
+        /// Only run the tests whose name contains this
+        #[facet(positional)]
+        filter: String,
+
+        /// Show the output of the tests
+        #[facet(named)]
+        no_capture: bool,
+    },
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct BuildArgs {
+    /// The target to build
+    #[facet(positional)]
+    target: String,
+
+    /// Build with optimizations
+    #[facet(named, short = 'r')]
+    release: bool,
+}
+
+#[test]
+fn test_newtype_subcommand() {
+    let args: Args = facet_args::from_slice(&["-v", "build", "--release", "app"])?;
+    assert_eq!(
+        args,
+        Args {
+            verbose: true,
+            jobs: 4,
+            log_file: None,
+            command: Command::Build(BuildArgs {
+                target: "app".to_string(),
+                release: true,
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_unit_subcommand() {
+    let args: Args = facet_args::from_slice(&["--jobs", "2", "--log-file", "out.log", "clean"])?;
+    assert_eq!(args.jobs, 2);
+    assert_eq!(args.log_file.as_deref(), Some("out.log"));
+    assert_eq!(args.command, Command::Clean);
+}
+
+#[test]
+fn test_struct_subcommand() {
+    let args: Args = facet_args::from_slice(&["run-tests", "--no-capture", "parse"])?;
+    assert!(!args.verbose);
+    assert_eq!(
+        args.command,
+        Command::RunTests {
+            filter: "parse".to_string(),
+            no_capture: true,
+        }
+    );
+}
+
+#[test]
+fn test_subcommand_flags_belong_to_subcommand() {
+    // `-v` comes after the subcommand, so it isn't one of the top-level flags
+    let args: Result<Args, _> = facet_args::from_slice(&["build", "-v", "app"]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn test_unknown_subcommand_lists_commands() {
+    let err = facet_args::from_slice_with_help::<Args>("tool", None, &["deploy"]).unwrap_err();
+    assert!(matches!(err, ArgsError::Invalid(_)));
+    assert_eq!(err.exit_code(), 2);
+
+    let message = err.to_string();
+    assert!(message.contains("Valid flags: -v/--verbose, -j/--jobs, --log-file, -h/--help"));
+    assert!(message.contains("Valid commands: build, clean, run-tests"));
+    assert!(message.ends_with("For more information, try '--help'."));
+}
+
+#[test]
+fn test_unknown_flag_lists_flags() {
+    let err = facet_args::from_slice_with_help::<Args>("tool", None, &["--jbos", "2", "clean"])
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("jbos"));
+    assert!(message.contains("Valid flags: -v/--verbose, -j/--jobs, --log-file, -h/--help"));
+}
@@ -165,6 +165,10 @@ where
                                     let map = ptr.get::<Self>();
                                     map.get(key.get()).map(|v| PtrConst::new(v as *const _))
                                 })
+                                .get_value_mut_ptr(|ptr, key| unsafe {
+                                    let map = ptr.as_mut::<Self>();
+                                    map.get_mut(key.get()).map(|v| PtrMut::new(v as *mut _))
+                                })
                                 .iter_vtable(
                                     IterVTable::builder()
                                         .init_with_value(|ptr| unsafe {
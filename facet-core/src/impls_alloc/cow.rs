@@ -0,0 +1,188 @@
+use crate::*;
+use core::hash::Hash as _;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+type CowSliceIterator<'mem, T> = core::slice::Iter<'mem, T>;
+
+unsafe impl<'a, T> Facet<'a> for Cow<'a, [T]>
+where
+    T: Facet<'a> + Clone,
+{
+    const VTABLE: &'static ValueVTable = &const {
+        ValueVTable::builder::<Self>()
+            .type_name(|f, opts| {
+                if let Some(opts) = opts.for_children() {
+                    write!(f, "{}<'_, [", Self::SHAPE.type_identifier)?;
+                    T::SHAPE.vtable.type_name()(f, opts)?;
+                    write!(f, "]>")
+                } else {
+                    write!(f, "{}<'_, [⋯]>", Self::SHAPE.type_identifier)
+                }
+            })
+            .default_in_place(|| Some(|target| unsafe { target.put(Self::default()) }))
+            .clone_into(|| {
+                if T::SHAPE.vtable.has_clone_into() {
+                    Some(|src, dst| unsafe {
+                        let mut new_vec = Vec::with_capacity(src.len());
+
+                        let t_clone_into = <VTableView<T>>::of().clone_into().unwrap();
+
+                        for item in src.iter() {
+                            use crate::TypedPtrUninit;
+                            use core::mem::MaybeUninit;
+
+                            let mut new_item = MaybeUninit::<T>::uninit();
+                            let uninit_item = TypedPtrUninit::new(new_item.as_mut_ptr());
+
+                            (t_clone_into)(item, uninit_item);
+
+                            new_vec.push(new_item.assume_init());
+                        }
+
+                        dst.put(Self::Owned(new_vec))
+                    })
+                } else {
+                    None
+                }
+            })
+            .debug(|| {
+                if T::SHAPE.vtable.has_debug() {
+                    Some(|value, f| {
+                        write!(f, "[")?;
+                        for (i, item) in value.iter().enumerate() {
+                            if i > 0 {
+                                write!(f, ", ")?;
+                            }
+                            (<VTableView<T>>::of().debug().unwrap())(item, f)?;
+                        }
+                        write!(f, "]")
+                    })
+                } else {
+                    None
+                }
+            })
+            .partial_eq(|| {
+                if T::SHAPE.vtable.has_partial_eq() {
+                    Some(|a, b| {
+                        if a.len() != b.len() {
+                            return false;
+                        }
+                        for (item_a, item_b) in a.iter().zip(b.iter()) {
+                            if !(<VTableView<T>>::of().partial_eq().unwrap())(item_a, item_b) {
+                                return false;
+                            }
+                        }
+                        true
+                    })
+                } else {
+                    None
+                }
+            })
+            .hash(|| {
+                if T::SHAPE.vtable.has_hash() {
+                    Some(|cow, hasher_this, hasher_write_fn| unsafe {
+                        use crate::HasherProxy;
+                        let t_hash = <VTableView<T>>::of().hash().unwrap_unchecked();
+                        let mut hasher = HasherProxy::new(hasher_this, hasher_write_fn);
+                        cow.len().hash(&mut hasher);
+                        for item in cow.iter() {
+                            (t_hash)(item, hasher_this, hasher_write_fn);
+                        }
+                    })
+                } else {
+                    None
+                }
+            })
+            .marker_traits(|| {
+                MarkerTraits::SEND
+                    .union(MarkerTraits::SYNC)
+                    .union(MarkerTraits::EQ)
+                    .union(MarkerTraits::UNPIN)
+                    .union(MarkerTraits::UNWIND_SAFE)
+                    .union(MarkerTraits::REF_UNWIND_SAFE)
+                    .intersection(T::SHAPE.vtable.marker_traits())
+            })
+            .build()
+    };
+
+    const SHAPE: &'static Shape<'static> = &const {
+        Shape::builder_for_sized::<Self>()
+            .type_identifier("Cow")
+            .type_params(&[TypeParam {
+                name: "T",
+                shape: || T::SHAPE,
+            }])
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::List(
+                ListDef::builder()
+                    .vtable(
+                        &const {
+                            ListVTable::builder()
+                                .init_in_place_with_capacity(|data, capacity| unsafe {
+                                    data.put(Self::Owned(Vec::with_capacity(capacity)))
+                                })
+                                .push(|ptr, item| unsafe {
+                                    let cow = ptr.as_mut::<Self>();
+                                    let item = item.read::<T>();
+                                    cow.to_mut().push(item);
+                                })
+                                .len(|ptr| unsafe {
+                                    let cow = ptr.get::<Self>();
+                                    cow.len()
+                                })
+                                .get(|ptr, index| unsafe {
+                                    let cow = ptr.get::<Self>();
+                                    let item = cow.get(index)?;
+                                    Some(PtrConst::new(item))
+                                })
+                                .get_mut(|ptr, index| unsafe {
+                                    let cow = ptr.as_mut::<Self>();
+                                    let item = cow.to_mut().get_mut(index)?;
+                                    Some(PtrMut::new(item))
+                                })
+                                .as_ptr(|ptr| unsafe {
+                                    let cow = ptr.get::<Self>();
+                                    PtrConst::new(cow.as_ptr())
+                                })
+                                .as_mut_ptr(|ptr| unsafe {
+                                    let cow = ptr.as_mut::<Self>();
+                                    PtrMut::new(cow.to_mut().as_mut_ptr())
+                                })
+                                .iter_vtable(
+                                    IterVTable::builder()
+                                        .init_with_value(|ptr| unsafe {
+                                            let cow = ptr.get::<Self>();
+                                            let iter: CowSliceIterator<T> = cow.iter();
+                                            let iter_state = Box::new(iter);
+                                            PtrMut::new(Box::into_raw(iter_state) as *mut u8)
+                                        })
+                                        .next(|iter_ptr| unsafe {
+                                            let state =
+                                                iter_ptr.as_mut::<CowSliceIterator<'_, T>>();
+                                            state.next().map(|value| PtrConst::new(value))
+                                        })
+                                        .next_back(|iter_ptr| unsafe {
+                                            let state =
+                                                iter_ptr.as_mut::<CowSliceIterator<'_, T>>();
+                                            state.next_back().map(|value| PtrConst::new(value))
+                                        })
+                                        .dealloc(|iter_ptr| unsafe {
+                                            drop(Box::from_raw(
+                                                iter_ptr.as_ptr::<CowSliceIterator<'_, T>>()
+                                                    as *mut CowSliceIterator<'_, T>,
+                                            ));
+                                        })
+                                        .build(),
+                                )
+                                .build()
+                        },
+                    )
+                    .t(|| T::SHAPE)
+                    .build(),
+            ))
+            .build()
+    };
+}
@@ -1,7 +1,11 @@
 mod arc;
+mod binary_heap;
 mod boxed;
 mod btreemap;
 mod btreeset;
+mod cow;
+mod linked_list;
 mod rc;
 mod string;
 mod vec;
+mod vec_deque;
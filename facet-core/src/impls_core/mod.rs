@@ -7,6 +7,7 @@ mod dyn_;
 mod ops;
 mod option;
 mod pointer;
+mod result;
 mod scalar;
 mod slice;
 mod smartptr;
@@ -0,0 +1,158 @@
+use core::hash::Hash as _;
+
+use crate::*;
+
+unsafe impl<'a, T: Facet<'a>, E: Facet<'a>> Facet<'a> for Result<T, E> {
+    const VTABLE: &'static ValueVTable = &const {
+        ValueVTable::builder::<Self>()
+            .type_name(|f, opts| {
+                if let Some(opts) = opts.for_children() {
+                    write!(f, "{}<", Self::SHAPE.type_identifier)?;
+                    T::SHAPE.vtable.type_name()(f, opts)?;
+                    write!(f, ", ")?;
+                    E::SHAPE.vtable.type_name()(f, opts)?;
+                    write!(f, ">")
+                } else {
+                    write!(f, "{}<⋯>", Self::SHAPE.type_identifier)
+                }
+            })
+            .clone_into(|| {
+                if T::SHAPE.vtable.has_clone_into() && E::SHAPE.vtable.has_clone_into() {
+                    Some(|src, dst| unsafe {
+                        use core::mem::MaybeUninit;
+
+                        let result = match src {
+                            Ok(value) => {
+                                let mut new_value = MaybeUninit::<T>::uninit();
+                                (<VTableView<T>>::of().clone_into().unwrap())(
+                                    value,
+                                    TypedPtrUninit::new(new_value.as_mut_ptr()),
+                                );
+                                Ok(new_value.assume_init())
+                            }
+                            Err(error) => {
+                                let mut new_error = MaybeUninit::<E>::uninit();
+                                (<VTableView<E>>::of().clone_into().unwrap())(
+                                    error,
+                                    TypedPtrUninit::new(new_error.as_mut_ptr()),
+                                );
+                                Err(new_error.assume_init())
+                            }
+                        };
+                        dst.put(result)
+                    })
+                } else {
+                    None
+                }
+            })
+            .debug(|| {
+                if T::SHAPE.vtable.has_debug() && E::SHAPE.vtable.has_debug() {
+                    Some(|value, f| {
+                        match value {
+                            Ok(value) => {
+                                write!(f, "Ok(")?;
+                                (<VTableView<T>>::of().debug().unwrap())(value, f)?;
+                            }
+                            Err(error) => {
+                                write!(f, "Err(")?;
+                                (<VTableView<E>>::of().debug().unwrap())(error, f)?;
+                            }
+                        }
+                        write!(f, ")")
+                    })
+                } else {
+                    None
+                }
+            })
+            .partial_eq(|| {
+                if T::SHAPE.vtable.has_partial_eq() && E::SHAPE.vtable.has_partial_eq() {
+                    Some(|a, b| match (a, b) {
+                        (Ok(a), Ok(b)) => (<VTableView<T>>::of().partial_eq().unwrap())(a, b),
+                        (Err(a), Err(b)) => (<VTableView<E>>::of().partial_eq().unwrap())(a, b),
+                        _ => false,
+                    })
+                } else {
+                    None
+                }
+            })
+            .hash(|| {
+                if T::SHAPE.vtable.has_hash() && E::SHAPE.vtable.has_hash() {
+                    Some(|result, hasher_this, hasher_write_fn| unsafe {
+                        let mut hasher = HasherProxy::new(hasher_this, hasher_write_fn);
+                        result.is_ok().hash(&mut hasher);
+                        match result {
+                            Ok(value) => (<VTableView<T>>::of().hash().unwrap_unchecked())(
+                                value,
+                                hasher_this,
+                                hasher_write_fn,
+                            ),
+                            Err(error) => (<VTableView<E>>::of().hash().unwrap_unchecked())(
+                                error,
+                                hasher_this,
+                                hasher_write_fn,
+                            ),
+                        }
+                    })
+                } else {
+                    None
+                }
+            })
+            .marker_traits(|| {
+                T::SHAPE
+                    .vtable
+                    .marker_traits()
+                    .intersection(E::SHAPE.vtable.marker_traits())
+            })
+            .build()
+    };
+
+    const SHAPE: &'static Shape<'static> = &const {
+        Shape::builder_for_sized::<Self>()
+            .type_identifier("Result")
+            .type_params(&[
+                TypeParam {
+                    name: "T",
+                    shape: || T::SHAPE,
+                },
+                TypeParam {
+                    name: "E",
+                    shape: || E::SHAPE,
+                },
+            ])
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Result(
+                ResultDef::builder()
+                    .t(T::SHAPE)
+                    .e(E::SHAPE)
+                    .vtable(
+                        const {
+                            &ResultVTable::builder()
+                                .is_ok(|result| unsafe { result.get::<Result<T, E>>().is_ok() })
+                                .get_ok(|result| unsafe {
+                                    result
+                                        .get::<Result<T, E>>()
+                                        .as_ref()
+                                        .ok()
+                                        .map(|t| PtrConst::new(t as *const T))
+                                })
+                                .get_err(|result| unsafe {
+                                    result
+                                        .get::<Result<T, E>>()
+                                        .as_ref()
+                                        .err()
+                                        .map(|e| PtrConst::new(e as *const E))
+                                })
+                                .init_ok(|result, value| unsafe {
+                                    result.put(Result::<T, E>::Ok(value.read::<T>()))
+                                })
+                                .init_err(|result, error| unsafe {
+                                    result.put(Result::<T, E>::Err(error.read::<E>()))
+                                })
+                                .build()
+                        },
+                    )
+                    .build(),
+            ))
+            .build()
+    };
+}
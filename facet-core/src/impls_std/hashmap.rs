@@ -173,6 +173,10 @@ where
                                     let map = ptr.get::<HashMap<K, V>>();
                                     map.get(key.get()).map(|v| PtrConst::new(v))
                                 })
+                                .get_value_mut_ptr(|ptr, key| unsafe {
+                                    let map = ptr.as_mut::<HashMap<K, V>>();
+                                    map.get_mut(key.get()).map(|v| PtrMut::new(v))
+                                })
                                 .iter_vtable(
                                     IterVTable::builder()
                                         .init_with_value(|ptr| unsafe {
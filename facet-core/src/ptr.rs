@@ -326,6 +326,20 @@ impl<'mem> PtrMut<'mem> {
         PtrConst(self.0, PhantomData)
     }
 
+    /// Returns a pointer with the given offset added
+    ///
+    /// # Safety
+    ///
+    /// Offset must be within the bounds of the allocated memory,
+    /// and the resulting pointer must be properly aligned.
+    #[inline]
+    pub const unsafe fn field(self, offset: usize) -> PtrMut<'mem> {
+        PtrMut(
+            unsafe { NonNull::new_unchecked(self.0.as_ptr().byte_add(offset)) },
+            PhantomData,
+        )
+    }
+
     /// Exposes [`core::ptr::read`]
     ///
     /// # Safety
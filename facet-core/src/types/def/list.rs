@@ -240,9 +240,8 @@ impl ListVTableBuilder {
     ///
     /// # Panics
     ///
-    /// Panic if any of the required fields (len, get, as_ptr, iter_vtable) are `None`.
+    /// Panic if any of the required fields (len, get, iter_vtable) are `None`.
     pub const fn build(self) -> ListVTable {
-        assert!(self.as_ptr.is_some());
         ListVTable {
             init_in_place_with_capacity: self.init_in_place_with_capacity,
             push: self.push,
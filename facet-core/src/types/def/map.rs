@@ -120,6 +120,14 @@ pub type MapContainsKeyFn =
 pub type MapGetValuePtrFn =
     for<'map, 'key> unsafe fn(map: PtrConst<'map>, key: PtrConst<'key>) -> Option<PtrConst<'map>>;
 
+/// Get a mutable pointer to a value for a given key, returns None if not found
+///
+/// # Safety
+///
+/// The `map` parameter must point to aligned, initialized memory of the correct type.
+pub type MapGetValueMutPtrFn =
+    for<'map, 'key> unsafe fn(map: PtrMut<'map>, key: PtrConst<'key>) -> Option<PtrMut<'map>>;
+
 /// Virtual table for a Map<K, V>
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 #[repr(C)]
@@ -139,6 +147,9 @@ pub struct MapVTable {
     /// cf. [`MapGetValuePtrFn`]
     pub get_value_ptr_fn: MapGetValuePtrFn,
 
+    /// cf. [`MapGetValueMutPtrFn`]
+    pub get_value_mut_ptr_fn: Option<MapGetValueMutPtrFn>,
+
     /// Virtual table for map iterator operations
     pub iter_vtable: IterVTable<(PtrConst<'static>, PtrConst<'static>)>,
 }
@@ -157,6 +168,7 @@ pub struct MapVTableBuilder {
     len_fn: Option<MapLenFn>,
     contains_key_fn: Option<MapContainsKeyFn>,
     get_value_ptr_fn: Option<MapGetValuePtrFn>,
+    get_value_mut_ptr_fn: Option<MapGetValueMutPtrFn>,
     iter_vtable: Option<IterVTable<(PtrConst<'static>, PtrConst<'static>)>>,
 }
 
@@ -170,6 +182,7 @@ impl MapVTableBuilder {
             len_fn: None,
             contains_key_fn: None,
             get_value_ptr_fn: None,
+            get_value_mut_ptr_fn: None,
             iter_vtable: None,
         }
     }
@@ -204,6 +217,12 @@ impl MapVTableBuilder {
         self
     }
 
+    /// Sets the get_value_mut_ptr_fn field
+    pub const fn get_value_mut_ptr(mut self, f: MapGetValueMutPtrFn) -> Self {
+        self.get_value_mut_ptr_fn = Some(f);
+        self
+    }
+
     /// Sets the iter_vtable field
     pub const fn iter_vtable(
         mut self,
@@ -225,6 +244,7 @@ impl MapVTableBuilder {
             len_fn: self.len_fn.unwrap(),
             contains_key_fn: self.contains_key_fn.unwrap(),
             get_value_ptr_fn: self.get_value_ptr_fn.unwrap(),
+            get_value_mut_ptr_fn: self.get_value_mut_ptr_fn,
             iter_vtable: self.iter_vtable.unwrap(),
         }
     }
@@ -21,6 +21,9 @@ pub use set::*;
 mod option;
 pub use option::*;
 
+mod result;
+pub use result::*;
+
 mod smartptr;
 pub use smartptr::*;
 
@@ -76,6 +79,11 @@ pub enum Def<'shape> {
     /// e.g. `Option<T>`
     Option(OptionDef<'shape>),
 
+    /// Result — either a value or an error
+    ///
+    /// e.g. `Result<T, E>`
+    Result(ResultDef<'shape>),
+
     /// Smart pointers, like `Arc<T>`, `Rc<T>`, etc.
     SmartPointer(SmartPointerDef<'shape>),
 }
@@ -110,6 +118,9 @@ impl<'shape> core::fmt::Debug for Def<'shape> {
             Def::Array(array_def) => write!(f, "Array<{}; {}>", array_def.t, array_def.n),
             Def::Slice(slice_def) => write!(f, "Slice<{}>", slice_def.t),
             Def::Option(option_def) => write!(f, "Option<{}>", option_def.t),
+            Def::Result(result_def) => {
+                write!(f, "Result<{}, {}>", result_def.t, result_def.e)
+            }
             Def::SmartPointer(smart_ptr_def) => {
                 if let Some(pointee) = smart_ptr_def.pointee {
                     write!(f, "SmartPointer<{}>", pointee())
@@ -174,6 +185,13 @@ impl<'shape> Def<'shape> {
             _ => Err(self),
         }
     }
+    /// Returns the `ResultDef` wrapped in an `Ok` if this is a [`Def::Result`].
+    pub fn into_result(self) -> Result<ResultDef<'shape>, Self> {
+        match self {
+            Self::Result(def) => Ok(def),
+            _ => Err(self),
+        }
+    }
     /// Returns the `SmartPointerDef` wrapped in an `Ok` if this is a [`Def::SmartPointer`].
     pub fn into_smart_pointer(self) -> Result<SmartPointerDef<'shape>, Self> {
         match self {
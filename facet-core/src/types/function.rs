@@ -0,0 +1,165 @@
+use crate::{PtrMut, PtrUninit, Shape};
+
+/// Describes a function annotated with `#[facet_fn]`: its name, parameters, return type and
+/// documentation, along with a type-erased way to call it.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[non_exhaustive]
+pub struct FunctionShape<'shape> {
+    /// Name of the function
+    pub name: &'shape str,
+
+    /// Number of parameters
+    pub param_count: usize,
+
+    /// Names of the parameters, in declaration order
+    pub param_names: &'shape [&'shape str],
+
+    /// Shapes of the parameters, in declaration order
+    pub param_shapes: &'shape [&'shape Shape<'shape>],
+
+    /// Shape of the tuple of all parameters, private so that it can't get out of sync with
+    /// `invoke`
+    args_shape: &'shape Shape<'shape>,
+
+    /// Shape of the return type, private for the same reason
+    return_shape: &'shape Shape<'shape>,
+
+    /// Doc comment lines, collected by facet-macros. Note that they tend to
+    /// start with a space.
+    pub documentation: &'shape [&'shape str],
+
+    /// Calls the function
+    invoke: InvokeFn,
+}
+
+/// Calls a function with the tuple of its arguments, and writes what it returns.
+///
+/// # Safety
+///
+/// `args` must point to an initialized value of the `args_shape` of the function, which gets
+/// moved out. `ret` must point to uninitialized memory with the layout of its `return_shape`,
+/// which gets initialized and returned.
+pub type InvokeFn =
+    for<'args, 'ret> unsafe fn(args: PtrMut<'args>, ret: PtrUninit<'ret>) -> PtrMut<'ret>;
+
+impl<'shape> FunctionShape<'shape> {
+    /// Returns a builder for FunctionShape
+    pub const fn builder() -> FunctionShapeBuilder<'shape> {
+        FunctionShapeBuilder::new()
+    }
+
+    /// Shape of the tuple of all parameters, which is what [`FunctionShape::invoke`] takes
+    pub const fn args_shape(&self) -> &'shape Shape<'shape> {
+        self.args_shape
+    }
+
+    /// Shape of the return type, `()` when there's none
+    pub const fn return_shape(&self) -> &'shape Shape<'shape> {
+        self.return_shape
+    }
+
+    /// The type-erased function, which takes a value of [`FunctionShape::args_shape`] and
+    /// returns one of [`FunctionShape::return_shape`]
+    pub const fn invoke(&self) -> InvokeFn {
+        self.invoke
+    }
+}
+
+/// Builder for FunctionShape
+pub struct FunctionShapeBuilder<'shape> {
+    name: Option<&'shape str>,
+    param_names: &'shape [&'shape str],
+    param_shapes: &'shape [&'shape Shape<'shape>],
+    args_shape: Option<&'shape Shape<'shape>>,
+    return_shape: Option<&'shape Shape<'shape>>,
+    documentation: &'shape [&'shape str],
+    invoke: Option<InvokeFn>,
+}
+
+impl<'shape> FunctionShapeBuilder<'shape> {
+    /// Creates a new FunctionShapeBuilder
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            name: None,
+            param_names: &[],
+            param_shapes: &[],
+            args_shape: None,
+            return_shape: None,
+            documentation: &[],
+            invoke: None,
+        }
+    }
+
+    /// Sets the name for the FunctionShape
+    pub const fn name(mut self, name: &'shape str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the parameter names for the FunctionShape
+    pub const fn param_names(mut self, param_names: &'shape [&'shape str]) -> Self {
+        self.param_names = param_names;
+        self
+    }
+
+    /// Sets the parameter shapes for the FunctionShape
+    pub const fn param_shapes(mut self, param_shapes: &'shape [&'shape Shape<'shape>]) -> Self {
+        self.param_shapes = param_shapes;
+        self
+    }
+
+    /// Sets the shape of the tuple of all parameters for the FunctionShape
+    pub const fn args_shape(mut self, args_shape: &'shape Shape<'shape>) -> Self {
+        self.args_shape = Some(args_shape);
+        self
+    }
+
+    /// Sets the return shape for the FunctionShape
+    pub const fn return_shape(mut self, return_shape: &'shape Shape<'shape>) -> Self {
+        self.return_shape = Some(return_shape);
+        self
+    }
+
+    /// Sets the documentation for the FunctionShape
+    pub const fn documentation(mut self, documentation: &'shape [&'shape str]) -> Self {
+        self.documentation = documentation;
+        self
+    }
+
+    /// Sets the invoke function for the FunctionShape
+    ///
+    /// # Safety
+    ///
+    /// `invoke` must take a value of the shape given to [`Self::args_shape`], and write a value
+    /// of the shape given to [`Self::return_shape`], as described on [`InvokeFn`]. Safe code
+    /// like `facet_reflect::invoke` relies on it to call the function.
+    pub const unsafe fn invoke(mut self, invoke: InvokeFn) -> Self {
+        self.invoke = Some(invoke);
+        self
+    }
+
+    /// Builds the FunctionShape
+    ///
+    /// # Panics
+    ///
+    /// When the name, the shape of the arguments, the return shape or the invoke function are
+    /// missing, or when there aren't as many parameter names as parameter shapes.
+    pub const fn build(self) -> FunctionShape<'shape> {
+        assert!(
+            self.param_names.len() == self.param_shapes.len(),
+            "each parameter needs a name and a shape"
+        );
+        FunctionShape {
+            name: self.name.unwrap(),
+            param_count: self.param_names.len(),
+            param_names: self.param_names,
+            param_shapes: self.param_shapes,
+            args_shape: self.args_shape.unwrap(),
+            return_shape: self.return_shape.unwrap(),
+            documentation: self.documentation,
+            invoke: self.invoke.unwrap(),
+        }
+    }
+}
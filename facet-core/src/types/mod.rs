@@ -17,6 +17,9 @@ pub use def::*;
 mod ty;
 pub use ty::*;
 
+mod function;
+pub use function::*;
+
 use crate::{ConstTypeId, Facet};
 
 /// Schema for reflection of a type
@@ -135,6 +138,23 @@ pub enum ShapeAttribute<'shape> {
     Transparent,
     /// Specifies a case conversion rule for all fields or variants
     RenameAll(&'shape str),
+    /// Indicates that an enum is internally tagged: the variant name is stored
+    /// under this key, alongside the variant's fields (`{"type": "Variant", ...}`).
+    ///
+    /// When combined with [`ShapeAttribute::Content`], the enum is adjacently tagged.
+    Tag(&'shape str),
+    /// Indicates that an enum is adjacently tagged: the variant's data is stored
+    /// under this key, next to the [`ShapeAttribute::Tag`] key (`{"t": "Variant", "c": ...}`).
+    Content(&'shape str),
+    /// Indicates that an enum is untagged: variants are serialized as their
+    /// data alone, and deserialization tries each variant in turn.
+    Untagged,
+    /// Identifies the type as an extension type with this id, for formats that have them,
+    /// like MessagePack. The type's single field holds the extension's bytes.
+    Ext(i8),
+    /// Indicates that a struct is written as an array of its fields in order, instead of
+    /// a map keyed by field name, for formats that support it like MessagePack.
+    AsArray,
     /// Custom field attribute containing arbitrary text
     Arbitrary(&'shape str),
 }
@@ -190,6 +210,49 @@ impl<'shape> Shape<'shape> {
             }
         })
     }
+
+    /// See [`ShapeAttribute::Tag`]
+    pub fn get_tag_attr(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|attr| {
+            if let ShapeAttribute::Tag(tag) = attr {
+                Some(*tag)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// See [`ShapeAttribute::Content`]
+    pub fn get_content_attr(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|attr| {
+            if let ShapeAttribute::Content(content) = attr {
+                Some(*content)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// See [`ShapeAttribute::Untagged`]
+    pub fn has_untagged_attr(&self) -> bool {
+        self.attributes.contains(&ShapeAttribute::Untagged)
+    }
+
+    /// See [`ShapeAttribute::AsArray`]
+    pub fn has_as_array_attr(&self) -> bool {
+        self.attributes.contains(&ShapeAttribute::AsArray)
+    }
+
+    /// See [`ShapeAttribute::Ext`]
+    pub fn get_ext_attr(&self) -> Option<i8> {
+        self.attributes.iter().find_map(|attr| {
+            if let ShapeAttribute::Ext(id) = attr {
+                Some(*id)
+            } else {
+                None
+            }
+        })
+    }
 }
 
 /// Builder for [`Shape`]
@@ -30,7 +30,8 @@ pub struct Field<'shape> {
     pub vtable: &'shape FieldVTable,
 
     /// true if returned from `fields_for_serialize` and it was flattened - which
-    /// means, if it's an enum, the outer variant shouldn't be written.
+    /// means, if it's an enum, the outer variant shouldn't be written, and if it's
+    /// a map, its entries should be written inline in the parent object.
     pub flattened: bool,
 }
 
@@ -242,7 +243,8 @@ bitflags! {
         const SKIP_SERIALIZING = 1 << 1;
 
         /// Flag indicating that this field should be flattened: if it's a struct, all its
-        /// fields should be apparent on the parent structure, etc.
+        /// fields should be apparent on the parent structure, if it's a map, it collects
+        /// the parent's unknown fields, etc.
         const FLATTEN = 1 << 2;
 
         /// For KDL/XML formats, indicates that this field is a child, not an attribute
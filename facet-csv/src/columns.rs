@@ -0,0 +1,130 @@
+//! How a row struct is laid out as CSV columns
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
+
+use facet_core::{
+    Def, Field, FieldFlags, PointerType, Shape, ShapeAttribute, StructKind, Type, UserType,
+};
+
+/// The columns of a row struct, shared by the reader and the writer
+pub(crate) struct Layout<'shape> {
+    /// The name of every column, in order
+    pub(crate) names: Vec<String>,
+    /// The fields of the row struct, each spanning one or more columns
+    pub(crate) slots: Vec<Slot<'shape>>,
+}
+
+/// A struct field and the columns it spans
+pub(crate) struct Slot<'shape> {
+    /// Index of the field in its struct
+    pub(crate) index: usize,
+    pub(crate) field: Field<'shape>,
+    /// Indices of the columns taken by the field, in [`Layout::names`]
+    pub(crate) columns: Range<usize>,
+    pub(crate) kind: SlotKind<'shape>,
+}
+
+pub(crate) enum SlotKind<'shape> {
+    /// The field takes a single cell
+    Cell,
+    /// The field is a nested struct, possibly optional, spread over the columns of its own fields
+    Struct {
+        optional: bool,
+        slots: Vec<Slot<'shape>>,
+    },
+}
+
+impl<'shape> Layout<'shape> {
+    /// Lays out a struct as columns, or returns `None` if the shape isn't a struct with
+    /// named fields.
+    ///
+    /// Nested structs take one column per field, named with the path to that field
+    /// (`address.city`), unless they're flattened, in which case their fields are named
+    /// as if they belonged to the parent. Optional structs are laid out like the struct
+    /// they wrap.
+    pub(crate) fn of(shape: &'shape Shape<'shape>) -> Option<Self> {
+        let fields = record_fields(shape)?;
+        let mut names = Vec::new();
+        let slots = slots(fields, "", &mut names);
+        Some(Self { names, slots })
+    }
+}
+
+/// The shape of a single row: the element type of a list, array or slice, and the
+/// shape itself otherwise.
+pub(crate) fn row_shape<'shape>(shape: &'shape Shape<'shape>) -> &'shape Shape<'shape> {
+    match (shape.def, shape.ty) {
+        (Def::List(ld), _) => ld.t(),
+        (Def::Array(ad), _) => ad.t(),
+        (Def::Slice(sd), _) => sd.t(),
+        (_, Type::Pointer(PointerType::Reference(vpt))) => row_shape(vpt.target()),
+        _ => shape,
+    }
+}
+
+/// The fields of a struct that spreads over several columns
+fn record_fields<'shape>(shape: &'shape Shape<'shape>) -> Option<&'shape [Field<'shape>]> {
+    if !matches!(shape.def, Def::Undefined)
+        || shape.attributes.contains(&ShapeAttribute::Transparent)
+    {
+        return None;
+    }
+    match shape.ty {
+        Type::User(UserType::Struct(sd))
+            if sd.kind == StructKind::Struct && !sd.fields.is_empty() =>
+        {
+            Some(sd.fields)
+        }
+        _ => None,
+    }
+}
+
+fn slots<'shape>(
+    fields: &'shape [Field<'shape>],
+    prefix: &str,
+    names: &mut Vec<String>,
+) -> Vec<Slot<'shape>> {
+    let mut result = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        if field.flags.contains(FieldFlags::SKIP_SERIALIZING) {
+            continue;
+        }
+
+        let (optional, inner) = match field.shape.def {
+            Def::Option(od) => (true, od.t),
+            _ => (false, field.shape),
+        };
+        let nested = record_fields(inner);
+        let name = if nested.is_some() && field.flags.contains(FieldFlags::FLATTEN) {
+            prefix.to_string()
+        } else if prefix.is_empty() {
+            field.name.to_string()
+        } else {
+            format!("{prefix}.{}", field.name)
+        };
+
+        let start = names.len();
+        let kind = match nested {
+            Some(fields) => SlotKind::Struct {
+                optional,
+                slots: slots(fields, &name, names),
+            },
+            None => {
+                names.push(name);
+                SlotKind::Cell
+            }
+        };
+        result.push(Slot {
+            index,
+            field: *field,
+            columns: start..names.len(),
+            kind,
+        });
+    }
+    result
+}
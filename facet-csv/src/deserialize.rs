@@ -0,0 +1,624 @@
+use alloc::borrow::Cow;
+use core::{fmt, marker::PhantomData};
+use std::io::{self, BufRead, BufReader, Read};
+
+use facet_core::{Def, Facet, Field, FieldFlags, Shape, ShapeAttribute, Type, UserType};
+use facet_reflect::{Partial, ReflectError, ScalarType};
+
+use crate::{
+    CsvOptions,
+    columns::{Layout, Slot, SlotKind},
+};
+
+/// An error that occurred while reading CSV
+#[derive(Debug)]
+pub struct CsvError<'shape> {
+    /// What went wrong
+    pub kind: CsvErrorKind<'shape>,
+    /// The line of the input where the faulty record starts, counting from 1
+    pub line: Option<usize>,
+}
+
+/// The different kinds of errors that can occur while reading CSV
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsvErrorKind<'shape> {
+    /// The input couldn't be read
+    Io(io::Error),
+
+    /// A quoted cell is never closed
+    UnterminatedQuote,
+
+    /// A record isn't valid UTF-8
+    InvalidUtf8,
+
+    /// The shape can't be read from CSV
+    Unsupported {
+        /// The shape that isn't supported
+        shape: &'shape Shape<'shape>,
+    },
+
+    /// The header names a column the row type doesn't know about, and the type denies
+    /// unknown fields
+    UnknownColumn {
+        /// The row type
+        shape: &'shape Shape<'shape>,
+        /// The name of the unknown column
+        column: String,
+    },
+
+    /// A field has no column and no default
+    MissingField {
+        /// The struct being built
+        shape: &'shape Shape<'shape>,
+        /// The name of the missing field
+        field: &'shape str,
+    },
+
+    /// A cell can't be parsed as the type of its field
+    InvalidValue {
+        /// The type of the field
+        shape: &'shape Shape<'shape>,
+        /// The text of the cell
+        value: String,
+    },
+
+    /// An error occurred while building the value
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for CsvErrorKind<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        CsvErrorKind::Reflect(err)
+    }
+}
+
+impl<'shape> From<CsvErrorKind<'shape>> for CsvError<'shape> {
+    fn from(kind: CsvErrorKind<'shape>) -> Self {
+        CsvError { kind, line: None }
+    }
+}
+
+impl<'shape> From<ReflectError<'shape>> for CsvError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        CsvErrorKind::Reflect(err).into()
+    }
+}
+
+impl fmt::Display for CsvErrorKind<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvErrorKind::Io(err) => write!(f, "{err}"),
+            CsvErrorKind::UnterminatedQuote => write!(f, "Quoted cell is never closed"),
+            CsvErrorKind::InvalidUtf8 => write!(f, "Record is not valid UTF-8"),
+            CsvErrorKind::Unsupported { shape } => {
+                write!(f, "Shape {shape} can't be read from CSV")
+            }
+            CsvErrorKind::UnknownColumn { shape, column } => {
+                write!(f, "Unknown column `{column}` for {shape}")
+            }
+            CsvErrorKind::MissingField { shape, field } => {
+                write!(f, "Missing column for field `{field}` of {shape}")
+            }
+            CsvErrorKind::InvalidValue { shape, value } => {
+                write!(f, "Invalid value {value:?} for {shape}")
+            }
+            CsvErrorKind::Reflect(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl fmt::Display for CsvError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl core::error::Error for CsvErrorKind<'_> {}
+impl core::error::Error for CsvError<'_> {}
+
+/// Deserializes a list of rows from CSV
+///
+/// The first record is a header naming the column of each cell: cells are matched to
+/// the fields of the row struct by name, nested structs taking one column per field
+/// (`address.city`). Empty cells are read as `None` for optional fields.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Row {
+///     name: String,
+///     age: u32,
+///     email: Option<String>,
+/// }
+///
+/// let rows: Vec<Row> = facet_csv::from_str("age,name,email\n32,Alice,\n").unwrap();
+/// assert_eq!(
+///     rows,
+///     [Row {
+///         name: "Alice".to_string(),
+///         age: 32,
+///         email: None,
+///     }]
+/// );
+/// ```
+pub fn from_str<T: Facet<'static>>(input: &str) -> Result<T, CsvError<'static>> {
+    from_str_with_options(input, CsvOptions::default())
+}
+
+/// Deserializes a list of rows from CSV, with the given options
+pub fn from_str_with_options<T: Facet<'static>>(
+    input: &str,
+    options: CsvOptions,
+) -> Result<T, CsvError<'static>> {
+    let mut typed_partial = Partial::alloc::<T>()?;
+    let wip = typed_partial.inner_mut();
+    let shape = wip.shape();
+    let Def::List(ld) = shape.def else {
+        return Err(CsvErrorKind::Unsupported { shape }.into());
+    };
+
+    let mut reader = RowReader::new(input.as_bytes(), ld.t(), options)?;
+    wip.begin_list()?;
+    while let Some((line, cells)) = reader.next_row()? {
+        let at_line = |kind| CsvError {
+            kind,
+            line: Some(line),
+        };
+        wip.begin_list_item()?;
+        deserialize_row(wip, &reader.layout.slots, &cells).map_err(at_line)?;
+        wip.end()?;
+    }
+
+    let boxed_value = typed_partial.build()?;
+    Ok(*boxed_value)
+}
+
+/// Reads rows from CSV one at a time
+///
+/// See [`from_str`] for how cells are matched to fields.
+pub fn from_reader<T: Facet<'static>, R: Read>(reader: R) -> Rows<R, T> {
+    from_reader_with_options(reader, CsvOptions::default())
+}
+
+/// Reads rows from CSV one at a time, with the given options
+pub fn from_reader_with_options<T: Facet<'static>, R: Read>(
+    reader: R,
+    options: CsvOptions,
+) -> Rows<R, T> {
+    match RowReader::new(BufReader::new(reader), T::SHAPE, options) {
+        Ok(reader) => Rows {
+            reader: Some(reader),
+            error: None,
+            phantom: PhantomData,
+        },
+        Err(error) => Rows {
+            reader: None,
+            error: Some(error),
+            phantom: PhantomData,
+        },
+    }
+}
+
+/// An iterator over the rows of a CSV input, returned by [`from_reader`]
+///
+/// A row that can't be built yields an error and iteration moves on to the next one,
+/// while a broken input (an I/O error, an unterminated quote, or an invalid header)
+/// ends the iteration.
+pub struct Rows<R, T> {
+    /// `None` once the input is exhausted or broken
+    reader: Option<RowReader<'static, BufReader<R>>>,
+    /// An error to yield before anything else
+    error: Option<CsvError<'static>>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T: Facet<'static>> Iterator for Rows<R, T> {
+    type Item = Result<T, CsvError<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+
+        let reader = self.reader.as_mut()?;
+        match reader.next_row() {
+            Ok(Some((line, cells))) => Some(read_row(&reader.layout, line, &cells)),
+            Ok(None) => {
+                self.reader = None;
+                None
+            }
+            Err(error) => {
+                self.reader = None;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+fn read_row<T: Facet<'static>>(
+    layout: &Layout<'static>,
+    line: usize,
+    cells: &[Option<String>],
+) -> Result<T, CsvError<'static>> {
+    let at_line = |kind| CsvError {
+        kind,
+        line: Some(line),
+    };
+    let mut typed_partial = Partial::alloc::<T>().map_err(|err| at_line(err.into()))?;
+    deserialize_row(typed_partial.inner_mut(), &layout.slots, cells).map_err(at_line)?;
+    let boxed_value = typed_partial.build().map_err(|err| at_line(err.into()))?;
+    Ok(*boxed_value)
+}
+
+/// Reads records and lines their cells up with the columns of the row struct
+struct RowReader<'shape, R> {
+    records: Records<R>,
+    row_shape: &'shape Shape<'shape>,
+    layout: Layout<'shape>,
+    /// For each cell of a record, the column it belongs to, once the header is read
+    positions: Option<Vec<Option<usize>>>,
+}
+
+impl<'shape, R: BufRead> RowReader<'shape, R> {
+    fn new(
+        reader: R,
+        row_shape: &'shape Shape<'shape>,
+        options: CsvOptions,
+    ) -> Result<Self, CsvError<'shape>> {
+        let layout = Layout::of(row_shape).ok_or(CsvErrorKind::Unsupported { shape: row_shape })?;
+        let positions = if options.get_has_headers() {
+            None
+        } else {
+            Some((0..layout.names.len()).map(Some).collect())
+        };
+        Ok(Self {
+            records: Records {
+                reader,
+                options,
+                line: 0,
+                buf: Vec::new(),
+            },
+            row_shape,
+            layout,
+            positions,
+        })
+    }
+
+    /// Reads the next record, returning the line it starts on and the text of each
+    /// column: `None` for columns missing from the input
+    #[allow(clippy::type_complexity)]
+    fn next_row(&mut self) -> Result<Option<(usize, Vec<Option<String>>)>, CsvError<'shape>> {
+        if self.positions.is_none() {
+            let Some((line, headers)) = self.records.next_record()? else {
+                return Ok(None);
+            };
+            let mut positions = Vec::with_capacity(headers.len());
+            for header in headers {
+                let position = self.layout.names.iter().position(|name| *name == header);
+                if position.is_none() && self.row_shape.has_deny_unknown_fields_attr() {
+                    return Err(CsvError {
+                        kind: CsvErrorKind::UnknownColumn {
+                            shape: self.row_shape,
+                            column: header,
+                        },
+                        line: Some(line),
+                    });
+                }
+                positions.push(position);
+            }
+            self.positions = Some(positions);
+        }
+
+        let Some((line, record)) = self.records.next_record()? else {
+            return Ok(None);
+        };
+        let mut cells = vec![None; self.layout.names.len()];
+        for (cell, position) in record.into_iter().zip(self.positions.iter().flatten()) {
+            if let Some(column) = position {
+                cells[*column] = Some(cell);
+            }
+        }
+        Ok(Some((line, cells)))
+    }
+}
+
+/// Splits the input into records
+struct Records<R> {
+    reader: R,
+    options: CsvOptions,
+    /// How many lines have been consumed so far
+    line: usize,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> Records<R> {
+    /// Reads the next record that isn't blank, returning the line it starts on and its cells
+    fn next_record<'shape>(&mut self) -> Result<Option<(usize, Vec<String>)>, CsvError<'shape>> {
+        let newline = self.options.get_newline().as_str().as_bytes();
+        let terminator = newline[newline.len() - 1];
+
+        loop {
+            let line = self.line + 1;
+            let at_line = |kind| CsvError {
+                kind,
+                line: Some(line),
+            };
+
+            // Line breaks inside quoted cells belong to the cell, so keep reading until
+            // the quotes are balanced
+            self.buf.clear();
+            let cells = loop {
+                let read = self
+                    .reader
+                    .read_until(terminator, &mut self.buf)
+                    .map_err(|err| at_line(CsvErrorKind::Io(err)))?;
+                let at_eof = read == 0;
+                if !at_eof && !self.buf.ends_with(newline) {
+                    continue;
+                }
+                if at_eof && self.buf.is_empty() {
+                    return Ok(None);
+                }
+
+                let mut record = self.buf.strip_suffix(newline).unwrap_or(&self.buf[..]);
+                if newline == b"\n" {
+                    record = record.strip_suffix(b"\r").unwrap_or(record);
+                }
+                match split_record(record, &self.options) {
+                    Some(cells) => break cells,
+                    None if at_eof => return Err(at_line(CsvErrorKind::UnterminatedQuote)),
+                    None => continue,
+                }
+            };
+            self.line += count_newlines(&self.buf, newline);
+
+            if let [cell] = cells.as_slice() {
+                if cell.is_empty() && !self.buf.contains(&self.options.get_quote()) {
+                    // blank line
+                    continue;
+                }
+            }
+
+            let cells = cells
+                .into_iter()
+                .map(String::from_utf8)
+                .collect::<Result<_, _>>()
+                .map_err(|_| at_line(CsvErrorKind::InvalidUtf8))?;
+            return Ok(Some((line, cells)));
+        }
+    }
+}
+
+/// Splits a record into cells, unquoting them, or returns `None` if a quoted cell isn't
+/// closed by the end of the record
+fn split_record(record: &[u8], options: &CsvOptions) -> Option<Vec<Vec<u8>>> {
+    let delimiter = options.get_delimiter();
+    let quote = options.get_quote();
+
+    let mut cells = Vec::new();
+    let mut cell = Vec::new();
+    let mut in_quotes = false;
+    let mut bytes = record.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        if in_quotes {
+            if b != quote {
+                cell.push(b);
+            } else if bytes.next_if_eq(&quote).is_some() {
+                // a doubled quote is an escaped one
+                cell.push(quote);
+            } else {
+                in_quotes = false;
+            }
+        } else if b == quote {
+            in_quotes = true;
+        } else if b == delimiter {
+            cells.push(core::mem::take(&mut cell));
+        } else {
+            cell.push(b);
+        }
+    }
+
+    if in_quotes {
+        return None;
+    }
+    cells.push(cell);
+    Some(cells)
+}
+
+fn count_newlines(buf: &[u8], newline: &[u8]) -> usize {
+    buf.windows(newline.len())
+        .filter(|window| *window == newline)
+        .count()
+}
+
+/// Sets the fields of a struct from the cells of its columns
+fn deserialize_row<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    slots: &[Slot<'shape>],
+    cells: &[Option<String>],
+) -> Result<(), CsvErrorKind<'shape>> {
+    for slot in slots {
+        match &slot.kind {
+            SlotKind::Cell => {
+                // Empty cells count as missing for fields that have a default
+                let text = match &cells[slot.columns.start] {
+                    Some(text)
+                        if !(text.is_empty() && slot.field.flags.contains(FieldFlags::DEFAULT)) =>
+                    {
+                        text
+                    }
+                    _ => continue,
+                };
+                wip.begin_nth_field(slot.index)?;
+                deserialize_cell(wip, text)?;
+                wip.end()?;
+            }
+            SlotKind::Struct { optional, slots } => {
+                let columns = &cells[slot.columns.clone()];
+                if columns.iter().all(Option::is_none) {
+                    continue;
+                }
+
+                wip.begin_nth_field(slot.index)?;
+                if !optional {
+                    deserialize_row(wip, slots, cells)?;
+                } else if columns
+                    .iter()
+                    .all(|text| text.as_deref().is_none_or(str::is_empty))
+                {
+                    wip.set_default()?;
+                } else {
+                    wip.begin_some()?;
+                    deserialize_row(wip, slots, cells)?;
+                    wip.end()?;
+                }
+                wip.end()?;
+            }
+        }
+    }
+
+    let fields: &[Field<'shape>] = match wip.shape().ty {
+        Type::User(UserType::Struct(sd)) => sd.fields,
+        _ => &[],
+    };
+    fill_defaults(wip, fields)
+}
+
+/// Absent fields fall back to their default, or to `None` for options
+fn fill_defaults<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+) -> Result<(), CsvErrorKind<'shape>> {
+    let shape = wip.shape();
+
+    for (index, field) in fields.iter().enumerate() {
+        if wip.is_field_set(index)? {
+            continue;
+        }
+        if field.flags.contains(FieldFlags::DEFAULT) {
+            wip.begin_nth_field(index)?;
+            if let Some(field_default_fn) = field.vtable.default_fn {
+                wip.set_field_default(field_default_fn)?;
+            } else {
+                wip.set_default()?;
+            }
+            wip.end()?;
+        } else if matches!(field.shape.def, Def::Option(_)) {
+            wip.begin_nth_field(index)?;
+            wip.set_default()?;
+            wip.end()?;
+        }
+    }
+    wip.fill_unset_fields_from_default()?;
+
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            return Err(CsvErrorKind::MissingField {
+                shape,
+                field: field.name,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Sets a value from the text of a single cell
+fn deserialize_cell<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    text: &str,
+) -> Result<(), CsvErrorKind<'shape>> {
+    let shape = wip.shape();
+    let invalid = || CsvErrorKind::InvalidValue {
+        shape,
+        value: text.to_string(),
+    };
+
+    if shape.attributes.contains(&ShapeAttribute::Transparent) {
+        wip.begin_inner()?;
+        deserialize_cell(wip, text)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            if text.is_empty() {
+                wip.set_default()?;
+            } else {
+                wip.begin_some()?;
+                deserialize_cell(wip, text)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::SmartPointer(_) => {
+            wip.begin_smart_ptr()?;
+            deserialize_cell(wip, text)?;
+            wip.end()?;
+            return Ok(());
+        }
+        Def::Scalar(_) => {
+            match ScalarType::try_from_shape(shape) {
+                Some(ScalarType::String) => {
+                    wip.set(text.to_string())?;
+                }
+                Some(ScalarType::CowStr) => {
+                    wip.set(Cow::<'facet, str>::Owned(text.to_string()))?;
+                }
+                Some(ScalarType::Char) => {
+                    let mut chars = text.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => wip.set(c)?,
+                        _ => return Err(invalid()),
+                    };
+                }
+                Some(ScalarType::Unit) if text.is_empty() => {
+                    wip.set(())?;
+                }
+                // Wrappers like `NonZero<T>` are built from their inner value
+                None if shape.inner.is_some() => {
+                    wip.begin_inner()?;
+                    deserialize_cell(wip, text)?;
+                    wip.end()?;
+                }
+                _ => parse_cell(wip, text)?,
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    match shape.ty {
+        // Enums are written as the name of a unit variant
+        Type::User(UserType::Enum(_)) => match wip.find_variant(text) {
+            Some((index, variant)) if variant.data.fields.is_empty() => {
+                wip.select_nth_variant(index)?;
+                Ok(())
+            }
+            _ => Err(invalid()),
+        },
+        _ => parse_cell(wip, text),
+    }
+}
+
+/// Sets a value by parsing a cell with the type's parse function
+fn parse_cell<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    text: &str,
+) -> Result<(), CsvErrorKind<'shape>> {
+    let shape = wip.shape();
+    if shape.vtable.sized().and_then(|v| (v.parse)()).is_none() {
+        return Err(CsvErrorKind::Unsupported { shape });
+    }
+    wip.parse_from_str(text)
+        .map_err(|_| CsvErrorKind::InvalidValue {
+            shape,
+            value: text.to_string(),
+        })?;
+    Ok(())
+}
@@ -6,10 +6,18 @@
 #![doc = include_str!("../README.md")]
 extern crate alloc;
 
+mod options;
+pub use options::*;
+
+#[cfg(feature = "std")]
+mod columns;
+
 #[cfg(feature = "std")]
 mod serialize;
 #[cfg(feature = "std")]
 pub use serialize::*;
 
-// mod deserialize;
-// pub use deserialize::*;
+#[cfg(feature = "std")]
+mod deserialize;
+#[cfg(feature = "std")]
+pub use deserialize::*;
@@ -0,0 +1,115 @@
+/// What ends each record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Newline {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl Newline {
+    /// The characters ending each record
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Settings shared by the CSV reader and writer
+///
+/// ```
+/// use facet_csv::{CsvOptions, Newline};
+///
+/// let options = CsvOptions::default()
+///     .delimiter(b';')
+///     .newline(Newline::CrLf)
+///     .has_headers(false);
+/// assert_eq!(options.get_delimiter(), b';');
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    delimiter: u8,
+    quote: u8,
+    newline: Newline,
+    has_headers: bool,
+    write_headers: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            newline: Newline::Lf,
+            has_headers: true,
+            write_headers: true,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Sets the byte separating the cells of a record, `,` by default
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the byte used to quote cells, `"` by default
+    ///
+    /// Inside a quoted cell, the quote is escaped by doubling it.
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets what ends each record, `\n` by default
+    ///
+    /// When reading with the default, `\r\n` line endings are accepted as well.
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    /// Sets whether the first record read holds the column names, `true` by default
+    ///
+    /// Without headers, cells are matched to fields by position.
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Sets whether the writer starts with a header record naming the columns, `true`
+    /// by default
+    pub fn write_headers(mut self, write_headers: bool) -> Self {
+        self.write_headers = write_headers;
+        self
+    }
+
+    /// The byte separating the cells of a record
+    pub fn get_delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// The byte used to quote cells
+    pub fn get_quote(&self) -> u8 {
+        self.quote
+    }
+
+    /// What ends each record
+    pub fn get_newline(&self) -> Newline {
+        self.newline
+    }
+
+    /// Whether the first record read holds the column names
+    pub fn get_has_headers(&self) -> bool {
+        self.has_headers
+    }
+
+    /// Whether the writer starts with a header record
+    pub fn get_write_headers(&self) -> bool {
+        self.write_headers
+    }
+}
@@ -1,96 +1,249 @@
-use facet_core::Facet;
+use crate::{
+    CsvOptions,
+    columns::{Layout, row_shape},
+};
+use facet_core::{Facet, Shape};
 use facet_reflect::Peek;
 use facet_serialize::{Serializer, serialize_iterative};
 use std::io::{self, Write};
 
 /// Serializes a value to CSV
-pub fn to_string<'a, T: Facet<'a>>(value: &'a T) -> String {
+///
+/// The value is either a single row struct, or a list, array or slice of them.
+pub fn to_string<'a, T: Facet<'a>>(value: &'a T) -> io::Result<String> {
+    to_string_with_options(value, CsvOptions::default())
+}
+
+/// Serializes a value to CSV, with the given options
+pub fn to_string_with_options<'a, T: Facet<'a>>(
+    value: &'a T,
+    options: CsvOptions,
+) -> io::Result<String> {
     let peek = Peek::new(value);
-    let mut output = Vec::new();
-    let mut serializer = CsvSerializer::new(&mut output);
-    serialize_iterative(peek, &mut serializer).unwrap();
-    String::from_utf8(output).unwrap()
+    peek_to_string_with_options(&peek, options)
 }
 
 /// Serializes a Peek instance to CSV
-pub fn peek_to_string<'a>(peek: &'a Peek<'_, 'a, '_>) -> String {
+pub fn peek_to_string<'a>(peek: &'a Peek<'_, 'a, '_>) -> io::Result<String> {
+    peek_to_string_with_options(peek, CsvOptions::default())
+}
+
+fn peek_to_string_with_options(peek: &Peek<'_, '_, '_>, options: CsvOptions) -> io::Result<String> {
     let mut output = Vec::new();
-    let mut serializer = CsvSerializer::new(&mut output);
-    serialize_iterative(*peek, &mut serializer).unwrap();
-    String::from_utf8(output).unwrap()
+    peek_to_writer_with_options(peek, &mut output, options)?;
+    String::from_utf8(output).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
 /// Serializes a value to a writer in CSV format
 pub fn to_writer<'a, T: Facet<'a>, W: Write>(value: &'a T, writer: &mut W) -> io::Result<()> {
+    to_writer_with_options(value, writer, CsvOptions::default())
+}
+
+/// Serializes a value to a writer in CSV format, with the given options
+pub fn to_writer_with_options<'a, T: Facet<'a>, W: Write>(
+    value: &'a T,
+    writer: &mut W,
+    options: CsvOptions,
+) -> io::Result<()> {
     let peek = Peek::new(value);
-    let mut serializer = CsvSerializer::new(writer);
-    serialize_iterative(peek, &mut serializer)
+    peek_to_writer_with_options(&peek, writer, options)
 }
 
 /// Serializes a Peek instance to a writer in CSV format
 pub fn peek_to_writer<'a, W: Write>(peek: &'a Peek<'_, 'a, '_>, writer: &mut W) -> io::Result<()> {
-    let mut serializer = CsvSerializer::new(writer);
-    serialize_iterative(*peek, &mut serializer)
+    peek_to_writer_with_options(peek, writer, CsvOptions::default())
+}
+
+/// Serializes a Peek instance to a writer in CSV format, with the given options
+pub fn peek_to_writer_with_options<W: Write>(
+    peek: &Peek<'_, '_, '_>,
+    writer: &mut W,
+    options: CsvOptions,
+) -> io::Result<()> {
+    let mut serializer = CsvSerializer::with_options(writer, peek.shape(), options);
+    serialize_iterative(*peek, &mut serializer)?;
+    serializer.finish()
 }
 
 /// A struct to handle the CSV serializer logic
+///
+/// Every struct reaching the top level (either directly, or as an item of a top-level
+/// list) is written as one record. Cells are matched to the columns of the row struct
+/// by field name, so that fields skipped during serialization and `None` values leave
+/// an empty cell behind.
 pub struct CsvSerializer<W> {
     /// Owned writer
     writer: W,
 
-    /// The current position in a row
-    pos: usize,
+    /// Delimiter, quote, newline and header settings
+    options: CsvOptions,
+
+    /// The name of every column, or `None` if the rows aren't structs
+    columns: Option<Vec<String>>,
+
+    /// The row type, for error messages
+    row_shape: String,
+
+    /// Whether the header record has been written
+    header_written: bool,
 
-    /// Initialized by `start_object`
-    n_fields: usize,
+    /// The index of the next column to write in the current record
+    column: usize,
 
-    /// Delimeter used to separate values
-    delim: &'static [u8],
+    /// How many objects deep we are, 1 being the row itself
+    depth: usize,
 
-    /// Newline encoding
-    newline: &'static [u8],
+    /// Whether we're inside the top-level list of rows
+    in_rows: bool,
+
+    /// The dotted path to the field being written, e.g. `address.city`
+    path: String,
+
+    /// Lengths of `path` before each field name was appended to it
+    path_marks: Vec<usize>,
 }
+
 impl<W> CsvSerializer<W>
 where
     W: Write,
 {
-    /// Initializes a new CSV Serializer
-    pub fn new(writer: W) -> Self {
+    /// Initializes a new CSV Serializer for values of the given shape: a row struct, or a
+    /// list of them
+    pub fn new<'shape>(writer: W, shape: &'shape Shape<'shape>) -> Self {
+        Self::with_options(writer, shape, CsvOptions::default())
+    }
+
+    /// Initializes a new CSV Serializer with the given options
+    pub fn with_options<'shape>(
+        writer: W,
+        shape: &'shape Shape<'shape>,
+        options: CsvOptions,
+    ) -> Self {
+        let row_shape = row_shape(shape);
         Self {
             writer,
-            pos: 0,
-            n_fields: 0,
-            delim: b",",
-            newline: b"\n",
+            options,
+            columns: Layout::of(row_shape).map(|layout| layout.names),
+            row_shape: row_shape.to_string(),
+            header_written: false,
+            column: 0,
+            depth: 0,
+            in_rows: false,
+            path: String::new(),
+            path_marks: Vec::new(),
         }
     }
 
-    fn set_n_fields(&mut self, n_fields: usize) {
-        self.n_fields = n_fields;
+    /// Writes the header record if it's wanted and hasn't been written yet: call this
+    /// once done, so that an empty list of rows still gets a header
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.write_header()?;
+        self.writer.flush()
     }
 
-    /// Conditionally prefix the value with the required delimeter
-    fn start_value(&mut self) -> Result<(), io::Error> {
-        if self.pos == 0 {
-            // no prefix
-            Ok(())
-        } else {
-            self.writer.write_all(self.delim)
+    fn columns(&self) -> io::Result<&[String]> {
+        self.columns.as_deref().ok_or_else(|| {
+            io::Error::other(format!(
+                "CSV rows must be structs with named fields, not {}",
+                self.row_shape
+            ))
+        })
+    }
+
+    fn unsupported(&self, what: &str) -> io::Error {
+        io::Error::other(format!(
+            "{what} can't be written in a CSV cell (at `{}` in {})",
+            self.path, self.row_shape
+        ))
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.header_written || !self.options.get_write_headers() {
+            return Ok(());
+        }
+        self.header_written = true;
+
+        let columns = self.columns()?.to_vec();
+        for (index, name) in columns.iter().enumerate() {
+            if index > 0 {
+                self.writer.write_all(&[self.options.get_delimiter()])?;
+            }
+            self.write_escaped(name)?;
+        }
+        self.writer
+            .write_all(self.options.get_newline().as_str().as_bytes())
+    }
+
+    /// Writes a value in the column named after the current path, leaving the columns
+    /// before it empty
+    fn write_cell(&mut self, value: &str) -> io::Result<()> {
+        if self.depth == 0 {
+            // Not inside a row: this is the whole value, or an item of the top-level list
+            return Err(io::Error::other(format!(
+                "CSV rows must be structs with named fields, not {}",
+                self.row_shape
+            )));
+        }
+
+        let columns = self.columns()?;
+        let Some(index) = columns[self.column..]
+            .iter()
+            .position(|name| *name == self.path)
+            .map(|offset| self.column + offset)
+        else {
+            return Err(io::Error::other(format!(
+                "no CSV column for `{}` in {}",
+                self.path, self.row_shape
+            )));
+        };
+
+        while self.column < index {
+            self.start_cell()?;
         }
+        self.start_cell()?;
+        self.write_escaped(value)
     }
 
-    /// Conditionally suffix the value with the required newline
-    fn end_value(&mut self) -> Result<(), io::Error> {
-        if self.pos == self.n_fields - 1 {
-            // Reset the position to zero
-            self.pos = 0;
-            self.writer.write_all(self.newline)
-        } else {
-            // Increment the position
-            self.pos += 1;
-            // no suffix
-            Ok(())
+    /// Writes the delimiter before the next cell if it's not the first one, and moves on
+    /// to the next column
+    fn start_cell(&mut self) -> io::Result<()> {
+        if self.column > 0 {
+            self.writer.write_all(&[self.options.get_delimiter()])?;
         }
+        self.column += 1;
+        Ok(())
+    }
+
+    /// Leaves the remaining columns of the record empty, and terminates it
+    fn end_record(&mut self) -> io::Result<()> {
+        let n_columns = self.columns()?.len();
+        while self.column < n_columns {
+            self.start_cell()?;
+        }
+        self.column = 0;
+        self.writer
+            .write_all(self.options.get_newline().as_str().as_bytes())
+    }
+
+    /// Writes a cell's text, quoted if it contains the delimiter, the quote or a line break
+    fn write_escaped(&mut self, value: &str) -> io::Result<()> {
+        let delimiter = self.options.get_delimiter();
+        let quote = self.options.get_quote();
+        let needs_quotes = value
+            .bytes()
+            .any(|b| b == delimiter || b == quote || b == b'\r' || b == b'\n');
+        if !needs_quotes {
+            return self.writer.write_all(value.as_bytes());
+        }
+
+        self.writer.write_all(&[quote])?;
+        for (index, part) in value.split(quote as char).enumerate() {
+            if index > 0 {
+                self.writer.write_all(&[quote, quote])?;
+            }
+            self.writer.write_all(part.as_bytes())?;
+        }
+        self.writer.write_all(&[quote])
     }
 }
 
@@ -100,160 +253,152 @@ where
 {
     type Error = io::Error;
 
-    fn start_object(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
-        self.set_n_fields(len.expect("Must know the length of the object for CSV"));
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        if self.depth == 0 {
+            self.write_header()?;
+        }
+        self.depth += 1;
         Ok(())
     }
 
     fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.end_record()?;
+        }
         Ok(())
     }
 
     fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        unimplemented!("Arrays are not implemented yet in CSV")
+        if self.depth > 0 || self.in_rows {
+            return Err(self.unsupported("lists"));
+        }
+        self.write_header()?;
+        self.in_rows = true;
+        Ok(())
     }
 
     fn end_array(&mut self) -> Result<(), Self::Error> {
-        unimplemented!("Arrays are not implemented yet in CSV")
+        self.in_rows = false;
+        Ok(())
     }
 
     fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        unimplemented!("Maps are not implemented yet in CSV")
+        Err(self.unsupported("maps"))
+    }
+
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error {
+        io::Error::other(format!("cannot serialize {shape}: {reason}"))
     }
 
-    fn end_map(&mut self) -> Result<(), Self::Error> {
-        unimplemented!("Maps are not implemented yet in CSV")
+    fn begin_map_key(&mut self) -> Result<(), Self::Error> {
+        // flattened maps don't go through `start_map`
+        Err(self.unsupported("maps"))
     }
 
-    fn serialize_field_name(&mut self, _name: &str) -> Result<(), Self::Error> {
-        // field names are not serialized in CSV
+    fn serialize_field_name(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.path_marks.push(self.path.len());
+        if !self.path.is_empty() {
+            self.path.push('.');
+        }
+        self.path.push_str(name);
+        Ok(())
+    }
+
+    fn end_field(&mut self) -> Result<(), Self::Error> {
+        if let Some(mark) = self.path_marks.pop() {
+            self.path.truncate(mark);
+        }
         Ok(())
     }
 
     fn serialize_unit_variant(
         &mut self,
         _variant_index: usize,
-        _variant_name: &str,
+        variant_name: &str,
     ) -> Result<(), Self::Error> {
-        // unit variants should not serialize to anything
-        Ok(())
+        self.write_cell(variant_name)
     }
 
     fn serialize_u8(&mut self, value: u8) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_u16(&mut self, value: u16) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_u32(&mut self, value: u32) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_usize(&mut self, value: usize) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_i8(&mut self, value: i8) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_i16(&mut self, value: i16) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_i32(&mut self, value: i32) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_isize(&mut self, value: isize) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(&value.to_string())
     }
 
     fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", if value { "true" } else { "false" })?;
-        self.end_value()
+        self.write_cell(if value { "true" } else { "false" })
     }
 
     fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(value.encode_utf8(&mut [0; 4]))
     }
 
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_cell(value)
     }
 
     fn serialize_bytes(&mut self, _value: &[u8]) -> Result<(), Self::Error> {
-        panic!("CSV does not support byte arrays")
+        Err(self.unsupported("byte arrays"))
     }
 
     fn serialize_none(&mut self) -> Result<(), Self::Error> {
-        self.start_value()?;
-        // skip empty columns
-        self.end_value()
+        // empty columns are filled in by the next cell, or at the end of the record
+        Ok(())
     }
 
     fn serialize_unit(&mut self) -> Result<(), Self::Error> {
-        self.start_value()?;
-        // skip empty columns
-        self.end_value()
+        // empty columns are filled in by the next cell, or at the end of the record
+        Ok(())
     }
 }
@@ -0,0 +1,184 @@
+use facet::Facet;
+use facet_csv::{CsvErrorKind, CsvOptions};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Status {
+    Active,
+    Retired,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[facet(rename_all = "kebab-case")]
+struct Employee {
+    full_name: String,
+    #[facet(rename = "years")]
+    age: Option<u32>,
+    status: Status,
+    address: Option<Address>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[facet(deny_unknown_fields)]
+struct Point {
+    x: i32,
+    y: i32,
+    #[facet(default)]
+    label: String,
+}
+
+#[test]
+fn test_reading_rows() {
+    let input = concat!(
+        "status,full-name,years,address.city,address.zip,notes\n",
+        "Retired,Ada Lovelace,36,London,W1,first programmer\n",
+        "\n",
+        "Active,\"Smith, \"\"Agent\"\"\",,,,\n",
+        "Active,\"Grace\r\nHopper\",85,Arlington,,\r\n",
+    );
+    let employees: Vec<Employee> = facet_csv::from_str(input)?;
+    assert_eq!(
+        employees,
+        [
+            Employee {
+                full_name: "Ada Lovelace".to_string(),
+                age: Some(36),
+                status: Status::Retired,
+                address: Some(Address {
+                    city: "London".to_string(),
+                    zip: Some("W1".to_string()),
+                }),
+            },
+            Employee {
+                full_name: "Smith, \"Agent\"".to_string(),
+                age: None,
+                status: Status::Active,
+                address: None,
+            },
+            Employee {
+                full_name: "Grace\r\nHopper".to_string(),
+                age: Some(85),
+                status: Status::Active,
+                address: Some(Address {
+                    city: "Arlington".to_string(),
+                    zip: None,
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_reading_with_options() {
+    let options = CsvOptions::default()
+        .delimiter(b'\t')
+        .quote(b'\'')
+        .has_headers(false);
+    let points: Vec<Point> = facet_csv::from_str_with_options("1\t2\t'a\tb'\n-3\t4\n", options)?;
+    assert_eq!(
+        points,
+        [
+            Point {
+                x: 1,
+                y: 2,
+                label: "a\tb".to_string(),
+            },
+            Point {
+                x: -3,
+                y: 4,
+                label: String::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_reading_from_reader() {
+    let input = "x,y\n1,2\nnope,3\n5,6\n";
+    let rows: Vec<_> = facet_csv::from_reader::<Point, _>(input.as_bytes()).collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        rows[0].as_ref().unwrap(),
+        &Point {
+            x: 1,
+            y: 2,
+            label: String::new(),
+        }
+    );
+    let err = rows[1].as_ref().unwrap_err();
+    assert_eq!(err.line, Some(3));
+    assert!(matches!(err.kind, CsvErrorKind::InvalidValue { ref value, .. } if value == "nope"));
+    assert_eq!(rows[2].as_ref().unwrap().x, 5);
+}
+
+#[test]
+fn test_reading_errors() {
+    let err = facet_csv::from_str::<Vec<Point>>("x,y,z\n1,2,3\n").unwrap_err();
+    assert_eq!(err.line, Some(1));
+    assert!(matches!(err.kind, CsvErrorKind::UnknownColumn { ref column, .. } if column == "z"));
+
+    let err = facet_csv::from_str::<Vec<Point>>("x\n1\n").unwrap_err();
+    assert_eq!(err.line, Some(2));
+    assert!(matches!(
+        err.kind,
+        CsvErrorKind::MissingField { field: "y", .. }
+    ));
+
+    let err = facet_csv::from_str::<Vec<Point>>("x,y\n1,\"2\n").unwrap_err();
+    assert!(matches!(err.kind, CsvErrorKind::UnterminatedQuote));
+
+    let err = facet_csv::from_str::<Vec<Employee>>("full-name,status\nBob,Sleeping\n").unwrap_err();
+    assert!(
+        matches!(err.kind, CsvErrorKind::InvalidValue { ref value, .. } if value == "Sleeping")
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let points = vec![
+        Point {
+            x: 1,
+            y: 2,
+            label: "a, \"b\"".to_string(),
+        },
+        Point {
+            x: 3,
+            y: 4,
+            label: String::new(),
+        },
+    ];
+    let csv = facet_csv::to_string(&points)?;
+    assert_eq!(csv, "x,y,label\n1,2,\"a, \"\"b\"\"\"\n3,4,\n");
+    let back: Vec<Point> = facet_csv::from_str(&csv)?;
+    assert_eq!(back, points);
+}
+
+#[test]
+fn test_round_trip_without_headers() {
+    let points = vec![
+        Point {
+            x: 1,
+            y: 2,
+            label: "a".to_string(),
+        },
+        Point {
+            x: 3,
+            y: 4,
+            label: "b".to_string(),
+        },
+    ];
+    let options = CsvOptions::default()
+        .has_headers(false)
+        .write_headers(false);
+    let csv = facet_csv::to_string_with_options(&points, options)?;
+    assert_eq!(csv, "1,2,a\n3,4,b\n");
+    let back: Vec<Point> = facet_csv::from_str_with_options(&csv, options)?;
+    assert_eq!(back, points);
+}
@@ -1,3 +1,5 @@
+use facet::Facet;
+use facet_csv::{CsvOptions, Newline};
 use facet_testhelpers::test;
 
 #[test]
@@ -9,11 +11,128 @@ fn test_writing_flat_structs() {
         value3: bool,
     }
 
-    let expected_mystruct = "1,some,false\n";
+    let expected_mystruct = "value1,value2,value3\n1,some,false\n";
     let actual = facet_csv::to_string(&MyStruct {
         value1: 1,
         value2: "some",
         value3: false,
-    });
+    })?;
     assert_eq!(expected_mystruct, actual);
 }
+
+#[derive(Facet)]
+struct Address {
+    city: String,
+    zip: String,
+}
+
+#[derive(Facet)]
+struct Audit {
+    created_by: String,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Active,
+    Retired,
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "kebab-case")]
+struct Employee {
+    full_name: String,
+    age: Option<u32>,
+    status: Status,
+    address: Option<Address>,
+    #[facet(flatten)]
+    audit: Audit,
+}
+
+#[test]
+fn test_writing_rows() {
+    let employees = vec![
+        Employee {
+            full_name: "Ada Lovelace".to_string(),
+            age: Some(36),
+            status: Status::Retired,
+            address: Some(Address {
+                city: "London".to_string(),
+                zip: "W1".to_string(),
+            }),
+            audit: Audit {
+                created_by: "hr".to_string(),
+            },
+        },
+        Employee {
+            full_name: "Smith, \"Agent\"".to_string(),
+            age: None,
+            status: Status::Active,
+            address: None,
+            audit: Audit {
+                created_by: "line one\nline two".to_string(),
+            },
+        },
+    ];
+
+    assert_eq!(
+        facet_csv::to_string(&employees)?,
+        concat!(
+            "full-name,age,status,address.city,address.zip,created_by\n",
+            "Ada Lovelace,36,Retired,London,W1,hr\n",
+            "\"Smith, \"\"Agent\"\"\",,Active,,,\"line one\nline two\"\n",
+        )
+    );
+
+    let empty: Vec<Employee> = vec![];
+    assert_eq!(
+        facet_csv::to_string(&empty)?,
+        "full-name,age,status,address.city,address.zip,created_by\n"
+    );
+}
+
+#[test]
+fn test_writing_with_options() {
+    #[derive(Facet)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    let points = [Point { x: 0.5, y: -1.0 }, Point { x: 2.0, y: 3.25 }];
+    let options = CsvOptions::default()
+        .delimiter(b';')
+        .newline(Newline::CrLf)
+        .write_headers(false);
+    assert_eq!(
+        facet_csv::to_string_with_options(&points, options)?,
+        "0.5;-1\r\n2;3.25\r\n"
+    );
+}
+
+#[test]
+fn test_writing_unsupported_values() {
+    #[derive(Facet)]
+    struct Tagged {
+        name: String,
+        tags: Vec<String>,
+    }
+
+    let mut output = Vec::new();
+    let err = facet_csv::to_writer(
+        &Tagged {
+            name: "a".to_string(),
+            tags: vec!["b".to_string()],
+        },
+        &mut output,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("lists"), "{err}");
+
+    let err = facet_csv::to_writer(&vec![1_u32, 2], &mut output).unwrap_err();
+    assert!(err.to_string().contains("must be structs"), "{err}");
+
+    let err = facet_csv::to_string(&vec![1_u32, 2]).unwrap_err();
+    assert!(err.to_string().contains("must be structs"), "{err}");
+}
@@ -136,6 +136,22 @@ pub enum DeserErrorKind<'shape> {
     /// An error occurred when reflecting an enum variant (index) from a user type.
     VariantError(VariantError),
 
+    /// The key holding the variant name (or the variant's content) of a tagged enum
+    /// was missing from the input.
+    MissingTag {
+        /// The name of the missing key
+        tag: &'shape str,
+
+        /// The enum shape definition
+        enum_shape: &'shape Shape<'shape>,
+    },
+
+    /// None of the variants of an untagged enum could be deserialized from the input.
+    NoMatchingVariant {
+        /// The enum shape definition
+        enum_shape: &'shape Shape<'shape>,
+    },
+
     /// Too many elements for an array.
     ArrayOverflow {
         /// The array shape
@@ -287,6 +303,21 @@ impl core::fmt::Display for DeserErrorMessage<'_, '_> {
             DeserErrorKind::VariantError(e) => {
                 write!(f, "Variant error: {e}")
             }
+            DeserErrorKind::MissingTag { tag, enum_shape } => {
+                write!(
+                    f,
+                    "Missing key {} for tagged enum {}",
+                    tag.red(),
+                    enum_shape.yellow()
+                )
+            }
+            DeserErrorKind::NoMatchingVariant { enum_shape } => {
+                write!(
+                    f,
+                    "Data did not match any variant of untagged enum {}",
+                    enum_shape.yellow()
+                )
+            }
             DeserErrorKind::ArrayOverflow { shape, max_len } => {
                 write!(
                     f,
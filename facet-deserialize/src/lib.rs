@@ -7,9 +7,11 @@
 
 extern crate alloc;
 
+use alloc::collections::VecDeque;
 use alloc::string::ToString;
 use alloc::{vec, vec::Vec};
 use core::fmt::Debug;
+use core::ops::Range;
 
 mod debug;
 mod error;
@@ -20,7 +22,8 @@ pub use error::*;
 
 mod span;
 use facet_core::{
-    Characteristic, Def, Facet, FieldFlags, PointerType, ScalarAffinity, StructKind, Type, UserType,
+    Characteristic, Def, Facet, FieldFlags, PointerType, ScalarAffinity, Shape, StructKind, Type,
+    UserType, Variant,
 };
 use owo_colors::OwoColorize;
 pub use span::*;
@@ -299,6 +302,8 @@ pub enum Instruction {
     ListItemOrListClose,
     /// Triggers clearing a substack.
     SubstackClose,
+    /// Keep collecting the value of a tagged or untagged enum.
+    BufferValue,
 }
 
 /// Reasons for expecting a value, reflecting the current parse context.
@@ -462,29 +467,37 @@ where
         array_indices: Vec::new(),
         enum_tuple_field_count: None,
         enum_tuple_current_field: None,
+        enum_buffer: None,
+        replay: VecDeque::new(),
+        resume_span: None,
     };
 
     macro_rules! next {
         ($runner:ident, $wip:ident, $expectation:expr, $method:ident) => {{
-            let nd = NextData {
-                start: $runner.last_span.end(), // or supply the appropriate start value if available
-                runner: $runner,
-                wip: $wip,
+            let outcome = if let Some(outcome) = $runner.replay.pop_front() {
+                // Events collected for a tagged enum are fed back before parsing more input
+                outcome
+            } else {
+                let nd = NextData {
+                    start: $runner.last_span.end(), // or supply the appropriate start value if available
+                    runner: $runner,
+                    wip: $wip,
+                };
+                let (nd, res) = format.next(nd, $expectation);
+                $runner = nd.runner;
+                $wip = nd.wip;
+                res.map_err(|span_kind| {
+                    $runner.last_span = span_kind.span;
+                    let error = $runner.err(span_kind.node);
+                    // Convert the error's span to Cooked
+                    DeserError {
+                        input: error.input,
+                        span: error.span.to_cooked(format, input),
+                        kind: error.kind,
+                        source_id: error.source_id,
+                    }
+                })?
             };
-            let (nd, res) = format.next(nd, $expectation);
-            $runner = nd.runner;
-            $wip = nd.wip;
-            let outcome = res.map_err(|span_kind| {
-                $runner.last_span = span_kind.span;
-                let error = $runner.err(span_kind.node);
-                // Convert the error's span to Cooked
-                DeserError {
-                    input: error.input,
-                    span: error.span.to_cooked(format, input),
-                    kind: error.kind,
-                    source_id: error.source_id,
-                }
-            })?;
             if F::SpanType::USES_SUBSTACK {
                 if !$runner.substack.get().is_empty() {
                     trace!("Substack: {}", "carried".cyan());
@@ -506,6 +519,7 @@ where
                     source_id: error.source_id,
                 }
             })?;
+            $runner.finish_replay();
         }};
     }
 
@@ -595,6 +609,15 @@ where
             Instruction::SubstackClose => {
                 runner.substack.clear();
             }
+            Instruction::BufferValue => {
+                let expectation = runner.buffer_expectation();
+                next!(runner, wip, expectation, buffer_value);
+            }
+            Instruction::SkipValue if !runner.replay.is_empty() => {
+                // The value to skip was already collected for a tagged enum
+                runner.skip_replayed_value();
+                runner.finish_replay();
+            }
             Instruction::SkipValue => {
                 // Call F::skip to skip over the next value in the input
                 let nd = NextData {
@@ -880,6 +903,180 @@ pub struct StackRunner<'input, C = Cooked, I: ?Sized + 'input = [u8]> {
 
     /// Tuple variant field tracking - current field index being processed
     pub enum_tuple_current_field: Option<usize>,
+
+    /// Events of a tagged or untagged enum value being collected
+    enum_buffer: Option<EnumBuffer<'input, C>>,
+
+    /// Collected events to process before parsing more input
+    replay: VecDeque<Spanned<Outcome<'input>, C>>,
+
+    /// Span of the last collected event, to resume parsing from once `replay` is exhausted
+    resume_span: Option<Span<C>>,
+}
+
+/// The kind of container an [`EnumBuffer`] is currently inside of
+enum BufferedContainer {
+    /// A list/array
+    List,
+    /// An object/map, which either expects a key or a value next
+    Object { expecting_key: bool },
+}
+
+/// Collects the events making up the value of an internally tagged, adjacently tagged
+/// or untagged enum: its variant can only be determined once the whole value has been seen.
+struct EnumBuffer<'input, C> {
+    /// The events collected so far
+    events: Vec<Spanned<Outcome<'input>, C>>,
+
+    /// The containers that are still open
+    containers: Vec<BufferedContainer>,
+}
+
+impl<'input, C> EnumBuffer<'input, C> {
+    fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            containers: Vec::new(),
+        }
+    }
+
+    /// What the format should produce next
+    fn expectation(&self) -> Expectation {
+        match self.containers.last() {
+            Some(BufferedContainer::List) => Expectation::ListItemOrListClose,
+            Some(BufferedContainer::Object {
+                expecting_key: true,
+            }) => Expectation::ObjectKeyOrObjectClose,
+            Some(BufferedContainer::Object {
+                expecting_key: false,
+            }) => Expectation::ObjectVal,
+            None => Expectation::Value,
+        }
+    }
+
+    /// Records an event, keeping track of open containers
+    fn push(&mut self, outcome: Spanned<Outcome<'input>, C>) {
+        match &outcome.node {
+            Outcome::ObjectStarted => {
+                self.value_started();
+                self.containers.push(BufferedContainer::Object {
+                    expecting_key: true,
+                });
+            }
+            Outcome::ListStarted => {
+                self.value_started();
+                self.containers.push(BufferedContainer::List);
+            }
+            Outcome::ObjectEnded | Outcome::ListEnded => {
+                self.containers.pop();
+            }
+            Outcome::Scalar(_) => {
+                // Either a key or a value
+                if let Some(BufferedContainer::Object { expecting_key }) =
+                    self.containers.last_mut()
+                {
+                    *expecting_key = !*expecting_key;
+                }
+            }
+            Outcome::Resegmented(_) => return,
+        }
+        self.events.push(outcome);
+    }
+
+    /// Marks the value of the current object entry as started
+    fn value_started(&mut self) {
+        if let Some(BufferedContainer::Object { expecting_key }) = self.containers.last_mut() {
+            *expecting_key = true;
+        }
+    }
+
+    /// Whether the whole value has been collected
+    fn is_complete(&self) -> bool {
+        !self.events.is_empty() && self.containers.is_empty()
+    }
+}
+
+/// Whether the variant of an enum of this shape can only be determined after collecting
+/// its whole value, as is the case for internally tagged, adjacently tagged and untagged enums.
+fn needs_enum_buffer(shape: &Shape) -> bool {
+    matches!(shape.ty, Type::User(UserType::Enum(_)))
+        && (shape.has_untagged_attr() || shape.get_tag_attr().is_some())
+}
+
+/// Whether a variant wraps a single unnamed value, like `Variant(T)`
+fn variant_is_newtype_like(variant: &Variant) -> bool {
+    variant.data.kind == StructKind::Tuple && variant.data.fields.len() == 1
+}
+
+/// Whether the data of a variant could be deserialized from a value starting with
+/// `first`, so untagged enums only replay their events for plausible variants.
+/// Shapes whose representation can't be told from the first event are kept.
+fn variant_could_start_with(variant: &Variant, first: &Outcome) -> bool {
+    if variant.data.fields.is_empty() {
+        // Unit variants are represented as null
+        matches!(first, Outcome::Scalar(Scalar::Null))
+    } else if variant_is_newtype_like(variant) {
+        shape_could_start_with(variant.data.fields[0].shape(), first)
+    } else if variant.data.kind == StructKind::Struct {
+        matches!(first, Outcome::ObjectStarted)
+    } else {
+        matches!(first, Outcome::ListStarted)
+    }
+}
+
+fn shape_could_start_with(shape: &Shape, first: &Outcome) -> bool {
+    match shape.def {
+        Def::Scalar(_) => matches!(first, Outcome::Scalar(_)),
+        Def::Map(_) => matches!(first, Outcome::ObjectStarted),
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => matches!(first, Outcome::ListStarted),
+        Def::Undefined if shape.inner.is_none() => match shape.ty {
+            Type::User(UserType::Struct(sd)) => match sd.kind {
+                StructKind::Struct => matches!(first, Outcome::ObjectStarted),
+                StructKind::Tuple | StructKind::TupleStruct => {
+                    matches!(first, Outcome::ListStarted)
+                }
+                _ => true,
+            },
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+fn clone_event<'input, C>(event: &Spanned<Outcome<'input>, C>) -> Spanned<Outcome<'input>, C> {
+    Spanned {
+        node: event.node.clone(),
+        span: event.span,
+    }
+}
+
+/// Splits the collected events of an object into its entries: the key, and the range
+/// of events making up the value.
+fn buffered_entries<'a, C>(events: &'a [Spanned<Outcome<'_>, C>]) -> Vec<(&'a str, Range<usize>)> {
+    let mut entries = Vec::new();
+
+    // Skip the `ObjectStarted` event
+    let mut index = 1;
+    while let Some(Outcome::Scalar(Scalar::String(key))) = events.get(index).map(|e| &e.node) {
+        let start = index + 1;
+        let mut end = start;
+        let mut depth = 0usize;
+        while let Some(event) = events.get(end) {
+            match event.node {
+                Outcome::ObjectStarted | Outcome::ListStarted => depth += 1,
+                Outcome::ObjectEnded | Outcome::ListEnded => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            end += 1;
+            if depth == 0 {
+                break;
+            }
+        }
+        entries.push((key.as_ref(), start..end));
+        index = end;
+    }
+
+    entries
 }
 
 impl<'input, 'shape, C, I: ?Sized + 'input> StackRunner<'input, C, I>
@@ -926,7 +1123,19 @@ where
                         self.reflect_err(err)
                     })?;
                     if !is_set {
-                        if field.flags.contains(FieldFlags::DEFAULT) {
+                        if field.flags.contains(FieldFlags::FLATTEN)
+                            && matches!(field.shape().def, Def::Map(_))
+                        {
+                            // A flattened map that didn't collect any unknown field is empty
+                            wip.begin_nth_map_field(index)
+                                .map_err(|e| self.reflect_err(e))?;
+                            wip.end().map_err(|e| self.reflect_err(e))?;
+                            trace!(
+                                "Flattened map #{} {} was set to an empty map",
+                                index.yellow(),
+                                field.name.green(),
+                            );
+                        } else if field.flags.contains(FieldFlags::DEFAULT) {
                             wip.begin_nth_field(index)
                                 .map_err(|e| self.reflect_err(e))?;
 
@@ -1179,6 +1388,342 @@ where
         Ok(wip)
     }
 
+    /// Once all collected events have been processed, resume parsing after them.
+    fn finish_replay(&mut self) {
+        if self.replay.is_empty() {
+            if let Some(span) = self.resume_span.take() {
+                self.last_span = span;
+            }
+        }
+    }
+
+    /// Queues collected events, to be processed before parsing more input.
+    fn queue_replay(&mut self, events: Vec<Spanned<Outcome<'input>, C>>) {
+        if self.resume_span.is_none() {
+            self.resume_span = Some(self.last_span);
+        }
+        for event in events.into_iter().rev() {
+            self.replay.push_front(event);
+        }
+    }
+
+    /// Skips the next value among the collected events.
+    fn skip_replayed_value(&mut self) {
+        let mut depth = 0usize;
+        while let Some(event) = self.replay.pop_front() {
+            self.last_span = event.span;
+            match event.node {
+                Outcome::ObjectStarted | Outcome::ListStarted => depth += 1,
+                Outcome::ObjectEnded | Outcome::ListEnded => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+
+    /// What the format should produce next for the enum value being collected.
+    fn buffer_expectation(&self) -> Expectation {
+        self.enum_buffer
+            .as_ref()
+            .map(|buffer| buffer.expectation())
+            .unwrap_or(Expectation::Value)
+    }
+
+    /// Collects an event of a tagged or untagged enum value, and selects the variant
+    /// once the whole value has been seen.
+    fn buffer_value<'facet>(
+        &mut self,
+        wip: Partial<'facet, 'shape>,
+        outcome: Spanned<Outcome<'input>, C>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        let buffer = self
+            .enum_buffer
+            .as_mut()
+            .expect("enum buffer should be active");
+        buffer.push(outcome);
+
+        if !buffer.is_complete() {
+            self.stack.push(Instruction::BufferValue);
+            return Ok(wip);
+        }
+
+        let events = self.enum_buffer.take().unwrap().events;
+        trace!(
+            "Collected {} events for {}",
+            events.len(),
+            wip.shape().blue()
+        );
+        if wip.shape().has_untagged_attr() {
+            self.select_untagged_variant(wip, events)
+        } else {
+            self.select_tagged_variant(wip, events)
+        }
+    }
+
+    /// Selects the variant of an internally or adjacently tagged enum from its collected
+    /// events, then queues the events making up the variant's data.
+    fn select_tagged_variant<'facet>(
+        &mut self,
+        mut wip: Partial<'facet, 'shape>,
+        events: Vec<Spanned<Outcome<'input>, C>>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        let enum_shape = wip.shape();
+        let tag = enum_shape.get_tag_attr().unwrap();
+
+        if !matches!(events[0].node, Outcome::ObjectStarted) {
+            return Err(self.err(DeserErrorKind::UnexpectedOutcome {
+                got: events[0].node.clone().into_owned(),
+                wanted: "object for tagged enum",
+            }));
+        }
+
+        let entries = buffered_entries(&events);
+        let Some((_, tag_range)) = entries.iter().find(|(key, _)| *key == tag).cloned() else {
+            return Err(self.err(DeserErrorKind::MissingTag { tag, enum_shape }));
+        };
+        let variant_name = match &events[tag_range.start].node {
+            Outcome::Scalar(Scalar::String(name)) if tag_range.len() == 1 => name,
+            other => {
+                return Err(self.err(DeserErrorKind::UnexpectedOutcome {
+                    got: other.clone().into_owned(),
+                    wanted: "string naming the enum variant",
+                }));
+            }
+        };
+        let Some((variant_index, variant)) = wip.find_variant(variant_name) else {
+            return Err(self.err(DeserErrorKind::NoSuchVariant {
+                name: variant_name.to_string(),
+                enum_shape,
+            }));
+        };
+        trace!(
+            "Selecting variant {}::{} from tag {}",
+            enum_shape.blue(),
+            variant.name.yellow(),
+            tag.green()
+        );
+        wip.select_nth_variant(variant_index)
+            .map_err(|e| self.reflect_err(e))?;
+
+        if variant.data.fields.is_empty() {
+            // Unit variants have no data, anything besides the tag is ignored
+            return Ok(wip);
+        }
+
+        let replay: Vec<_> = if let Some(content) = enum_shape.get_content_attr() {
+            // Adjacently tagged: the data is the value of the content key
+            let Some((_, content_range)) = entries.iter().find(|(key, _)| *key == content) else {
+                return Err(self.err(DeserErrorKind::MissingTag {
+                    tag: content,
+                    enum_shape,
+                }));
+            };
+            events[content_range.clone()]
+                .iter()
+                .map(clone_event)
+                .collect()
+        } else {
+            // Internally tagged: the data is the object itself, minus the tag
+            if !(variant.data.kind == StructKind::Struct || variant_is_newtype_like(variant)) {
+                return Err(self.err(DeserErrorKind::UnsupportedType {
+                    got: enum_shape,
+                    wanted: "struct or newtype variant for internally tagged enum",
+                }));
+            }
+            let tag_key_index = tag_range.start - 1;
+            events
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != tag_key_index && !tag_range.contains(index))
+                .map(|(_, event)| clone_event(event))
+                .collect()
+        };
+
+        if variant_is_newtype_like(variant) {
+            wip.begin_nth_field(0).map_err(|e| self.reflect_err(e))?;
+            self.stack.push(Instruction::Pop(PopReason::ObjectVal));
+        }
+        self.stack.push(Instruction::Value(ValueReason::TopLevel));
+        self.queue_replay(replay);
+        Ok(wip)
+    }
+
+    /// Selects the first variant of an untagged enum its collected events can be
+    /// deserialized into.
+    ///
+    /// Every candidate replays the collected events from the start, so variants whose
+    /// data can't begin with the first event are skipped without a replay. Reading an
+    /// untagged value therefore costs at most one replay of its events per variant
+    /// whose representation matches how the value starts.
+    fn select_untagged_variant<'facet>(
+        &mut self,
+        mut wip: Partial<'facet, 'shape>,
+        events: Vec<Spanned<Outcome<'input>, C>>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        let enum_shape = wip.shape();
+        let Type::User(UserType::Enum(ed)) = enum_shape.ty else {
+            unreachable!("untagged attribute on a non-enum shape")
+        };
+
+        let Some(first) = events.first() else {
+            return Err(self.err(DeserErrorKind::UnexpectedEof {
+                wanted: "value for untagged enum",
+            }));
+        };
+
+        for (variant_index, variant) in ed.variants.iter().enumerate() {
+            if !variant_could_start_with(variant, &first.node) {
+                continue;
+            }
+            trace!(
+                "Trying variant {}::{}",
+                enum_shape.blue(),
+                variant.name.yellow()
+            );
+
+            if variant.data.fields.is_empty() {
+                // Unit variants are represented as null
+                if matches!(
+                    events[..],
+                    [Spanned {
+                        node: Outcome::Scalar(Scalar::Null),
+                        ..
+                    }]
+                ) {
+                    wip.select_nth_variant(variant_index)
+                        .map_err(|e| self.reflect_err(e))?;
+                    return Ok(wip);
+                }
+                continue;
+            }
+
+            match self.deserialize_untagged_variant(enum_shape, variant_index, variant, &events) {
+                Ok(value) => {
+                    wip.set_from_heap_value(value)
+                        .map_err(|e| self.reflect_err(e))?;
+                    return Ok(wip);
+                }
+                Err(_) => {
+                    trace!(
+                        "Variant {}::{} does not match",
+                        enum_shape.blue(),
+                        variant.name.yellow()
+                    );
+                }
+            }
+        }
+
+        Err(self.err(DeserErrorKind::NoMatchingVariant { enum_shape }))
+    }
+
+    /// Attempts to deserialize collected events as the given variant of an untagged enum.
+    fn deserialize_untagged_variant<'facet>(
+        &self,
+        enum_shape: &'shape Shape<'shape>,
+        variant_index: usize,
+        variant: &'shape Variant<'shape>,
+        events: &[Spanned<Outcome<'input>, C>],
+    ) -> Result<HeapValue<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        let mut runner = StackRunner {
+            original_input: self.original_input,
+            input: self.input,
+            stack: vec![Instruction::Pop(PopReason::TopLevel)],
+            substack: Substack::new(),
+            last_span: self.last_span,
+            format_source: self.format_source,
+            array_indices: Vec::new(),
+            enum_tuple_field_count: None,
+            enum_tuple_current_field: None,
+            enum_buffer: None,
+            replay: events.iter().map(clone_event).collect(),
+            resume_span: None,
+        };
+
+        let mut wip = Partial::alloc_shape(enum_shape).map_err(|e| self.reflect_err(e))?;
+        wip.select_nth_variant(variant_index)
+            .map_err(|e| self.reflect_err(e))?;
+        if variant_is_newtype_like(variant) {
+            wip.begin_nth_field(0).map_err(|e| self.reflect_err(e))?;
+            runner.stack.push(Instruction::Pop(PopReason::ObjectVal));
+        }
+        runner.stack.push(Instruction::Value(ValueReason::TopLevel));
+
+        runner.run_replay(wip)
+    }
+
+    /// Drives the instruction stack using collected events only, until the top-level
+    /// value is complete.
+    fn run_replay<'facet>(
+        &mut self,
+        mut wip: Partial<'facet, 'shape>,
+    ) -> Result<HeapValue<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        loop {
+            let insn = match self.stack.pop() {
+                Some(insn) => insn,
+                None => unreachable!("Instruction stack is empty"),
+            };
+            trace!("Instruction {:?} (replay)", insn.bright_red());
+
+            if let Instruction::Pop(reason) = insn {
+                wip = self.pop(wip, reason)?;
+                if reason == PopReason::TopLevel {
+                    if let Some(event) = self.replay.front() {
+                        return Err(self.err(DeserErrorKind::UnexpectedOutcome {
+                            got: event.node.clone().into_owned(),
+                            wanted: "end of value",
+                        }));
+                    }
+                    return wip.build().map_err(|e| self.reflect_err(e));
+                }
+                wip.end().map_err(|e| self.reflect_err(e))?;
+                continue;
+            }
+
+            match insn {
+                Instruction::SubstackClose => self.substack.clear(),
+                Instruction::SkipValue => self.skip_replayed_value(),
+                _ => {
+                    let Some(outcome) = self.replay.pop_front() else {
+                        return Err(self.err(DeserErrorKind::UnexpectedEof {
+                            wanted: "more data for enum variant",
+                        }));
+                    };
+                    self.last_span = outcome.span;
+                    wip = match insn {
+                        Instruction::Value(_) => self.value(wip, outcome)?,
+                        Instruction::ObjectKeyOrObjectClose => {
+                            self.object_key_or_object_close(wip, outcome)?
+                        }
+                        Instruction::ListItemOrListClose => {
+                            self.list_item_or_list_close(wip, outcome)?
+                        }
+                        Instruction::BufferValue => self.buffer_value(wip, outcome)?,
+                        Instruction::Pop(_)
+                        | Instruction::SubstackClose
+                        | Instruction::SkipValue => unreachable!(),
+                    };
+                }
+            }
+        }
+    }
+
     /// Internal common handler for GotScalar outcome, to deduplicate code.
     /// Helper to set numeric values with type conversion
     fn set_numeric_value<'facet, N>(
@@ -1421,8 +1966,10 @@ where
         let original_shape = wip.shape();
         trace!("Handling value of type {}", original_shape.blue());
 
-        // Handle null values
-        if matches!(outcome.node, Outcome::Scalar(Scalar::Null)) {
+        // Handle null values (which may select a unit variant of an untagged enum)
+        if matches!(outcome.node, Outcome::Scalar(Scalar::Null))
+            && !(needs_enum_buffer(original_shape) && wip.selected_variant().is_none())
+        {
             wip.set_default().map_err(|e| self.reflect_err(e))?;
             return Ok(wip);
         }
@@ -1470,6 +2017,15 @@ where
             );
         }
 
+        if needs_enum_buffer(wip.shape()) && wip.selected_variant().is_none() {
+            trace!(
+                "Collecting value of {} to determine its variant",
+                wip.shape().blue()
+            );
+            self.enum_buffer = Some(EnumBuffer::new());
+            return self.buffer_value(wip, outcome);
+        }
+
         match outcome.node {
             Outcome::Scalar(s) => {
                 trace!("Parsed scalar value: {}", s.cyan());
@@ -1596,6 +2152,10 @@ where
                         trace!("Object starting for map value ({})!", shape.blue());
                         wip.begin_map().map_err(|e| self.reflect_err(e))?;
                     }
+                    Def::Result(_) => {
+                        trace!("Object starting for result value ({})!", shape.blue());
+                        // nothing to do here, the key picks `Ok` or `Err`
+                    }
                     _ => {
                         // For non-collection types, check the Type enum
                         if let Type::User(user_ty) = shape.ty {
@@ -1651,6 +2211,33 @@ where
         Ok(wip)
     }
 
+    /// Inserts a new entry with the given key into the map at the top of the stack, and
+    /// begins its value.
+    fn begin_map_entry<'facet>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        key: &str,
+    ) -> Result<(), DeserError<'input, 'shape, C>> {
+        wip.begin_key().map_err(|e| self.reflect_err(e))?;
+
+        // Check if the map key type is transparent (has an inner shape)
+        if wip.shape().inner.is_some() {
+            // For transparent types, we need to navigate into the inner type
+            // The inner type should be String for JSON object keys
+            // Use begin_inner for consistency with begin_* naming convention
+            wip.begin_inner().map_err(|e| self.reflect_err(e))?;
+            wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
+            wip.end().map_err(|e| self.reflect_err(e))?; // End inner
+        } else {
+            // For non-transparent types, set the string directly
+            wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
+        }
+
+        wip.end().map_err(|e| self.reflect_err(e))?; // Complete the key frame
+        wip.begin_value().map_err(|e| self.reflect_err(e))?;
+        Ok(())
+    }
+
     fn object_key_or_object_close<'facet>(
         &mut self,
         mut wip: Partial<'facet, 'shape>,
@@ -1672,6 +2259,7 @@ where
                 let mut ignore = false;
                 let mut needs_pop = true;
                 let mut handled_by_flatten = false;
+                let mut handled_by_flattened_map = false;
                 let has_substack = !self.substack.get().is_empty();
 
                 let shape = wip.innermost_shape();
@@ -1688,10 +2276,12 @@ where
                                 shape.blue()
                             );
 
-                            // Check for flattened fields
+                            // Check for flattened fields (flattened maps are only a fallback)
                             let mut found_in_flatten = false;
                             for (index, field) in sd.fields.iter().enumerate() {
-                                if field.flags.contains(FieldFlags::FLATTEN) {
+                                if field.flags.contains(FieldFlags::FLATTEN)
+                                    && !matches!(field.shape().def, Def::Map(_))
+                                {
                                     trace!("Found flattened field #{}", index);
                                     // Enter the flattened field
                                     wip.begin_nth_field(index)
@@ -1720,6 +2310,21 @@ where
                                 }
                             }
 
+                            // A flattened map collects every key that isn't claimed by a field
+                            if !found_in_flatten {
+                                if let Some(index) = sd.fields.iter().position(|field| {
+                                    field.flags.contains(FieldFlags::FLATTEN)
+                                        && matches!(field.shape().def, Def::Map(_))
+                                }) {
+                                    trace!("Collecting key {} into flattened map #{}", key, index);
+                                    wip.begin_nth_map_field(index)
+                                        .map_err(|e| self.reflect_err(e))?;
+                                    self.begin_map_entry(&mut wip, &key)?;
+                                    found_in_flatten = true;
+                                    handled_by_flattened_map = true;
+                                }
+                            }
+
                             if !found_in_flatten {
                                 if wip.shape().has_deny_unknown_fields_attr() {
                                     trace!(
@@ -1739,6 +2344,15 @@ where
                         }
                     }
                     Type::User(UserType::Enum(_ed)) => match wip.find_variant(&key) {
+                        // Fields of an already selected variant take precedence over variant names
+                        _ if wip.selected_variant().is_some()
+                            && wip.field_index(&key).is_some() =>
+                        {
+                            trace!("Found field {} in selected variant", key.blue());
+                            let index = wip.field_index(&key).unwrap();
+                            wip.begin_nth_field(index)
+                                .map_err(|e| self.reflect_err(e))?;
+                        }
                         Some((index, variant)) => {
                             trace!(
                                 "Selecting variant {}::{}",
@@ -1801,25 +2415,20 @@ where
                     },
                     _ => {
                         // Check if it's a map
-                        if let Def::Map(map_def) = shape.def {
-                            wip.begin_key().map_err(|e| self.reflect_err(e))?;
-
-                            // Check if the map key type is transparent (has an inner shape)
-                            let key_shape = map_def.k();
-                            if key_shape.inner.is_some() {
-                                // For transparent types, we need to navigate into the inner type
-                                // The inner type should be String for JSON object keys
-                                // Use begin_inner for consistency with begin_* naming convention
-                                wip.begin_inner().map_err(|e| self.reflect_err(e))?;
-                                wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
-                                wip.end().map_err(|e| self.reflect_err(e))?; // End inner
-                            } else {
-                                // For non-transparent types, set the string directly
-                                wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
-                            }
-
-                            wip.end().map_err(|e| self.reflect_err(e))?; // Complete the key frame
-                            wip.begin_value().map_err(|e| self.reflect_err(e))?;
+                        if let Def::Map(_) = shape.def {
+                            self.begin_map_entry(&mut wip, &key)?;
+                        } else if let Def::Result(_) = shape.def {
+                            // Results are externally tagged, like enums
+                            match key.as_ref() {
+                                "Ok" => wip.begin_ok().map_err(|e| self.reflect_err(e))?,
+                                "Err" => wip.begin_err().map_err(|e| self.reflect_err(e))?,
+                                _ => {
+                                    return Err(self.err(DeserErrorKind::NoSuchVariant {
+                                        name: key.to_string(),
+                                        enum_shape: wip.shape(),
+                                    }));
+                                }
+                            };
                         } else {
                             return Err(self.err(DeserErrorKind::Unimplemented(
                                 "object key for non-struct/map",
@@ -1832,7 +2441,17 @@ where
                 if ignore {
                     self.stack.push(Instruction::SkipValue);
                 } else {
-                    if needs_pop && !handled_by_flatten {
+                    if handled_by_flattened_map {
+                        // Unlike flattened structs, the map is left right after its entry, so
+                        // that the next key is looked up in the outer struct again
+                        trace!("Pushing Pop insns to stack (ObjectVal) for flattened map entry");
+                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
+                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
+                        if has_substack {
+                            trace!("Pushing SubstackClose insn to stack");
+                            self.stack.push(Instruction::SubstackClose);
+                        }
+                    } else if needs_pop && !handled_by_flatten {
                         trace!("Pushing Pop insn to stack (ObjectVal)");
                         self.stack.push(Instruction::Pop(PopReason::ObjectVal));
                         if has_substack {
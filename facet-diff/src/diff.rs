@@ -0,0 +1,360 @@
+//! Structural diffing of values through reflection
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use facet_core::{Def, Facet, Field, FieldFlags, StructType, Type, UserType, Variant};
+use facet_reflect::Peek;
+
+use crate::sequence::diff_sequences;
+
+/// The difference between two values of the same shape
+///
+/// Leaves (scalars, and anything facet can't look into) are compared with their
+/// `PartialEq` implementation. Structs, enums, lists and maps are walked so that
+/// only the parts that actually changed are reported.
+pub enum Diff<'mem, 'facet, 'shape> {
+    /// Both values are equal
+    Equal,
+
+    /// The value was replaced as a whole: a changed scalar, a different enum variant,
+    /// `Some` vs `None`, or two values of different shapes.
+    Replace {
+        /// The old value
+        from: Peek<'mem, 'facet, 'shape>,
+        /// The new value
+        to: Peek<'mem, 'facet, 'shape>,
+    },
+
+    /// A field marked as sensitive changed. Its values are not kept, so that they
+    /// can't end up in test output or logs.
+    Redacted,
+
+    /// Some fields of a struct, or of an enum variant that is active on both sides, changed
+    Fields {
+        /// The old value
+        from: Peek<'mem, 'facet, 'shape>,
+        /// The new value
+        to: Peek<'mem, 'facet, 'shape>,
+        /// The active variant, if the value is an enum
+        variant: Option<&'shape Variant<'shape>>,
+        /// The fields that changed, in declaration order
+        changes: Vec<FieldChange<'mem, 'facet, 'shape>>,
+    },
+
+    /// Elements were inserted into, deleted from, or changed in a list, array or slice
+    Sequence {
+        /// The old value
+        from: Peek<'mem, 'facet, 'shape>,
+        /// The new value
+        to: Peek<'mem, 'facet, 'shape>,
+        /// The element changes
+        changes: Vec<SeqChange<'mem, 'facet, 'shape>>,
+    },
+
+    /// Keys were added to, removed from, or changed in a map
+    Map {
+        /// The old value
+        from: Peek<'mem, 'facet, 'shape>,
+        /// The new value
+        to: Peek<'mem, 'facet, 'shape>,
+        /// The entry changes
+        changes: Vec<MapChange<'mem, 'facet, 'shape>>,
+    },
+}
+
+/// A change to a single field of a struct or enum variant
+pub struct FieldChange<'mem, 'facet, 'shape> {
+    /// The name of the field
+    pub name: &'shape str,
+    /// How the field changed
+    pub diff: Diff<'mem, 'facet, 'shape>,
+}
+
+/// A change to an element of a list, array or slice
+pub enum SeqChange<'mem, 'facet, 'shape> {
+    /// An element of the old sequence that is not in the new one
+    Deleted {
+        /// The index of the element in the old sequence
+        index: usize,
+        /// The element
+        value: Peek<'mem, 'facet, 'shape>,
+    },
+
+    /// An element of the new sequence that was not in the old one
+    Inserted {
+        /// The index of the element in the new sequence
+        index: usize,
+        /// The element
+        value: Peek<'mem, 'facet, 'shape>,
+    },
+
+    /// An element that was modified
+    Changed {
+        /// The index of the element in the old sequence
+        from_index: usize,
+        /// The index of the element in the new sequence
+        to_index: usize,
+        /// How the element changed
+        diff: Diff<'mem, 'facet, 'shape>,
+    },
+}
+
+/// A change to an entry of a map
+pub enum MapChange<'mem, 'facet, 'shape> {
+    /// A key that is only in the new map
+    Added {
+        /// The key
+        key: Peek<'mem, 'facet, 'shape>,
+        /// Its value in the new map
+        value: Peek<'mem, 'facet, 'shape>,
+    },
+
+    /// A key that is only in the old map
+    Removed {
+        /// The key
+        key: Peek<'mem, 'facet, 'shape>,
+        /// Its value in the old map
+        value: Peek<'mem, 'facet, 'shape>,
+    },
+
+    /// A key that is in both maps, with different values
+    Changed {
+        /// The key
+        key: Peek<'mem, 'facet, 'shape>,
+        /// How the value changed
+        diff: Diff<'mem, 'facet, 'shape>,
+    },
+}
+
+impl<'mem, 'facet, 'shape> Diff<'mem, 'facet, 'shape> {
+    /// Computes the difference between two values
+    pub fn new(from: Peek<'mem, 'facet, 'shape>, to: Peek<'mem, 'facet, 'shape>) -> Self {
+        if from.shape() != to.shape() {
+            return Diff::Replace { from, to };
+        }
+
+        match from.shape().def {
+            Def::Scalar(_) => Self::leaf(from, to),
+            Def::Option(_) => Self::option(from, to),
+            Def::Map(_) => Self::map(from, to),
+            Def::List(_) | Def::Array(_) => Self::sequence(from, to),
+            Def::SmartPointer(_) => Self::smart_pointer(from, to),
+            _ => match from.shape().ty {
+                Type::User(UserType::Struct(ty)) => Self::struct_(from, to, ty),
+                Type::User(UserType::Enum(_)) => Self::enum_(from, to),
+                // `&[T]` is a pointer to a slice
+                Type::Pointer(_) if from.into_list_like().is_ok() => Self::sequence(from, to),
+                _ => Self::leaf(from, to),
+            },
+        }
+    }
+
+    /// Returns true if both values are equal
+    pub fn is_equal(&self) -> bool {
+        matches!(self, Diff::Equal)
+    }
+
+    fn leaf(from: Peek<'mem, 'facet, 'shape>, to: Peek<'mem, 'facet, 'shape>) -> Self {
+        // Values that can't be compared are reported as changed
+        match from.partial_eq(&to) {
+            Some(true) => Diff::Equal,
+            _ => Diff::Replace { from, to },
+        }
+    }
+
+    fn option(from: Peek<'mem, 'facet, 'shape>, to: Peek<'mem, 'facet, 'shape>) -> Self {
+        let (Ok(from_option), Ok(to_option)) = (from.into_option(), to.into_option()) else {
+            return Self::leaf(from, to);
+        };
+        match (from_option.value(), to_option.value()) {
+            (Some(from_inner), Some(to_inner)) => Self::new(from_inner, to_inner),
+            (None, None) => Diff::Equal,
+            _ => Diff::Replace { from, to },
+        }
+    }
+
+    fn smart_pointer(from: Peek<'mem, 'facet, 'shape>, to: Peek<'mem, 'facet, 'shape>) -> Self {
+        let from_inner = from
+            .into_smart_pointer()
+            .ok()
+            .and_then(|ptr| ptr.borrow_inner());
+        let to_inner = to
+            .into_smart_pointer()
+            .ok()
+            .and_then(|ptr| ptr.borrow_inner());
+        match (from_inner, to_inner) {
+            (Some(from_inner), Some(to_inner)) => Self::new(from_inner, to_inner),
+            _ => Self::leaf(from, to),
+        }
+    }
+
+    fn struct_(
+        from: Peek<'mem, 'facet, 'shape>,
+        to: Peek<'mem, 'facet, 'shape>,
+        ty: StructType<'shape>,
+    ) -> Self {
+        let (Ok(from_struct), Ok(to_struct)) = (from.into_struct(), to.into_struct()) else {
+            return Self::leaf(from, to);
+        };
+
+        let mut changes = Vec::new();
+        for (index, field) in ty.fields.iter().enumerate() {
+            if let (Ok(from_field), Ok(to_field)) =
+                (from_struct.field(index), to_struct.field(index))
+            {
+                changes.extend(FieldChange::new(field, from_field, to_field));
+            }
+        }
+        Self::fields(from, to, None, changes)
+    }
+
+    fn enum_(from: Peek<'mem, 'facet, 'shape>, to: Peek<'mem, 'facet, 'shape>) -> Self {
+        let (Ok(from_enum), Ok(to_enum)) = (from.into_enum(), to.into_enum()) else {
+            return Self::leaf(from, to);
+        };
+        let (Ok(from_variant), Ok(to_variant)) =
+            (from_enum.active_variant(), to_enum.active_variant())
+        else {
+            return Self::leaf(from, to);
+        };
+        if !core::ptr::eq(from_variant, to_variant) {
+            return Diff::Replace { from, to };
+        }
+
+        let mut changes = Vec::new();
+        for (index, field) in from_variant.data.fields.iter().enumerate() {
+            if let (Ok(Some(from_field)), Ok(Some(to_field))) =
+                (from_enum.field(index), to_enum.field(index))
+            {
+                changes.extend(FieldChange::new(field, from_field, to_field));
+            }
+        }
+        Self::fields(from, to, Some(from_variant), changes)
+    }
+
+    fn fields(
+        from: Peek<'mem, 'facet, 'shape>,
+        to: Peek<'mem, 'facet, 'shape>,
+        variant: Option<&'shape Variant<'shape>>,
+        changes: Vec<FieldChange<'mem, 'facet, 'shape>>,
+    ) -> Self {
+        if changes.is_empty() {
+            Diff::Equal
+        } else {
+            Diff::Fields {
+                from,
+                to,
+                variant,
+                changes,
+            }
+        }
+    }
+
+    fn sequence(from: Peek<'mem, 'facet, 'shape>, to: Peek<'mem, 'facet, 'shape>) -> Self {
+        let (Ok(from_list), Ok(to_list)) = (from.into_list_like(), to.into_list_like()) else {
+            return Self::leaf(from, to);
+        };
+        let from_items: Vec<_> = from_list.iter().collect();
+        let to_items: Vec<_> = to_list.iter().collect();
+
+        let changes = diff_sequences(&from_items, &to_items);
+        if changes.is_empty() {
+            Diff::Equal
+        } else {
+            Diff::Sequence { from, to, changes }
+        }
+    }
+
+    fn map(from: Peek<'mem, 'facet, 'shape>, to: Peek<'mem, 'facet, 'shape>) -> Self {
+        let (Ok(from_map), Ok(to_map)) = (from.into_map(), to.into_map()) else {
+            return Self::leaf(from, to);
+        };
+
+        let mut changes = Vec::new();
+        for (key, from_value) in from_map.iter() {
+            match to_map.get_peek(key) {
+                Some(to_value) => {
+                    let diff = Self::new(from_value, to_value);
+                    if !diff.is_equal() {
+                        changes.push(MapChange::Changed { key, diff });
+                    }
+                }
+                None => changes.push(MapChange::Removed {
+                    key,
+                    value: from_value,
+                }),
+            }
+        }
+        for (key, to_value) in to_map.iter() {
+            if from_map.get_peek(key).is_none() {
+                changes.push(MapChange::Added {
+                    key,
+                    value: to_value,
+                });
+            }
+        }
+        // Hash maps iterate in an arbitrary order, so changes are sorted by key
+        changes.sort_by(|a, b| compare_keys(a.key(), b.key()));
+
+        if changes.is_empty() {
+            Diff::Equal
+        } else {
+            Diff::Map { from, to, changes }
+        }
+    }
+}
+
+impl<'mem, 'facet, 'shape> MapChange<'mem, 'facet, 'shape> {
+    fn key(&self) -> Peek<'mem, 'facet, 'shape> {
+        match self {
+            MapChange::Added { key, .. }
+            | MapChange::Removed { key, .. }
+            | MapChange::Changed { key, .. } => *key,
+        }
+    }
+}
+
+/// Orders map keys with their `PartialOrd` implementation, or by their `Display`
+/// output for keys that don't have one.
+fn compare_keys(a: Peek<'_, '_, '_>, b: Peek<'_, '_, '_>) -> Ordering {
+    match a.partial_cmp(&b) {
+        Some(Some(ordering)) => ordering,
+        _ => alloc::format!("{a}").cmp(&alloc::format!("{b}")),
+    }
+}
+
+impl<'mem, 'facet, 'shape> FieldChange<'mem, 'facet, 'shape> {
+    /// Diffs a field, returning `None` if it didn't change
+    fn new(
+        field: &Field<'shape>,
+        from: Peek<'mem, 'facet, 'shape>,
+        to: Peek<'mem, 'facet, 'shape>,
+    ) -> Option<Self> {
+        let diff = Diff::new(from, to);
+        if diff.is_equal() {
+            return None;
+        }
+        let diff = if field.flags.contains(FieldFlags::SENSITIVE) {
+            Diff::Redacted
+        } else {
+            diff
+        };
+        Some(Self {
+            name: field.name,
+            diff,
+        })
+    }
+}
+
+/// Extension trait to diff values that implement [`Facet`]
+pub trait FacetDiff<'facet>: Facet<'facet> {
+    /// Computes the difference between `self` (the old value) and `other` (the new value)
+    fn diff<'a>(&'a self, other: &'a Self) -> Diff<'a, 'facet, 'static>;
+}
+
+impl<'facet, T: Facet<'facet>> FacetDiff<'facet> for T {
+    fn diff<'a>(&'a self, other: &'a Self) -> Diff<'a, 'facet, 'static> {
+        Diff::new(Peek::new(self), Peek::new(other))
+    }
+}
@@ -0,0 +1,264 @@
+//! Rendering of diffs, with values pretty-printed by `facet-pretty`
+
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter, Write};
+
+use facet_pretty::PrettyPrinter;
+use facet_reflect::Peek;
+use owo_colors::{OwoColorize, Style};
+
+use crate::{Diff, MapChange, SeqChange};
+
+/// A formatter for diffs
+///
+/// Each line starts with a `-` (old value), `+` (new value) or blank (context) marker.
+pub struct DiffPrinter {
+    indent_size: usize,
+    use_colors: bool,
+}
+
+impl Default for DiffPrinter {
+    fn default() -> Self {
+        Self {
+            indent_size: 2,
+            use_colors: PrettyPrinter::default().colors_enabled(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Marker {
+    Context,
+    Deleted,
+    Inserted,
+}
+
+impl DiffPrinter {
+    /// Create a new DiffPrinter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the indentation size
+    pub fn with_indent_size(mut self, size: usize) -> Self {
+        self.indent_size = size;
+        self
+    }
+
+    /// Enable or disable colors
+    pub fn with_colors(mut self, use_colors: bool) -> Self {
+        self.use_colors = use_colors;
+        self
+    }
+
+    /// Format a diff to a string
+    pub fn format(&self, diff: &Diff<'_, '_, '_>) -> String {
+        let mut output = String::new();
+        self.format_to(diff, &mut output)
+            .expect("Formatting failed");
+        output
+    }
+
+    /// Format a diff to a writer
+    pub fn format_to(&self, diff: &Diff<'_, '_, '_>, f: &mut impl Write) -> fmt::Result {
+        if diff.is_equal() {
+            self.write_prefix(f, Marker::Context, 0)?;
+            self.write_dim(f, "(no changes)")?;
+            return writeln!(f);
+        }
+        self.write_diff(f, diff, 0, None)
+    }
+
+    fn write_diff(
+        &self,
+        f: &mut impl Write,
+        diff: &Diff<'_, '_, '_>,
+        depth: usize,
+        label: Option<&str>,
+    ) -> fmt::Result {
+        match diff {
+            Diff::Equal => Ok(()),
+            Diff::Replace { from, to } => {
+                self.write_value(f, Marker::Deleted, depth, label, *from)?;
+                self.write_value(f, Marker::Inserted, depth, label, *to)
+            }
+            Diff::Redacted => {
+                for marker in [Marker::Deleted, Marker::Inserted] {
+                    self.write_prefix(f, marker, depth)?;
+                    self.write_label(f, label)?;
+                    self.write_redacted(f, "[REDACTED]")?;
+                    writeln!(f)?;
+                }
+                Ok(())
+            }
+            Diff::Fields {
+                from,
+                variant,
+                changes,
+                ..
+            } => {
+                self.write_prefix(f, Marker::Context, depth)?;
+                self.write_label(f, label)?;
+                self.write_type_name(f, *from)?;
+                if let Some(variant) = variant {
+                    self.write_dim(f, "::")?;
+                    self.write_bold(f, variant.name)?;
+                }
+                self.write_dim(f, " {")?;
+                writeln!(f)?;
+
+                for change in changes {
+                    let name = self.style_field_name(change.name);
+                    self.write_diff(f, &change.diff, depth + 1, Some(&name))?;
+                }
+                self.write_closing(f, depth, "}")
+            }
+            Diff::Sequence { from, changes, .. } => {
+                self.write_prefix(f, Marker::Context, depth)?;
+                self.write_label(f, label)?;
+                self.write_type_name(f, *from)?;
+                self.write_dim(f, " [")?;
+                writeln!(f)?;
+
+                for change in changes {
+                    match change {
+                        SeqChange::Deleted { index, value } => {
+                            let index = format!("[{index}]");
+                            self.write_value(f, Marker::Deleted, depth + 1, Some(&index), *value)?;
+                        }
+                        SeqChange::Inserted { index, value } => {
+                            let index = format!("[{index}]");
+                            self.write_value(f, Marker::Inserted, depth + 1, Some(&index), *value)?;
+                        }
+                        SeqChange::Changed {
+                            from_index,
+                            to_index,
+                            diff,
+                        } => {
+                            let index = if from_index == to_index {
+                                format!("[{from_index}]")
+                            } else {
+                                format!("[{from_index} → {to_index}]")
+                            };
+                            self.write_diff(f, diff, depth + 1, Some(&index))?;
+                        }
+                    }
+                }
+                self.write_closing(f, depth, "]")
+            }
+            Diff::Map { from, changes, .. } => {
+                self.write_prefix(f, Marker::Context, depth)?;
+                self.write_label(f, label)?;
+                self.write_type_name(f, *from)?;
+                self.write_dim(f, " {")?;
+                writeln!(f)?;
+
+                for change in changes {
+                    match change {
+                        MapChange::Added { key, value } => {
+                            let key = self.format_peek(*key);
+                            self.write_value(f, Marker::Inserted, depth + 1, Some(&key), *value)?;
+                        }
+                        MapChange::Removed { key, value } => {
+                            let key = self.format_peek(*key);
+                            self.write_value(f, Marker::Deleted, depth + 1, Some(&key), *value)?;
+                        }
+                        MapChange::Changed { key, diff } => {
+                            let key = self.format_peek(*key);
+                            self.write_diff(f, diff, depth + 1, Some(&key))?;
+                        }
+                    }
+                }
+                self.write_closing(f, depth, "}")
+            }
+        }
+    }
+
+    /// Writes a whole value, pretty-printed, with every line marked
+    fn write_value(
+        &self,
+        f: &mut impl Write,
+        marker: Marker,
+        depth: usize,
+        label: Option<&str>,
+        value: Peek<'_, '_, '_>,
+    ) -> fmt::Result {
+        let formatted = self.format_peek(value);
+        for (index, line) in formatted.lines().enumerate() {
+            self.write_prefix(f, marker, depth)?;
+            if index == 0 {
+                self.write_label(f, label)?;
+            }
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+
+    fn format_peek(&self, value: Peek<'_, '_, '_>) -> String {
+        PrettyPrinter::new()
+            .with_indent_size(self.indent_size)
+            .with_colors(self.use_colors)
+            .format_peek(value)
+    }
+
+    fn write_closing(&self, f: &mut impl Write, depth: usize, text: &str) -> fmt::Result {
+        self.write_prefix(f, Marker::Context, depth)?;
+        self.write_dim(f, text)?;
+        writeln!(f)
+    }
+
+    /// Writes the marker column, then the indentation for `depth`
+    fn write_prefix(&self, f: &mut impl Write, marker: Marker, depth: usize) -> fmt::Result {
+        match marker {
+            Marker::Context => write!(f, " ")?,
+            Marker::Deleted => self.write_styled(f, "-", Style::new().red())?,
+            Marker::Inserted => self.write_styled(f, "+", Style::new().green())?,
+        }
+        write!(f, " {:width$}", "", width = depth * self.indent_size)
+    }
+
+    fn write_label(&self, f: &mut impl Write, label: Option<&str>) -> fmt::Result {
+        if let Some(label) = label {
+            write!(f, "{label}")?;
+            self.write_dim(f, ": ")?;
+        }
+        Ok(())
+    }
+
+    fn write_type_name(&self, f: &mut impl Write, value: Peek<'_, '_, '_>) -> fmt::Result {
+        self.write_bold(f, &value.shape().to_string())
+    }
+
+    fn style_field_name(&self, name: &str) -> String {
+        let mut styled = String::new();
+        // Writing to a string can't fail
+        let _ = self.write_styled(&mut styled, name, Style::new().cyan());
+        styled
+    }
+
+    fn write_bold(&self, f: &mut impl Write, text: &str) -> fmt::Result {
+        self.write_styled(f, text, Style::new().bold())
+    }
+
+    fn write_dim(&self, f: &mut impl Write, text: &str) -> fmt::Result {
+        self.write_styled(f, text, Style::new().dimmed())
+    }
+
+    fn write_redacted(&self, f: &mut impl Write, text: &str) -> fmt::Result {
+        self.write_styled(f, text, Style::new().bright_red().bold())
+    }
+
+    fn write_styled(&self, f: &mut impl Write, text: &str, style: Style) -> fmt::Result {
+        if self.use_colors {
+            write!(f, "{}", text.style(style))
+        } else {
+            f.write_str(text)
+        }
+    }
+}
+
+impl Display for Diff<'_, '_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        DiffPrinter::default().format_to(self, f)
+    }
+}
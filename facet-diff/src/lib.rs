@@ -0,0 +1,14 @@
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+mod diff;
+mod display;
+mod sequence;
+
+pub use diff::*;
+pub use display::*;
@@ -0,0 +1,207 @@
+//! Alignment of list elements, based on their longest common subsequence
+
+use alloc::vec::Vec;
+
+use facet_reflect::{Peek, reflect_eq};
+
+use crate::{Diff, SeqChange};
+
+/// Aligns two sequences and returns the changes needed to go from `from` to `to`.
+///
+/// Elements are matched along a longest common subsequence of equal elements, found
+/// with Myers' algorithm in O((n + m) * d) time and linear space, where d is the number
+/// of deleted and inserted elements. Runs of deleted and inserted elements between two
+/// matches are then paired up, and reported as changed elements, so that a modified
+/// struct in a list shows up as a nested diff rather than as a deletion and an insertion.
+///
+/// Elements are compared with `PartialEq` (or structurally, when a type doesn't have
+/// it), and full diffs are only computed for the changed pairs.
+pub(crate) fn diff_sequences<'mem, 'facet, 'shape>(
+    from: &[Peek<'mem, 'facet, 'shape>],
+    to: &[Peek<'mem, 'facet, 'shape>],
+) -> Vec<SeqChange<'mem, 'facet, 'shape>> {
+    // Values that can't be compared are reported as changed, like in `Diff::new`
+    let equal = |i: usize, j: usize| reflect_eq(from[i], to[j]).unwrap_or(false);
+
+    let mut myers = Myers::new(&equal, from.len(), to.len());
+    let mut matches = Vec::new();
+    myers.matches(0, from.len(), 0, to.len(), &mut matches);
+
+    let mut run = Run::default();
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (matched_i, matched_j) in matches.into_iter().chain([(from.len(), to.len())]) {
+        run.deleted.extend(i..matched_i);
+        run.inserted.extend(j..matched_j);
+        run.flush(from, to, &mut changes);
+        (i, j) = (matched_i + 1, matched_j + 1);
+    }
+
+    changes
+}
+
+/// Linear space variant of Myers' diff algorithm, from "An O(ND) Difference Algorithm
+/// and Its Variations": the middle snake of an optimal edit script is found by searching
+/// forwards and backwards at the same time, and both halves around it are then aligned
+/// recursively.
+struct Myers<'a, F> {
+    equal: &'a F,
+    /// Furthest reaching x on each diagonal k = x - y, searching forwards
+    forward: Vec<usize>,
+    /// Furthest reaching x on each diagonal, searching backwards from the end
+    backward: Vec<usize>,
+    /// Offset of diagonal 0 in `forward` and `backward`
+    offset: isize,
+}
+
+impl<'a, F: Fn(usize, usize) -> bool> Myers<'a, F> {
+    fn new(equal: &'a F, n: usize, m: usize) -> Self {
+        let max_d = (n + m).div_ceil(2) + 1;
+        Self {
+            equal,
+            forward: alloc::vec![0; 2 * max_d + 1],
+            backward: alloc::vec![0; 2 * max_d + 1],
+            offset: max_d as isize,
+        }
+    }
+
+    fn index(&self, k: isize) -> usize {
+        (k + self.offset) as usize
+    }
+
+    /// Pushes the indices of the matched elements of `from[from_start..from_end]` and
+    /// `to[to_start..to_end]` to `matches`, in order.
+    fn matches(
+        &mut self,
+        mut from_start: usize,
+        mut from_end: usize,
+        mut to_start: usize,
+        mut to_end: usize,
+        matches: &mut Vec<(usize, usize)>,
+    ) {
+        // Common prefixes and suffixes are by far the most common case, and don't
+        // need to go through the search.
+        while from_start < from_end && to_start < to_end && (self.equal)(from_start, to_start) {
+            matches.push((from_start, to_start));
+            from_start += 1;
+            to_start += 1;
+        }
+        let mut suffix = 0;
+        while from_start < from_end && to_start < to_end && (self.equal)(from_end - 1, to_end - 1) {
+            from_end -= 1;
+            to_end -= 1;
+            suffix += 1;
+        }
+
+        if from_start < from_end && to_start < to_end {
+            let (x, y) = self.middle_snake(from_start, from_end, to_start, to_end);
+            self.matches(from_start, x, to_start, y, matches);
+            self.matches(x, from_end, y, to_end, matches);
+        }
+
+        matches.extend((0..suffix).map(|offset| (from_end + offset, to_end + offset)));
+    }
+
+    /// Returns the start of the middle snake of an optimal edit script between two
+    /// non-empty ranges, which differ in their first and last elements.
+    fn middle_snake(
+        &mut self,
+        from_start: usize,
+        from_end: usize,
+        to_start: usize,
+        to_end: usize,
+    ) -> (usize, usize) {
+        let n = from_end - from_start;
+        let m = to_end - to_start;
+        let delta = n as isize - m as isize;
+        let odd = delta % 2 != 0;
+        let index_1 = self.index(1);
+        self.forward[index_1] = 0;
+        self.backward[index_1] = 0;
+
+        for d in 0..=(n + m).div_ceil(2) as isize {
+            for k in (-d..=d).rev().step_by(2) {
+                let mut x = if k == -d
+                    || (k != d && self.forward[self.index(k - 1)] < self.forward[self.index(k + 1)])
+                {
+                    self.forward[self.index(k + 1)]
+                } else {
+                    self.forward[self.index(k - 1)] + 1
+                };
+                let mut y = (x as isize - k) as usize;
+                let (snake_x, snake_y) = (x, y);
+                while x < n && y < m && (self.equal)(from_start + x, to_start + y) {
+                    x += 1;
+                    y += 1;
+                }
+                let index_k = self.index(k);
+                self.forward[index_k] = x;
+                if odd && (k - delta).abs() < d && x + self.backward[self.index(delta - k)] >= n {
+                    return (from_start + snake_x, to_start + snake_y);
+                }
+            }
+
+            for k in (-d..=d).rev().step_by(2) {
+                let mut x = if k == -d
+                    || (k != d
+                        && self.backward[self.index(k - 1)] < self.backward[self.index(k + 1)])
+                {
+                    self.backward[self.index(k + 1)]
+                } else {
+                    self.backward[self.index(k - 1)] + 1
+                };
+                let mut y = (x as isize - k) as usize;
+                while x < n && y < m && (self.equal)(from_end - x - 1, to_end - y - 1) {
+                    x += 1;
+                    y += 1;
+                }
+                let index_k = self.index(k);
+                self.backward[index_k] = x;
+                if !odd && (k - delta).abs() <= d && x + self.forward[self.index(delta - k)] >= n {
+                    return (from_end - x, to_end - y);
+                }
+            }
+        }
+
+        unreachable!("the forward and backward searches meet after (n + m) / 2 steps")
+    }
+}
+
+/// Deleted and inserted elements between two matched elements
+#[derive(Default)]
+struct Run {
+    deleted: Vec<usize>,
+    inserted: Vec<usize>,
+}
+
+impl Run {
+    fn flush<'mem, 'facet, 'shape>(
+        &mut self,
+        from: &[Peek<'mem, 'facet, 'shape>],
+        to: &[Peek<'mem, 'facet, 'shape>],
+        changes: &mut Vec<SeqChange<'mem, 'facet, 'shape>>,
+    ) {
+        let paired = self.deleted.len().min(self.inserted.len());
+        for (&from_index, &to_index) in self.deleted.iter().zip(&self.inserted) {
+            changes.push(SeqChange::Changed {
+                from_index,
+                to_index,
+                diff: Diff::new(from[from_index], to[to_index]),
+            });
+        }
+        for &index in &self.deleted[paired..] {
+            changes.push(SeqChange::Deleted {
+                index,
+                value: from[index],
+            });
+        }
+        for &index in &self.inserted[paired..] {
+            changes.push(SeqChange::Inserted {
+                index,
+                value: to[index],
+            });
+        }
+        self.deleted.clear();
+        self.inserted.clear();
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashMap};
+
+use facet::Facet;
+use facet_diff::{Diff, DiffPrinter, FacetDiff, MapChange, SeqChange};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Account {
+    name: String,
+    balance: u64,
+    #[facet(sensitive)]
+    password: String,
+}
+
+fn account() -> Account {
+    Account {
+        name: "alice".to_string(),
+        balance: 10,
+        password: "hunter2".to_string(),
+    }
+}
+
+fn plain(diff: &Diff) -> String {
+    DiffPrinter::new().with_colors(false).format(diff)
+}
+
+#[test]
+fn equal_values() {
+    let a = account();
+    let b = account();
+    let diff = a.diff(&b);
+    assert!(diff.is_equal());
+    assert_eq!(plain(&diff), "  (no changes)\n");
+
+    assert!([1, 2, 3].diff(&[1, 2, 3]).is_equal());
+    assert!(Some(5_u8).diff(&Some(5_u8)).is_equal());
+}
+
+#[test]
+fn changed_scalar() {
+    let (a, b) = (1_u32, 2_u32);
+    let diff = a.diff(&b);
+    assert!(matches!(diff, Diff::Replace { .. }));
+    assert_eq!(plain(&diff), "- 1\n+ 2\n");
+}
+
+#[test]
+fn changed_struct_fields() {
+    let a = account();
+    let b = Account {
+        balance: 20,
+        ..account()
+    };
+
+    let diff = a.diff(&b);
+    let Diff::Fields {
+        variant, changes, ..
+    } = &diff
+    else {
+        panic!("expected a fields diff");
+    };
+    assert!(variant.is_none());
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].name, "balance");
+
+    assert_eq!(
+        plain(&diff),
+        concat!(
+            "  Account {\n",
+            "-   balance: 10\n",
+            "+   balance: 20\n",
+            "  }\n",
+        )
+    );
+}
+
+#[test]
+fn sensitive_fields_are_redacted() {
+    let a = account();
+    let b = Account {
+        password: "correct horse battery staple".to_string(),
+        ..account()
+    };
+
+    let diff = a.diff(&b);
+    let Diff::Fields { changes, .. } = &diff else {
+        panic!("expected a fields diff");
+    };
+    assert_eq!(changes[0].name, "password");
+    assert!(matches!(changes[0].diff, Diff::Redacted));
+
+    let output = plain(&diff);
+    assert!(output.contains("[REDACTED]"), "{output}");
+    assert!(!output.contains("hunter2"), "{output}");
+    assert!(!output.contains("horse"), "{output}");
+}
+
+#[test]
+fn enum_variants() {
+    #[derive(Facet, Debug)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Status {
+        Active { since: u32 },
+        Suspended(String),
+    }
+
+    let a = Status::Active { since: 1 };
+    let b = Status::Active { since: 2 };
+    let diff = a.diff(&b);
+    let Diff::Fields {
+        variant, changes, ..
+    } = &diff
+    else {
+        panic!("expected a fields diff");
+    };
+    assert_eq!(variant.unwrap().name, "Active");
+    assert_eq!(changes[0].name, "since");
+
+    let c = Status::Suspended("spam".to_string());
+    assert!(matches!(a.diff(&c), Diff::Replace { .. }));
+
+    let d = Status::Suspended("spam".to_string());
+    assert!(c.diff(&d).is_equal());
+}
+
+#[test]
+fn list_alignment() {
+    let a = vec![1, 2, 3, 4, 5];
+    let b = vec![1, 3, 4, 6, 5, 7];
+
+    let diff = a.diff(&b);
+    let Diff::Sequence { changes, .. } = &diff else {
+        panic!("expected a sequence diff");
+    };
+    assert_eq!(changes.len(), 3);
+    assert!(matches!(changes[0], SeqChange::Deleted { index: 1, .. }));
+    assert!(matches!(changes[1], SeqChange::Inserted { index: 3, .. }));
+    assert!(matches!(changes[2], SeqChange::Inserted { index: 5, .. }));
+}
+
+#[test]
+fn list_of_structs_pairs_changed_elements() {
+    let a = vec![account(), account()];
+    let mut b = a.clone();
+    b[1].name = "bob".to_string();
+
+    let diff = a.diff(&b);
+    let Diff::Sequence { changes, .. } = &diff else {
+        panic!("expected a sequence diff");
+    };
+    assert_eq!(changes.len(), 1);
+    let SeqChange::Changed {
+        from_index,
+        to_index,
+        diff,
+    } = &changes[0]
+    else {
+        panic!("expected a changed element");
+    };
+    assert_eq!((*from_index, *to_index), (1, 1));
+    assert!(matches!(diff, Diff::Fields { .. }));
+}
+
+#[test]
+fn map_keys() {
+    let map = |entries: &[(&str, i32)]| -> BTreeMap<String, i32> {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    };
+    let a = map(&[("a", 1), ("b", 2), ("c", 3)]);
+    let b = map(&[("a", 1), ("b", 20), ("d", 4)]);
+
+    let diff = a.diff(&b);
+    let Diff::Map { changes, .. } = &diff else {
+        panic!("expected a map diff");
+    };
+    assert_eq!(changes.len(), 3);
+    assert!(matches!(changes[0], MapChange::Changed { .. }));
+    assert!(matches!(changes[1], MapChange::Removed { .. }));
+    assert!(matches!(changes[2], MapChange::Added { .. }));
+
+    let output = plain(&diff);
+    assert!(output.contains("-   b: 2\n"), "{output}");
+    assert!(output.contains("+   b: 20\n"), "{output}");
+    assert!(output.contains("-   c: 3\n"), "{output}");
+    assert!(output.contains("+   d: 4\n"), "{output}");
+}
+
+#[test]
+fn hash_map_changes_are_sorted_by_key() {
+    let a: HashMap<u32, u32> = (0..100).map(|k| (k, k)).collect();
+    let b: HashMap<u32, u32> = (50..150).map(|k| (k, k + 1)).collect();
+
+    let diff = a.diff(&b);
+    let Diff::Map { changes, .. } = &diff else {
+        panic!("expected a map diff");
+    };
+    let keys: Vec<u32> = changes
+        .iter()
+        .map(|change| match change {
+            MapChange::Added { key, .. }
+            | MapChange::Removed { key, .. }
+            | MapChange::Changed { key, .. } => *key.get::<u32>().unwrap(),
+        })
+        .collect();
+    assert_eq!(keys, (0..150).collect::<Vec<_>>());
+}
+
+#[test]
+fn nested_option() {
+    #[derive(Facet)]
+    #[allow(dead_code)]
+    struct Profile {
+        nickname: Option<String>,
+        age: Option<u8>,
+    }
+
+    let a = Profile {
+        nickname: None,
+        age: Some(30),
+    };
+    let b = Profile {
+        nickname: Some("al".to_string()),
+        age: Some(31),
+    };
+
+    let Diff::Fields { changes, .. } = a.diff(&b) else {
+        panic!("expected a fields diff");
+    };
+    assert_eq!(changes.len(), 2);
+    assert!(matches!(changes[0].diff, Diff::Replace { .. }));
+    assert!(matches!(changes[1].diff, Diff::Replace { .. }));
+}
+
+#[test]
+fn colored_output() {
+    let (a, b) = (1_u32, 2_u32);
+    let diff = a.diff(&b);
+    let output = DiffPrinter::new().with_colors(true).format(&diff);
+    assert!(output.contains("\x1b[31m-"), "{output:?}");
+    assert!(output.contains("\x1b[32m+"), "{output:?}");
+}
@@ -1,10 +1,11 @@
 use alloc::format;
 
-use facet_core::Facet;
+use facet_core::{Facet, FunctionShape};
 use facet_deserialize::{
     Cooked, Expectation, Format, NextData, NextResult, Outcome, Scalar, Span, Spannable, Spanned,
 };
 pub use facet_deserialize::{DeserError, DeserErrorKind};
+use facet_reflect::{HeapValue, Partial};
 use log::trace;
 
 use crate::tokenizer::{Token, TokenError, TokenErrorKind, Tokenizer};
@@ -29,6 +30,44 @@ where
     from_slice(input.as_bytes())
 }
 
+/// Call a function annotated with `#[facet_fn]`, with its arguments given as a JSON array
+///
+/// The arguments are deserialized as the tuple of the parameters of the function, so they're
+/// given in declaration order. What the function returns comes back as a [`HeapValue`].
+///
+/// ```
+/// use facet::{facet_fn, fn_shape};
+///
+/// #[facet_fn]
+/// fn add(x: i32, y: i32) -> i32 {
+///     x + y
+/// }
+///
+/// let sum = facet_json::call(&fn_shape!(add), "[2, 3]").unwrap();
+/// assert_eq!(sum.materialize::<i32>().unwrap(), 5);
+/// ```
+pub fn call<'input, 'shape>(
+    function: &FunctionShape<'shape>,
+    input: &'input str,
+) -> Result<HeapValue<'input, 'shape>, DeserError<'input, 'shape>>
+where
+    'shape: 'input,
+{
+    let input = input.as_bytes();
+    let reflect_error = |e| {
+        DeserError::new(
+            DeserErrorKind::ReflectError(e),
+            input,
+            Span::default(),
+            "json",
+        )
+    };
+
+    let wip = Partial::alloc_shape(function.args_shape()).map_err(reflect_error)?;
+    let args = facet_deserialize::deserialize_wip(wip, input, &mut crate::Json)?;
+    facet_reflect::invoke(function, args).map_err(reflect_error)
+}
+
 impl Format for crate::Json {
     type Input<'input> = [u8];
     type SpanType = Cooked;
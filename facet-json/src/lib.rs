@@ -6,6 +6,8 @@
 #![doc = include_str!("../README.md")]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use alloc::vec::Vec;
 pub use facet_deserialize::{DeserError, DeserErrorKind, DeserErrorMessage};
@@ -16,6 +18,11 @@ pub use deserialize::*;
 mod serialize;
 pub use serialize::*;
 
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "std")]
+pub use read::*;
+
 mod tokenizer;
 
 /// The JSON format
@@ -126,20 +133,7 @@ fn write_json_escaped_char<W: JsonWrite>(writer: &mut W, c: char) {
         '\t' => writer.write(b"\\t"),
         '\u{08}' => writer.write(b"\\b"),
         '\u{0C}' => writer.write(b"\\f"),
-        c if c.is_ascii_control() => {
-            let code_point = c as u32;
-            // Extract individual hex digits (nibbles) from the code point
-            let to_hex = |d: u32| char::from_digit(d, 16).unwrap() as u8;
-            let buf = [
-                b'\\',
-                b'u',
-                to_hex((code_point >> 12) & 0xF),
-                to_hex((code_point >> 8) & 0xF),
-                to_hex((code_point >> 4) & 0xF),
-                to_hex(code_point & 0xF),
-            ];
-            writer.write(&buf);
-        }
+        c if c.is_ascii_control() => write_json_unicode_escape(writer, c as u16),
         c if c.is_ascii() => {
             writer.write(&[c as u8]);
         }
@@ -151,6 +145,39 @@ fn write_json_escaped_char<W: JsonWrite>(writer: &mut W, c: char) {
     }
 }
 
+/// Writes a `\uXXXX` escape for a UTF-16 code unit
+#[inline]
+fn write_json_unicode_escape<W: JsonWrite>(writer: &mut W, code_unit: u16) {
+    let code_point = code_unit as u32;
+    // Extract individual hex digits (nibbles) from the code point
+    let to_hex = |d: u32| char::from_digit(d, 16).unwrap() as u8;
+    let buf = [
+        b'\\',
+        b'u',
+        to_hex((code_point >> 12) & 0xF),
+        to_hex((code_point >> 8) & 0xF),
+        to_hex((code_point >> 4) & 0xF),
+        to_hex(code_point & 0xF),
+    ];
+    writer.write(&buf);
+}
+
+/// Properly escapes and writes a JSON string, escaping every non-ASCII character as
+/// `\uXXXX` (or a surrogate pair of them) so that the output is pure ASCII
+fn write_json_string_ascii<W: JsonWrite>(writer: &mut W, s: &str) {
+    writer.write(b"\"");
+    for c in s.chars() {
+        if c.is_ascii() {
+            write_json_escaped_char(writer, c);
+        } else {
+            for code_unit in c.encode_utf16(&mut [0; 2]) {
+                write_json_unicode_escape(writer, *code_unit);
+            }
+        }
+    }
+    writer.write(b"\"")
+}
+
 #[inline]
 fn contains_0x22(val: u128) -> bool {
     let xor_result = val ^ 0x22222222222222222222222222222222;
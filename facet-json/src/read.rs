@@ -0,0 +1,307 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use std::io::{self, Read};
+
+use facet_core::Facet;
+use facet_deserialize::{DeserError, DeserErrorKind, Span};
+
+use crate::from_slice;
+
+/// How many bytes are read from the reader at a time
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Errors when deserializing JSON from a reader
+#[derive(Debug)]
+pub enum ReadError<'shape> {
+    /// Reading from the reader failed
+    Io(io::Error),
+    /// The JSON could not be deserialized
+    Deser(DeserError<'static, 'shape>),
+}
+
+impl core::fmt::Display for ReadError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "IO error: {}", error),
+            ReadError::Deser(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl core::error::Error for ReadError<'_> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ReadError::Io(error) => Some(error),
+            ReadError::Deser(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError<'_> {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+impl<'shape> From<DeserError<'static, 'shape>> for ReadError<'shape> {
+    fn from(error: DeserError<'static, 'shape>) -> Self {
+        ReadError::Deser(error)
+    }
+}
+
+/// Deserialize a single JSON value from a reader
+///
+/// The input is read in chunks rather than all at once, and only whitespace may follow the
+/// value. Since the input doesn't outlive this call, `T` can't borrow from it.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_json::from_reader;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let input = br#"{"x": 1, "y": 2}"#;
+/// let point: Point = from_reader(&input[..]).unwrap();
+/// assert_eq!(point, Point { x: 1, y: 2 });
+/// ```
+pub fn from_reader<'shape, T, R>(reader: R) -> Result<T, ReadError<'shape>>
+where
+    T: for<'facet> Facet<'facet>,
+    R: Read,
+{
+    let mut stream = StreamDeserializer::new(reader);
+    let value = match stream.next() {
+        Some(value) => value?,
+        // Report an empty input the same way `from_slice` does
+        None => from_slice(b"").map_err(|e| e.into_owned())?,
+    };
+    stream.end()?;
+    Ok(value)
+}
+
+/// An iterator over the JSON values of a reader, deserialized one after the other
+///
+/// Values may be separated by whitespace, or by nothing at all when that's unambiguous, so
+/// this reads newline-delimited JSON (NDJSON, JSON Lines) as well as concatenated JSON. Only
+/// the value being deserialized and one chunk of input are held in memory at a time.
+///
+/// A value that fails to deserialize is skipped, so iteration carries on with the next one.
+/// Iteration stops after an IO error.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_json::StreamDeserializer;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Event {
+///     id: u32,
+///     kind: String,
+/// }
+///
+/// let input = b"{\"id\": 1, \"kind\": \"open\"}\n{\"id\": 2, \"kind\": \"close\"}\n";
+/// let events = StreamDeserializer::<_, Event>::new(&input[..])
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(events[1], Event { id: 2, kind: "close".to_string() });
+/// ```
+pub struct StreamDeserializer<'shape, R, T> {
+    reader: R,
+
+    /// Input read but not deserialized yet
+    buf: Vec<u8>,
+
+    /// How much of `buf` has been fed to the scanner
+    scanned: usize,
+
+    /// Finds where the next value ends
+    scanner: ValueScanner,
+
+    /// How many bytes of the input were consumed by the values yielded so far
+    offset: usize,
+
+    /// Whether reading failed, which ends iteration
+    failed: bool,
+
+    _marker: PhantomData<fn() -> Result<T, ReadError<'shape>>>,
+}
+
+impl<'shape, R: Read, T> StreamDeserializer<'shape, R, T>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    /// Creates a stream deserializer reading JSON values from `reader`
+    ///
+    /// Reads are done in chunks, so wrapping `reader` in a [`std::io::BufReader`] isn't needed.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            scanned: 0,
+            scanner: ValueScanner::default(),
+            offset: 0,
+            failed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns how many bytes of the input were consumed by the values yielded so far
+    pub fn byte_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Reads another chunk of input into `buf`. Returns `false` at the end of the input.
+    fn fill(&mut self) -> io::Result<bool> {
+        let len = self.buf.len();
+        self.buf.resize(len + CHUNK_SIZE, 0);
+        loop {
+            match self.reader.read(&mut self.buf[len..]) {
+                Ok(read) => {
+                    self.buf.truncate(len + read);
+                    return Ok(read > 0);
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.buf.truncate(len);
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Checks that only whitespace is left in the input
+    fn end(&mut self) -> Result<(), ReadError<'shape>> {
+        loop {
+            if let Some(index) = self
+                .buf
+                .iter()
+                .position(|b| !matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+            {
+                let error = DeserError::new(
+                    DeserErrorKind::UnexpectedChar {
+                        got: self.buf[index] as char,
+                        wanted: "end of input",
+                    },
+                    &self.buf[..],
+                    Span::new(index, 1),
+                    "json",
+                );
+                return Err(error.into_owned().into());
+            }
+            self.offset += self.buf.len();
+            self.buf.clear();
+            if !self.fill()? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl<'shape, R: Read, T> Iterator for StreamDeserializer<'shape, R, T>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    type Item = Result<T, ReadError<'shape>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let end = loop {
+            if let Some(len) = self.scanner.feed(&self.buf[self.scanned..]) {
+                break self.scanned + len;
+            }
+            self.scanned = self.buf.len();
+            match self.fill() {
+                Ok(true) => {}
+                // A value running to the end of the input, like a number
+                Ok(false) if self.scanner.started => break self.buf.len(),
+                Ok(false) => return None,
+                Err(error) => {
+                    self.failed = true;
+                    return Some(Err(error.into()));
+                }
+            }
+        };
+        self.scanner = ValueScanner::default();
+        self.scanned = 0;
+
+        let result = from_slice(&self.buf[..end]).map_err(|e| e.into_owned().into());
+        self.buf.drain(..end);
+        self.offset += end;
+        Some(result)
+    }
+}
+
+/// Finds where a JSON value ends without parsing it, so that it can be deserialized whole
+#[derive(Default)]
+struct ValueScanner {
+    /// Whether anything but whitespace was seen
+    started: bool,
+    /// How many objects and arrays are open
+    depth: usize,
+    in_string: bool,
+    /// Whether the previous byte in a string was an unescaped backslash
+    escaped: bool,
+    /// Whether the value is a number or a literal, which end at the first byte not part of them
+    bare: bool,
+}
+
+impl ValueScanner {
+    /// Scans `bytes`, which follow the bytes scanned before. Returns the length of the value
+    /// within `bytes` if it ends there.
+    fn feed(&mut self, bytes: &[u8]) -> Option<usize> {
+        for (index, &byte) in bytes.iter().enumerate() {
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                    if self.depth == 0 {
+                        return Some(index + 1);
+                    }
+                }
+                continue;
+            }
+
+            if self.bare {
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'+' | b'.') {
+                    continue;
+                }
+                return Some(index);
+            }
+
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' => {}
+                b'"' => {
+                    self.started = true;
+                    self.in_string = true;
+                }
+                b'{' | b'[' => {
+                    self.started = true;
+                    self.depth += 1;
+                }
+                b'}' | b']' => {
+                    self.started = true;
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.depth == 0 {
+                        return Some(index + 1);
+                    }
+                }
+                _ => {
+                    self.started = true;
+                    if self.depth == 0 {
+                        self.bare = true;
+                    }
+                }
+            }
+        }
+        None
+    }
+}
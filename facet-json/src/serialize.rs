@@ -1,6 +1,6 @@
 use alloc::string::String;
 use alloc::vec::Vec;
-use facet_core::Facet;
+use facet_core::{Def, Facet, Shape};
 use facet_reflect::Peek;
 use facet_serialize::{Serializer, serialize_iterative};
 use log::debug;
@@ -10,10 +10,55 @@ pub fn to_string<'facet, T: Facet<'facet>>(value: &T) -> String {
     peek_to_string(Peek::new(value))
 }
 
+/// Serializes a value implementing `Facet` to an indented JSON string, with two spaces per
+/// level and a space after each `:`.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_json::to_string_pretty;
+///
+/// #[derive(Facet)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// assert_eq!(
+///     to_string_pretty(&Point { x: 1, y: 2 }),
+///     "{\n  \"x\": 1,\n  \"y\": 2\n}"
+/// );
+/// ```
+pub fn to_string_pretty<'facet, T: Facet<'facet>>(value: &T) -> String {
+    to_string_with_options(value, SerializeOptions::pretty())
+}
+
+/// Serializes a value implementing `Facet` to a JSON string, with the given options.
+pub fn to_string_with_options<'facet, T: Facet<'facet>>(
+    value: &T,
+    options: SerializeOptions,
+) -> String {
+    peek_to_string_with_options(Peek::new(value), options)
+}
+
 /// Serializes a `Peek` instance to a JSON string.
 pub fn peek_to_string<'input, 'facet, 'shape>(peek: Peek<'input, 'facet, 'shape>) -> String {
+    peek_to_string_with_options(peek, SerializeOptions::default())
+}
+
+/// Serializes a `Peek` instance to a JSON string, with the given options.
+///
+/// # Panics
+///
+/// Panics if the value has a shape JSON can't represent, which `#[derive(Facet)]` never
+/// produces. Use [`peek_to_writer_with_options`] to get the error instead.
+pub fn peek_to_string_with_options<'input, 'facet, 'shape>(
+    peek: Peek<'input, 'facet, 'shape>,
+    options: SerializeOptions,
+) -> String {
     let mut s = Vec::new();
-    peek_to_writer(peek, &mut s).unwrap();
+    if let Err(err) = peek_to_writer_with_options(peek, &mut s, options) {
+        panic!("{err}");
+    }
     String::from_utf8(s).unwrap()
 }
 
@@ -25,18 +70,146 @@ pub fn to_writer<'mem, 'facet, T: Facet<'facet>, W: crate::JsonWrite>(
     peek_to_writer(Peek::new(value), writer)
 }
 
+/// Serializes a `Facet` value to JSON and writes it to the given writer, with the given options.
+pub fn to_writer_with_options<'mem, 'facet, T: Facet<'facet>, W: crate::JsonWrite>(
+    value: &'mem T,
+    writer: W,
+    options: SerializeOptions,
+) -> Result<(), SerializeError> {
+    peek_to_writer_with_options(Peek::new(value), writer, options)
+}
+
 /// Serializes a `Peek` value to JSON and writes it to the given writer.
 pub fn peek_to_writer<'mem, 'facet, 'shape, W: crate::JsonWrite>(
     peek: Peek<'mem, 'facet, 'shape>,
     writer: W,
 ) -> Result<(), SerializeError> {
-    let mut serializer = JsonSerializer::new(writer);
+    peek_to_writer_with_options(peek, writer, SerializeOptions::default())
+}
+
+/// Serializes a `Peek` value to JSON and writes it to the given writer, with the given options.
+pub fn peek_to_writer_with_options<'mem, 'facet, 'shape, W: crate::JsonWrite>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    writer: W,
+    options: SerializeOptions,
+) -> Result<(), SerializeError> {
+    let mut serializer = JsonSerializer::with_options(writer, options);
     serialize_iterative(peek, &mut serializer)
 }
 
-/// Serialization error for json, which cannot fail.
+/// How each level of nesting is indented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// This many spaces per level
+    Spaces(usize),
+    /// One tab per level
+    Tab,
+}
+
+/// What ends each line of indented output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Newline {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+/// How JSON is written: on one line by default, or indented, with map keys in order and
+/// non-ASCII characters escaped so that the output is stable and diffs well
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use facet_json::{Indent, SerializeOptions, to_string_with_options};
+///
+/// let mut map = HashMap::new();
+/// map.insert("b".to_string(), "ü".to_string());
+/// map.insert("a".to_string(), "x".to_string());
+///
+/// let options = SerializeOptions::default()
+///     .indent(Indent::Tab)
+///     .sort_keys(true)
+///     .ascii_only(true);
+/// assert_eq!(
+///     to_string_with_options(&map, options),
+///     "{\n\t\"a\":\"x\",\n\t\"b\":\"\\u00fc\"\n}"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SerializeOptions {
+    indent: Option<Indent>,
+    newline: Newline,
+    space_after_colon: bool,
+    sort_keys: bool,
+    ascii_only: bool,
+}
+
+impl SerializeOptions {
+    /// Options for indented output, with two spaces per level and a space after each `:`
+    pub fn pretty() -> Self {
+        Self::default()
+            .indent(Indent::Spaces(2))
+            .space_after_colon(true)
+    }
+
+    /// Puts each array item and object entry on its own line, indented by nesting level.
+    /// Empty arrays and objects stay on one line.
+    pub fn indent(mut self, indent: Indent) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    /// Sets what ends each line of indented output, [`Newline::Lf`] by default
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    /// Sets whether a space follows the `:` between a key and its value
+    pub fn space_after_colon(mut self, space_after_colon: bool) -> Self {
+        self.space_after_colon = space_after_colon;
+        self
+    }
+
+    /// Sets whether the entries of maps are written ordered by their key, rather than in
+    /// the order the map iterates them. Struct fields keep their declaration order.
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// Sets whether non-ASCII characters in strings are escaped as `\uXXXX`
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+}
+
+/// Serialization error for json.
 #[derive(Debug)]
-pub enum SerializeError {}
+pub enum SerializeError {
+    /// The value has a shape that can't be written as JSON.
+    Unsupported {
+        /// The shape of the value
+        shape: String,
+        /// Why it can't be serialized
+        reason: String,
+    },
+}
+
+impl core::fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SerializeError::Unsupported { shape, reason } => {
+                write!(f, "cannot serialize {shape}: {reason}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SerializeError {}
 
 #[derive(Debug)]
 enum StackItem {
@@ -55,46 +228,61 @@ enum ObjectItemState {
 pub struct JsonSerializer<W: crate::JsonWrite> {
     writer: W,
     stack: Vec<StackItem>,
+    options: SerializeOptions,
 }
 
 impl<W: crate::JsonWrite> JsonSerializer<W> {
     /// Creates a new JSON serializer with the given writer.
     pub fn new(writer: W) -> Self {
+        Self::with_options(writer, SerializeOptions::default())
+    }
+
+    /// Creates a new JSON serializer with the given writer and options.
+    pub fn with_options(writer: W, options: SerializeOptions) -> Self {
         Self {
             writer,
             stack: Vec::new(),
+            options,
         }
     }
 
     fn start_value(&mut self) -> Result<(), SerializeError> {
         debug!("start_value, stack = {:?}", self.stack);
 
-        match self.stack.last_mut() {
-            Some(StackItem::ArrayItem { first }) => {
-                if *first {
-                    *first = false;
-                } else {
-                    self.writer.write(b",");
-                }
-            }
+        let mut colon = false;
+        let item = match self.stack.last_mut() {
+            Some(StackItem::ArrayItem { first }) => Some(core::mem::replace(first, false)),
             Some(StackItem::ObjectItem { object_state }) => {
                 debug!("ObjectItem: object_state = {:?}", object_state);
                 match object_state {
                     ObjectItemState::FirstKey => {
                         *object_state = ObjectItemState::Value;
+                        Some(true)
                     }
                     ObjectItemState::Key => {
-                        self.writer.write(b",");
                         *object_state = ObjectItemState::Value;
+                        Some(false)
                     }
                     ObjectItemState::Value => {
-                        self.writer.write(b":");
                         *object_state = ObjectItemState::Key;
+                        colon = true;
+                        None
                     }
                 }
             }
             None => {
                 debug!("No stack frame (top-level value)");
+                None
+            }
+        };
+
+        if let Some(first) = item {
+            self.start_item(first);
+        }
+        if colon {
+            self.writer.write(b":");
+            if self.options.space_after_colon {
+                self.writer.write(b" ");
             }
         }
 
@@ -104,6 +292,43 @@ impl<W: crate::JsonWrite> JsonSerializer<W> {
     fn end_value(&mut self) -> Result<(), SerializeError> {
         Ok(())
     }
+
+    /// Writes what comes before an array item or an object key
+    fn start_item(&mut self, first: bool) {
+        if !first {
+            self.writer.write(b",");
+        }
+        self.write_line_break();
+    }
+
+    /// Starts a new line indented for the current nesting level, if output is indented
+    fn write_line_break(&mut self) {
+        let Some(indent) = self.options.indent else {
+            return;
+        };
+        self.writer.write(match self.options.newline {
+            Newline::Lf => b"\n",
+            Newline::CrLf => b"\r\n",
+        });
+        for _ in 0..self.stack.len() {
+            match indent {
+                Indent::Spaces(width) => {
+                    for _ in 0..width {
+                        self.writer.write(b" ");
+                    }
+                }
+                Indent::Tab => self.writer.write(b"\t"),
+            }
+        }
+    }
+
+    fn write_string(&mut self, value: &str) {
+        if self.options.ascii_only {
+            crate::write_json_string_ascii(&mut self.writer, value);
+        } else {
+            crate::write_json_string(&mut self.writer, value);
+        }
+    }
 }
 
 impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
@@ -217,16 +442,20 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
 
     fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
         self.start_value()?;
-        self.writer.write(b"\"");
-        crate::write_json_escaped_char(&mut self.writer, value);
-        self.writer.write(b"\"");
+        if self.options.ascii_only {
+            self.write_string(value.encode_utf8(&mut [0; 4]));
+        } else {
+            self.writer.write(b"\"");
+            crate::write_json_escaped_char(&mut self.writer, value);
+            self.writer.write(b"\"");
+        }
         self.end_value()
     }
 
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
         self.writer.reserve(value.len() + 2);
         self.start_value()?;
-        crate::write_json_string(&mut self.writer, value);
+        self.write_string(value);
         self.end_value()
     }
 
@@ -256,7 +485,7 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
         variant_name: &'shape str,
     ) -> Result<(), Self::Error> {
         self.start_value()?;
-        crate::write_json_string(&mut self.writer, variant_name);
+        self.write_string(variant_name);
         self.end_value()
     }
 
@@ -274,9 +503,10 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
         match object {
             StackItem::ArrayItem { .. } => unreachable!(),
             StackItem::ObjectItem { object_state } => match object_state {
-                ObjectItemState::FirstKey | ObjectItemState::Key => {
-                    // good
+                ObjectItemState::FirstKey => {
+                    // good, and empty
                 }
+                ObjectItemState::Key => self.write_line_break(),
                 ObjectItemState::Value => unreachable!(),
             },
         }
@@ -295,8 +525,10 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
     fn end_array(&mut self) -> Result<(), Self::Error> {
         let item = self.stack.pop().unwrap();
         match item {
-            StackItem::ArrayItem { .. } => {
-                // good
+            StackItem::ArrayItem { first } => {
+                if !first {
+                    self.write_line_break();
+                }
             }
             StackItem::ObjectItem { .. } => unreachable!(),
         }
@@ -309,27 +541,63 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
         self.start_object(_len)
     }
 
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error {
+        SerializeError::Unsupported {
+            shape: alloc::format!("{shape}"),
+            reason: reason.into(),
+        }
+    }
+
     fn end_map(&mut self) -> Result<(), Self::Error> {
         self.end_object()
     }
 
     fn serialize_field_name(&mut self, name: &'shape str) -> Result<(), Self::Error> {
         // Handle object key comma logic
-        if let Some(StackItem::ObjectItem { object_state }) = self.stack.last_mut() {
-            match object_state {
+        let first = match self.stack.last_mut() {
+            Some(StackItem::ObjectItem { object_state }) => match object_state {
                 ObjectItemState::FirstKey => {
                     *object_state = ObjectItemState::Key;
+                    Some(true)
                 }
-                ObjectItemState::Key => {
-                    self.writer.write(b",");
-                }
+                ObjectItemState::Key => Some(false),
                 ObjectItemState::Value => unreachable!(),
-            }
+            },
+            _ => None,
+        };
+        if let Some(first) = first {
+            self.start_item(first);
         }
-        crate::write_json_string(&mut self.writer, name);
+        self.write_string(name);
         if let Some(StackItem::ObjectItem { object_state }) = self.stack.last_mut() {
             *object_state = ObjectItemState::Value;
         }
         Ok(())
     }
+
+    fn serialize_custom(&mut self, value: Peek<'_, '_, 'shape>) -> Result<bool, Self::Error> {
+        if !self.options.sort_keys || !matches!(value.shape().def, Def::Map(_)) {
+            return Ok(false);
+        }
+
+        // Entries are ordered by the compact JSON text of their key
+        let map = value.into_map().unwrap();
+        let mut entries: Vec<_> = map
+            .iter()
+            .map(|(key, value)| (peek_to_string(key), key, value))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        self.start_map(Some(entries.len()))?;
+        for (_, key, value) in entries {
+            self.begin_map_key()?;
+            serialize_iterative(key, self)?;
+            self.end_map_key()?;
+            self.begin_map_value()?;
+            serialize_iterative(value, self)?;
+            self.end_map_value()?;
+        }
+        self.end_map()?;
+        Ok(true)
+    }
 }
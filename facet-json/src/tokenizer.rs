@@ -335,9 +335,34 @@ impl<'input> Tokenizer<'input> {
                                 }
                             };
 
+                            // Code points outside the BMP are escaped as a surrogate pair
+                            let code_point = if (0xD800..0xDC00).contains(&code_point) {
+                                let low_start = hex_start + 4;
+                                let low_surrogate = self
+                                    .input
+                                    .get(low_start..low_start + 6)
+                                    .filter(|escape| escape.starts_with(b"\\u"))
+                                    .and_then(|escape| str::from_utf8(&escape[2..]).ok())
+                                    .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+                                    .filter(|low| (0xDC00..0xE000).contains(low));
+                                let Some(low_surrogate) = low_surrogate else {
+                                    return Err(TokenError {
+                                        kind: TokenErrorKind::InvalidUtf8(
+                                            "unpaired surrogate in Unicode escape".to_string(),
+                                        ),
+                                        span: Span::new(hex_start, 4),
+                                    });
+                                };
+                                self.pos += 6;
+                                0x10000
+                                    + ((code_point as u32 - 0xD800) << 10)
+                                    + (low_surrogate as u32 - 0xDC00)
+                            } else {
+                                code_point as u32
+                            };
+
                             // Convert to UTF-8 and append to buffer
-                            // Handle basic Unicode code points (BMP)
-                            let c = match char::from_u32(code_point as u32) {
+                            let c = match char::from_u32(code_point) {
                                 Some(c) => c,
                                 None => {
                                     return Err(TokenError {
@@ -0,0 +1,68 @@
+use facet::{Facet, facet_fn, fn_shape};
+use facet_json::call;
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_call() {
+    #[facet_fn]
+    fn add(x: i32, y: i32) -> i32 {
+        x + y
+    }
+
+    let sum = call(&fn_shape!(add), "[2, 3]")?;
+    assert_eq!(sum.materialize::<i32>()?, 5);
+}
+
+#[test]
+fn test_call_with_structs() {
+    #[facet_fn]
+    fn translate(point: Point, by: i32) -> Point {
+        Point {
+            x: point.x + by,
+            y: point.y + by,
+        }
+    }
+
+    let moved = call(&fn_shape!(translate), r#"[{"x": 1, "y": 2}, 10]"#)?;
+    assert_eq!(moved.materialize::<Point>()?, Point { x: 11, y: 12 });
+}
+
+#[test]
+fn test_call_without_parameters() {
+    #[facet_fn]
+    fn answer() -> u64 {
+        42
+    }
+
+    let answer = call(&fn_shape!(answer), "[]")?;
+    assert_eq!(answer.materialize::<u64>()?, 42);
+}
+
+#[test]
+fn test_call_generic() {
+    #[facet_fn]
+    fn twice<T: Clone>(item: T) -> Vec<T> {
+        vec![item.clone(), item]
+    }
+
+    let items = call(&fn_shape!(twice<String>), r#"["a"]"#)?;
+    assert_eq!(items.materialize::<Vec<String>>()?, ["a", "a"]);
+}
+
+#[test]
+fn test_call_wrong_arguments() {
+    #[facet_fn]
+    fn greet(name: String) -> String {
+        format!("Hello, {name}!")
+    }
+
+    assert!(call(&fn_shape!(greet), "[1]").is_err());
+    assert!(call(&fn_shape!(greet), r#"["a", "b"]"#).is_err());
+    assert!(call(&fn_shape!(greet), r#"{"name": "a"}"#).is_err());
+}
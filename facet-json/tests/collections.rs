@@ -0,0 +1,58 @@
+use std::borrow::Cow;
+use std::collections::{BinaryHeap, LinkedList, VecDeque};
+
+use facet::Facet;
+use facet_json::{from_str, to_string};
+use facet_testhelpers::test;
+
+#[test]
+fn json_read_vec_deque() {
+    let v: VecDeque<u64> = from_str(r#"[1, 2, 3]"#)?;
+    assert_eq!(v, [1, 2, 3]);
+}
+
+#[test]
+fn json_read_linked_list() {
+    let v: LinkedList<String> = from_str(r#"["a", "b"]"#)?;
+    assert_eq!(v.into_iter().collect::<Vec<_>>(), ["a", "b"]);
+}
+
+#[test]
+fn json_read_binary_heap() {
+    let v: BinaryHeap<u32> = from_str(r#"[5, 1, 9]"#)?;
+    assert_eq!(v.into_sorted_vec(), [1, 5, 9]);
+}
+
+#[test]
+fn json_read_cow_slice() {
+    let v: Cow<'_, [u32]> = from_str(r#"[4, 5]"#)?;
+    assert!(matches!(v, Cow::Owned(_)));
+    assert_eq!(&*v, [4, 5]);
+}
+
+#[test]
+fn json_roundtrip_collections() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Collections<'a> {
+        queue: VecDeque<i32>,
+        list: LinkedList<String>,
+        cow: Cow<'a, [u16]>,
+    }
+
+    let value = Collections {
+        queue: VecDeque::from([3, 1, 2]),
+        list: LinkedList::from(["x".to_string(), "y".to_string()]),
+        cow: Cow::Borrowed(&[7, 8]),
+    };
+
+    let json = to_string(&value);
+    assert_eq!(json, r#"{"queue":[3,1,2],"list":["x","y"],"cow":[7,8]}"#);
+    let roundtrip: Collections = from_str(&json)?;
+    assert_eq!(roundtrip, value);
+}
+
+#[test]
+fn json_write_binary_heap() {
+    let heap = BinaryHeap::from([1u32]);
+    assert_eq!(to_string(&heap), "[1]");
+}
@@ -0,0 +1,243 @@
+use facet::Facet;
+use facet_json::{from_str, to_string};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Circle {
+    radius: u32,
+}
+
+#[test]
+fn internally_tagged_enum() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(tag = "type")]
+    #[repr(u8)]
+    enum Shape {
+        Empty,
+        Circle(Circle),
+        Rect { width: u32, height: u32 },
+    }
+
+    assert_eq!(to_string(&Shape::Empty), r#"{"type":"Empty"}"#);
+    assert_eq!(
+        to_string(&Shape::Circle(Circle { radius: 3 })),
+        r#"{"type":"Circle","radius":3}"#
+    );
+    assert_eq!(
+        to_string(&Shape::Rect {
+            width: 1,
+            height: 2
+        }),
+        r#"{"type":"Rect","width":1,"height":2}"#
+    );
+
+    assert_eq!(from_str::<Shape>(r#"{"type":"Empty"}"#)?, Shape::Empty);
+    assert_eq!(
+        from_str::<Shape>(r#"{"type":"Circle","radius":3}"#)?,
+        Shape::Circle(Circle { radius: 3 })
+    );
+    // The tag doesn't have to come first
+    assert_eq!(
+        from_str::<Shape>(r#"{"width":1,"height":2,"type":"Rect"}"#)?,
+        Shape::Rect {
+            width: 1,
+            height: 2
+        }
+    );
+}
+
+#[test]
+fn internally_tagged_enum_in_struct() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(tag = "kind", rename_all = "snake_case")]
+    #[repr(u8)]
+    enum Event {
+        Created { id: u64 },
+        Deleted { id: u64, reason: String },
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Envelope {
+        events: Vec<Event>,
+        count: usize,
+    }
+
+    let envelope = Envelope {
+        events: vec![
+            Event::Created { id: 1 },
+            Event::Deleted {
+                id: 1,
+                reason: "gone".to_string(),
+            },
+        ],
+        count: 2,
+    };
+    let json = to_string(&envelope);
+    assert_eq!(
+        json,
+        r#"{"events":[{"kind":"created","id":1},{"kind":"deleted","id":1,"reason":"gone"}],"count":2}"#
+    );
+    assert_eq!(from_str::<Envelope>(&json)?, envelope);
+}
+
+#[test]
+fn adjacently_tagged_enum() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(tag = "t", content = "c")]
+    #[repr(u8)]
+    enum Message {
+        Ping,
+        Text(String),
+        Move(i32, i32),
+        Resize { width: u32, height: u32 },
+    }
+
+    let cases = [
+        (Message::Ping, r#"{"t":"Ping"}"#),
+        (Message::Text("hi".to_string()), r#"{"t":"Text","c":"hi"}"#),
+        (Message::Move(1, -2), r#"{"t":"Move","c":[1,-2]}"#),
+        (
+            Message::Resize {
+                width: 3,
+                height: 4,
+            },
+            r#"{"t":"Resize","c":{"width":3,"height":4}}"#,
+        ),
+    ];
+    for (message, json) in cases {
+        assert_eq!(to_string(&message), json);
+        assert_eq!(from_str::<Message>(json)?, message);
+    }
+
+    // The content may come before the tag
+    assert_eq!(
+        from_str::<Message>(r#"{"c":[5,6],"t":"Move"}"#)?,
+        Message::Move(5, 6)
+    );
+}
+
+#[test]
+fn untagged_enum() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    enum Value {
+        Nothing,
+        Number(u64),
+        Text(String),
+        Pair(u64, String),
+        Circle(Circle),
+    }
+
+    let cases = [
+        (Value::Nothing, "null"),
+        (Value::Number(42), "42"),
+        (Value::Text("hello".to_string()), r#""hello""#),
+        (Value::Pair(1, "one".to_string()), r#"[1,"one"]"#),
+        (Value::Circle(Circle { radius: 7 }), r#"{"radius":7}"#),
+    ];
+    for (value, json) in cases {
+        assert_eq!(to_string(&value), json);
+        assert_eq!(from_str::<Value>(json)?, value);
+    }
+}
+
+#[test]
+fn untagged_enum_in_struct() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    enum Id {
+        Number(u64),
+        Name(String),
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Item {
+        id: Id,
+        tags: Vec<String>,
+    }
+
+    let item: Item = from_str(r#"{"id":"abc","tags":["a","b"]}"#)?;
+    assert_eq!(
+        item,
+        Item {
+            id: Id::Name("abc".to_string()),
+            tags: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+
+    let item: Item = from_str(r#"{"id":12,"tags":[]}"#)?;
+    assert_eq!(
+        item,
+        Item {
+            id: Id::Number(12),
+            tags: vec![],
+        }
+    );
+}
+
+#[test]
+fn untagged_enum_nested() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    enum Tree {
+        Node { value: u64, children: Vec<Tree> },
+        Pair(u64, u64),
+        Leaf(u64),
+        Branch(Vec<Tree>),
+    }
+
+    // Only the variants that can start with `[` are replayed at each level, so
+    // deep nesting stays cheap even though `Branch` is tried last.
+    let depth = 64;
+    let json = format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+    let mut expected = Tree::Leaf(1);
+    for _ in 0..depth {
+        expected = Tree::Branch(vec![expected]);
+    }
+    assert_eq!(from_str::<Tree>(&json)?, expected);
+
+    let tree: Tree = from_str(r#"[{"value":1,"children":[[2,3],4]},[5,6]]"#)?;
+    assert_eq!(
+        tree,
+        Tree::Branch(vec![
+            Tree::Node {
+                value: 1,
+                children: vec![Tree::Pair(2, 3), Tree::Leaf(4)],
+            },
+            Tree::Pair(5, 6),
+        ])
+    );
+}
+
+#[test]
+fn tagged_enum_errors() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(tag = "type")]
+    #[repr(u8)]
+    enum Shape {
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    enum Number {
+        Small(u8),
+        Big(u64),
+    }
+
+    let err = from_str::<Shape>(r#"{"width":1,"height":2}"#).unwrap_err();
+    assert!(err.to_string().contains("Missing key"), "{err}");
+
+    let err = from_str::<Shape>(r#"{"type":"Square","width":1}"#).unwrap_err();
+    assert!(err.to_string().contains("Square"), "{err}");
+
+    let err = from_str::<Number>(r#""not a number""#).unwrap_err();
+    assert!(
+        err.to_string().contains("did not match any variant"),
+        "{err}"
+    );
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_json::{
+    Indent, Newline, SerializeOptions, from_str, to_string, to_string_pretty,
+    to_string_with_options,
+};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Fixture {
+    name: String,
+    tags: Vec<String>,
+    empty: Vec<u32>,
+    nested: Nested,
+    scores: HashMap<String, u32>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Nested {
+    enabled: bool,
+    ratio: f64,
+}
+
+fn fixture() -> Fixture {
+    let mut scores = HashMap::new();
+    scores.insert("zeta".to_string(), 3);
+    scores.insert("alpha".to_string(), 1);
+    scores.insert("mu".to_string(), 2);
+    Fixture {
+        name: "Zoë".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+        empty: vec![],
+        nested: Nested {
+            enabled: true,
+            ratio: 0.5,
+        },
+        scores,
+    }
+}
+
+#[test]
+fn test_pretty_round_trip() {
+    let json = to_string_pretty(&fixture());
+    assert!(json.contains("\n  \"tags\": [\n    \"a\",\n    \"b\"\n  ],\n"));
+    assert!(json.contains("\n  \"empty\": [],\n"));
+    assert!(json.contains("\n  \"nested\": {\n    \"enabled\": true,\n    \"ratio\": 0.5\n  },\n"));
+    assert_eq!(from_str::<Fixture>(&json)?, fixture());
+}
+
+#[test]
+fn test_compact_is_unchanged() {
+    let json = to_string(&vec![vec![1, 2], vec![]]);
+    assert_eq!(json, "[[1,2],[]]");
+}
+
+#[test]
+fn test_sorted_keys_are_deterministic() {
+    let options = SerializeOptions::pretty().sort_keys(true);
+    let json = to_string_with_options(&fixture(), options);
+    assert!(
+        json.ends_with(
+            "  \"scores\": {\n    \"alpha\": 1,\n    \"mu\": 2,\n    \"zeta\": 3\n  }\n}"
+        )
+    );
+    for _ in 0..10 {
+        assert_eq!(to_string_with_options(&fixture(), options), json);
+    }
+}
+
+#[test]
+fn test_indent_newline_and_colon_options() {
+    let value = Nested {
+        enabled: false,
+        ratio: 1.0,
+    };
+
+    let tabs = SerializeOptions::default()
+        .indent(Indent::Tab)
+        .newline(Newline::CrLf);
+    assert_eq!(
+        to_string_with_options(&value, tabs),
+        "{\r\n\t\"enabled\":false,\r\n\t\"ratio\":1.0\r\n}"
+    );
+
+    let four = SerializeOptions::default()
+        .indent(Indent::Spaces(4))
+        .space_after_colon(true);
+    assert_eq!(
+        to_string_with_options(&value, four),
+        "{\n    \"enabled\": false,\n    \"ratio\": 1.0\n}"
+    );
+
+    let spaced = SerializeOptions::default().space_after_colon(true);
+    assert_eq!(
+        to_string_with_options(&value, spaced),
+        "{\"enabled\": false,\"ratio\": 1.0}"
+    );
+}
+
+#[test]
+fn test_ascii_only() {
+    let options = SerializeOptions::default().ascii_only(true);
+    let json = to_string_with_options(&vec!["Zoë 🦀".to_string()], options);
+    assert_eq!(json, "[\"Zo\\u00eb \\ud83e\\udd80\"]");
+    assert_eq!(from_str::<Vec<String>>(&json)?, vec!["Zoë 🦀".to_string()]);
+}
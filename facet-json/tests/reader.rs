@@ -0,0 +1,130 @@
+use std::io::{self, Read};
+
+use facet::Facet;
+use facet_json::{ReadError, StreamDeserializer, from_reader};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Event {
+    id: u32,
+    message: String,
+}
+
+/// Hands out at most one byte per read, to split values across reads everywhere
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((byte, rest)), Some(slot)) => {
+                *slot = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn test_from_reader() {
+    let input = br#"  {"id": 1, "message": "hello"}  "#;
+    let event: Event = from_reader(&input[..])?;
+    assert_eq!(
+        event,
+        Event {
+            id: 1,
+            message: "hello".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_from_reader_rejects_trailing_characters() {
+    let input = br#"{"id": 1, "message": "hello"} {"#;
+    let result = from_reader::<Event, _>(&input[..]);
+    assert!(matches!(result, Err(ReadError::Deser(_))));
+}
+
+#[test]
+fn test_from_reader_empty_input() {
+    let result = from_reader::<Event, _>(&b"   "[..]);
+    assert!(matches!(result, Err(ReadError::Deser(_))));
+}
+
+#[test]
+fn test_stream_ndjson() {
+    let input = b"{\"id\": 1, \"message\": \"a\"}\n{\"id\": 2, \"message\": \"b\"}\n\n{\"id\": 3, \"message\": \"c\"}\n";
+    let events = StreamDeserializer::<_, Event>::new(&input[..]).collect::<Result<Vec<_>, _>>()?;
+    let ids: Vec<u32> = events.iter().map(|e| e.id).collect();
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[test]
+fn test_stream_concatenated() {
+    let input = br#"{"id":1,"message":"}{"}{"id":2,"message":"\"]"} {"id":3,"message":""}"#;
+    let events =
+        StreamDeserializer::<_, Event>::new(Trickle(input)).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].message, "}{");
+    assert_eq!(events[1].message, "\"]");
+    assert_eq!(events[2].id, 3);
+}
+
+#[test]
+fn test_stream_scalars() {
+    let numbers =
+        StreamDeserializer::<_, i64>::new(Trickle(b"1 -2\n30")).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(numbers, [1, -2, 30]);
+
+    let strings = StreamDeserializer::<_, String>::new(&br#""a""b" "c\\""#[..])
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(strings, ["a", "b", "c\\"]);
+}
+
+#[test]
+fn test_stream_value_larger_than_a_chunk() {
+    let long = "x".repeat(20_000);
+    let input =
+        format!("{{\"id\": 1, \"message\": \"{long}\"}}\n{{\"id\": 2, \"message\": \"short\"}}");
+    let events =
+        StreamDeserializer::<_, Event>::new(input.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events[0].message, long);
+    assert_eq!(events[1].message, "short");
+}
+
+#[test]
+fn test_stream_skips_invalid_values() {
+    let input = b"{\"id\": 1, \"message\": \"a\"}\n{\"id\": \"two\", \"message\": \"b\"}\n{\"id\": 3, \"message\": \"c\"}";
+    let mut stream = StreamDeserializer::<_, Event>::new(&input[..]);
+    assert_eq!(stream.next().unwrap()?.id, 1);
+    assert!(matches!(stream.next(), Some(Err(ReadError::Deser(_)))));
+    assert_eq!(stream.next().unwrap()?.id, 3);
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_stream_byte_offset() {
+    let input = b"{\"id\": 1, \"message\": \"a\"}\n{\"id\": 2, \"message\": \"b\"}\n";
+    let mut stream = StreamDeserializer::<_, Event>::new(&input[..]);
+    assert_eq!(stream.byte_offset(), 0);
+    stream.next().unwrap()?;
+    assert_eq!(stream.byte_offset(), 25);
+    stream.next().unwrap()?;
+    assert_eq!(stream.byte_offset(), 51);
+}
+
+#[test]
+fn test_stream_stops_after_io_error() {
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    let mut stream = StreamDeserializer::<_, Event>::new(Failing);
+    assert!(matches!(stream.next(), Some(Err(ReadError::Io(_)))));
+    assert!(stream.next().is_none());
+}
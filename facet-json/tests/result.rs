@@ -0,0 +1,50 @@
+use facet::Facet;
+use facet_json::{from_str, to_string};
+use facet_testhelpers::test;
+
+#[test]
+fn json_read_result() {
+    let ok: Result<u32, String> = from_str(r#"{"Ok": 42}"#)?;
+    assert_eq!(ok, Ok(42));
+
+    let err: Result<u32, String> = from_str(r#"{"Err": "nope"}"#)?;
+    assert_eq!(err, Err("nope".to_string()));
+}
+
+#[test]
+fn json_write_result() {
+    assert_eq!(to_string(&Ok::<u32, String>(42)), r#"{"Ok":42}"#);
+    assert_eq!(
+        to_string(&Err::<u32, String>("nope".to_string())),
+        r#"{"Err":"nope"}"#
+    );
+}
+
+#[test]
+fn json_read_result_unknown_variant() {
+    assert!(from_str::<Result<u32, String>>(r#"{"Fine": 42}"#).is_err());
+}
+
+#[test]
+fn json_roundtrip_result_in_struct() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Job {
+        name: String,
+        outcome: Result<Vec<u32>, Option<String>>,
+        history: Vec<Result<u8, bool>>,
+    }
+
+    let job = Job {
+        name: "build".to_string(),
+        outcome: Err(Some("timed out".to_string())),
+        history: vec![Ok(1), Err(false)],
+    };
+
+    let json = to_string(&job);
+    assert_eq!(
+        json,
+        r#"{"name":"build","outcome":{"Err":"timed out"},"history":[{"Ok":1},{"Err":false}]}"#
+    );
+    let roundtrip: Job = from_str(&json)?;
+    assert_eq!(roundtrip, job);
+}
@@ -166,3 +166,66 @@ fn test_writing_flat_structs() {
     });
     assert_eq!(expected3, actual3);
 }
+
+#[test]
+fn test_flattened_map_collects_unknown_fields() {
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Manifest {
+        kind: String,
+        #[facet(flatten)]
+        rest: BTreeMap<String, String>,
+        version: u32,
+    }
+
+    let json = r#"{"kind":"Pod","metadata":"x","version":2,"spec":"y"}"#;
+    let manifest: Manifest = facet_json::from_str(json)?;
+    assert_eq!(
+        manifest,
+        Manifest {
+            kind: "Pod".to_string(),
+            rest: BTreeMap::from([
+                ("metadata".to_string(), "x".to_string()),
+                ("spec".to_string(), "y".to_string()),
+            ]),
+            version: 2,
+        }
+    );
+
+    let json = facet_json::to_string(&manifest);
+    assert_eq!(
+        json,
+        r#"{"kind":"Pod","metadata":"x","spec":"y","version":2}"#
+    );
+    let back: Manifest = facet_json::from_str(&json)?;
+    assert_eq!(back, manifest);
+
+    // Without unknown fields, the map is empty
+    let manifest: Manifest = facet_json::from_str(r#"{"kind":"Pod","version":1}"#)?;
+    assert!(manifest.rest.is_empty());
+    assert_eq!(
+        facet_json::to_string(&manifest),
+        r#"{"kind":"Pod","version":1}"#
+    );
+}
+
+#[test]
+fn test_flattened_map_with_deny_unknown_fields() {
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        name: String,
+        #[facet(flatten)]
+        extra: HashMap<String, u64>,
+    }
+
+    let strict: Strict = facet_json::from_str(r#"{"name":"a","b":1,"c":2}"#)?;
+    assert_eq!(strict.name, "a");
+    assert_eq!(
+        strict.extra,
+        HashMap::from([("b".to_string(), 1), ("c".to_string(), 2)])
+    );
+}
@@ -5,23 +5,48 @@
 #![doc = include_str!("../README.md")]
 
 extern crate facet_core as facet;
-use facet::{PointerType, SmartPointerDef};
-use facet_core::{Def, Facet, ScalarDef, Shape, Type, UserType};
+use facet_core::{Facet, Shape};
+use facet_json::SerializeOptions;
+use facet_value::Value;
 
-use std::io::Write;
+mod schema;
 
 /// Convert a `Facet` type to a JSON schema string.
+///
+/// # Panics
+///
+/// Panics if the type has more than one `id` attribute.
 pub fn to_string<'a, T: Facet<'a>>() -> String {
-    let mut buffer = Vec::new();
-    write!(buffer, "{{").unwrap();
-    write!(
-        buffer,
-        "\"$schema\": \"https://json-schema.org/draft/2020-12/schema\","
-    )
-    .unwrap();
+    let options = SerializeOptions::default().space_after_colon(true);
+    facet_json::to_string_with_options(&to_value(T::SHAPE), options)
+}
+
+/// Convert a `Facet` type to an indented JSON schema string.
+///
+/// # Panics
+///
+/// Panics if the type has more than one `id` attribute.
+pub fn to_string_pretty<'a, T: Facet<'a>>() -> String {
+    facet_json::to_string_pretty(&to_value(T::SHAPE))
+}
+
+/// Builds the JSON schema of a shape, as a [`Value`].
+///
+/// Named types that are used more than once, or that contain themselves, are
+/// described once under `$defs` and referenced with `$ref`.
+///
+/// # Panics
+///
+/// Panics if the shape has more than one `id` attribute.
+pub fn to_value<'shape>(shape: &'shape Shape<'shape>) -> Value {
+    let mut root = Value::object([(
+        "$schema",
+        Value::from("https://json-schema.org/draft/2020-12/schema"),
+    )]);
+    let map = root.as_object_mut().unwrap();
 
     // Find the first attribute that starts with "id=", if it exists more than once is an error
-    let mut id = T::SHAPE.attributes.iter().filter_map(|attr| match attr {
+    let mut id = shape.attributes.iter().filter_map(|attr| match attr {
         facet_core::ShapeAttribute::Arbitrary(attr_str) => {
             if attr_str.starts_with("id") {
                 let id = attr_str
@@ -40,231 +65,26 @@ pub fn to_string<'a, T: Facet<'a>>() -> String {
     match (id.next(), id.next()) {
         (Some(_), Some(_)) => panic!("More than one id attribute found"),
         (Some(id), None) => {
-            write!(buffer, "\"$id\": \"{id}\",").unwrap();
+            map.insert("$id", Value::from(id));
         }
         _ => {
             // No id attribute found, do nothing
         }
     }
 
-    serialize(T::SHAPE, &[], &mut buffer).unwrap();
-    write!(buffer, "}}").unwrap();
-    String::from_utf8(buffer).unwrap()
-}
-
-fn serialize<'shape, W: Write>(
-    shape: &'shape Shape<'shape>,
-    doc: &[&str],
-    writer: &mut W,
-) -> std::io::Result<()> {
-    serialize_doc(&[shape.doc, doc].concat(), writer)?;
-
-    // First check the type system (Type)
-    match &shape.ty {
-        Type::User(UserType::Struct(struct_def)) => {
-            serialize_struct(struct_def, writer)?;
-            return Ok(());
-        }
-        Type::User(UserType::Enum(_enum_def)) => {
-            todo!("Enum");
-        }
-        Type::Sequence(sequence_type) => {
-            use facet_core::SequenceType;
-            match sequence_type {
-                SequenceType::Slice(_slice_type) => {
-                    // For slices, use the Def::Slice if available
-                    if let Def::Slice(slice_def) = shape.def {
-                        serialize_slice(slice_def, writer)?;
-                        return Ok(());
-                    }
-                }
-                SequenceType::Array(_array_type) => {
-                    // For arrays, use the Def::Array if available
-                    if let Def::Array(array_def) = shape.def {
-                        serialize_array(array_def, writer)?;
-                        return Ok(());
-                    }
-                }
-            }
-        }
-        _ => {} // Continue to check the def system
+    let (schema, defs) = schema::Generator::generate(shape);
+    map.extend(schema);
+    if !defs.is_empty() {
+        map.insert("$defs", Value::Object(defs));
     }
-
-    // Then check the def system (Def)
-    match shape.def {
-        Def::Scalar(ref scalar_def) => serialize_scalar(scalar_def, writer)?,
-        Def::Map(_map_def) => todo!("Map"),
-        Def::List(list_def) => serialize_list(list_def, writer)?,
-        Def::Slice(slice_def) => serialize_slice(slice_def, writer)?,
-        Def::Array(array_def) => serialize_array(array_def, writer)?,
-        Def::Option(option_def) => serialize_option(option_def, writer)?,
-        Def::SmartPointer(SmartPointerDef {
-            pointee: Some(inner_shape),
-            ..
-        }) => serialize(inner_shape(), &[], writer)?,
-        Def::Undefined => {
-            // Handle the case when not yet migrated to the Type enum
-            // For primitives, we can try to infer the type
-            match &shape.ty {
-                Type::Primitive(primitive) => {
-                    use facet_core::{NumericType, PrimitiveType, TextualType};
-                    match primitive {
-                        PrimitiveType::Numeric(NumericType::Float) => {
-                            write!(writer, "\"type\": \"number\", \"format\": \"double\"")?;
-                        }
-                        PrimitiveType::Boolean => {
-                            write!(writer, "\"type\": \"boolean\"")?;
-                        }
-                        PrimitiveType::Textual(TextualType::Str) => {
-                            write!(writer, "\"type\": \"string\"")?;
-                        }
-                        _ => {
-                            write!(writer, "\"type\": \"unknown\"")?;
-                        }
-                    }
-                }
-                Type::Pointer(PointerType::Reference(pt) | PointerType::Raw(pt)) => {
-                    serialize((pt.target)(), &[], writer)?
-                }
-                _ => {
-                    write!(writer, "\"type\": \"unknown\"")?;
-                }
-            }
-        }
-        _ => {
-            write!(writer, "\"type\": \"unknown\"")?;
-        }
-    }
-
-    Ok(())
-}
-
-fn serialize_doc<W: Write>(doc: &[&str], writer: &mut W) -> Result<(), std::io::Error> {
-    if !doc.is_empty() {
-        let doc = doc.join("\n");
-        write!(writer, "\"description\": \"{}\",", doc.trim())?;
-    }
-    Ok(())
-}
-
-/// Serialize a scalar definition to JSON schema format.
-fn serialize_scalar<W: Write>(scalar_def: &ScalarDef, writer: &mut W) -> std::io::Result<()> {
-    match scalar_def.affinity {
-        facet_core::ScalarAffinity::Number(number_affinity) => {
-            match number_affinity.bits {
-                facet_core::NumberBits::Integer { size, sign } => {
-                    write!(writer, "\"type\": \"integer\"")?;
-                    let bits = match size {
-                        facet_core::IntegerSize::Fixed(bits) => bits,
-                        facet_core::IntegerSize::PointerSized => core::mem::size_of::<usize>() * 8,
-                    };
-                    match sign {
-                        facet_core::Signedness::Unsigned => {
-                            write!(writer, ", \"format\": \"uint{bits}\"")?;
-                            write!(writer, ", \"minimum\": 0")?;
-                        }
-                        facet_core::Signedness::Signed => {
-                            write!(writer, ", \"format\": \"int{bits}\"")?;
-                        }
-                    }
-                }
-                facet_core::NumberBits::Float { .. } => {
-                    write!(writer, "\"type\": \"number\"")?;
-                    write!(writer, ", \"format\": \"double\"")?;
-                }
-                _ => unimplemented!(),
-            }
-            Ok(())
-        }
-        facet_core::ScalarAffinity::String(_) => {
-            write!(writer, "\"type\": \"string\"")?;
-            Ok(())
-        }
-        facet_core::ScalarAffinity::Boolean(_) => {
-            write!(writer, "\"type\": \"boolean\"")?;
-            Ok(())
-        }
-        _ => Err(std::io::Error::other(format!(
-            "facet-jsonschema: nsupported scalar type: {scalar_def:#?}"
-        ))),
-    }
-}
-
-fn serialize_struct<W: Write>(
-    struct_type: &facet_core::StructType,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"object\",")?;
-    let required = struct_type
-        .fields
-        .iter()
-        .map(|f| format!("\"{}\"", f.name))
-        .collect::<Vec<_>>()
-        .join(",");
-    write!(writer, "\"required\": [{required}],")?;
-    write!(writer, "\"properties\": {{")?;
-    let mut first = true;
-    for field in struct_type.fields {
-        if !first {
-            write!(writer, ",")?;
-        }
-        first = false;
-        write!(writer, "\"{}\": {{", field.name)?;
-        serialize(field.shape(), field.doc, writer)?;
-        write!(writer, "}}")?;
-    }
-    write!(writer, "}}")?;
-    Ok(())
-}
-
-/// Serialize a list definition to JSON schema format.
-fn serialize_list<W: Write>(list_def: facet_core::ListDef, writer: &mut W) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"array\",")?;
-    write!(writer, "\"items\": {{")?;
-    serialize(list_def.t(), &[], writer)?;
-    write!(writer, "}}")?;
-    Ok(())
-}
-
-/// Serialize a slice definition to JSON schema format.
-fn serialize_slice<W: Write>(
-    slice_def: facet_core::SliceDef,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"array\",")?;
-    write!(writer, "\"items\": {{")?;
-    serialize(slice_def.t(), &[], writer)?;
-    write!(writer, "}}")?;
-    Ok(())
-}
-
-/// Serialize an array definition to JSON schema format.
-fn serialize_array<W: Write>(
-    array_def: facet_core::ArrayDef,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"array\",")?;
-    write!(writer, "\"minItems\": {},", array_def.n)?;
-    write!(writer, "\"maxItems\": {},", array_def.n)?;
-    write!(writer, "\"items\": {{")?;
-    serialize(array_def.t(), &[], writer)?;
-    write!(writer, "}}")?;
-    Ok(())
-}
-
-/// Serialize an option definition to JSON schema format.
-fn serialize_option<W: Write>(
-    _option_def: facet_core::OptionDef,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"[]\",")?;
-    unimplemented!("serialize_option");
+    root
 }
 
 #[cfg(test)]
 mod tests {
     extern crate alloc;
     use alloc::{rc::Rc, sync::Arc};
+    use std::collections::HashMap;
 
     use super::*;
     use facet_macros::Facet;
@@ -306,4 +126,139 @@ mod tests {
         let schema = to_string::<TestStruct>();
         assert_snapshot!(schema);
     }
+
+    #[test]
+    fn test_enums() {
+        #[derive(Facet)]
+        #[repr(u8)]
+        #[allow(dead_code)]
+        enum Level {
+            Low,
+            High,
+        }
+
+        /// A drawing command
+        #[derive(Facet)]
+        #[repr(u8)]
+        #[allow(dead_code)]
+        enum Command {
+            /// Clears the canvas
+            Clear,
+            Move(i32, i32),
+            Color(String),
+            Resize {
+                width: u32,
+                height: u32,
+            },
+        }
+
+        #[derive(Facet)]
+        #[facet(tag = "type")]
+        #[repr(u8)]
+        #[allow(dead_code)]
+        enum Event {
+            Ping,
+            Click { x: i32 },
+        }
+
+        #[derive(Facet)]
+        #[facet(tag = "t", content = "c")]
+        #[repr(u8)]
+        #[allow(dead_code)]
+        enum Adjacent {
+            Unit,
+            Value(bool),
+        }
+
+        #[derive(Facet)]
+        #[facet(untagged)]
+        #[repr(u8)]
+        #[allow(dead_code)]
+        enum Untagged {
+            Number(f64),
+            Text(String),
+        }
+
+        #[derive(Facet)]
+        struct Enums {
+            level: Level,
+            command: Command,
+            event: Event,
+            adjacent: Adjacent,
+            untagged: Untagged,
+        }
+
+        let schema = to_string::<Enums>();
+        assert_snapshot!(schema);
+    }
+
+    #[test]
+    fn test_options_maps_and_defaults() {
+        #[derive(Facet)]
+        #[facet(deny_unknown_fields)]
+        struct Settings {
+            name: Option<String>,
+            limits: HashMap<String, u64>,
+            #[facet(default = 8080)]
+            port: u16,
+            #[facet(default)]
+            tags: Vec<String>,
+            scale: Option<[f32; 2]>,
+        }
+
+        let schema = to_string::<Settings>();
+        assert_snapshot!(schema);
+    }
+
+    #[test]
+    fn test_defs() {
+        #[derive(Facet)]
+        struct Point {
+            x: i64,
+            y: i64,
+        }
+
+        /// A tree of labels
+        #[derive(Facet)]
+        struct Tree {
+            label: String,
+            children: Vec<Tree>,
+        }
+
+        #[derive(Facet)]
+        struct Line {
+            from: Point,
+            to: Point,
+            tree: Tree,
+            next: Option<Box<Line>>,
+        }
+
+        let schema = to_string::<Line>();
+        assert_snapshot!(schema);
+    }
+
+    #[test]
+    fn test_pretty() {
+        #[derive(Facet)]
+        struct Point {
+            x: i64,
+        }
+
+        assert_eq!(
+            to_string_pretty::<Point>(),
+            r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "type": "object",
+  "required": [
+    "x"
+  ],
+  "properties": {
+    "x": {
+      "type": "integer",
+      "format": "int64"
+    }
+  }
+}"#
+        );
+    }
 }
@@ -0,0 +1,550 @@
+//! Builds the schema of a shape
+
+use std::collections::{HashMap, HashSet};
+
+use facet_core::{
+    ConstTypeId, Def, EnumType, Field, FieldFlags, NumberBits, PointerType, ScalarAffinity,
+    ScalarDef, Shape, ShapeAttribute, Signedness, SmartPointerDef, StructKind, StructType, Type,
+    UserType, Variant,
+};
+use facet_reflect::Partial;
+use facet_value::{Map, Value};
+
+/// Builds schemas, hoisting named types that are used more than once into `$defs`
+pub(crate) struct Generator {
+    /// The type the schema is about, referenced as `#` from inside itself
+    root: ConstTypeId,
+    /// Named types that are referenced more than once
+    hoisted: HashSet<ConstTypeId>,
+    /// The `$defs` name of each hoisted type that has been generated
+    names: HashMap<ConstTypeId, String>,
+    defs: Map,
+}
+
+/// The properties of an object schema, gathered from struct fields
+#[derive(Default)]
+struct Object {
+    properties: Map,
+    required: Vec<Value>,
+    /// The value schema of a flattened map, which takes all the other properties
+    additional: Option<Value>,
+    /// Flattened enums, which add properties depending on their variant
+    all_of: Vec<Value>,
+}
+
+impl Generator {
+    /// Returns the schema of a shape, and the `$defs` it refers to
+    pub(crate) fn generate<'shape>(shape: &'shape Shape<'shape>) -> (Map, Map) {
+        let mut counts = HashMap::new();
+        count_references(shape, &mut counts);
+
+        let mut generator = Generator {
+            root: shape.id,
+            hoisted: counts
+                .into_iter()
+                .filter(|&(id, count)| count > 1 && id != shape.id)
+                .map(|(id, _)| id)
+                .collect(),
+            names: HashMap::new(),
+            defs: Map::new(),
+        };
+        let schema = generator.inline(shape);
+        (schema, generator.defs)
+    }
+
+    /// The schema of a shape, as a reference if it's hoisted
+    fn schema<'shape>(&mut self, shape: &'shape Shape<'shape>) -> Value {
+        if shape.id == self.root {
+            return reference("#");
+        }
+        if !self.hoisted.contains(&shape.id) {
+            return Value::Object(self.inline(shape));
+        }
+
+        let name = match self.names.get(&shape.id) {
+            Some(name) => name.clone(),
+            None => {
+                let mut name = shape.type_identifier.to_string();
+                let mut suffix = 1;
+                while self.defs.contains_key(&name) {
+                    suffix += 1;
+                    name = format!("{}_{suffix}", shape.type_identifier);
+                }
+                // Reserve the name first, so that recursive references terminate
+                self.names.insert(shape.id, name.clone());
+                self.defs.insert(name.clone(), Value::Null);
+                let schema = self.inline(shape);
+                self.defs.insert(name.clone(), Value::Object(schema));
+                name
+            }
+        };
+        reference(&format!("#/$defs/{name}"))
+    }
+
+    /// The schema of a shape, written out in full
+    fn inline<'shape>(&mut self, shape: &'shape Shape<'shape>) -> Map {
+        let mut schema = Map::new();
+        if let Some(description) = description(shape.doc) {
+            schema.insert("description", description);
+        }
+
+        if shape.attributes.contains(&ShapeAttribute::Transparent) {
+            if let Type::User(UserType::Struct(sd)) = shape.ty {
+                if let Some(field) = sd.fields.first() {
+                    merge(&mut schema, self.schema(field.shape()));
+                    return schema;
+                }
+            }
+        }
+
+        match shape.def {
+            Def::Scalar(ref scalar_def) => schema.extend(scalar(scalar_def)),
+            Def::Map(map_def) => {
+                schema.insert("type", Value::from("object"));
+                schema.insert("additionalProperties", self.schema(map_def.v()));
+            }
+            Def::Set(set_def) => {
+                schema.insert("type", Value::from("array"));
+                schema.insert("uniqueItems", Value::from(true));
+                schema.insert("items", self.schema((set_def.t)()));
+            }
+            Def::List(list_def) => {
+                schema.insert("type", Value::from("array"));
+                schema.insert("items", self.schema(list_def.t()));
+            }
+            Def::Slice(slice_def) => {
+                schema.insert("type", Value::from("array"));
+                schema.insert("items", self.schema(slice_def.t()));
+            }
+            Def::Array(array_def) => {
+                schema.insert("type", Value::from("array"));
+                schema.insert("minItems", Value::from(array_def.n));
+                schema.insert("maxItems", Value::from(array_def.n));
+                schema.insert("items", self.schema(array_def.t()));
+            }
+            Def::Option(option_def) => {
+                let inner = self.schema(option_def.t);
+                merge(&mut schema, nullable(inner));
+            }
+            Def::SmartPointer(SmartPointerDef {
+                pointee: Some(pointee),
+                ..
+            }) => merge(&mut schema, self.schema(pointee())),
+            _ => match shape.ty {
+                Type::User(UserType::Struct(sd)) => schema.extend(self.struct_schema(shape, sd)),
+                Type::User(UserType::Enum(ed)) => schema.extend(self.enum_schema(shape, ed)),
+                Type::Pointer(PointerType::Reference(pt) | PointerType::Raw(pt)) => {
+                    merge(&mut schema, self.schema(pt.target()))
+                }
+                Type::Primitive(primitive) => {
+                    use facet_core::{NumericType, PrimitiveType, TextualType};
+                    match primitive {
+                        PrimitiveType::Numeric(NumericType::Float) => {
+                            schema.insert("type", Value::from("number"));
+                            schema.insert("format", Value::from("double"));
+                        }
+                        PrimitiveType::Boolean => {
+                            schema.insert("type", Value::from("boolean"));
+                        }
+                        PrimitiveType::Textual(TextualType::Str) => {
+                            schema.insert("type", Value::from("string"));
+                        }
+                        // Anything goes
+                        _ => {}
+                    }
+                }
+                // Anything goes
+                _ => {}
+            },
+        }
+        schema
+    }
+
+    fn struct_schema<'shape>(
+        &mut self,
+        shape: &'shape Shape<'shape>,
+        sd: StructType<'shape>,
+    ) -> Map {
+        match sd.kind {
+            StructKind::Unit => Map::from_iter([("type", Value::from("null"))]),
+            StructKind::Tuple | StructKind::TupleStruct => self.tuple_schema(sd.fields),
+            _ => {
+                // With `#[facet(default)]` on the struct, every field defaults to its value
+                // in the struct's default
+                let defaults = if shape.has_default_attr() {
+                    default_value(shape, None)
+                } else {
+                    None
+                };
+
+                let mut object = Object::default();
+                self.add_fields(&mut object, sd.fields, defaults.as_ref());
+                if shape.has_deny_unknown_fields_attr() && object.additional.is_none() {
+                    object.additional = Some(Value::from(false));
+                }
+                object.into_schema()
+            }
+        }
+    }
+
+    /// Adds the fields of a struct or struct variant to an object schema
+    fn add_fields<'shape>(
+        &mut self,
+        object: &mut Object,
+        fields: &'shape [Field<'shape>],
+        defaults: Option<&Value>,
+    ) {
+        for field in fields {
+            if field.flags.contains(FieldFlags::SKIP_SERIALIZING) {
+                continue;
+            }
+
+            if field.flags.contains(FieldFlags::FLATTEN) {
+                match (field.shape().def, field.shape().ty) {
+                    (Def::Map(map_def), _) => {
+                        object.additional = Some(self.schema(map_def.v()));
+                    }
+                    (_, Type::User(UserType::Struct(sd))) if sd.kind == StructKind::Struct => {
+                        self.add_fields(object, sd.fields, None);
+                    }
+                    _ => object.all_of.push(self.schema(field.shape())),
+                }
+                continue;
+            }
+
+            let mut schema = self.schema(field.shape());
+            if let Value::Object(map) = &mut schema {
+                if let Some(description) = description(field.doc) {
+                    describe(map, description);
+                }
+                let default = if field.flags.contains(FieldFlags::DEFAULT) {
+                    default_value(field.shape(), Some(field))
+                } else {
+                    defaults
+                        .and_then(|defaults| defaults.get(field.name))
+                        .cloned()
+                };
+                if let Some(default) = default {
+                    map.insert("default", default);
+                }
+            }
+            object.properties.insert(field.name, schema);
+
+            let optional = field.flags.contains(FieldFlags::DEFAULT)
+                || defaults.is_some()
+                || matches!(field.shape().def, Def::Option(_));
+            if !optional {
+                object.required.push(Value::from(field.name));
+            }
+        }
+    }
+
+    fn tuple_schema<'shape>(&mut self, fields: &'shape [Field<'shape>]) -> Map {
+        let items: Vec<Value> = fields
+            .iter()
+            .map(|field| self.schema(field.shape()))
+            .collect();
+        Map::from_iter([
+            ("type", Value::from("array")),
+            ("minItems", Value::from(items.len())),
+            ("maxItems", Value::from(items.len())),
+            ("prefixItems", Value::Array(items)),
+        ])
+    }
+
+    /// Enums follow their tagging attributes: each variant is one alternative of a `oneOf`
+    fn enum_schema<'shape>(&mut self, shape: &'shape Shape<'shape>, ed: EnumType<'shape>) -> Map {
+        let all_units = ed.variants.iter().all(|v| v.data.fields.is_empty());
+        let tag = shape.get_tag_attr();
+        let content = shape.get_content_attr();
+
+        if all_units && tag.is_none() && !shape.has_untagged_attr() {
+            // Externally tagged unit variants are written as their name
+            let names = ed.variants.iter().map(|v| Value::from(v.name)).collect();
+            return Map::from_iter([
+                ("type", Value::from("string")),
+                ("enum", Value::Array(names)),
+            ]);
+        }
+
+        let variants = ed
+            .variants
+            .iter()
+            .map(|variant| {
+                let has_data = !variant.data.fields.is_empty();
+                let mut schema = match (tag, content) {
+                    _ if shape.has_untagged_attr() => {
+                        if has_data {
+                            self.variant_data(variant)
+                        } else {
+                            Map::from_iter([("type", Value::from("null"))])
+                        }
+                    }
+                    (Some(tag), Some(content)) => {
+                        let mut object = Object::default();
+                        object.properties.insert(tag, constant(variant.name));
+                        object.required.push(Value::from(tag));
+                        if has_data {
+                            let data = Value::Object(self.variant_data(variant));
+                            object.properties.insert(content, data);
+                            object.required.push(Value::from(content));
+                        }
+                        object.additional = Some(Value::from(false));
+                        object.into_schema()
+                    }
+                    (Some(tag), None) => {
+                        let mut object = Object::default();
+                        object.properties.insert(tag, constant(variant.name));
+                        object.required.push(Value::from(tag));
+                        match variant.data.fields {
+                            // The fields of a newtype variant's struct sit next to the tag
+                            [field] if variant.data.kind == StructKind::Tuple => {
+                                if let Type::User(UserType::Struct(sd)) = field.shape().ty {
+                                    self.add_fields(&mut object, sd.fields, None);
+                                }
+                            }
+                            fields => self.add_fields(&mut object, fields, None),
+                        }
+                        object.into_schema()
+                    }
+                    (None, _) if has_data => {
+                        let mut object = Object::default();
+                        let data = Value::Object(self.variant_data(variant));
+                        object.properties.insert(variant.name, data);
+                        object.required.push(Value::from(variant.name));
+                        object.additional = Some(Value::from(false));
+                        object.into_schema()
+                    }
+                    (None, _) => match constant(variant.name) {
+                        Value::Object(map) => map,
+                        _ => unreachable!(),
+                    },
+                };
+                if let Some(description) = description(variant.doc) {
+                    describe(&mut schema, description);
+                }
+                Value::Object(schema)
+            })
+            .collect();
+        Map::from_iter([("oneOf", Value::Array(variants))])
+    }
+
+    /// The schema of a variant's data, without its tag
+    fn variant_data<'shape>(&mut self, variant: &Variant<'shape>) -> Map {
+        match variant.data.fields {
+            [field] if variant.data.kind == StructKind::Tuple => {
+                let mut schema = Map::new();
+                merge(&mut schema, self.schema(field.shape()));
+                schema
+            }
+            fields if variant.data.kind == StructKind::Struct => {
+                let mut object = Object::default();
+                self.add_fields(&mut object, fields, None);
+                object.into_schema()
+            }
+            fields => self.tuple_schema(fields),
+        }
+    }
+}
+
+impl Object {
+    fn into_schema(self) -> Map {
+        let mut schema = Map::from_iter([("type", Value::from("object"))]);
+        if !self.required.is_empty() {
+            schema.insert("required", Value::Array(self.required));
+        }
+        schema.insert("properties", Value::Object(self.properties));
+        if let Some(additional) = self.additional {
+            schema.insert("additionalProperties", additional);
+        }
+        if !self.all_of.is_empty() {
+            schema.insert("allOf", Value::Array(self.all_of));
+        }
+        schema
+    }
+}
+
+/// Counts how many times each named type is referenced, without going through the same
+/// type twice
+fn count_references<'shape>(
+    shape: &'shape Shape<'shape>,
+    counts: &mut HashMap<ConstTypeId, usize>,
+) {
+    if is_named(shape) {
+        let count = counts.entry(shape.id).or_default();
+        *count += 1;
+        if *count > 1 {
+            return;
+        }
+    }
+
+    let mut visit = |shape: &'shape Shape<'shape>| count_references(shape, counts);
+    match shape.def {
+        Def::Scalar(_) => {}
+        Def::Map(map_def) => visit(map_def.v()),
+        Def::Set(set_def) => visit((set_def.t)()),
+        Def::List(list_def) => visit(list_def.t()),
+        Def::Slice(slice_def) => visit(slice_def.t()),
+        Def::Array(array_def) => visit(array_def.t()),
+        Def::Option(option_def) => visit(option_def.t),
+        Def::SmartPointer(SmartPointerDef {
+            pointee: Some(pointee),
+            ..
+        }) => visit(pointee()),
+        _ => match shape.ty {
+            Type::User(UserType::Struct(sd)) => sd.fields.iter().for_each(|f| visit(f.shape())),
+            Type::User(UserType::Enum(ed)) => ed
+                .variants
+                .iter()
+                .flat_map(|v| v.data.fields)
+                .for_each(|f| visit(f.shape())),
+            Type::Pointer(PointerType::Reference(pt) | PointerType::Raw(pt)) => visit(pt.target()),
+            _ => {}
+        },
+    }
+}
+
+/// Whether a shape is a user-defined struct or enum, which can go in `$defs`
+fn is_named(shape: &Shape<'_>) -> bool {
+    if !matches!(shape.def, Def::Undefined)
+        || shape.attributes.contains(&ShapeAttribute::Transparent)
+    {
+        return false;
+    }
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => sd.kind != StructKind::Tuple,
+        Type::User(UserType::Enum(_)) => true,
+        _ => false,
+    }
+}
+
+/// Serialize a scalar definition to JSON schema format.
+fn scalar(scalar_def: &ScalarDef<'_>) -> Map {
+    let mut schema = Map::new();
+    match scalar_def.affinity {
+        ScalarAffinity::Number(number_affinity) => match number_affinity.bits {
+            NumberBits::Integer { size, sign } => {
+                schema.insert("type", Value::from("integer"));
+                let bits = size.bits();
+                match sign {
+                    Signedness::Unsigned => {
+                        schema.insert("format", Value::from(format!("uint{bits}")));
+                        schema.insert("minimum", Value::from(0));
+                    }
+                    Signedness::Signed => {
+                        schema.insert("format", Value::from(format!("int{bits}")));
+                    }
+                }
+            }
+            NumberBits::Float { .. } => {
+                schema.insert("type", Value::from("number"));
+                schema.insert("format", Value::from("double"));
+            }
+            _ => {
+                schema.insert("type", Value::from("number"));
+            }
+        },
+        ScalarAffinity::Boolean(_) => {
+            schema.insert("type", Value::from("boolean"));
+        }
+        ScalarAffinity::Empty(_) => {
+            schema.insert("type", Value::from("null"));
+        }
+        ScalarAffinity::Char(_) => {
+            schema.insert("type", Value::from("string"));
+            schema.insert("minLength", Value::from(1));
+            schema.insert("maxLength", Value::from(1));
+        }
+        ScalarAffinity::String(_)
+        | ScalarAffinity::SocketAddr(_)
+        | ScalarAffinity::IpAddr(_)
+        | ScalarAffinity::ULID(_)
+        | ScalarAffinity::Path(_) => {
+            schema.insert("type", Value::from("string"));
+        }
+        ScalarAffinity::Url(_) => {
+            schema.insert("type", Value::from("string"));
+            schema.insert("format", Value::from("uri"));
+        }
+        ScalarAffinity::UUID(_) => {
+            schema.insert("type", Value::from("string"));
+            schema.insert("format", Value::from("uuid"));
+        }
+        ScalarAffinity::Time(_) => {
+            schema.insert("type", Value::from("string"));
+            schema.insert("format", Value::from("date-time"));
+        }
+        // Anything goes
+        _ => {}
+    }
+    schema
+}
+
+/// Makes a schema accept `null` as well
+fn nullable(schema: Value) -> Value {
+    if let Value::Object(map) = &schema {
+        let simple = !map.contains_key("enum") && !map.contains_key("const");
+        if let (Some(Value::String(ty)), true) = (map.get("type"), simple) {
+            let mut map = map.clone();
+            let ty = Value::array([Value::from(ty.as_str()), Value::from("null")]);
+            map.insert("type", ty);
+            return Value::Object(map);
+        }
+    }
+    Value::object([(
+        "anyOf",
+        Value::array([schema, Value::object([("type", Value::from("null"))])]),
+    )])
+}
+
+/// Puts a description at the start of a schema, in place of any description it had
+fn describe(schema: &mut Map, description: Value) {
+    schema.remove("description");
+    let rest = core::mem::take(schema);
+    schema.insert("description", description);
+    schema.extend(rest);
+}
+
+/// Adds the entries of a schema to another
+fn merge(schema: &mut Map, other: Value) {
+    if let Value::Object(other) = other {
+        schema.extend(other);
+    }
+}
+
+fn reference(target: &str) -> Value {
+    Value::object([("$ref", Value::from(target))])
+}
+
+fn constant(value: &str) -> Value {
+    Value::object([("const", Value::from(value))])
+}
+
+/// Doc comments become descriptions
+fn description(doc: &[&str]) -> Option<Value> {
+    let lines: Vec<&str> = doc
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    let description = lines.join("\n");
+    let description = description.trim();
+    if description.is_empty() {
+        None
+    } else {
+        Some(Value::from(description))
+    }
+}
+
+/// The default value of a field, or of a type if there's no field
+fn default_value<'shape>(
+    shape: &'shape Shape<'shape>,
+    field: Option<&Field<'shape>>,
+) -> Option<Value> {
+    let mut partial = Partial::alloc_shape(shape).ok()?;
+    match field.and_then(|field| field.vtable.default_fn) {
+        Some(default_fn) => partial.set_field_default(default_fn).ok()?,
+        None => partial.set_default().ok()?,
+    };
+    let value = partial.build().ok()?;
+    Value::from_peek(value.peek()).ok()
+}
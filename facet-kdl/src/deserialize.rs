@@ -0,0 +1,770 @@
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{self, Display},
+};
+
+use facet_core::{
+    Def, EnumType, Facet, Field, FieldFlags, Shape, ShapeAttribute, StructKind, Type, UserType,
+    Variant,
+};
+use facet_reflect::{Partial, ReflectError, ScalarType};
+use kdl::{KdlDocument, KdlEntry, KdlError as KdlParseError, KdlNode, KdlValue};
+
+use crate::FieldKind;
+
+// DESIGN: Like `facet-toml`, this crate currently fully parses KDL into an AST before doing any deserialization. In the
+// long-term, I think it's important that the code in `facet-kdl` stays as minimally complex and easy to maintain as
+// possible — I'd like to get "free" KDL format / parsing updates from `kdl-rs`, and a "free" derive macro from `facet`.
+// For this prototype then, I'm really going to try to avoid any premature optimisation — I'll try to take inspiration
+// from `facet-toml` and split things into easy-to-understand functions that I can call recursively as I crawl down the
+// KDL AST. After I'm happy with the API and have a really solid set of tests, we can look into making some more
+// optimisations, like flattening this recursive structure into something more iterative / imparative (as in
+// `facet-json`) or parsing things more incrementally by using `KdlNode::parse()` or `KdlEntry::parse`.
+
+/// Error type for KDL deserialization.
+#[derive(Debug)]
+pub struct KdlError<'shape> {
+    kind: KdlErrorKind<'shape>,
+}
+
+impl Display for KdlError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let kind = &self.kind;
+        write!(f, "{kind}")
+    }
+}
+impl Error for KdlError<'_> {}
+
+// FIXME: Replace this with a proper constructor once there is other information to put into `KdlError`!
+impl<'shape, K: Into<KdlErrorKind<'shape>>> From<K> for KdlError<'shape> {
+    fn from(value: K) -> Self {
+        let kind = value.into();
+        KdlError { kind }
+    }
+}
+
+#[derive(Debug)]
+enum KdlErrorKind<'shape> {
+    InvalidDocumentShape(&'shape Shape<'shape>),
+    ExpectedArgument(&'shape Shape<'shape>),
+    ExpectedVariant(&'shape Shape<'shape>),
+    UnexpectedArgument(&'shape Shape<'shape>),
+    UnknownProperty {
+        shape: &'shape Shape<'shape>,
+        property: String,
+    },
+    UnknownNode {
+        shape: &'shape Shape<'shape>,
+        node: String,
+    },
+    MissingField {
+        shape: &'shape Shape<'shape>,
+        field: &'shape str,
+    },
+    NoSuchVariant {
+        shape: &'shape Shape<'shape>,
+        variant: String,
+    },
+    InvalidValue {
+        shape: &'shape Shape<'shape>,
+        value: KdlValue,
+    },
+    Unsupported(&'shape Shape<'shape>),
+    Parse(KdlParseError),
+    Reflect(ReflectError<'shape>),
+}
+
+impl Display for KdlErrorKind<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KdlErrorKind::InvalidDocumentShape(shape) => write!(
+                f,
+                "cannot read a KDL document into {shape}, expected a struct, a map or a list"
+            ),
+            KdlErrorKind::ExpectedArgument(shape) => {
+                write!(f, "expected a node with a single argument for {shape}")
+            }
+            KdlErrorKind::ExpectedVariant(shape) => write!(
+                f,
+                "expected a variant name or a single child node for enum {shape}"
+            ),
+            KdlErrorKind::UnexpectedArgument(shape) => {
+                write!(f, "unexpected argument for {shape}")
+            }
+            KdlErrorKind::UnknownProperty { shape, property } => {
+                write!(f, "unknown property `{property}` for {shape}")
+            }
+            KdlErrorKind::UnknownNode { shape, node } => {
+                write!(f, "unknown child node `{node}` for {shape}")
+            }
+            KdlErrorKind::MissingField { shape, field } => {
+                write!(f, "missing field `{field}` of {shape}")
+            }
+            KdlErrorKind::NoSuchVariant { shape, variant } => {
+                write!(f, "enum {shape} has no variant named `{variant}`")
+            }
+            KdlErrorKind::InvalidValue { shape, value } => {
+                write!(f, "cannot read {value} as {shape}")
+            }
+            KdlErrorKind::Unsupported(shape) => {
+                write!(f, "{shape} cannot be deserialized from KDL")
+            }
+            KdlErrorKind::Parse(kdl_error) => write!(f, "{kdl_error}"),
+            KdlErrorKind::Reflect(reflect_error) => write!(f, "{reflect_error}"),
+        }
+    }
+}
+
+impl From<KdlParseError> for KdlErrorKind<'_> {
+    fn from(value: KdlParseError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+impl<'shape> From<ReflectError<'shape>> for KdlErrorKind<'shape> {
+    fn from(value: ReflectError<'shape>) -> Self {
+        Self::Reflect(value)
+    }
+}
+
+type Result<'shape, T> = std::result::Result<T, KdlError<'shape>>;
+
+/// Deserialize a value of type `T` from a KDL string.
+///
+/// Structs are read from the nodes of the document, lists from one node per element and
+/// maps from one node per entry. Inside a node, struct fields are picked by their
+/// attributes:
+///
+/// - `#[facet(argument)]` fields take the node's arguments, in order, and an
+///   `#[facet(arguments)]` list collects the arguments that are left over;
+/// - `#[facet(property)]` fields take the node's `key=value` properties;
+/// - `#[facet(child)]` fields, and fields without a KDL attribute, take the child nodes
+///   named after them. A list field takes every node with its name, and an enum field
+///   also takes nodes named after one of its variants;
+/// - an `#[facet(children)]` list collects the child nodes no other field claimed, where
+///   the node name selects the variant of enum elements.
+///
+/// Returns a [`KdlError`] if the input KDL is invalid or doesn't match `T`.
+///
+/// # Example
+/// ```
+/// # use facet::Facet;
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Config {
+///     #[facet(child)]
+///     server: Server,
+/// }
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Server {
+///     #[facet(argument)]
+///     host: String,
+///     #[facet(property)]
+///     port: u16,
+/// }
+///
+/// let config: Config = facet_kdl::from_str(r#"server "localhost" port=8080"#).unwrap();
+/// assert_eq!(config.server.host, "localhost");
+/// assert_eq!(config.server.port, 8080);
+/// ```
+pub fn from_str<'input, 'facet: 'shape, 'shape, T>(kdl: &'input str) -> Result<'shape, T>
+where
+    T: Facet<'facet>,
+    'input: 'facet,
+{
+    log::trace!("Entering `from_str` function");
+
+    // PERF: This definitely isn't zero-copy, so it might be worth seeing if that's something that can be added to
+    // `kdl-rs` at some point in the future?
+    let document: KdlDocument = kdl.parse()?;
+    log::trace!("KDL parsed");
+
+    let mut typed_partial = Partial::alloc::<T>()?;
+    log::trace!(
+        "Allocated WIP for type {}",
+        typed_partial.inner_mut().shape()
+    );
+
+    deserialize_document(typed_partial.inner_mut(), &document)?;
+
+    let boxed_value = typed_partial.build()?;
+    log::trace!("WIP fully built");
+
+    Ok(*boxed_value)
+}
+
+fn deserialize_document<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    document: &KdlDocument,
+) -> Result<'shape, ()> {
+    let shape = wip.shape();
+    log::trace!("Deserializing document into {shape}");
+
+    if shape.attributes.contains(&ShapeAttribute::Transparent) {
+        wip.begin_inner()?;
+        deserialize_document(wip, document)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match (shape.def, shape.ty) {
+        (Def::List(_) | Def::Array(_), _) => {
+            deserialize_sequence(wip, document.nodes(), deserialize_element)
+        }
+        (Def::Map(_), _) => {
+            wip.begin_map()?;
+            for node in document.nodes() {
+                deserialize_map_entry(wip, node.name().value(), |wip| deserialize_node(wip, node))?;
+            }
+            Ok(())
+        }
+        (_, Type::User(UserType::Struct(sd))) if sd.kind == StructKind::Struct => {
+            deserialize_fields(wip, sd.fields, &[], Some(document))
+        }
+        _ => Err(KdlErrorKind::InvalidDocumentShape(shape).into()),
+    }
+}
+
+/// Deserializes a node into the current value, whatever the node is named.
+fn deserialize_node<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    node: &KdlNode,
+) -> Result<'shape, ()> {
+    let shape = wip.shape();
+    log::trace!("Deserializing node `{}` into {shape}", node.name().value());
+
+    if shape.attributes.contains(&ShapeAttribute::Transparent) {
+        wip.begin_inner()?;
+        deserialize_node(wip, node)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match shape.def {
+        Def::Scalar(_) => return deserialize_value(wip, single_argument(shape, node)?),
+        Def::List(_) | Def::Array(_) => {
+            // Elements are either the node's arguments, or its children
+            return match node.children() {
+                Some(children) if !children.nodes().is_empty() => {
+                    deserialize_sequence(wip, children.nodes(), deserialize_element)
+                }
+                _ => deserialize_sequence(wip, arguments(node), deserialize_value),
+            };
+        }
+        Def::Map(_) => {
+            wip.begin_map()?;
+            for entry in node.entries() {
+                let Some(key) = entry.name() else {
+                    return Err(KdlErrorKind::UnexpectedArgument(shape).into());
+                };
+                deserialize_map_entry(wip, key.value(), |wip| {
+                    deserialize_value(wip, entry.value())
+                })?;
+            }
+            for child in node.children().map(KdlDocument::nodes).unwrap_or_default() {
+                deserialize_map_entry(wip, child.name().value(), |wip| {
+                    deserialize_node(wip, child)
+                })?;
+            }
+            return Ok(());
+        }
+        Def::Option(_) => {
+            if is_null(node) {
+                wip.set_default()?;
+            } else {
+                wip.begin_some()?;
+                deserialize_node(wip, node)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::SmartPointer(_) => {
+            wip.begin_smart_ptr()?;
+            deserialize_node(wip, node)?;
+            wip.end()?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) if sd.fields.is_empty() => {
+            // A struct without fields is zero-sized: there's nothing to write
+            wip.set_from_function(|_| Ok(()))?;
+            Ok(())
+        }
+        Type::User(UserType::Struct(sd)) => match sd.kind {
+            StructKind::Tuple | StructKind::TupleStruct => deserialize_tuple(wip, sd.fields, node),
+            _ => deserialize_fields(wip, sd.fields, node.entries(), node.children()),
+        },
+        Type::User(UserType::Enum(_)) => {
+            // Either `field "Variant"`, or `field { Variant ... }`
+            match (node.entries(), node.children().map(KdlDocument::nodes)) {
+                ([entry], None | Some([])) if entry.name().is_none() => {
+                    deserialize_value(wip, entry.value())
+                }
+                ([], Some([child])) => deserialize_variant(wip, child),
+                _ => Err(KdlErrorKind::ExpectedVariant(shape).into()),
+            }
+        }
+        _ => Err(KdlErrorKind::Unsupported(shape).into()),
+    }
+}
+
+/// Deserializes a node standing for a whole value, like an element of a list of nodes.
+/// Unlike [`deserialize_node`], the node name selects the variant of enums.
+fn deserialize_element<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    node: &KdlNode,
+) -> Result<'shape, ()> {
+    let shape = wip.shape();
+    match (shape.def, shape.ty) {
+        (Def::Option(_), _) if enum_type(shape).is_some() => {
+            wip.begin_some()?;
+            deserialize_element(wip, node)?;
+            wip.end()?;
+            Ok(())
+        }
+        (_, Type::User(UserType::Enum(_))) => deserialize_variant(wip, node),
+        _ => deserialize_node(wip, node),
+    }
+}
+
+/// Selects the variant named after the node, and deserializes its data from the node.
+fn deserialize_variant<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    node: &KdlNode,
+) -> Result<'shape, ()> {
+    let variant = select_variant(wip, node.name().value())?;
+
+    match variant.data.kind {
+        _ if variant.data.fields.is_empty() => Ok(()),
+        StructKind::Tuple if variant.data.fields.len() == 1 => {
+            // Newtype variants are the node itself
+            wip.begin_nth_field(0)?;
+            deserialize_node(wip, node)?;
+            wip.end()?;
+            Ok(())
+        }
+        StructKind::Tuple | StructKind::TupleStruct => {
+            deserialize_tuple(wip, variant.data.fields, node)
+        }
+        _ => deserialize_fields(wip, variant.data.fields, node.entries(), node.children()),
+    }
+}
+
+fn select_variant<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    name: &str,
+) -> Result<'shape, &'shape Variant<'shape>> {
+    let Some((index, variant)) = wip.find_variant(name) else {
+        return Err(KdlErrorKind::NoSuchVariant {
+            shape: wip.shape(),
+            variant: name.to_owned(),
+        }
+        .into());
+    };
+    wip.select_nth_variant(index)?;
+    Ok(variant)
+}
+
+/// Sets the fields of a struct, or of the selected enum variant, from the entries and
+/// children of a node.
+fn deserialize_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    entries: &[KdlEntry],
+    children: Option<&KdlDocument>,
+) -> Result<'shape, ()> {
+    let shape = wip.shape();
+    let deny_unknown_fields = shape.has_deny_unknown_fields_attr();
+    let kind_of = |index: usize| FieldKind::of(&fields[index]);
+
+    // Arguments fill the argument fields in order, the rest go to the arguments field
+    let mut argument_fields =
+        (0..fields.len()).filter(|&index| kind_of(index) == FieldKind::Argument);
+    let arguments_field = (0..fields.len()).find(|&index| kind_of(index) == FieldKind::Arguments);
+    let mut rest = Vec::new();
+
+    for entry in entries {
+        let Some(name) = entry.name() else {
+            if let Some(index) = argument_fields.next() {
+                wip.begin_nth_field(index)?;
+                deserialize_value(wip, entry.value())?;
+                wip.end()?;
+            } else if arguments_field.is_some() {
+                rest.push(entry.value());
+            } else {
+                return Err(KdlErrorKind::UnexpectedArgument(shape).into());
+            }
+            continue;
+        };
+
+        let index = wip
+            .field_index(name.value())
+            .filter(|&index| matches!(kind_of(index), FieldKind::Property | FieldKind::Child));
+        match index {
+            Some(index) => {
+                wip.begin_nth_field(index)?;
+                deserialize_value(wip, entry.value())?;
+                wip.end()?;
+            }
+            None if deny_unknown_fields => {
+                return Err(KdlErrorKind::UnknownProperty {
+                    shape,
+                    property: name.value().to_owned(),
+                }
+                .into());
+            }
+            None => log::trace!("Ignoring unknown property `{}`", name.value()),
+        }
+    }
+
+    if let Some(index) = arguments_field {
+        wip.begin_nth_field(index)?;
+        deserialize_sequence(wip, rest, deserialize_value)?;
+        wip.end()?;
+    }
+
+    // Child nodes are grouped by field first, since a field can only be visited once. Each
+    // node is kept along with whether it was matched by variant name rather than by field name.
+    let children_field = (0..fields.len()).find(|&index| kind_of(index) == FieldKind::Children);
+    let mut groups = vec![Vec::new(); fields.len()];
+    for node in children.map(KdlDocument::nodes).unwrap_or_default() {
+        let name = node.name().value();
+        let by_field_name = (0..fields.len())
+            .find(|&index| kind_of(index) == FieldKind::Child && fields[index].name == name);
+        let by_variant_name = || {
+            (0..fields.len()).find(|&index| {
+                kind_of(index) == FieldKind::Child
+                    && enum_type(fields[index].shape)
+                        .is_some_and(|et| et.variants.iter().any(|v| v.name == name))
+            })
+        };
+
+        match (by_field_name, by_variant_name().or(children_field)) {
+            (Some(index), _) => groups[index].push((node, false)),
+            (None, Some(index)) => groups[index].push((node, true)),
+            (None, None) if deny_unknown_fields => {
+                return Err(KdlErrorKind::UnknownNode {
+                    shape,
+                    node: name.to_owned(),
+                }
+                .into());
+            }
+            (None, None) => log::trace!("Ignoring unknown child node `{name}`"),
+        }
+    }
+
+    let deserialize_child =
+        |wip: &mut Partial<'facet, 'shape>, (node, by_variant_name): (&KdlNode, bool)| {
+            if by_variant_name {
+                deserialize_element(wip, node)
+            } else {
+                deserialize_node(wip, node)
+            }
+        };
+    for (index, nodes) in groups.into_iter().enumerate() {
+        let Some(&last) = nodes.last() else {
+            continue;
+        };
+        wip.begin_nth_field(index)?;
+        if matches!(fields[index].shape.def, Def::List(_) | Def::Array(_)) {
+            deserialize_sequence(wip, nodes, deserialize_child)?;
+        } else {
+            // Like in most KDL configuration files, the last node wins
+            deserialize_child(wip, last)?;
+        }
+        wip.end()?;
+    }
+
+    // Absent fields fall back to their default, to `None` for options and to an empty
+    // list for lists of nodes
+    for (index, field) in fields.iter().enumerate() {
+        if wip.is_field_set(index)? {
+            continue;
+        }
+        if field.flags.contains(FieldFlags::DEFAULT) {
+            wip.begin_nth_field(index)?;
+            if let Some(field_default_fn) = field.vtable.default_fn {
+                wip.set_field_default(field_default_fn)?;
+            } else {
+                wip.set_default()?;
+            }
+            wip.end()?;
+        } else if matches!(field.shape.def, Def::Option(_)) {
+            wip.begin_nth_field(index)?;
+            wip.set_default()?;
+            wip.end()?;
+        } else if matches!(field.shape.def, Def::List(_))
+            && matches!(kind_of(index), FieldKind::Child | FieldKind::Children)
+        {
+            wip.begin_nth_field(index)?;
+            wip.begin_list()?;
+            wip.end()?;
+        }
+    }
+    wip.fill_unset_fields_from_default()?;
+
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            return Err(KdlErrorKind::MissingField {
+                shape,
+                field: field.name,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Sets the fields of a tuple struct or tuple variant from the arguments of a node.
+fn deserialize_tuple<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    node: &KdlNode,
+) -> Result<'shape, ()> {
+    let shape = wip.shape();
+    for (index, value) in arguments(node).enumerate() {
+        if index >= fields.len() {
+            return Err(KdlErrorKind::UnexpectedArgument(shape).into());
+        }
+        wip.begin_nth_field(index)?;
+        deserialize_value(wip, value)?;
+        wip.end()?;
+    }
+
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            return Err(KdlErrorKind::MissingField {
+                shape,
+                field: field.name,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Deserializes a list or array, one item at a time.
+fn deserialize_sequence<'facet, 'shape, I>(
+    wip: &mut Partial<'facet, 'shape>,
+    items: I,
+    mut deserialize_item: impl FnMut(&mut Partial<'facet, 'shape>, I::Item) -> Result<'shape, ()>,
+) -> Result<'shape, ()>
+where
+    I: IntoIterator,
+{
+    let shape = wip.shape();
+    match shape.def {
+        Def::List(_) => {
+            wip.begin_list()?;
+            for item in items {
+                wip.begin_list_item()?;
+                deserialize_item(wip, item)?;
+                wip.end()?;
+            }
+        }
+        Def::Array(_) => {
+            for (index, item) in items.into_iter().enumerate() {
+                wip.begin_nth_element(index)?;
+                deserialize_item(wip, item)?;
+                wip.end()?;
+            }
+        }
+        _ => return Err(KdlErrorKind::Unsupported(shape).into()),
+    }
+    Ok(())
+}
+
+fn deserialize_map_entry<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    key: &str,
+    value: impl FnOnce(&mut Partial<'facet, 'shape>) -> Result<'shape, ()>,
+) -> Result<'shape, ()> {
+    // Keys go through the same path as string values, so that they can be
+    // parsed into integers, newtypes and so on
+    wip.begin_key()?;
+    deserialize_value(wip, &KdlValue::String(key.to_owned()))?;
+    wip.end()?;
+
+    wip.begin_value()?;
+    value(wip)?;
+    wip.end()?;
+    Ok(())
+}
+
+/// Deserializes an argument or property value into the current value.
+fn deserialize_value<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &KdlValue,
+) -> Result<'shape, ()> {
+    let shape = wip.shape();
+
+    if shape.attributes.contains(&ShapeAttribute::Transparent) {
+        wip.begin_inner()?;
+        deserialize_value(wip, value)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match (shape.def, shape.ty) {
+        (Def::Scalar(_), _) => deserialize_scalar(wip, value),
+        (Def::Option(_), _) => {
+            if matches!(value, KdlValue::Null) {
+                wip.set_default()?;
+            } else {
+                wip.begin_some()?;
+                deserialize_value(wip, value)?;
+                wip.end()?;
+            }
+            Ok(())
+        }
+        (Def::SmartPointer(_), _) => {
+            wip.begin_smart_ptr()?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+            Ok(())
+        }
+        (_, Type::User(UserType::Enum(_))) => {
+            // Unit variants are written as their name
+            let KdlValue::String(name) = value else {
+                return Err(invalid_value(shape, value));
+            };
+            let variant = select_variant(wip, name)?;
+            if !variant.data.fields.is_empty() {
+                return Err(invalid_value(shape, value));
+            }
+            Ok(())
+        }
+        _ => Err(invalid_value(shape, value)),
+    }
+}
+
+fn deserialize_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &KdlValue,
+) -> Result<'shape, ()> {
+    let shape = wip.shape();
+
+    let Some(scalar) = ScalarType::try_from_shape(shape) else {
+        // Wrappers like `NonZero<T>` are built from their inner value
+        if shape.inner.is_some() {
+            wip.begin_inner()?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+            return Ok(());
+        }
+        let KdlValue::String(s) = value else {
+            return Err(invalid_value(shape, value));
+        };
+        wip.parse_from_str(s)?;
+        return Ok(());
+    };
+
+    macro_rules! set_int {
+        ($ty:ty, $n:expr) => {{
+            let n = <$ty>::try_from($n).map_err(|_| invalid_value(shape, value))?;
+            wip.set(n)?;
+        }};
+    }
+
+    match (scalar, value) {
+        (ScalarType::Unit, KdlValue::Null) => {
+            wip.set(())?;
+        }
+        (ScalarType::Bool, KdlValue::Bool(b)) => {
+            wip.set(*b)?;
+        }
+        (ScalarType::String, KdlValue::String(s)) => {
+            wip.set(s.clone())?;
+        }
+        (ScalarType::CowStr, KdlValue::String(s)) => {
+            wip.set(Cow::<'facet, str>::Owned(s.clone()))?;
+        }
+        (ScalarType::Char, KdlValue::String(s)) if s.chars().count() == 1 => {
+            wip.set(s.chars().next().unwrap())?;
+        }
+        (ScalarType::F32, KdlValue::Float(n)) => {
+            wip.set(*n as f32)?;
+        }
+        (ScalarType::F64, KdlValue::Float(n)) => {
+            wip.set(*n)?;
+        }
+        (ScalarType::F32, KdlValue::Integer(n)) => {
+            wip.set(*n as f32)?;
+        }
+        (ScalarType::F64, KdlValue::Integer(n)) => {
+            wip.set(*n as f64)?;
+        }
+        (_, KdlValue::Integer(n)) => match scalar {
+            ScalarType::U8 => set_int!(u8, *n),
+            ScalarType::U16 => set_int!(u16, *n),
+            ScalarType::U32 => set_int!(u32, *n),
+            ScalarType::U64 => set_int!(u64, *n),
+            ScalarType::U128 => set_int!(u128, *n),
+            ScalarType::USize => set_int!(usize, *n),
+            ScalarType::I8 => set_int!(i8, *n),
+            ScalarType::I16 => set_int!(i16, *n),
+            ScalarType::I32 => set_int!(i32, *n),
+            ScalarType::I64 => set_int!(i64, *n),
+            ScalarType::I128 => {
+                wip.set(*n)?;
+            }
+            ScalarType::ISize => set_int!(isize, *n),
+            _ => return Err(invalid_value(shape, value)),
+        },
+        (_, KdlValue::String(s)) => {
+            wip.parse_from_str(s)?;
+        }
+        _ => return Err(invalid_value(shape, value)),
+    }
+    Ok(())
+}
+
+fn invalid_value<'shape>(shape: &'shape Shape<'shape>, value: &KdlValue) -> KdlError<'shape> {
+    KdlErrorKind::InvalidValue {
+        shape,
+        value: value.clone(),
+    }
+    .into()
+}
+
+/// The value of a node that has a single argument and nothing else, like `port 8080`.
+fn single_argument<'a, 'shape>(
+    shape: &'shape Shape<'shape>,
+    node: &'a KdlNode,
+) -> Result<'shape, &'a KdlValue> {
+    match (node.entries(), node.children().map(KdlDocument::nodes)) {
+        ([entry], None | Some([])) if entry.name().is_none() => Ok(entry.value()),
+        _ => Err(KdlErrorKind::ExpectedArgument(shape).into()),
+    }
+}
+
+/// Whether the node stands for `None`, like `nickname #null`.
+fn is_null(node: &KdlNode) -> bool {
+    matches!(node.entries(), [entry] if entry.name().is_none() && matches!(entry.value(), KdlValue::Null))
+        && node
+            .children()
+            .is_none_or(|children| children.nodes().is_empty())
+}
+
+fn arguments(node: &KdlNode) -> impl Iterator<Item = &KdlValue> {
+    node.entries()
+        .iter()
+        .filter(|entry| entry.name().is_none())
+        .map(KdlEntry::value)
+}
+
+/// The enum a field holds, possibly in an option or a list, if any: nodes named after one of
+/// its variants belong to that field.
+fn enum_type<'shape>(shape: &'shape Shape<'shape>) -> Option<EnumType<'shape>> {
+    match (shape.def, shape.ty) {
+        (Def::Option(od), _) => enum_type(od.t),
+        (Def::List(ld), _) => enum_type(ld.t()),
+        (Def::Array(ad), _) => enum_type(ad.t()),
+        (_, Type::User(UserType::Enum(et))) => Some(et),
+        _ => None,
+    }
+}
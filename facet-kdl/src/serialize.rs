@@ -0,0 +1,450 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use facet_core::{Def, Facet, Field, Shape};
+use facet_reflect::Peek;
+use facet_serialize::{Serializer, serialize_iterative};
+use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
+
+use crate::FieldKind;
+
+/// Any error from serializing KDL.
+#[derive(Debug)]
+pub enum KdlSerError {
+    /// KDL documents are lists of nodes: they can only be written from structs, maps and
+    /// lists, and structs at the top level can't have argument or property fields.
+    InvalidDocument,
+    /// An argument or property holds a value that isn't a scalar.
+    NonScalarEntry {
+        /// Name of the field holding the value.
+        name: String,
+    },
+    /// A map key isn't a string, an integer or a boolean.
+    InvalidKey,
+    /// An integer doesn't fit in the 128-bit signed integers of KDL.
+    IntegerOutOfRange,
+    /// A value was written into a struct or map without a field name or key before it.
+    MissingKey,
+    /// A value has a shape that can't be serialized at all.
+    Unsupported {
+        /// The shape of the value.
+        shape: String,
+        /// Why it can't be serialized.
+        reason: String,
+    },
+}
+
+impl Display for KdlSerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KdlSerError::InvalidDocument => write!(
+                f,
+                "KDL documents can only be written from structs, maps and lists of nodes"
+            ),
+            KdlSerError::NonScalarEntry { name } => {
+                write!(
+                    f,
+                    "`{name}` is an argument or a property, but isn't a scalar"
+                )
+            }
+            KdlSerError::InvalidKey => {
+                write!(f, "map keys must be strings, integers or booleans")
+            }
+            KdlSerError::IntegerOutOfRange => {
+                write!(f, "integer doesn't fit in a 128-bit signed integer")
+            }
+            KdlSerError::MissingKey => {
+                write!(f, "struct or map value written without a field name or key")
+            }
+            KdlSerError::Unsupported { shape, reason } => {
+                write!(f, "cannot serialize {shape}: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for KdlSerError {}
+
+/// Serialize any `Facet` type to a KDL string.
+///
+/// This is the reverse of [`crate::from_str`]: fields are written as arguments, properties
+/// or child nodes depending on their attributes, enums in a list of nodes are written as
+/// nodes named after the variant, and `None` child nodes and properties are left out.
+pub fn to_string<'a, T: Facet<'a>>(value: &'a T) -> Result<String, KdlSerError> {
+    let mut serializer = KdlSerializer::default();
+    serialize_iterative(Peek::new(value), &mut serializer)?;
+    let root = serializer.root.ok_or(KdlSerError::InvalidDocument)?;
+
+    let mut document = write_document(root)?;
+    document.autoformat();
+    Ok(document.to_string())
+}
+
+/// A value being serialized. KDL needs to know what a value is made of before it can
+/// decide how to write it (a list of scalars becomes arguments, but a list of structs
+/// becomes child nodes), so values are collected into this tree first.
+enum Item<'shape> {
+    /// A scalar, or `#null` for `None` and `()`
+    Value(KdlValue),
+    /// A unit variant, written as its name
+    UnitVariant(&'shape str),
+    /// A variant with data, wrapped in an object with a single key
+    Variant(String, Box<Item<'shape>>),
+    Array(Vec<Item<'shape>>),
+    Object(Vec<(Key<'shape>, Item<'shape>)>),
+}
+
+enum Key<'shape> {
+    /// A struct field, which knows if it's an argument, a property or a child
+    Field(Field<'shape>),
+    /// A map key, or a name without a field behind it, like a variant name
+    Name(String),
+}
+
+enum Container<'shape> {
+    Array(Vec<Item<'shape>>),
+    Object {
+        entries: Vec<(Key<'shape>, Item<'shape>)>,
+        key: Option<Key<'shape>>,
+        /// Whether this object may be the wrapper of an externally tagged variant
+        variant: bool,
+    },
+}
+
+#[derive(Default)]
+struct KdlSerializer<'shape> {
+    stack: Vec<Container<'shape>>,
+    root: Option<Item<'shape>>,
+    /// Whether the next value is a map key
+    map_key: bool,
+    /// Whether an enum variant was just started
+    variant: bool,
+}
+
+impl<'shape> KdlSerializer<'shape> {
+    fn push(&mut self, item: Item<'shape>) -> Result<(), KdlSerError> {
+        self.variant = false;
+
+        if self.map_key {
+            // Map keys become node names and property names
+            let name = match item {
+                Item::Value(KdlValue::String(s)) => s,
+                Item::Value(KdlValue::Integer(n)) => n.to_string(),
+                Item::Value(KdlValue::Bool(b)) => b.to_string(),
+                Item::UnitVariant(name) => name.to_owned(),
+                _ => return Err(KdlSerError::InvalidKey),
+            };
+            self.set_key(Key::Name(name));
+            return Ok(());
+        }
+
+        match self.stack.last_mut() {
+            None => self.root = Some(item),
+            Some(Container::Array(items)) => items.push(item),
+            Some(Container::Object { entries, key, .. }) => {
+                let key = key.take().ok_or(KdlSerError::MissingKey)?;
+                entries.push((key, item));
+            }
+        }
+        Ok(())
+    }
+
+    fn set_key(&mut self, name: Key<'shape>) {
+        if let Some(Container::Object { key, .. }) = self.stack.last_mut() {
+            *key = Some(name);
+        }
+    }
+
+    fn open_object(&mut self) {
+        let variant = core::mem::take(&mut self.variant);
+        self.stack.push(Container::Object {
+            entries: Vec::new(),
+            key: None,
+            variant,
+        });
+    }
+
+    fn close_object(&mut self) -> Result<(), KdlSerError> {
+        let Some(Container::Object {
+            mut entries,
+            variant,
+            ..
+        }) = self.stack.pop()
+        else {
+            unreachable!("objects are ended in the order they're started");
+        };
+
+        let item = match entries.pop() {
+            // `{ "Variant": data }`
+            Some((Key::Name(name), data)) if variant && entries.is_empty() => {
+                Item::Variant(name, Box::new(data))
+            }
+            Some(entry) => {
+                entries.push(entry);
+                Item::Object(entries)
+            }
+            None => Item::Object(entries),
+        };
+        self.push(item)
+    }
+}
+
+impl<'shape> Serializer<'shape> for KdlSerializer<'shape> {
+    type Error = KdlSerError;
+
+    fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::Integer(value.into())))
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        let value = i128::try_from(value).map_err(|_| KdlSerError::IntegerOutOfRange)?;
+        self.push(Item::Value(KdlValue::Integer(value)))
+    }
+
+    fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::Integer(value.into())))
+    }
+
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::Integer(value)))
+    }
+
+    fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::Float(value)))
+    }
+
+    fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::Bool(value)))
+    }
+
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::String(value.to_string())))
+    }
+
+    fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::String(value.to_owned())))
+    }
+
+    fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        // KDL has no byte strings, bytes are written like any other list of integers
+        let bytes = value
+            .iter()
+            .map(|&b| Item::Value(KdlValue::Integer(b.into())))
+            .collect();
+        self.push(Item::Array(bytes))
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::Null))
+    }
+
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.push(Item::Value(KdlValue::Null))
+    }
+
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        variant_name: &'shape str,
+    ) -> Result<(), Self::Error> {
+        self.push(Item::UnitVariant(variant_name))
+    }
+
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.open_object();
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.close_object()
+    }
+
+    fn serialize_field_name(&mut self, name: &'shape str) -> Result<(), Self::Error> {
+        self.set_key(Key::Name(name.to_owned()));
+        Ok(())
+    }
+
+    fn serialize_field(&mut self, field: &Field<'shape>) -> Result<(), Self::Error> {
+        self.set_key(Key::Field(*field));
+        Ok(())
+    }
+
+    fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.variant = false;
+        self.stack.push(Container::Array(Vec::new()));
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        let Some(Container::Array(items)) = self.stack.pop() else {
+            unreachable!("arrays are ended in the order they're started");
+        };
+        self.push(Item::Array(items))
+    }
+
+    fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.variant = false;
+        self.open_object();
+        Ok(())
+    }
+
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error {
+        KdlSerError::Unsupported {
+            shape: shape.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.close_object()
+    }
+
+    fn begin_map_key(&mut self) -> Result<(), Self::Error> {
+        self.map_key = true;
+        Ok(())
+    }
+
+    fn end_map_key(&mut self) -> Result<(), Self::Error> {
+        self.map_key = false;
+        Ok(())
+    }
+
+    fn start_enum_variant(&mut self, _discriminant: u64) -> Result<(), Self::Error> {
+        self.variant = true;
+        Ok(())
+    }
+}
+
+fn write_document(root: Item<'_>) -> Result<KdlDocument, KdlSerError> {
+    let mut document = KdlDocument::new();
+    match root {
+        Item::Object(fields) => {
+            let mut entries = Vec::new();
+            write_fields(fields, &mut entries, document.nodes_mut())?;
+            if !entries.is_empty() {
+                return Err(KdlSerError::InvalidDocument);
+            }
+        }
+        Item::Array(items) => {
+            for item in items {
+                document.nodes_mut().push(write_element(item)?);
+            }
+        }
+        _ => return Err(KdlSerError::InvalidDocument),
+    }
+    Ok(document)
+}
+
+/// Writes the fields of a struct, or the entries of a map, as the entries and children of
+/// a node.
+fn write_fields(
+    fields: Vec<(Key<'_>, Item<'_>)>,
+    entries: &mut Vec<KdlEntry>,
+    children: &mut Vec<KdlNode>,
+) -> Result<(), KdlSerError> {
+    for (key, item) in fields {
+        let (kind, name, is_list, flattened) = match key {
+            Key::Field(field) => (
+                FieldKind::of(&field),
+                field.name.to_owned(),
+                matches!(field.shape.def, Def::List(_) | Def::Array(_)),
+                field.flattened,
+            ),
+            Key::Name(name) => (FieldKind::Child, name, false, false),
+        };
+
+        match (kind, item) {
+            (FieldKind::Argument, item) => entries.push(KdlEntry::new(scalar(&name, item)?)),
+            (FieldKind::Arguments, Item::Array(items)) => {
+                for item in items {
+                    entries.push(KdlEntry::new(scalar(&name, item)?));
+                }
+            }
+            (FieldKind::Arguments, _) => return Err(KdlSerError::NonScalarEntry { name }),
+            // `None` properties and children are left out
+            (FieldKind::Property | FieldKind::Child, Item::Value(KdlValue::Null)) => {}
+            (FieldKind::Property, item) => {
+                let value = scalar(&name, item)?;
+                entries.push(KdlEntry::new_prop(name.as_str(), value));
+            }
+            (FieldKind::Children, Item::Array(items)) => {
+                for item in items {
+                    children.push(write_element(item)?);
+                }
+            }
+            (FieldKind::Children, item) => children.push(write_element(item)?),
+            // A list of children is written as one node per element
+            (FieldKind::Child, Item::Array(items)) if is_list => {
+                for item in items {
+                    children.push(write_node(&name, item)?);
+                }
+            }
+            // A flattened enum is already named after its variant
+            (FieldKind::Child, Item::UnitVariant(variant)) if flattened => {
+                children.push(KdlNode::new(variant));
+            }
+            (FieldKind::Child, item) => children.push(write_node(&name, item)?),
+        }
+    }
+    Ok(())
+}
+
+/// Writes a value as a node with the given name.
+fn write_node(name: &str, item: Item<'_>) -> Result<KdlNode, KdlSerError> {
+    let mut node = KdlNode::new(name);
+    let mut children = Vec::new();
+
+    match item {
+        Item::Value(value) => node.push(KdlEntry::new(value)),
+        Item::UnitVariant(variant) => {
+            node.push(KdlEntry::new(KdlValue::String(variant.to_owned())))
+        }
+        Item::Variant(variant, data) => children.push(write_node(&variant, *data)?),
+        Item::Array(items) => {
+            // Scalars are written as arguments, anything else as child nodes
+            if items.iter().all(|item| matches!(item, Item::Value(_))) {
+                for item in items {
+                    node.push(KdlEntry::new(scalar(name, item)?));
+                }
+            } else {
+                for item in items {
+                    children.push(write_element(item)?);
+                }
+            }
+        }
+        Item::Object(fields) => {
+            let mut entries = Vec::new();
+            write_fields(fields, &mut entries, &mut children)?;
+            for entry in entries {
+                node.push(entry);
+            }
+        }
+    }
+
+    if !children.is_empty() {
+        node.ensure_children().nodes_mut().extend(children);
+    }
+    Ok(node)
+}
+
+/// Writes an element of a list of nodes: variants are written as nodes named after the
+/// variant, and other values as `-` nodes.
+fn write_element(item: Item<'_>) -> Result<KdlNode, KdlSerError> {
+    match item {
+        Item::UnitVariant(variant) => Ok(KdlNode::new(variant)),
+        Item::Variant(variant, data) => write_node(&variant, *data),
+        item => write_node("-", item),
+    }
+}
+
+fn scalar(name: &str, item: Item<'_>) -> Result<KdlValue, KdlSerError> {
+    match item {
+        Item::Value(value) => Ok(value),
+        Item::UnitVariant(variant) => Ok(KdlValue::String(variant.to_owned())),
+        _ => Err(KdlSerError::NonScalarEntry {
+            name: name.to_owned(),
+        }),
+    }
+}
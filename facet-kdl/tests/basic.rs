@@ -3,7 +3,6 @@ use facet_testhelpers::test;
 use indoc::indoc;
 
 #[test]
-#[ignore]
 fn basic_node() {
     // QUESTION: I don't know when this would be particularly good practice, but it could be nice if `facet` shipped
     // some sort of macro that allowed libraries to rename the Facet trait / attributes? This might make it clearer
@@ -16,13 +15,13 @@ fn basic_node() {
     //
     // Overall I think this is a hard design question, but I do think it's worth considering how several `facet` crates
     // relying on arbitrary attributes should interact...
-    #[derive(Facet)]
+    #[derive(Facet, Debug, PartialEq)]
     struct Basic {
         #[facet(child)]
         title: Title,
     }
 
-    #[derive(Facet)]
+    #[derive(Facet, Debug, PartialEq)]
     struct Title {
         #[facet(argument)]
         title: String,
@@ -32,7 +31,239 @@ fn basic_node() {
         title "Hello, World"
     "#};
 
-    dbg!(Basic::SHAPE);
+    let basic: Basic = facet_kdl::from_str(kdl)?;
+    assert_eq!(
+        basic,
+        Basic {
+            title: Title {
+                title: "Hello, World".to_string()
+            }
+        }
+    );
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Config {
+    #[facet(child)]
+    server: Server,
+    #[facet(child)]
+    peer: Vec<Peer>,
+    #[facet(child)]
+    level: Option<Level>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    #[facet(argument)]
+    host: String,
+    #[facet(property)]
+    port: u16,
+    #[facet(property)]
+    tls: Option<bool>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Peer {
+    #[facet(argument)]
+    name: String,
+    #[facet(arguments)]
+    addresses: Vec<String>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[test]
+fn arguments_properties_and_children() {
+    let kdl = indoc! {r#"
+        server "localhost" port=8080
+        peer "alpha" "10.0.0.1" "10.0.0.2"
+        peer "beta"
+        level "Info"
+    "#};
+
+    let config: Config = facet_kdl::from_str(kdl)?;
+    assert_eq!(
+        config,
+        Config {
+            server: Server {
+                host: "localhost".to_string(),
+                port: 8080,
+                tls: None,
+            },
+            peer: vec![
+                Peer {
+                    name: "alpha".to_string(),
+                    addresses: vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()],
+                },
+                Peer {
+                    name: "beta".to_string(),
+                    addresses: vec![],
+                },
+            ],
+            level: Some(Level::Info),
+        }
+    );
+
+    // Lists of children may be empty, and optional children absent
+    let config: Config = facet_kdl::from_str(r#"server "::1" port=1 tls=#true"#)?;
+    assert_eq!(config.server.tls, Some(true));
+    assert_eq!(config.peer, vec![]);
+    assert_eq!(config.level, None);
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Step {
+    Checkout,
+    Run(String),
+    Copy {
+        #[facet(argument)]
+        from: String,
+        #[facet(argument)]
+        to: String,
+    },
+}
+
+#[test]
+fn enums_selected_by_node_name() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Pipeline {
+        #[facet(children)]
+        steps: Vec<Step>,
+    }
+
+    let kdl = indoc! {r#"
+        Checkout
+        Run "cargo test"
+        Copy "target/doc" "public"
+    "#};
+    let expected = vec![
+        Step::Checkout,
+        Step::Run("cargo test".to_string()),
+        Step::Copy {
+            from: "target/doc".to_string(),
+            to: "public".to_string(),
+        },
+    ];
+
+    let pipeline: Pipeline = facet_kdl::from_str(kdl)?;
+    assert_eq!(pipeline.steps, expected);
+
+    // A list is a valid document too
+    let steps: Vec<Step> = facet_kdl::from_str(kdl)?;
+    assert_eq!(steps, expected);
+
+    let err = facet_kdl::from_str::<Vec<Step>>("Deploy").unwrap_err();
+    assert!(err.to_string().contains("no variant named `Deploy`"));
+}
+
+#[test]
+fn enum_fields() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Output {
+        #[facet(child)]
+        format: Format,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Format {
+        Json,
+        Pretty {
+            #[facet(property)]
+            indent: u8,
+        },
+    }
+
+    let output: Output = facet_kdl::from_str(r#"format "Json""#)?;
+    assert_eq!(output.format, Format::Json);
+
+    let output: Output = facet_kdl::from_str("format { Pretty indent=2; }")?;
+    assert_eq!(output.format, Format::Pretty { indent: 2 });
+
+    // Nodes named after a variant go to the enum field
+    let output: Output = facet_kdl::from_str("Pretty indent=4")?;
+    assert_eq!(output.format, Format::Pretty { indent: 4 });
+}
+
+#[test]
+fn maps_and_tuples() {
+    use std::collections::BTreeMap;
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Process {
+        env: BTreeMap<String, String>,
+        origin: Point,
+        weights: Vec<f64>,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Point(i32, i32);
+
+    let kdl = indoc! {r#"
+        env PATH="/bin" {
+            HOME "/root"
+        }
+        origin 1 -2
+        weights 0.5
+        weights 2
+    "#};
+
+    let process: Process = facet_kdl::from_str(kdl)?;
+    assert_eq!(
+        process,
+        Process {
+            env: BTreeMap::from([
+                ("HOME".to_string(), "/root".to_string()),
+                ("PATH".to_string(), "/bin".to_string()),
+            ]),
+            origin: Point(1, -2),
+            weights: vec![0.5, 2.0],
+        }
+    );
+}
+
+#[test]
+fn unknown_and_missing_fields() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Lenient {
+        #[facet(property)]
+        name: String,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        #[facet(property)]
+        name: String,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct LenientDocument {
+        #[facet(child)]
+        item: Lenient,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct StrictDocument {
+        #[facet(child)]
+        item: Strict,
+    }
+
+    let document: LenientDocument = facet_kdl::from_str(r#"item name="a" extra=1 { more; }"#)?;
+    assert_eq!(document.item.name, "a");
+
+    let err = facet_kdl::from_str::<StrictDocument>(r#"item name="a" extra=1"#).unwrap_err();
+    assert!(err.to_string().contains("unknown property `extra`"));
+
+    let err = facet_kdl::from_str::<LenientDocument>("item").unwrap_err();
+    assert!(err.to_string().contains("missing field `name`"));
 
-    let _basic: Basic = facet_kdl::from_str(kdl)?;
+    let err = facet_kdl::from_str::<LenientDocument>(r#"item "a""#).unwrap_err();
+    assert!(err.to_string().contains("unexpected argument"));
 }
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Config {
+    #[facet(child)]
+    server: Server,
+    #[facet(child)]
+    peer: Vec<Peer>,
+    #[facet(child)]
+    level: Option<Level>,
+    #[facet(children)]
+    steps: Vec<Step>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    #[facet(argument)]
+    host: String,
+    #[facet(property)]
+    port: u16,
+    #[facet(property)]
+    tls: Option<bool>,
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Peer {
+    #[facet(argument)]
+    name: String,
+    #[facet(arguments)]
+    addresses: Vec<String>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Step {
+    Checkout,
+    Run(String),
+    Copy {
+        #[facet(argument)]
+        from: String,
+        #[facet(argument)]
+        to: String,
+    },
+    Retry(u8, u64),
+}
+
+fn config() -> Config {
+    Config {
+        server: Server {
+            host: "localhost".to_string(),
+            port: 8080,
+            tls: None,
+            labels: BTreeMap::from([("tier".to_string(), "web".to_string())]),
+        },
+        peer: vec![
+            Peer {
+                name: "alpha".to_string(),
+                addresses: vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()],
+            },
+            Peer {
+                name: "beta".to_string(),
+                addresses: vec![],
+            },
+        ],
+        level: Some(Level::Debug),
+        steps: vec![
+            Step::Checkout,
+            Step::Run("cargo test".to_string()),
+            Step::Copy {
+                from: "a".to_string(),
+                to: "b".to_string(),
+            },
+            Step::Retry(3, 1000),
+        ],
+    }
+}
+
+#[test]
+fn round_trip() {
+    let config = config();
+    let kdl = facet_kdl::to_string(&config)?;
+    let back: Config = facet_kdl::from_str(&kdl)?;
+    assert_eq!(back, config);
+
+    let config = Config {
+        level: None,
+        steps: vec![],
+        ..self::config()
+    };
+    let kdl = facet_kdl::to_string(&config)?;
+    let back: Config = facet_kdl::from_str(&kdl)?;
+    assert_eq!(back, config);
+}
+
+#[test]
+fn layout() {
+    let kdl = facet_kdl::to_string(&config())?;
+
+    // Arguments and properties are written on the node, children under it
+    assert!(kdl.contains("port=8080"), "{kdl}");
+    assert!(!kdl.contains("tls"), "{kdl}");
+    assert!(kdl.contains("10.0.0.2"), "{kdl}");
+    assert!(kdl.contains("labels"), "{kdl}");
+
+    // Enum elements are nodes named after their variant
+    let nodes: Vec<&str> = kdl
+        .lines()
+        .filter(|line| !line.starts_with([' ', '}']))
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    assert_eq!(
+        nodes,
+        [
+            "server", "peer", "peer", "level", "Checkout", "Run", "Copy", "Retry"
+        ]
+    );
+}
+
+#[test]
+fn list_documents() {
+    let steps = vec![Step::Checkout, Step::Retry(1, 2)];
+    let kdl = facet_kdl::to_string(&steps)?;
+    let back: Vec<Step> = facet_kdl::from_str(&kdl)?;
+    assert_eq!(back, steps);
+
+    let ports = vec![80_u16, 443];
+    let kdl = facet_kdl::to_string(&ports)?;
+    let back: Vec<u16> = facet_kdl::from_str(&kdl)?;
+    assert_eq!(back, ports);
+}
+
+#[test]
+fn invalid_documents() {
+    assert!(facet_kdl::to_string(&42_u32).is_err());
+    assert!(
+        facet_kdl::to_string(&Peer {
+            name: "alpha".to_string(),
+            addresses: vec![],
+        })
+        .is_err()
+    );
+}
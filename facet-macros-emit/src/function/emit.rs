@@ -1,5 +1,5 @@
 use facet_macros_parse::function::*;
-use facet_macros_parse::{FunctionSignature, Ident, Span, TokenStream, extract_type_params};
+use facet_macros_parse::{FunctionSignature, Ident, Span, TokenStream};
 use quote::quote;
 
 /// Entry point for the facet_fn attribute macro
@@ -58,30 +58,49 @@ pub fn generate_function_shape(parsed: FunctionSignature) -> TokenStream {
         .iter()
         .map(|p| p.name.to_string())
         .collect::<Vec<_>>();
-    let arity = params.len();
     let fn_name_str = fn_name.to_string();
 
-    // Extract  type parameters for PhantomData using unsynn parsing
-    let generics_type = if let Some(ref generics_ts) = generics {
-        extract_type_params(generics_ts.clone())
-    } else {
-        quote! { () }
-    };
-
     let documentation_lines: Vec<_> = parsed
         .documentation
         .iter()
         .map(|doc| quote! { #doc })
         .collect();
 
+    // Move the arguments out of their tuple, which has nothing to move for no parameters
+    let (args, read_args) = if params.is_empty() {
+        (quote! { _args }, quote! {})
+    } else {
+        (
+            quote! { args },
+            quote! {
+                let ( #( #idents, )* ) = unsafe { args.read::<( #( #types, )* )>() };
+            },
+        )
+    };
+
     let shape_definition = quote! {
-        pub fn shape #generics () -> FunctionShape<( #( #types ),* ), #return_type, #generics_type> {
-            FunctionShape::new(
-                #fn_name_str,
-                #arity,
-                &[ #( #names ),* ],
-                &[ #( #documentation_lines ),* ]
-            )
+        pub fn shape #generics () -> ::facet::FunctionShape<'static>
+        where
+            ( #( #types, )* ): ::facet::Facet<'static>,
+            #( #types: ::facet::Facet<'static>, )*
+            #return_type: ::facet::Facet<'static>,
+        {
+            let invoke: ::facet::InvokeFn = |#args, ret| {
+                // SAFETY: the caller passes the tuple of arguments, and memory for the return
+                // value, as documented on `InvokeFn`
+                #read_args
+                unsafe { ret.put::<#return_type>(inner( #( #idents ),* )) }
+            };
+            let builder = ::facet::FunctionShape::builder()
+                .name(#fn_name_str)
+                .param_names(&[ #( #names ),* ])
+                .param_shapes(const { &[ #( <#types as ::facet::Facet>::SHAPE ),* ] })
+                .args_shape(<( #( #types, )* ) as ::facet::Facet>::SHAPE)
+                .return_shape(<#return_type as ::facet::Facet>::SHAPE)
+                .documentation(&[ #( #documentation_lines ),* ]);
+            // SAFETY: `invoke` reads the tuple of parameters and writes the return type, which
+            // are the shapes given to the builder
+            unsafe { builder.invoke(invoke) }.build()
         }
     };
 
@@ -92,36 +111,6 @@ pub fn generate_function_shape(parsed: FunctionSignature) -> TokenStream {
             use super::*;
             pub(super) fn inner #generics ( #( #defs ),* ) -> #return_type #body
 
-            #[derive(Debug, Clone)]
-            pub struct FunctionShape<Args, Ret, Generics = ()> {
-                pub name: &'static str,
-                pub param_count: usize,
-                pub param_names: &'static [&'static str],
-                pub documentation: &'static [&'static str],
-                _args: core::marker::PhantomData<Args>,
-                _ret: core::marker::PhantomData<Ret>,
-                _generics: core::marker::PhantomData<Generics>,
-            }
-
-            impl<Args, Ret, Generics> FunctionShape<Args, Ret, Generics> {
-                pub const fn new(
-                    name: &'static str,
-                    param_count: usize,
-                    param_names: &'static [&'static str],
-                    documentation: &'static [&'static str],
-                ) -> Self {
-                    Self {
-                        name,
-                        param_count,
-                        param_names,
-                        documentation,
-                        _args: core::marker::PhantomData,
-                        _ret: core::marker::PhantomData,
-                        _generics: core::marker::PhantomData,
-                    }
-                }
-            }
-
             #shape_definition
         }
 
@@ -88,6 +88,30 @@ pub enum PFacetAttr {
     /// Valid in container
     /// `#[facet(type_tag = "com.example.MyType")]` — identify type by tag and serialize with this tag
     TypeTag { content: String },
+
+    /// Valid in container (enums)
+    /// `#[facet(tag = "type")]` — the variant name is stored under this key
+    /// (internally tagged, or adjacently tagged together with `content`)
+    Tag { name: String },
+
+    /// Valid in container (enums)
+    /// `#[facet(content = "c")]` — the variant's data is stored under this key,
+    /// next to the `tag` key (adjacently tagged)
+    Content { name: String },
+
+    /// Valid in container (enums)
+    /// `#[facet(untagged)]` — variants are represented by their data alone
+    Untagged,
+
+    /// Valid in container
+    /// `#[facet(ext = 5)]` — the type is an extension type with this id, in formats
+    /// that have them (like MessagePack)
+    Ext { expr: TokenStream },
+
+    /// Valid in container (structs)
+    /// `#[facet(as_array)]` — the struct is written as an array of its fields, in formats
+    /// that support it (like MessagePack)
+    AsArray,
 }
 
 impl PFacetAttr {
@@ -146,6 +170,21 @@ impl PFacetAttr {
                         content: type_tag.expr.as_str().to_string(),
                     });
                 }
+                FacetInner::Tag(tag) => {
+                    dest.push(PFacetAttr::Tag {
+                        name: tag.value.as_str().to_string(),
+                    });
+                }
+                FacetInner::Content(content) => {
+                    dest.push(PFacetAttr::Content {
+                        name: content.value.as_str().to_string(),
+                    });
+                }
+                FacetInner::Untagged(_) => dest.push(PFacetAttr::Untagged),
+                FacetInner::Ext(ext) => dest.push(PFacetAttr::Ext {
+                    expr: ext.expr.to_token_stream(),
+                }),
+                FacetInner::AsArray(_) => dest.push(PFacetAttr::AsArray),
             }
         }
     }
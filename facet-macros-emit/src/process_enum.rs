@@ -40,6 +40,15 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                     let rule_str = rule.apply(""); // Hack to get str - improve RenameRule display
                     attribute_tokens.push(quote! { ::facet::ShapeAttribute::RenameAll(#rule_str) });
                 }
+                PFacetAttr::Tag { name } => {
+                    attribute_tokens.push(quote! { ::facet::ShapeAttribute::Tag(#name) });
+                }
+                PFacetAttr::Content { name } => {
+                    attribute_tokens.push(quote! { ::facet::ShapeAttribute::Content(#name) });
+                }
+                PFacetAttr::Untagged => {
+                    attribute_tokens.push(quote! { ::facet::ShapeAttribute::Untagged });
+                }
                 PFacetAttr::Invariants { .. } => {
                     // Note: Facet vtable does not currently support invariants directly on enums
                     // Maybe panic or warn here? For now, ignoring.
@@ -57,6 +66,68 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
         }
     };
 
+    let facet_attrs = &pe.container.attrs.facet;
+
+    // Extension types wrap the bytes of a struct's single field
+    if facet_attrs
+        .iter()
+        .any(|attr| matches!(attr, PFacetAttr::Ext { .. }))
+    {
+        return quote! {
+            compile_error!("`#[facet(ext = ...)]` is only supported on structs");
+        };
+    }
+
+    // Internally tagged enums write the variant's fields next to the tag, so every
+    // variant needs named fields: tuple variants are rejected here, and newtype
+    // variants must wrap a struct, which is checked once the inner shape is known.
+    let mut internal_tag_asserts: Vec<TokenStream> = Vec::new();
+    let has_tag = facet_attrs
+        .iter()
+        .any(|attr| matches!(attr, PFacetAttr::Tag { .. }));
+    let has_content = facet_attrs
+        .iter()
+        .any(|attr| matches!(attr, PFacetAttr::Content { .. }));
+    if has_content && !has_tag {
+        return quote! {
+            compile_error!("`#[facet(content = ...)]` requires `#[facet(tag = ...)]`");
+        };
+    }
+    if has_tag && !has_content {
+        for pv in &pe.variants {
+            let PVariantKind::Tuple { fields } = &pv.kind else {
+                continue;
+            };
+            match &fields[..] {
+                [] => {}
+                [field] => {
+                    let field_type = &field.ty;
+                    let message = format!(
+                        "internally tagged newtype variant {}::{} must wrap a struct",
+                        enum_name_str, pv.name.effective
+                    );
+                    internal_tag_asserts.push(quote! {
+                        if !matches!(
+                            <#field_type as ::facet::Facet<'__facet>>::SHAPE.ty,
+                            ::facet::Type::User(::facet::UserType::Struct(_))
+                        ) {
+                            panic!(#message);
+                        }
+                    });
+                }
+                _ => {
+                    let message = format!(
+                        "Internally tagged enums (`#[facet(tag = ...)]` without `content`) cannot contain tuple variant `{}`",
+                        pv.name.effective
+                    );
+                    return quote! {
+                        compile_error!(#message);
+                    };
+                }
+            }
+        }
+    }
+
     let type_tag_maybe = {
         if let Some(type_tag) = pe.container.attrs.type_tag() {
             quote! { .type_tag(#type_tag) }
@@ -579,6 +650,8 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
             const SHAPE: &'static ::facet::Shape<'static> = &const {
                 #(#shadow_struct_defs)*
 
+                #(#internal_tag_asserts)*
+
                 let __facet_variants: &'static [::facet::Variant] = &const {[
                     #(#variant_expressions),*
                 ]};
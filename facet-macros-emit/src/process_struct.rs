@@ -119,7 +119,12 @@ pub(crate) fn gen_field_from_pfield(
             PFacetAttr::Transparent
             | PFacetAttr::Invariants { .. }
             | PFacetAttr::DenyUnknownFields
-            | PFacetAttr::TypeTag { .. } => {}
+            | PFacetAttr::TypeTag { .. }
+            | PFacetAttr::Tag { .. }
+            | PFacetAttr::Content { .. }
+            | PFacetAttr::Untagged
+            | PFacetAttr::Ext { .. }
+            | PFacetAttr::AsArray => {}
         }
     }
 
@@ -264,6 +269,12 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                 PFacetAttr::Arbitrary { content } => {
                     items.push(quote! { ::facet::ShapeAttribute::Arbitrary(#content) });
                 }
+                PFacetAttr::Ext { expr } => {
+                    items.push(quote! { ::facet::ShapeAttribute::Ext(#expr) });
+                }
+                PFacetAttr::AsArray => {
+                    items.push(quote! { ::facet::ShapeAttribute::AsArray });
+                }
                 // Others not applicable at container level or handled elsewhere
                 PFacetAttr::Sensitive
                 | PFacetAttr::Opaque
@@ -272,7 +283,10 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                 | PFacetAttr::SkipSerializingIf { .. }
                 | PFacetAttr::Flatten
                 | PFacetAttr::Child
-                | PFacetAttr::TypeTag { .. } => {}
+                | PFacetAttr::TypeTag { .. }
+                | PFacetAttr::Tag { .. }
+                | PFacetAttr::Content { .. }
+                | PFacetAttr::Untagged => {}
             }
         }
         if items.is_empty() {
@@ -0,0 +1,11 @@
+use facet::Facet;
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[facet(content = "value")]
+enum Message {
+    Text(String),
+    Count(u32),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use facet::Facet;
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[facet(ext = 1)]
+enum Payload {
+    Empty,
+    Bytes(Vec<u8>),
+}
+
+fn main() {}
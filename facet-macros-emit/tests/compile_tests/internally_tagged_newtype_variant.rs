@@ -0,0 +1,12 @@
+use facet::Facet;
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[facet(tag = "type")]
+enum Message {
+    Count(u32),
+}
+
+fn main() {
+    let _ = Message::SHAPE;
+}
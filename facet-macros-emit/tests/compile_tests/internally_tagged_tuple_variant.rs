@@ -0,0 +1,11 @@
+use facet::Facet;
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[facet(tag = "type")]
+enum Shape {
+    Point { x: f64, y: f64 },
+    Segment(f64, f64),
+}
+
+fn main() {}
@@ -190,3 +190,59 @@ fn test_default_but_no_default() {
     // Run the test
     run_compilation_test(&test);
 }
+
+#[test]
+#[cfg(not(miri))]
+fn test_internally_tagged_tuple_variant() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "internally_tagged_tuple_variant",
+        source: include_str!("./internally_tagged_tuple_variant.rs"),
+        expected_errors: &["cannot contain tuple variant `Segment`"],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_internally_tagged_newtype_variant() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "internally_tagged_newtype_variant",
+        source: include_str!("./internally_tagged_newtype_variant.rs"),
+        expected_errors: &["internally tagged newtype variant Message::Count must wrap a struct"],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_content_without_tag() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "content_without_tag",
+        source: include_str!("./content_without_tag.rs"),
+        expected_errors: &["`#[facet(content = ...)]` requires `#[facet(tag = ...)]`"],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_ext_on_enum() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "ext_on_enum",
+        source: include_str!("./ext_on_enum.rs"),
+        expected_errors: &["`#[facet(ext = ...)]` is only supported on structs"],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
@@ -58,6 +58,16 @@ keyword! {
     pub KSkipSerializingIf = "skip_serializing_if";
     /// The "type_tag" keyword.
     pub KTypeTag = "type_tag";
+    /// The "tag" keyword.
+    pub KTag = "tag";
+    /// The "content" keyword.
+    pub KContent = "content";
+    /// The "untagged" keyword.
+    pub KUntagged = "untagged";
+    /// The "ext" keyword.
+    pub KExt = "ext";
+    /// The "as_array" keyword.
+    pub KAsArray = "as_array";
 }
 
 operator! {
@@ -165,6 +175,16 @@ unsynn! {
         SkipSerializingIf(SkipSerializingIfInner),
         /// A type_tag attribute that specifies the identifying tag for self describing formats
         TypeTag(TypeTagInner),
+        /// A tag attribute that makes an enum internally tagged (#[facet(tag = "type")])
+        Tag(TagInner),
+        /// A content attribute that, together with `tag`, makes an enum adjacently tagged (#[facet(content = "c")])
+        Content(ContentInner),
+        /// An untagged attribute that makes an enum untagged (#[facet(untagged)])
+        Untagged(KUntagged),
+        /// An ext attribute that identifies the type as an extension type (#[facet(ext = 5)])
+        Ext(ExtInner),
+        /// An as_array attribute that writes a struct as an array of its fields (#[facet(as_array)])
+        AsArray(KAsArray),
         /// Any other attribute represented as a sequence of token trees.
         Arbitrary(VerbatimUntil<Comma>),
     }
@@ -207,6 +227,36 @@ unsynn! {
         pub expr: LiteralString,
     }
 
+    /// Inner value for #[facet(tag = ...)]
+    pub struct TagInner {
+        /// The "tag" keyword.
+        pub _kw_tag: KTag,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The value assigned, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[facet(content = ...)]
+    pub struct ContentInner {
+        /// The "content" keyword.
+        pub _kw_content: KContent,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The value assigned, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[facet(ext = ...)]
+    pub struct ExtInner {
+        /// The "ext" keyword.
+        pub _kw_ext: KExt,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The extension type id, as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
     /// Inner value for #[facet(default = ...)]
     pub struct DefaultEqualsInner {
         /// The "default" keyword.
@@ -63,6 +63,10 @@ pub const MSGPACK_FIXEXT8: u8 = 0xd7;
 /// Fixed-size 16-byte extension format (0xd8)
 pub const MSGPACK_FIXEXT16: u8 = 0xd8;
 
+/// Extension type of timestamps, the only one predefined by the specification
+/// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#timestamp-extension-type>
+pub const MSGPACK_EXT_TIMESTAMP: i8 = -1;
+
 /// String format family - Represents UTF-8 string
 /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#formats-str>
 pub const MSGPACK_STR8: u8 = 0xd9;
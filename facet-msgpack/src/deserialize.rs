@@ -1,7 +1,10 @@
 use crate::constants::*;
 use crate::errors::Error as DecodeError;
+use crate::timestamp;
 
-use facet_core::{Def, Facet, Type, UserType};
+use std::borrow::Cow;
+
+use facet_core::{Def, EnumType, Facet, PointerType, StructKind, Type, UserType, Variant};
 use facet_reflect::Partial;
 use log::trace;
 
@@ -28,18 +31,43 @@ use log::trace;
 /// let user: User = from_slice(&msgpack_data).unwrap();
 /// assert_eq!(user, User { id: 42, username: "user123".to_string() });
 /// ```
-pub fn from_slice<T: Facet<'static>>(msgpack: &[u8]) -> Result<T, DecodeError<'static>> {
+///
+/// Fields of type `&str`, `Cow<str>`, `&[u8]` and `Cow<[u8]>` borrow from `msgpack`
+/// instead of allocating:
+///
+/// ```
+/// use facet::Facet;
+/// use facet_msgpack::from_slice;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct User<'a> {
+///     id: u64,
+///     username: &'a str,
+/// }
+///
+/// let msgpack_data = [
+///     0x82, 0xa2, 0x69, 0x64, 0x2a, 0xa8, 0x75, 0x73,
+///     0x65, 0x72, 0x6e, 0x61, 0x6d, 0x65, 0xa7, 0x75,
+///     0x73, 0x65, 0x72, 0x31, 0x32, 0x33
+/// ];
+///
+/// let user: User = from_slice(&msgpack_data).unwrap();
+/// assert_eq!(user, User { id: 42, username: "user123" });
+/// ```
+pub fn from_slice<'input, 'facet, 'shape, T: Facet<'facet>>(
+    msgpack: &'input [u8],
+) -> Result<T, DecodeError<'shape>>
+where
+    'input: 'facet,
+{
     trace!("from_slice: Starting deserialization for type {}", T::SHAPE);
-    let mut typed_partial = Partial::alloc::<T>()?;
-    trace!(
-        "from_slice: Allocated TypedPartial, inner shape: {}",
-        typed_partial.inner_mut().shape()
-    );
-    from_slice_value(msgpack, typed_partial.inner_mut())?;
+    // Shapes of `T` are 'static, so the errors don't depend on how long the input lives
+    let mut partial = Partial::alloc_shape(T::SHAPE)?;
+    from_slice_value(msgpack, &mut partial)?;
     trace!("from_slice: Deserialization complete, building value");
-    let boxed_value = typed_partial.build()?;
+    let value = partial.build()?.materialize::<T>()?;
     trace!("from_slice: Value built successfully");
-    Ok(*boxed_value)
+    Ok(value)
 }
 
 /// Deserializes MessagePack-encoded data into a Facet value.
@@ -81,10 +109,13 @@ pub fn from_slice<T: Facet<'static>>(msgpack: &[u8]) -> Result<T, DecodeError<'s
 /// # MessagePack Format
 /// This implementation follows the MessagePack specification:
 /// <https://github.com/msgpack/msgpack/blob/master/spec.md>
-pub fn from_slice_value<'facet, 'shape>(
-    msgpack: &[u8],
+pub fn from_slice_value<'input, 'facet, 'shape>(
+    msgpack: &'input [u8],
     wip: &mut Partial<'facet, 'shape>,
-) -> Result<(), DecodeError<'shape>> {
+) -> Result<(), DecodeError<'shape>>
+where
+    'input: 'facet,
+{
     trace!("from_slice_value: Starting with shape {}", wip.shape());
     let mut decoder = Decoder::new(msgpack);
     let result = decoder.deserialize_value(wip);
@@ -154,21 +185,125 @@ impl<'input, 'shape> Decoder<'input> {
             MSGPACK_UINT8 => Ok(self.decode_u8()? as u64),
             MSGPACK_UINT16 => Ok(self.decode_u16()? as u64),
             MSGPACK_UINT32 => Ok(self.decode_u32()? as u64),
-            MSGPACK_UINT64 => {
-                if self.offset + 8 > self.input.len() {
-                    return Err(DecodeError::InsufficientData);
-                }
-                let value = u64::from_be_bytes(
-                    self.input[self.offset..self.offset + 8].try_into().unwrap(),
-                );
-                self.offset += 8;
-                Ok(value)
-            }
+            MSGPACK_UINT64 => self.decode_u64_be(),
             prefix @ MSGPACK_POSFIXINT_MIN..=MSGPACK_POSFIXINT_MAX => Ok(prefix as u64),
             _ => Err(DecodeError::UnexpectedType),
         }
     }
 
+    /// Decodes a 64-bit unsigned integer in big-endian byte order.
+    /// This is a low-level method used by other decoders.
+    fn decode_u64_be(&mut self) -> Result<u64, DecodeError<'static>> {
+        if self.offset + 8 > self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        let value =
+            u64::from_be_bytes(self.input[self.offset..self.offset + 8].try_into().unwrap());
+        self.offset += 8;
+        Ok(value)
+    }
+
+    /// Decodes a MessagePack-encoded signed 64-bit integer.
+    /// Handles the following MessagePack types:
+    /// - negative fixint (0xe0 - 0xff): single-byte negative integer
+    /// - int8 (0xd0): 8-bit signed integer
+    /// - int16 (0xd1): 16-bit signed integer (big-endian)
+    /// - int32 (0xd2): 32-bit signed integer (big-endian)
+    /// - int64 (0xd3): 64-bit signed integer (big-endian)
+    /// - any unsigned integer format, as long as the value fits
+    ///
+    /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#int-format-family>
+    fn decode_i64(&mut self) -> Result<i64, DecodeError<'static>> {
+        match self.decode_u8()? {
+            MSGPACK_INT8 => Ok(self.decode_u8()? as i8 as i64),
+            MSGPACK_INT16 => Ok(self.decode_u16()? as i16 as i64),
+            MSGPACK_INT32 => Ok(self.decode_u32()? as i32 as i64),
+            MSGPACK_INT64 => Ok(self.decode_u64_be()? as i64),
+            prefix if (MSGPACK_NEGFIXINT_MIN..=MSGPACK_NEGFIXINT_MAX).contains(&(prefix as i8)) => {
+                Ok(prefix as i8 as i64)
+            }
+            _ => {
+                // Not a signed format: go back and try the unsigned ones
+                self.offset -= 1;
+                let value = self.decode_u64()?;
+                i64::try_from(value).map_err(|_| DecodeError::IntegerOverflow)
+            }
+        }
+    }
+
+    /// Decodes a MessagePack-encoded floating point number.
+    /// Handles the following MessagePack types:
+    /// - float32 (0xca): single precision floating point number
+    /// - float64 (0xcb): double precision floating point number
+    /// - any integer format, converted to a floating point number
+    ///
+    /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#float-format-family>
+    fn decode_f64(&mut self) -> Result<f64, DecodeError<'static>> {
+        match self.decode_u8()? {
+            MSGPACK_FLOAT32 => Ok(f32::from_bits(self.decode_u32()?) as f64),
+            MSGPACK_FLOAT64 => Ok(f64::from_bits(self.decode_u64_be()?)),
+            _ => {
+                self.offset -= 1;
+                Ok(self.decode_i64()? as f64)
+            }
+        }
+    }
+
+    /// Decodes a MessagePack-encoded byte array.
+    /// Handles the following MessagePack types:
+    /// - bin8 (0xc4): byte array up to 255 bytes
+    /// - bin16 (0xc5): byte array up to 65535 bytes
+    /// - bin32 (0xc6): byte array up to 4294967295 bytes
+    ///
+    /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#formats-bin>
+    fn decode_bin(&mut self) -> Result<&'input [u8], DecodeError<'static>> {
+        let len = match self.decode_u8()? {
+            MSGPACK_BIN8 => self.decode_u8()? as usize,
+            MSGPACK_BIN16 => self.decode_u16()? as usize,
+            MSGPACK_BIN32 => self.decode_u32()? as usize,
+            _ => return Err(DecodeError::UnexpectedType),
+        };
+
+        if self.offset + len > self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+
+        let value = &self.input[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(value)
+    }
+
+    /// Decodes a MessagePack-encoded extension value, as its type and data.
+    /// Handles the following MessagePack types:
+    /// - fixext 1/2/4/8/16 (0xd4 - 0xd8): data of exactly 1, 2, 4, 8 or 16 bytes
+    /// - ext8 (0xc7): data up to 255 bytes
+    /// - ext16 (0xc8): data up to 65535 bytes
+    /// - ext32 (0xc9): data up to 4294967295 bytes
+    ///
+    /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#formats-ext>
+    fn decode_ext(&mut self) -> Result<(i8, &'input [u8]), DecodeError<'static>> {
+        let len = match self.decode_u8()? {
+            MSGPACK_FIXEXT1 => 1,
+            MSGPACK_FIXEXT2 => 2,
+            MSGPACK_FIXEXT4 => 4,
+            MSGPACK_FIXEXT8 => 8,
+            MSGPACK_FIXEXT16 => 16,
+            MSGPACK_EXT8 => self.decode_u8()? as usize,
+            MSGPACK_EXT16 => self.decode_u16()? as usize,
+            MSGPACK_EXT32 => self.decode_u32()? as usize,
+            _ => return Err(DecodeError::UnexpectedType),
+        };
+        let ext_type = self.decode_u8()? as i8;
+
+        if self.offset + len > self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+
+        let value = &self.input[self.offset..self.offset + len];
+        self.offset += len;
+        Ok((ext_type, value))
+    }
+
     /// Decodes a MessagePack-encoded string.
     /// Handles the following MessagePack types:
     /// - fixstr (0xa0 - 0xbf): string up to 31 bytes
@@ -176,8 +311,10 @@ impl<'input, 'shape> Decoder<'input> {
     /// - str16 (0xda): string up to 65535 bytes
     /// - str32 (0xdb): string up to 4294967295 bytes
     ///
+    /// The returned string borrows from the input.
+    ///
     /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#formats-str>
-    fn decode_string(&mut self) -> Result<String, DecodeError<'static>> {
+    fn decode_str(&mut self) -> Result<&'input str, DecodeError<'static>> {
         let prefix = self.decode_u8()?;
 
         let len = match prefix {
@@ -192,7 +329,7 @@ impl<'input, 'shape> Decoder<'input> {
             return Err(DecodeError::InsufficientData);
         }
 
-        let value = String::from_utf8(self.input[self.offset..self.offset + len].to_vec())
+        let value = core::str::from_utf8(&self.input[self.offset..self.offset + len])
             .map_err(|_| DecodeError::InvalidData)?;
         self.offset += len;
         Ok(value)
@@ -285,6 +422,41 @@ impl<'input, 'shape> Decoder<'input> {
             || prefix == MSGPACK_STR32)
     }
 
+    /// Peeks at the next byte to check if it's a byte array without advancing the offset.
+    /// Returns true if the next value is a byte array, false otherwise.
+    fn peek_bin(&mut self) -> Result<bool, DecodeError<'static>> {
+        if self.offset >= self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        let prefix = self.input[self.offset];
+        Ok(prefix == MSGPACK_BIN8 || prefix == MSGPACK_BIN16 || prefix == MSGPACK_BIN32)
+    }
+
+    /// Peeks at the next byte to check if it's an array without advancing the offset.
+    /// Returns true if the next value is an array, false otherwise.
+    fn peek_array(&mut self) -> Result<bool, DecodeError<'static>> {
+        if self.offset >= self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        let prefix = self.input[self.offset];
+        Ok(
+            (MSGPACK_FIXARRAY_MIN..=MSGPACK_FIXARRAY_MAX).contains(&prefix)
+                || prefix == MSGPACK_ARRAY16
+                || prefix == MSGPACK_ARRAY32,
+        )
+    }
+
+    /// Peeks at the next byte to check if it's an extension value without advancing the offset.
+    /// Returns true if the next value is an extension value, false otherwise.
+    fn peek_ext(&mut self) -> Result<bool, DecodeError<'static>> {
+        if self.offset >= self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        let prefix = self.input[self.offset];
+        Ok((MSGPACK_FIXEXT1..=MSGPACK_FIXEXT16).contains(&prefix)
+            || (MSGPACK_EXT8..=MSGPACK_EXT32).contains(&prefix))
+    }
+
     /// Skips a MessagePack value of any type.
     /// This is used when encountering unknown field names in a struct.
     fn skip_value(&mut self) -> Result<(), DecodeError<'static>> {
@@ -358,7 +530,34 @@ impl<'input, 'shape> Decoder<'input> {
                 self.offset += 8;
                 Ok(())
             }
-            // Fixed integers are already handled by decode_u8
+            MSGPACK_POSFIXINT_MIN..=MSGPACK_POSFIXINT_MAX => Ok(()),
+            prefix if (MSGPACK_NEGFIXINT_MIN..=MSGPACK_NEGFIXINT_MAX).contains(&(prefix as i8)) => {
+                Ok(())
+            }
+
+            // Float formats
+            MSGPACK_FLOAT32 => {
+                self.offset += 4;
+                Ok(())
+            }
+            MSGPACK_FLOAT64 => {
+                self.offset += 8;
+                Ok(())
+            }
+
+            // Binary formats
+            MSGPACK_BIN8 | MSGPACK_BIN16 | MSGPACK_BIN32 => {
+                self.offset -= 1;
+                self.decode_bin()?;
+                Ok(())
+            }
+
+            // Extension formats
+            MSGPACK_FIXEXT1..=MSGPACK_FIXEXT16 | MSGPACK_EXT8..=MSGPACK_EXT32 => {
+                self.offset -= 1;
+                self.decode_ext()?;
+                Ok(())
+            }
 
             // Boolean and nil
             MSGPACK_NIL | MSGPACK_TRUE | MSGPACK_FALSE => Ok(()),
@@ -419,33 +618,60 @@ impl<'input, 'shape> Decoder<'input> {
     fn deserialize_value<'facet>(
         &mut self,
         wip: &mut Partial<'facet, 'shape>,
-    ) -> Result<(), DecodeError<'shape>> {
+    ) -> Result<(), DecodeError<'shape>>
+    where
+        'input: 'facet,
+    {
         let shape = wip.shape();
         trace!("Deserializing {:?}", shape);
 
+        if let Some(ext_type) = shape.get_ext_attr() {
+            return self.deserialize_ext(wip, ext_type);
+        }
+
         // First check the type system (Type)
         match &shape.ty {
             Type::User(UserType::Struct(struct_type))
                 if struct_type.kind != facet_core::StructKind::Tuple =>
             {
                 trace!("Deserializing struct");
-                let map_len = self.decode_map_len()?;
 
                 // Track which fields we've seen so we can handle defaults for the rest
                 let mut seen_fields = vec![false; struct_type.fields.len()];
 
-                for _ in 0..map_len {
-                    let key = self.decode_string()?;
-                    match wip.field_index(&key) {
-                        Some(index) => {
-                            seen_fields[index] = true;
-                            self.deserialize_value(wip.begin_nth_field(index).unwrap())?;
-                            wip.end().unwrap();
+                if self.peek_array()? {
+                    // Written as an array of the fields in order: missing trailing fields are
+                    // handled like missing keys, and extra trailing elements are ignored
+                    trace!("Struct is written as an array");
+                    let array_len = self.decode_array_len()?;
+                    for (index, field) in struct_type.fields.iter().enumerate().take(array_len) {
+                        // Skipped fields are written as nil, to keep the positions of the others
+                        if !matches!(field.shape.def, Def::Option(_)) && self.peek_nil()? {
+                            self.decode_nil()?;
+                            continue;
                         }
-                        None => {
-                            // Skip unknown field value
-                            self.skip_value()?;
-                            trace!("Skipping unknown field: {}", key);
+                        seen_fields[index] = true;
+                        self.deserialize_value(wip.begin_nth_field(index)?)?;
+                        wip.end()?;
+                    }
+                    for _ in struct_type.fields.len()..array_len {
+                        self.skip_value()?;
+                    }
+                } else {
+                    let map_len = self.decode_map_len()?;
+                    for _ in 0..map_len {
+                        let key = self.decode_str()?;
+                        match wip.field_index(key) {
+                            Some(index) => {
+                                seen_fields[index] = true;
+                                self.deserialize_value(wip.begin_nth_field(index).unwrap())?;
+                                wip.end().unwrap();
+                            }
+                            None => {
+                                // Skip unknown field value
+                                self.skip_value()?;
+                                trace!("Skipping unknown field: {}", key);
+                            }
                         }
                     }
                 }
@@ -498,9 +724,16 @@ impl<'input, 'shape> Decoder<'input> {
             Type::User(UserType::Enum(enum_type)) => {
                 trace!("Deserializing enum");
 
+                if shape.has_untagged_attr() {
+                    return self.deserialize_untagged_enum(wip, enum_type);
+                }
+                if let Some(tag) = shape.get_tag_attr() {
+                    return self.deserialize_tagged_enum(wip, enum_type, tag);
+                }
+
                 // Check if it's a unit variant which is represented as a string
                 if self.peek_string()? {
-                    let variant_name = self.decode_string()?;
+                    let variant_name = self.decode_str()?;
                     for (idx, variant) in enum_type.variants.iter().enumerate() {
                         if variant.name == variant_name {
                             wip.select_nth_variant(idx)?;
@@ -519,7 +752,7 @@ impl<'input, 'shape> Decoder<'input> {
                     return Err(DecodeError::InvalidData);
                 }
 
-                let variant_name = self.decode_string()?;
+                let variant_name = self.decode_str()?;
 
                 for (idx, variant) in enum_type.variants.iter().enumerate() {
                     if variant.name == variant_name {
@@ -557,8 +790,8 @@ impl<'input, 'shape> Decoder<'input> {
 
                                 // Handle fields as a normal struct
                                 for _ in 0..map_len {
-                                    let field_name = self.decode_string()?;
-                                    match wip.field_index(&field_name) {
+                                    let field_name = self.decode_str()?;
+                                    match wip.field_index(field_name) {
                                         Some(field_idx) => {
                                             wip.begin_nth_enum_field(field_idx)?;
                                             self.deserialize_value(wip)?;
@@ -594,6 +827,18 @@ impl<'input, 'shape> Decoder<'input> {
                     variant_name
                 )));
             }
+            Type::Pointer(PointerType::Reference(_)) if shape.is_type::<&str>() => {
+                trace!("Deserializing borrowed string");
+                let s = self.decode_str()?;
+                wip.set(s)?;
+                return Ok(());
+            }
+            Type::Pointer(PointerType::Reference(_)) if shape.is_type::<&[u8]>() => {
+                trace!("Deserializing borrowed bytes");
+                let bytes = self.decode_bin()?;
+                wip.set(bytes)?;
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -601,8 +846,11 @@ impl<'input, 'shape> Decoder<'input> {
         if let Def::Scalar(_) = shape.def {
             trace!("Deserializing scalar");
             if shape.is_type::<String>() {
-                let s = self.decode_string()?;
-                wip.set(s)?;
+                let s = self.decode_str()?;
+                wip.set(s.to_owned())?;
+            } else if shape.is_type::<Cow<'_, str>>() {
+                let s = self.decode_str()?;
+                wip.set(Cow::Borrowed(s))?;
             } else if shape.is_type::<u64>() {
                 let n = self.decode_u64()?;
                 wip.set(n)?;
@@ -625,43 +873,46 @@ impl<'input, 'shape> Decoder<'input> {
                 }
                 wip.set(n as u8)?;
             } else if shape.is_type::<i64>() {
-                // TODO: implement proper signed int decoding including negative values
-                let n = self.decode_u64()?;
-                if n > i64::MAX as u64 {
-                    return Err(DecodeError::IntegerOverflow);
-                }
-                wip.set(n as i64)?;
+                let n = self.decode_i64()?;
+                wip.set(n)?;
             } else if shape.is_type::<i32>() {
-                let n = self.decode_u64()?;
-                if n > i32::MAX as u64 {
-                    return Err(DecodeError::IntegerOverflow);
-                }
-                wip.set(n as i32)?;
+                let n = self.decode_i64()?;
+                let n = i32::try_from(n).map_err(|_| DecodeError::IntegerOverflow)?;
+                wip.set(n)?;
             } else if shape.is_type::<i16>() {
-                let n = self.decode_u64()?;
-                if n > i16::MAX as u64 {
-                    return Err(DecodeError::IntegerOverflow);
-                }
-                wip.set(n as i16)?;
+                let n = self.decode_i64()?;
+                let n = i16::try_from(n).map_err(|_| DecodeError::IntegerOverflow)?;
+                wip.set(n)?;
             } else if shape.is_type::<i8>() {
-                let n = self.decode_u64()?;
-                if n > i8::MAX as u64 {
-                    return Err(DecodeError::IntegerOverflow);
-                }
-                wip.set(n as i8)?;
+                let n = self.decode_i64()?;
+                let n = i8::try_from(n).map_err(|_| DecodeError::IntegerOverflow)?;
+                wip.set(n)?;
             } else if shape.is_type::<f64>() {
-                // TODO: Implement proper f64 decoding from MessagePack format
-                return Err(DecodeError::NotImplemented(
-                    "f64 deserialization not yet implemented".to_string(),
-                ));
+                let n = self.decode_f64()?;
+                wip.set(n)?;
             } else if shape.is_type::<f32>() {
-                // TODO: Implement proper f32 decoding from MessagePack format
-                return Err(DecodeError::NotImplemented(
-                    "f32 deserialization not yet implemented".to_string(),
-                ));
+                let n = self.decode_f64()?;
+                wip.set(n as f32)?;
             } else if shape.is_type::<bool>() {
                 let b = self.decode_bool()?;
                 wip.set(b)?;
+            } else if self.peek_ext()? {
+                let (ext_type, data) = self.decode_ext()?;
+                if ext_type != MSGPACK_EXT_TIMESTAMP {
+                    return Err(DecodeError::UnexpectedExtType {
+                        expected: MSGPACK_EXT_TIMESTAMP,
+                        got: ext_type,
+                    });
+                }
+                let (secs, nanos) = timestamp::decode(data)?;
+                if !timestamp::set(wip, secs, nanos)? {
+                    return Err(DecodeError::UnsupportedType(format!("{}", shape)));
+                }
+            } else if self.peek_string()? {
+                // Scalars written in their string form, like date-times without the timestamp
+                // feature of their crate enabled
+                let s = self.decode_str()?;
+                wip.parse_from_str(s)?;
             } else {
                 return Err(DecodeError::UnsupportedType(format!("{}", shape)));
             }
@@ -680,7 +931,23 @@ impl<'input, 'shape> Decoder<'input> {
                 self.deserialize_value(wip)?;
                 wip.end()?;
             }
-        } else if let Def::List(_list_def) = shape.def {
+        } else if let Def::List(list_def) = shape.def {
+            if list_def.t().is_type::<u8>() && self.peek_bin()? {
+                let bytes = self.decode_bin()?;
+                if shape.is_type::<Cow<'_, [u8]>>() {
+                    trace!("Deserializing borrowed bytes");
+                    wip.set(Cow::Borrowed(bytes))?;
+                    return Ok(());
+                }
+
+                trace!("Deserializing byte list");
+                wip.begin_list()?;
+                for &byte in bytes {
+                    wip.push(byte)?;
+                }
+                return Ok(());
+            }
+
             trace!("Deserializing list");
             let array_len = self.decode_array_len()?;
             wip.begin_list()?;
@@ -708,10 +975,238 @@ impl<'input, 'shape> Decoder<'input> {
                 wip.end()?;
                 trace!("After end, wip shape: {}", wip.shape());
             }
+        } else if let Def::Result(_result_def) = shape.def {
+            // Represented like an enum variant, as a map with a single `Ok` or `Err` entry
+            trace!("Deserializing result with shape: {}", shape);
+            let map_len = self.decode_map_len()?;
+            if map_len != 1 {
+                return Err(DecodeError::InvalidData);
+            }
+
+            match self.decode_str()? {
+                "Ok" => wip.begin_ok()?,
+                "Err" => wip.begin_err()?,
+                variant_name => {
+                    return Err(DecodeError::InvalidEnum(format!(
+                        "Unknown variant: {}",
+                        variant_name
+                    )));
+                }
+            };
+            self.deserialize_value(wip)?;
+            wip.end()?;
         } else {
             return Err(DecodeError::UnsupportedShape(format!("{:?}", shape)));
         }
 
         Ok(())
     }
+
+    /// Deserializes a type marked with `#[facet(ext = ...)]` from an extension value, whose data
+    /// goes into the type's single field.
+    fn deserialize_ext<'facet>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        ext_type: i8,
+    ) -> Result<(), DecodeError<'shape>>
+    where
+        'input: 'facet,
+    {
+        let (got, data) = self.decode_ext()?;
+        if got != ext_type {
+            return Err(DecodeError::UnexpectedExtType {
+                expected: ext_type,
+                got,
+            });
+        }
+
+        wip.begin_nth_field(0)?;
+        let shape = wip.shape();
+        if shape.is_type::<Vec<u8>>() {
+            wip.set(data.to_vec())?;
+        } else if shape.is_type::<&[u8]>() {
+            wip.set(data)?;
+        } else {
+            return Err(DecodeError::UnsupportedType(format!(
+                "{} as the data of an extension type",
+                shape
+            )));
+        }
+        wip.end()?;
+
+        Ok(())
+    }
+
+    /// Deserializes an internally or adjacently tagged enum, represented as a map
+    /// containing the variant name under the `tag` key.
+    fn deserialize_tagged_enum<'facet>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        enum_type: &EnumType<'shape>,
+        tag: &'shape str,
+    ) -> Result<(), DecodeError<'shape>>
+    where
+        'input: 'facet,
+    {
+        let content = wip.shape().get_content_attr();
+        let start = self.offset;
+
+        // Find the variant name first, it may come after the variant's data
+        let map_len = self.decode_map_len()?;
+        let mut variant_name = None;
+        for _ in 0..map_len {
+            let key = self.decode_str()?;
+            if key == tag {
+                variant_name = Some(self.decode_str()?);
+            } else {
+                self.skip_value()?;
+            }
+        }
+        let end = self.offset;
+
+        let variant_name =
+            variant_name.ok_or_else(|| DecodeError::MissingField(tag.to_string()))?;
+        let (idx, variant) = enum_type
+            .variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| variant.name == variant_name)
+            .ok_or_else(|| {
+                DecodeError::InvalidEnum(format!("Unknown variant: {}", variant_name))
+            })?;
+        trace!("Selected variant {} from tag {}", variant.name, tag);
+        wip.select_nth_variant(idx)?;
+
+        if variant.data.kind == StructKind::Unit {
+            return Ok(());
+        }
+
+        // Then go over the map again to deserialize the variant's data
+        self.offset = start;
+        match content {
+            Some(content) => {
+                let map_len = self.decode_map_len()?;
+                let mut found = false;
+                for _ in 0..map_len {
+                    let key = self.decode_str()?;
+                    if key == content {
+                        self.deserialize_variant_data(wip, variant)?;
+                        found = true;
+                    } else {
+                        self.skip_value()?;
+                    }
+                }
+                if !found {
+                    return Err(DecodeError::MissingField(content.to_string()));
+                }
+            }
+            None => {
+                // The tag is skipped like any unknown field
+                self.deserialize_variant_data(wip, variant)?;
+            }
+        }
+        self.offset = end;
+
+        Ok(())
+    }
+
+    /// Deserializes an untagged enum by trying each variant in turn, until one matches.
+    fn deserialize_untagged_enum<'facet>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        enum_type: &EnumType<'shape>,
+    ) -> Result<(), DecodeError<'shape>>
+    where
+        'input: 'facet,
+    {
+        let start = self.offset;
+
+        for (idx, variant) in enum_type.variants.iter().enumerate() {
+            trace!("Trying variant {}", variant.name);
+            self.offset = start;
+
+            // Unit variants are represented as nil
+            if variant.data.kind == StructKind::Unit {
+                if self.peek_nil()? {
+                    self.decode_nil()?;
+                    wip.select_nth_variant(idx)?;
+                    return Ok(());
+                }
+                continue;
+            }
+
+            let mut attempt = Partial::alloc_shape(wip.shape())?;
+            attempt.select_nth_variant(idx)?;
+            if self
+                .deserialize_variant_data(&mut attempt, variant)
+                .is_err()
+            {
+                continue;
+            }
+            if let Ok(value) = attempt.build() {
+                wip.set_from_heap_value(value)?;
+                return Ok(());
+            }
+        }
+
+        Err(DecodeError::InvalidEnum(format!(
+            "Data did not match any variant of untagged enum {}",
+            wip.shape()
+        )))
+    }
+
+    /// Deserializes the data of the selected variant, without any variant name around it.
+    fn deserialize_variant_data<'facet>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        variant: &Variant<'shape>,
+    ) -> Result<(), DecodeError<'shape>>
+    where
+        'input: 'facet,
+    {
+        match variant.data.kind {
+            StructKind::Unit => Ok(()),
+            // Newtype variants contain the inner value directly
+            StructKind::Tuple | StructKind::TupleStruct if variant.data.fields.len() == 1 => {
+                wip.begin_nth_field(0)?;
+                self.deserialize_value(wip)?;
+                wip.end()?;
+                Ok(())
+            }
+            StructKind::Tuple | StructKind::TupleStruct => {
+                let array_len = self.decode_array_len()?;
+                if array_len != variant.data.fields.len() {
+                    return Err(DecodeError::InvalidData);
+                }
+                for field_idx in 0..array_len {
+                    wip.begin_nth_field(field_idx)?;
+                    self.deserialize_value(wip)?;
+                    wip.end()?;
+                }
+                Ok(())
+            }
+            StructKind::Struct => {
+                let map_len = self.decode_map_len()?;
+                for _ in 0..map_len {
+                    let field_name = self.decode_str()?;
+                    match wip.field_index(field_name) {
+                        Some(field_idx) => {
+                            wip.begin_nth_field(field_idx)?;
+                            self.deserialize_value(wip)?;
+                            wip.end()?;
+                        }
+                        None => {
+                            self.skip_value()?;
+                            trace!("Skipping unknown field in enum: {}", field_name);
+                        }
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(DecodeError::UnsupportedType(format!(
+                "Unsupported enum variant kind: {:?}",
+                variant.data.kind
+            ))),
+        }
+    }
 }
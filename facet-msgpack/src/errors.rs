@@ -28,6 +28,13 @@ pub enum Error<'shape> {
     ReflectError(ReflectError<'shape>),
     /// Invalid enum variant
     InvalidEnum(String),
+    /// Encountered an extension type other than the expected one
+    UnexpectedExtType {
+        /// Extension type the target type is read from
+        expected: i8,
+        /// Extension type found in the input
+        got: i8,
+    },
 }
 
 impl<'shape> From<ReflectError<'shape>> for Error<'shape> {
@@ -60,6 +67,9 @@ impl fmt::Display for Error<'_> {
             Error::InvalidEnum(message) => {
                 write!(f, "Invalid enum variant: {}", message)
             }
+            Error::UnexpectedExtType { expected, got } => {
+                write!(f, "Expected extension type {}, got {}", expected, got)
+            }
         }
     }
 }
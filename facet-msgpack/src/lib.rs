@@ -13,3 +13,5 @@ pub use deserialize::*;
 
 mod serialize;
 pub use serialize::*;
+
+mod timestamp;
@@ -1,15 +1,63 @@
-use facet_core::Facet;
-use facet_reflect::Peek;
+use crate::constants::MSGPACK_EXT_TIMESTAMP;
+use crate::timestamp;
+
+use facet_core::{Def, Facet, FieldFlags, Shape, ShapeAttribute, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek};
 use facet_serialize::{Serializer, serialize_iterative}; // Import the necessary items from facet-serialize
 use log::trace;
 use std::io::{self, Write};
 
+/// Settings for writing MessagePack
+///
+/// ```
+/// use facet::Facet;
+/// use facet_msgpack::{SerializeOptions, to_vec_with_options};
+///
+/// #[derive(Facet)]
+/// struct Point {
+///     x: u8,
+///     y: u8,
+/// }
+///
+/// let options = SerializeOptions::default().struct_as_array(true);
+/// let bytes = to_vec_with_options(&Point { x: 1, y: 2 }, options);
+/// assert_eq!(bytes, [0x92, 0x01, 0x02]);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SerializeOptions {
+    struct_as_array: bool,
+}
+
+impl SerializeOptions {
+    /// Sets whether all structs are written as arrays of their fields in order, instead of
+    /// maps keyed by field name, `false` by default
+    ///
+    /// This is like rmp-serde's compact mode. Structs marked with `#[facet(as_array)]` are
+    /// written as arrays either way. Fields that would be skipped are written as nil, to keep
+    /// the position of the fields after them.
+    pub fn struct_as_array(mut self, struct_as_array: bool) -> Self {
+        self.struct_as_array = struct_as_array;
+        self
+    }
+
+    /// Whether all structs are written as arrays of their fields
+    pub fn get_struct_as_array(&self) -> bool {
+        self.struct_as_array
+    }
+}
+
 /// Serializes any Facet type to MessagePack bytes
 pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> Vec<u8> {
+    to_vec_with_options(value, SerializeOptions::default())
+}
+
+/// Serializes any Facet type to MessagePack bytes, with the given options
+pub fn to_vec_with_options<'a, T: Facet<'a>>(value: &'a T, options: SerializeOptions) -> Vec<u8> {
     let mut buffer = Vec::new();
     let peek = Peek::new(value);
     let mut serializer = MessagePackSerializer {
         writer: &mut buffer,
+        options,
     }; // Create the serializer
     serialize_iterative(peek, &mut serializer).unwrap(); // Use the iterative serializer
     buffer
@@ -18,6 +66,7 @@ pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> Vec<u8> {
 // Define the MessagePackSerializer struct
 struct MessagePackSerializer<'w, W: Write> {
     writer: &'w mut W,
+    options: SerializeOptions,
 }
 
 // Implement the Serializer trait for MessagePackSerializer
@@ -199,6 +248,10 @@ impl<'shape, W: Write> Serializer<'shape> for MessagePackSerializer<'_, W> {
         }
     }
 
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error {
+        io::Error::other(format!("cannot serialize {shape}: {reason}"))
+    }
+
     fn end_map(&mut self) -> Result<(), Self::Error> {
         trace!("Ending map");
         // No explicit end marker needed for fixed-length maps in MessagePack
@@ -210,6 +263,73 @@ impl<'shape, W: Write> Serializer<'shape> for MessagePackSerializer<'_, W> {
         trace!("Serializing field name: {}", name);
         write_str(self.writer, name)
     }
+
+    // Extension types and date-times are written as extension values
+    fn serialize_custom(&mut self, value: Peek<'_, '_, 'shape>) -> Result<bool, Self::Error> {
+        if let Some(ext_type) = value.shape().get_ext_attr() {
+            trace!("Serializing extension type {}: {}", ext_type, value.shape());
+            let field = value
+                .into_struct()
+                .ok()
+                .filter(|value| value.field_count() == 1)
+                .and_then(|value| value.field(0).ok())
+                .ok_or_else(|| {
+                    io::Error::other(format!(
+                        "extension type {} must have a single field",
+                        value.shape()
+                    ))
+                })?;
+            let data: &[u8] = if let Ok(data) = field.get::<Vec<u8>>() {
+                data
+            } else if let Ok(data) = field.get::<&[u8]>() {
+                data
+            } else {
+                return Err(io::Error::other(format!(
+                    "{} can't be the data of an extension type",
+                    field.shape()
+                )));
+            };
+            write_ext(self.writer, ext_type, data)?;
+            return Ok(true);
+        }
+
+        let shape = value.shape();
+        if let (Def::Undefined, Type::User(UserType::Struct(struct_type))) = (shape.def, shape.ty) {
+            if struct_type.kind == StructKind::Struct
+                && (self.options.struct_as_array || shape.has_as_array_attr())
+                && !shape.attributes.contains(&ShapeAttribute::Transparent)
+            {
+                trace!("Serializing struct as array: {}", shape);
+                let peek_struct = value.into_struct().unwrap();
+                let written: Vec<&str> = peek_struct
+                    .fields_for_serialize()
+                    .map(|(field, _)| field.name)
+                    .collect();
+                write_array_len(self.writer, peek_struct.field_count())?;
+                for (field, field_value) in peek_struct.fields() {
+                    if field.flags.contains(FieldFlags::FLATTEN) || written.contains(&field.name) {
+                        serialize_iterative(field_value, self)?;
+                    } else {
+                        // Keep the position of the fields after it
+                        write_nil(self.writer)?;
+                    }
+                }
+                return Ok(true);
+            }
+        }
+
+        if let Some((secs, nanos)) = timestamp::of(value) {
+            trace!("Serializing timestamp: {}s {}ns", secs, nanos);
+            write_ext(
+                self.writer,
+                MSGPACK_EXT_TIMESTAMP,
+                &timestamp::encode(secs, nanos),
+            )?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
 }
 
 fn write_nil<W: Write>(writer: &mut W) -> io::Result<()> {
@@ -255,6 +375,38 @@ fn write_bin<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
     writer.write_all(bytes)
 }
 
+fn write_ext<W: Write>(writer: &mut W, ext_type: i8, data: &[u8]) -> io::Result<()> {
+    let len = data.len();
+    match len {
+        1 => writer.write_all(&[0xd4])?,  // fixext 1
+        2 => writer.write_all(&[0xd5])?,  // fixext 2
+        4 => writer.write_all(&[0xd6])?,  // fixext 4
+        8 => writer.write_all(&[0xd7])?,  // fixext 8
+        16 => writer.write_all(&[0xd8])?, // fixext 16
+        0..=255 => {
+            // ext 8
+            writer.write_all(&[0xc7, len as u8])?;
+        }
+        256..=65535 => {
+            // ext 16
+            writer.write_all(&[0xc8])?;
+            writer.write_all(&(len as u16).to_be_bytes())?;
+        }
+        _ => {
+            // ext 32
+            let len = u32::try_from(len).map_err(|_| {
+                io::Error::other(format!(
+                    "extension data of {len} bytes is longer than MessagePack allows"
+                ))
+            })?;
+            writer.write_all(&[0xc9])?;
+            writer.write_all(&len.to_be_bytes())?;
+        }
+    }
+    writer.write_all(&[ext_type as u8])?;
+    writer.write_all(data)
+}
+
 fn write_array_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
     match len {
         0..=15 => {
@@ -0,0 +1,138 @@
+//! The timestamp extension type, and the date-time types read from and written as it.
+//!
+//! Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#timestamp-extension-type>
+
+use crate::errors::Error as DecodeError;
+
+use facet_reflect::{Partial, Peek};
+
+/// Encodes seconds and nanoseconds since the Unix epoch as the data of a timestamp, picking
+/// the smallest of the timestamp 32, 64 and 96 formats that fits.
+pub(crate) fn encode(secs: i64, nanos: u32) -> Vec<u8> {
+    if secs >> 34 == 0 {
+        let data = ((nanos as u64) << 34) | secs as u64;
+        if data >> 32 == 0 {
+            // timestamp 32: seconds only
+            (data as u32).to_be_bytes().to_vec()
+        } else {
+            // timestamp 64: 30 bits of nanoseconds, 34 bits of seconds
+            data.to_be_bytes().to_vec()
+        }
+    } else {
+        // timestamp 96: 32 bits of nanoseconds, 64 bits of signed seconds
+        let mut data = Vec::with_capacity(12);
+        data.extend_from_slice(&nanos.to_be_bytes());
+        data.extend_from_slice(&secs.to_be_bytes());
+        data
+    }
+}
+
+/// Decodes the data of a timestamp into seconds and nanoseconds since the Unix epoch.
+pub(crate) fn decode(data: &[u8]) -> Result<(i64, u32), DecodeError<'static>> {
+    let (secs, nanos) = match data.len() {
+        4 => (u32::from_be_bytes(data.try_into().unwrap()) as i64, 0),
+        8 => {
+            let data = u64::from_be_bytes(data.try_into().unwrap());
+            ((data & 0x3_ffff_ffff) as i64, (data >> 34) as u32)
+        }
+        12 => (
+            i64::from_be_bytes(data[4..].try_into().unwrap()),
+            u32::from_be_bytes(data[..4].try_into().unwrap()),
+        ),
+        _ => return Err(DecodeError::InvalidData),
+    };
+    if nanos >= 1_000_000_000 {
+        return Err(DecodeError::InvalidData);
+    }
+    Ok((secs, nanos))
+}
+
+/// Returns the seconds and nanoseconds since the Unix epoch of `value`, if it's one of the
+/// date-time types written as timestamps.
+pub(crate) fn of(value: Peek<'_, '_, '_>) -> Option<(i64, u32)> {
+    #[cfg(feature = "time")]
+    {
+        if let Ok(value) = value.get::<time::OffsetDateTime>() {
+            return Some((value.unix_timestamp(), value.nanosecond()));
+        }
+        if let Ok(value) = value.get::<time::UtcDateTime>() {
+            return Some((value.unix_timestamp(), value.nanosecond()));
+        }
+    }
+    #[cfg(feature = "chrono")]
+    {
+        if let Ok(value) = value.get::<chrono::DateTime<chrono::Utc>>() {
+            return Some((value.timestamp(), value.timestamp_subsec_nanos()));
+        }
+        if let Ok(value) = value.get::<chrono::DateTime<chrono::FixedOffset>>() {
+            return Some((value.timestamp(), value.timestamp_subsec_nanos()));
+        }
+    }
+    #[cfg(feature = "jiff02")]
+    {
+        if let Ok(value) = value.get::<jiff::Timestamp>() {
+            let nanos = value.as_nanosecond();
+            return Some((
+                nanos.div_euclid(1_000_000_000) as i64,
+                nanos.rem_euclid(1_000_000_000) as u32,
+            ));
+        }
+    }
+    let _ = value;
+    None
+}
+
+/// Sets `wip` to the instant `secs` and `nanos` after the Unix epoch, if its shape is one of the
+/// date-time types read from timestamps. Returns whether it was.
+///
+/// Types with an offset are set in UTC, since timestamps don't keep the offset.
+pub(crate) fn set<'shape>(
+    wip: &mut Partial<'_, 'shape>,
+    secs: i64,
+    nanos: u32,
+) -> Result<bool, DecodeError<'shape>> {
+    let shape = wip.shape();
+    #[cfg(feature = "time")]
+    {
+        let unix_nanos = secs as i128 * 1_000_000_000 + nanos as i128;
+        if shape.is_type::<time::OffsetDateTime>() {
+            let value = time::OffsetDateTime::from_unix_timestamp_nanos(unix_nanos)
+                .map_err(|_| DecodeError::InvalidData)?;
+            wip.set(value)?;
+            return Ok(true);
+        }
+        if shape.is_type::<time::UtcDateTime>() {
+            let value = time::UtcDateTime::from_unix_timestamp_nanos(unix_nanos)
+                .map_err(|_| DecodeError::InvalidData)?;
+            wip.set(value)?;
+            return Ok(true);
+        }
+    }
+    #[cfg(feature = "chrono")]
+    {
+        if shape.is_type::<chrono::DateTime<chrono::Utc>>() {
+            let value = chrono::DateTime::<chrono::Utc>::from_timestamp(secs, nanos)
+                .ok_or(DecodeError::InvalidData)?;
+            wip.set(value)?;
+            return Ok(true);
+        }
+        if shape.is_type::<chrono::DateTime<chrono::FixedOffset>>() {
+            let value = chrono::DateTime::<chrono::Utc>::from_timestamp(secs, nanos)
+                .ok_or(DecodeError::InvalidData)?;
+            wip.set(value.fixed_offset())?;
+            return Ok(true);
+        }
+    }
+    #[cfg(feature = "jiff02")]
+    {
+        if shape.is_type::<jiff::Timestamp>() {
+            let unix_nanos = secs as i128 * 1_000_000_000 + nanos as i128;
+            let value = jiff::Timestamp::from_nanosecond(unix_nanos)
+                .map_err(|_| DecodeError::InvalidData)?;
+            wip.set(value)?;
+            return Ok(true);
+        }
+    }
+    let _ = (shape, secs, nanos);
+    Ok(false)
+}
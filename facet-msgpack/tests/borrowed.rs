@@ -0,0 +1,122 @@
+use std::borrow::Cow;
+
+use eyre::Result;
+use facet::Facet;
+use facet_msgpack::{DecodeError, from_slice};
+
+#[test]
+fn msgpack_deserialize_borrowed_fields() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Message<'a> {
+        name: &'a str,
+        note: Cow<'a, str>,
+        payload: &'a [u8],
+    }
+
+    let data = [
+        0x83, // Fixmap with 3 elements
+        0xa4, // Fixstr with length 4
+        0x6e, 0x61, 0x6d, 0x65, // "name"
+        0xa5, // Fixstr with length 5
+        0x41, 0x6c, 0x69, 0x63, 0x65, // "Alice"
+        0xa4, // Fixstr with length 4
+        0x6e, 0x6f, 0x74, 0x65, // "note"
+        0xa2, // Fixstr with length 2
+        0x68, 0x69, // "hi"
+        0xa7, // Fixstr with length 7
+        0x70, 0x61, 0x79, 0x6c, 0x6f, 0x61, 0x64, // "payload"
+        0xc4, // bin8
+        0x03, // length 3
+        0x01, 0x02, 0x03,
+    ];
+
+    let message: Message = from_slice(&data)?;
+    assert_eq!(
+        message,
+        Message {
+            name: "Alice",
+            note: Cow::Borrowed("hi"),
+            payload: &[1, 2, 3],
+        }
+    );
+
+    // Nothing was copied out of the input
+    let input = data.as_ptr_range();
+    assert!(input.contains(&message.name.as_ptr()));
+    assert!(matches!(message.note, Cow::Borrowed(note) if input.contains(&note.as_ptr())));
+    assert!(input.contains(&message.payload.as_ptr()));
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_deserialize_borrowed_cow_bytes() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0xc4, // bin8
+        0x03, // length 3
+        0x01, 0x02, 0x03,
+    ];
+
+    let bytes: Cow<[u8]> = from_slice(&data)?;
+    assert_eq!(*bytes, [1, 2, 3]);
+    let input = data.as_ptr_range();
+    assert!(matches!(bytes, Cow::Borrowed(bytes) if input.contains(&bytes.as_ptr())));
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_deserialize_borrowed_strings_in_containers() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x93, // Array with 3 elements
+        0xa3, 0x66, 0x6f, 0x6f, // "foo"
+        0xc0, // nil
+        0xa3, 0x62, 0x61, 0x72, // "bar"
+    ];
+
+    let v: Vec<Option<&str>> = from_slice(&data)?;
+    assert_eq!(v, vec![Some("foo"), None, Some("bar")]);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_deserialize_borrowed_invalid_utf8() {
+    facet_testhelpers::setup();
+
+    let data = [
+        0xa2, // Fixstr with length 2
+        0xff, 0xfe, // not UTF-8
+    ];
+
+    assert!(from_slice::<&str>(&data).is_err());
+}
+
+#[test]
+fn msgpack_deserialize_error_outlives_input() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet)]
+    struct Message<'a> {
+        name: &'a str,
+    }
+
+    let err = {
+        let data = vec![
+            0x81, // Fixmap with 1 element
+            0xa4, 0x6e, 0x61, 0x6d, 0x65, // "name"
+            0x2a, // 42, not a string
+        ];
+        from_slice::<Message>(&data).unwrap_err()
+    };
+
+    // The input buffer is gone, but the error is still around to be reported
+    assert!(matches!(err, DecodeError::UnexpectedType));
+    assert!(!err.to_string().is_empty());
+}
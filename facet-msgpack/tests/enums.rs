@@ -107,3 +107,145 @@ fn msgpack_deserialize_struct_variant() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn msgpack_roundtrip_tagged_enums() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(tag = "type")]
+    #[repr(u8)]
+    enum Internal {
+        Empty,
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(tag = "t", content = "c")]
+    #[repr(u8)]
+    enum Adjacent {
+        Ping,
+        Text(String),
+        Pair(u32, bool),
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    enum Untagged {
+        Nothing,
+        Number(u64),
+        Text(String),
+    }
+
+    // { "type": "Rect", "width": 1, "height": 2 }
+    let data = [
+        0x83, // Map with 3 elements
+        0xa4, 0x74, 0x79, 0x70, 0x65, // "type"
+        0xa4, 0x52, 0x65, 0x63, 0x74, // "Rect"
+        0xa5, 0x77, 0x69, 0x64, 0x74, 0x68, // "width"
+        0x01, // 1
+        0xa6, 0x68, 0x65, 0x69, 0x67, 0x68, 0x74, // "height"
+        0x02, // 2
+    ];
+    let rect = Internal::Rect {
+        width: 1,
+        height: 2,
+    };
+    assert_eq!(facet_msgpack::to_vec(&rect), data);
+    assert_eq!(from_slice::<Internal>(&data)?, rect);
+
+    let empty = Internal::Empty;
+    assert_eq!(
+        from_slice::<Internal>(&facet_msgpack::to_vec(&empty))?,
+        empty
+    );
+
+    for value in [
+        Adjacent::Ping,
+        Adjacent::Text("hi".to_string()),
+        Adjacent::Pair(3, true),
+    ] {
+        assert_eq!(
+            from_slice::<Adjacent>(&facet_msgpack::to_vec(&value))?,
+            value
+        );
+    }
+
+    for value in [
+        Untagged::Nothing,
+        Untagged::Number(42),
+        Untagged::Text("hello".to_string()),
+    ] {
+        assert_eq!(
+            from_slice::<Untagged>(&facet_msgpack::to_vec(&value))?,
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_serialize_externally_tagged_enum() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Pair(u32, u32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Root {
+        a: Shape,
+        b: Shape,
+    }
+
+    // { "a": { "Circle": 2 }, "b": { "Rect": { "width": 1, "height": 2 } } }
+    let root = Root {
+        a: Shape::Circle(2),
+        b: Shape::Rect {
+            width: 1,
+            height: 2,
+        },
+    };
+    let data = [
+        0x82, // Map with 2 elements
+        0xa1, 0x61, // "a"
+        0x81, // Map with 1 element
+        0xa6, 0x43, 0x69, 0x72, 0x63, 0x6c, 0x65, // "Circle"
+        0x02, // 2
+        0xa1, 0x62, // "b"
+        0x81, // Map with 1 element
+        0xa4, 0x52, 0x65, 0x63, 0x74, // "Rect"
+        0x82, // Map with 2 elements
+        0xa5, 0x77, 0x69, 0x64, 0x74, 0x68, // "width"
+        0x01, // 1
+        0xa6, 0x68, 0x65, 0x69, 0x67, 0x68, 0x74, // "height"
+        0x02, // 2
+    ];
+    assert_eq!(facet_msgpack::to_vec(&root), data);
+
+    // { "a": "Empty", "b": { "Pair": [1, 2] } }
+    let root = Root {
+        a: Shape::Empty,
+        b: Shape::Pair(1, 2),
+    };
+    let data = [
+        0x82, // Map with 2 elements
+        0xa1, 0x61, // "a"
+        0xa5, 0x45, 0x6d, 0x70, 0x74, 0x79, // "Empty"
+        0xa1, 0x62, // "b"
+        0x81, // Map with 1 element
+        0xa4, 0x50, 0x61, 0x69, 0x72, // "Pair"
+        0x92, // Array with 2 elements
+        0x01, 0x02, // 1, 2
+    ];
+    assert_eq!(facet_msgpack::to_vec(&root), data);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use eyre::Result;
+use facet::Facet;
+use facet_msgpack::{DecodeError, from_slice, to_vec};
+
+#[derive(Debug, PartialEq, Facet)]
+#[facet(ext = 5)]
+struct Color(Vec<u8>);
+
+#[derive(Debug, PartialEq, Facet)]
+struct Pixel {
+    color: Color,
+}
+
+#[test]
+fn msgpack_write_ext_type() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let pixel = Pixel {
+        color: Color(vec![0xff, 0x80, 0x00, 0x40]),
+    };
+
+    let data = to_vec(&pixel);
+    assert_eq!(
+        data,
+        [
+            0x81, // Fixmap with 1 element
+            0xa5, // Fixstr with length 5
+            0x63, 0x6f, 0x6c, 0x6f, 0x72, // "color"
+            0xd6, // fixext 4
+            0x05, // extension type 5
+            0xff, 0x80, 0x00, 0x40,
+        ]
+    );
+    assert_eq!(from_slice::<Pixel>(&data)?, pixel);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_ext_type_sizes() -> Result<()> {
+    facet_testhelpers::setup();
+
+    for len in [0, 1, 2, 3, 4, 8, 16, 17, 255, 256, 70000] {
+        let color = Color(vec![0x2a; len]);
+        let data = to_vec(&color);
+        assert_eq!(from_slice::<Color>(&data)?, color, "{len} bytes");
+    }
+
+    // ext 8 with 3 bytes
+    assert_eq!(&to_vec(&Color(vec![1, 2, 3]))[..3], [0xc7, 0x03, 0x05]);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_read_borrowed_ext_type() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[facet(ext = 42)]
+    struct Raw<'a>(&'a [u8]);
+
+    let data = [
+        0xd5, // fixext 2
+        0x2a, // extension type 42
+        0x01, 0x02,
+    ];
+
+    let raw: Raw = from_slice(&data)?;
+    assert_eq!(raw, Raw(&[1, 2]));
+    assert_eq!(to_vec(&raw), data);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_read_wrong_ext_type() {
+    facet_testhelpers::setup();
+
+    let data = [
+        0xd4, // fixext 1
+        0x06, // extension type 6
+        0x01,
+    ];
+
+    let err = from_slice::<Color>(&data).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::UnexpectedExtType {
+            expected: 5,
+            got: 6
+        }
+    ));
+}
+
+#[test]
+fn msgpack_skip_unknown_ext_field() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Named {
+        name: String,
+    }
+
+    let data = [
+        0x82, // Fixmap with 2 elements
+        0xa2, // Fixstr with length 2
+        0x61, 0x74, // "at"
+        0xd6, // fixext 4
+        0xff, // timestamp
+        0x63, 0xc3, 0xf2, 0xf0, // 2023-01-15T12:34:56Z
+        0xa4, // Fixstr with length 4
+        0x6e, 0x61, 0x6d, 0x65, // "name"
+        0xa2, // Fixstr with length 2
+        0x6f, 0x6b, // "ok"
+    ];
+
+    let named: Named = from_slice(&data)?;
+    assert_eq!(
+        named,
+        Named {
+            name: "ok".to_string()
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use eyre::Result;
+use facet::Facet;
+use facet_msgpack::{from_slice, to_vec};
+
+#[test]
+fn msgpack_deserialize_result() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x81, // Map with 1 element
+        0xa2, 0x4f, 0x6b, // "Ok"
+        0x2a, // 42
+    ];
+    let ok: core::result::Result<u32, String> = from_slice(&data)?;
+    assert_eq!(ok, Ok(42));
+
+    let data = [
+        0x81, // Map with 1 element
+        0xa3, 0x45, 0x72, 0x72, // "Err"
+        0xa4, 0x6e, 0x6f, 0x70, 0x65, // "nope"
+    ];
+    let err: core::result::Result<u32, String> = from_slice(&data)?;
+    assert_eq!(err, Err("nope".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_deserialize_result_unknown_variant() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x81, // Map with 1 element
+        0xa4, 0x46, 0x69, 0x6e, 0x65, // "Fine"
+        0x2a, // 42
+    ];
+    assert!(from_slice::<core::result::Result<u32, String>>(&data).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_roundtrip_result_in_struct() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Job {
+        name: String,
+        outcome: core::result::Result<Vec<u32>, Option<String>>,
+        history: Vec<core::result::Result<u8, bool>>,
+    }
+
+    let job = Job {
+        name: "build".to_string(),
+        outcome: Err(Some("timed out".to_string())),
+        history: vec![Ok(1), Err(false)],
+    };
+    assert_eq!(from_slice::<Job>(&to_vec(&job))?, job);
+
+    let job = Job {
+        outcome: Ok(vec![1, 2]),
+        ..job
+    };
+    assert_eq!(from_slice::<Job>(&to_vec(&job))?, job);
+
+    Ok(())
+}
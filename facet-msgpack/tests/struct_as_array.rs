@@ -0,0 +1,175 @@
+use eyre::Result;
+use facet::Facet;
+use facet_msgpack::{SerializeOptions, from_slice, to_vec, to_vec_with_options};
+
+#[derive(Debug, PartialEq, Facet)]
+struct User {
+    id: u32,
+    name: String,
+    address: Address,
+}
+
+#[derive(Debug, PartialEq, Facet)]
+struct Address {
+    city: String,
+}
+
+#[test]
+fn msgpack_write_struct_as_array_option() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let user = User {
+        id: 1,
+        name: "abc".to_string(),
+        address: Address {
+            city: "x".to_string(),
+        },
+    };
+
+    let data = to_vec_with_options(&user, SerializeOptions::default().struct_as_array(true));
+    assert_eq!(
+        data,
+        [
+            0x93, // Array with 3 elements
+            0x01, // 1
+            0xa3, 0x61, 0x62, 0x63, // "abc"
+            0x91, // Array with 1 element
+            0xa1, 0x78, // "x"
+        ]
+    );
+    assert_eq!(from_slice::<User>(&data)?, user);
+
+    // The default is still a map, and either form can be read
+    let data = to_vec(&user);
+    assert_eq!(data[0], 0x83);
+    assert_eq!(from_slice::<User>(&data)?, user);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_write_struct_as_array_attribute() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[facet(as_array)]
+    struct Point {
+        x: i8,
+        y: i8,
+    }
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Line {
+        from: Point,
+        to: Point,
+    }
+
+    let line = Line {
+        from: Point { x: 0, y: -1 },
+        to: Point { x: 2, y: 3 },
+    };
+
+    let data = to_vec(&line);
+    assert_eq!(
+        data,
+        [
+            0x82, // Fixmap with 2 elements
+            0xa4, 0x66, 0x72, 0x6f, 0x6d, // "from"
+            0x92, 0x00, 0xff, // [0, -1]
+            0xa2, 0x74, 0x6f, // "to"
+            0x92, 0x02, 0x03, // [2, 3]
+        ]
+    );
+    assert_eq!(from_slice::<Line>(&data)?, line);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_read_struct_as_array_schema_evolution() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Settings {
+        volume: u8,
+        #[facet(default)]
+        muted: bool,
+        #[facet(default)]
+        theme: Option<String>,
+    }
+
+    // An older client that doesn't know about the trailing fields
+    let data = [
+        0x91, // Array with 1 element
+        0x32, // 50
+    ];
+    assert_eq!(
+        from_slice::<Settings>(&data)?,
+        Settings {
+            volume: 50,
+            muted: false,
+            theme: None,
+        }
+    );
+
+    // A newer client with fields we don't know about yet
+    let data = [
+        0x95, // Array with 5 elements
+        0x32, // 50
+        0xc3, // true
+        0xa4, 0x64, 0x61, 0x72, 0x6b, // "dark"
+        0xa3, 0x6e, 0x65, 0x77, // "new"
+        0x93, 0x01, 0x02, 0x03, // [1, 2, 3]
+    ];
+    assert_eq!(
+        from_slice::<Settings>(&data)?,
+        Settings {
+            volume: 50,
+            muted: true,
+            theme: Some("dark".to_string()),
+        }
+    );
+
+    // Missing fields without a default are still an error
+    let data = [
+        0x90, // Array with 0 elements
+    ];
+    assert!(from_slice::<Settings>(&data).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_struct_as_array_keeps_positions_of_skipped_fields() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[facet(as_array)]
+    struct Profile {
+        #[facet(default, skip_serializing_if = String::is_empty)]
+        nickname: String,
+        #[facet(skip_serializing_if = Option::is_none)]
+        bio: Option<String>,
+        age: u8,
+    }
+
+    let profile = Profile {
+        nickname: String::new(),
+        bio: None,
+        age: 30,
+    };
+
+    let data = to_vec(&profile);
+    assert_eq!(
+        data,
+        [
+            0x93, // Array with 3 elements
+            0xc0, // nil
+            0xc0, // nil
+            0x1e, // 30
+        ]
+    );
+    assert_eq!(from_slice::<Profile>(&data)?, profile);
+
+    Ok(())
+}
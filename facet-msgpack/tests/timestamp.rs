@@ -0,0 +1,138 @@
+#![cfg(any(feature = "time", feature = "chrono", feature = "jiff02"))]
+
+use eyre::Result;
+use facet_msgpack::{from_slice, to_vec};
+
+// 2023-01-15T12:34:56Z as timestamp 32
+const TIMESTAMP_32: [u8; 6] = [
+    0xd6, // fixext 4
+    0xff, // timestamp
+    0x63, 0xc3, 0xf2, 0xf0, // seconds
+];
+
+// 2023-01-15T12:34:56.5Z as timestamp 64
+const TIMESTAMP_64: [u8; 10] = [
+    0xd7, // fixext 8
+    0xff, // timestamp
+    0x77, 0x35, 0x94, 0x00, 0x63, 0xc3, 0xf2, 0xf0, // nanoseconds and seconds
+];
+
+// 1969-07-20T20:17:40Z as timestamp 96
+const TIMESTAMP_96: [u8; 15] = [
+    0xc7, // ext 8
+    0x0c, // length 12
+    0xff, // timestamp
+    0x00, 0x00, 0x00, 0x00, // nanoseconds
+    0xff, 0xff, 0xff, 0xff, 0xff, 0x27, 0x95, 0xe4, // seconds
+];
+
+#[test]
+#[cfg(feature = "time")]
+fn msgpack_timestamp_time() -> Result<()> {
+    use facet::Facet;
+    use time::OffsetDateTime;
+    use time::macros::datetime;
+
+    facet_testhelpers::setup();
+
+    let cases = [
+        (datetime!(2023-01-15 12:34:56 UTC), &TIMESTAMP_32[..]),
+        (datetime!(2023-01-15 12:34:56.5 UTC), &TIMESTAMP_64[..]),
+        (datetime!(1969-07-20 20:17:40 UTC), &TIMESTAMP_96[..]),
+    ];
+    for (value, data) in cases {
+        assert_eq!(to_vec(&value), data);
+        assert_eq!(from_slice::<OffsetDateTime>(data)?, value);
+    }
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Event {
+        at: Option<OffsetDateTime>,
+    }
+
+    let event = Event {
+        at: Some(datetime!(2023-01-15 12:34:56.5 UTC)),
+    };
+    assert_eq!(from_slice::<Event>(&to_vec(&event))?, event);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "time")]
+fn msgpack_timestamp_time_offset_becomes_utc() -> Result<()> {
+    use time::OffsetDateTime;
+    use time::macros::datetime;
+
+    facet_testhelpers::setup();
+
+    let value = datetime!(2023-01-15 14:34:56 +02:00);
+    assert_eq!(to_vec(&value), TIMESTAMP_32);
+
+    let read = from_slice::<OffsetDateTime>(&TIMESTAMP_32)?;
+    assert_eq!(read, value);
+    assert_eq!(read.offset(), time::UtcOffset::UTC);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn msgpack_timestamp_chrono() -> Result<()> {
+    use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+    facet_testhelpers::setup();
+
+    let value = Utc.with_ymd_and_hms(2023, 1, 15, 12, 34, 56).unwrap();
+    assert_eq!(to_vec(&value), TIMESTAMP_32);
+    assert_eq!(from_slice::<DateTime<Utc>>(&TIMESTAMP_32)?, value);
+
+    let value = value + chrono::Duration::milliseconds(500);
+    assert_eq!(to_vec(&value), TIMESTAMP_64);
+    assert_eq!(from_slice::<DateTime<Utc>>(&TIMESTAMP_64)?, value);
+
+    let value = Utc.with_ymd_and_hms(1969, 7, 20, 20, 17, 40).unwrap();
+    assert_eq!(to_vec(&value), TIMESTAMP_96);
+    assert_eq!(from_slice::<DateTime<FixedOffset>>(&TIMESTAMP_96)?, value);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "jiff02")]
+fn msgpack_timestamp_jiff() -> Result<()> {
+    use jiff::Timestamp;
+
+    facet_testhelpers::setup();
+
+    let cases = [
+        ("2023-01-15T12:34:56Z", &TIMESTAMP_32[..]),
+        ("2023-01-15T12:34:56.5Z", &TIMESTAMP_64[..]),
+        ("1969-07-20T20:17:40Z", &TIMESTAMP_96[..]),
+    ];
+    for (value, data) in cases {
+        let value: Timestamp = value.parse()?;
+        assert_eq!(to_vec(&value), data);
+        assert_eq!(from_slice::<Timestamp>(data)?, value);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_timestamp_invalid_nanoseconds() {
+    facet_testhelpers::setup();
+
+    let data = [
+        0xd7, // fixext 8
+        0xff, // timestamp
+        0xff, 0xff, 0xff, 0xfc, 0x00, 0x00, 0x00, 0x00, // 2^30 - 1 nanoseconds
+    ];
+
+    #[cfg(feature = "time")]
+    assert!(from_slice::<time::OffsetDateTime>(&data).is_err());
+    #[cfg(feature = "chrono")]
+    assert!(from_slice::<chrono::DateTime<chrono::Utc>>(&data).is_err());
+    #[cfg(feature = "jiff02")]
+    assert!(from_slice::<jiff::Timestamp>(&data).is_err());
+}
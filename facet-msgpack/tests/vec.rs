@@ -31,6 +31,38 @@ fn msgpack_deserialize_vec() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn msgpack_deserialize_bin_as_byte_vec() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0xc4, 0x03, // bin8 with 3 bytes
+        0x00, 0x7f, 0xff,
+    ];
+
+    let v: Vec<u8> = from_slice(&data)?;
+    assert_eq!(v, vec![0x00, 0x7f, 0xff]);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_deserialize_vec_of_negative_ints() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x93, // Array with 3 elements
+        0xff, // negative fixint (-1)
+        0xd0, 0x80, // signed 8-bit int (-128)
+        0x05, // positive fixint (5)
+    ];
+
+    let v: Vec<i32> = from_slice(&data)?;
+    assert_eq!(v, vec![-1, -128, 5]);
+
+    Ok(())
+}
+
 #[test]
 fn test_nested_arrays() -> Result<()> {
     facet_testhelpers::setup();
@@ -60,3 +92,29 @@ fn test_nested_arrays() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn msgpack_roundtrip_collections() -> Result<()> {
+    facet_testhelpers::setup();
+
+    use std::borrow::Cow;
+    use std::collections::{BinaryHeap, LinkedList, VecDeque};
+
+    let data = [
+        0x93, // Array with 3 elements
+        0x01, 0x02, 0x03, // 1, 2, 3
+    ];
+    let queue: VecDeque<u64> = from_slice(&data)?;
+    assert_eq!(queue, [1, 2, 3]);
+    let list: LinkedList<u64> = from_slice(&data)?;
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+    let heap: BinaryHeap<u64> = from_slice(&data)?;
+    assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+    let cow: Cow<'_, [u64]> = from_slice(&data)?;
+    assert_eq!(&*cow, [1, 2, 3]);
+
+    let queue = VecDeque::from([3u16, 1, 2]);
+    assert_eq!(facet_msgpack::to_vec(&queue), [0x93, 0x03, 0x01, 0x02]);
+
+    Ok(())
+}
@@ -91,6 +91,11 @@ impl PrettyPrinter {
         self
     }
 
+    /// Whether colors are enabled. They are by default, unless `NO_COLOR` is set.
+    pub fn colors_enabled(&self) -> bool {
+        self.use_colors
+    }
+
     /// Format a value to a string
     pub fn format<'a, T: Facet<'a>>(&self, value: &T) -> String {
         let value = Peek::new(value);
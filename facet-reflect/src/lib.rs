@@ -9,6 +9,9 @@ extern crate alloc;
 mod error;
 pub use error::*;
 
+mod ops;
+pub use ops::*;
+
 #[cfg(feature = "alloc")]
 mod partial;
 #[cfg(feature = "alloc")]
@@ -17,6 +20,9 @@ pub use partial::*;
 mod peek;
 pub use peek::*;
 
+mod poke;
+pub use poke::*;
+
 mod scalar;
 pub use scalar::*;
 
@@ -0,0 +1,439 @@
+//! Equality, hashing, cloning and defaults for values that are only known by their shape.
+//!
+//! The vtable of a shape has these operations when its type implements the matching trait.
+//! When it doesn't, they are synthesized by walking the structure of the value instead.
+
+use core::hash::Hasher;
+
+use facet_core::{Characteristic, Def, Shape, Type, UserType};
+
+use crate::{HasFields, Peek, ReflectError, VariantError};
+#[cfg(feature = "alloc")]
+use crate::{HeapValue, Partial};
+
+/// Checks whether two values are equal, like [`PartialEq`] would.
+///
+/// Uses the vtable of the shape when it has `partial_eq`, and otherwise compares options,
+/// results, lists, arrays, maps, smart pointers, struct fields and enum variants one by one.
+///
+/// Values of different shapes are never equal. Fails when the values contain something that
+/// can't be compared, like a scalar without `partial_eq`.
+pub fn reflect_eq<'facet, 'shape>(
+    a: Peek<'_, 'facet, 'shape>,
+    b: Peek<'_, 'facet, 'shape>,
+) -> Result<bool, ReflectError<'shape>> {
+    if a.shape() != b.shape() {
+        return Ok(false);
+    }
+    if let Some(eq) = a.partial_eq(&b) {
+        return Ok(eq);
+    }
+
+    let shape = a.shape();
+    match shape.def {
+        Def::Option(_) => match (a.into_option()?.value(), b.into_option()?.value()) {
+            (Some(a), Some(b)) => reflect_eq(a, b),
+            (None, None) => Ok(true),
+            _ => Ok(false),
+        },
+        Def::Result(_) => {
+            let ((a_is_ok, a), (b_is_ok, b)) = (result_inner(a)?, result_inner(b)?);
+            if a_is_ok != b_is_ok {
+                return Ok(false);
+            }
+            reflect_eq(a, b)
+        }
+        Def::Map(_) => {
+            let (a, b) = (a.into_map()?, b.into_map()?);
+            if a.len() != b.len() {
+                return Ok(false);
+            }
+            for (key, value) in a.iter() {
+                let Some(other) = b.get_peek(key) else {
+                    return Ok(false);
+                };
+                if !reflect_eq(value, other)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        Def::SmartPointer(_) => reflect_eq(borrow_inner(a)?, borrow_inner(b)?),
+        _ => {
+            if let (Ok(a), Ok(b)) = (a.into_list_like(), b.into_list_like()) {
+                if a.len() != b.len() {
+                    return Ok(false);
+                }
+                for (a, b) in a.iter().zip(b.iter()) {
+                    if !reflect_eq(a, b)? {
+                        return Ok(false);
+                    }
+                }
+                return Ok(true);
+            }
+
+            match shape.ty {
+                Type::User(UserType::Struct(_)) => {
+                    let (a, b) = (a.into_struct()?, b.into_struct()?);
+                    for ((_, a), (_, b)) in a.fields().zip(b.fields()) {
+                        if !reflect_eq(a, b)? {
+                            return Ok(false);
+                        }
+                    }
+                    Ok(true)
+                }
+                Type::User(UserType::Enum(_)) => {
+                    let (a, b) = (a.into_enum()?, b.into_enum()?);
+                    let a_index = a.variant_index().map_err(|e| variant_error(shape, e))?;
+                    let b_index = b.variant_index().map_err(|e| variant_error(shape, e))?;
+                    if a_index != b_index {
+                        return Ok(false);
+                    }
+                    for ((_, a), (_, b)) in a.fields().zip(b.fields()) {
+                        if !reflect_eq(a, b)? {
+                            return Ok(false);
+                        }
+                    }
+                    Ok(true)
+                }
+                _ => Err(ReflectError::MissingCharacteristic {
+                    shape,
+                    characteristic: Characteristic::PartialEq,
+                }),
+            }
+        }
+    }
+}
+
+/// Feeds a value into `hasher`, like [`Hash`](core::hash::Hash) would.
+///
+/// Uses the vtable of the shape when it has `hash`, and otherwise hashes options, results,
+/// lists, arrays, maps, smart pointers, struct fields and enum variants one by one. Values that are
+/// equal according to [`reflect_eq`] hash the same, whatever the order of their map entries.
+///
+/// Fails when the value contains something that can't be hashed, like a scalar without `hash`.
+pub fn reflect_hash<'shape, H: Hasher>(
+    value: Peek<'_, '_, 'shape>,
+    hasher: &mut H,
+) -> Result<(), ReflectError<'shape>> {
+    if value.hash(hasher).is_ok() {
+        return Ok(());
+    }
+
+    let shape = value.shape();
+    match shape.def {
+        Def::Option(_) => match value.into_option()?.value() {
+            Some(inner) => {
+                hasher.write_u8(1);
+                reflect_hash(inner, hasher)
+            }
+            None => {
+                hasher.write_u8(0);
+                Ok(())
+            }
+        },
+        Def::Result(_) => {
+            let (is_ok, inner) = result_inner(value)?;
+            hasher.write_u8(is_ok as u8);
+            reflect_hash(inner, hasher)
+        }
+        Def::Map(_) => {
+            // Entries come in no particular order, so their hashes are combined with a sum
+            let map = value.into_map()?;
+            let mut sum = 0u64;
+            for (key, value) in map.iter() {
+                let mut entry_hasher = FnvHasher::default();
+                reflect_hash(key, &mut entry_hasher)?;
+                reflect_hash(value, &mut entry_hasher)?;
+                sum = sum.wrapping_add(entry_hasher.finish());
+            }
+            hasher.write_usize(map.len());
+            hasher.write_u64(sum);
+            Ok(())
+        }
+        Def::SmartPointer(_) => reflect_hash(borrow_inner(value)?, hasher),
+        _ => {
+            if let Ok(list) = value.into_list_like() {
+                hasher.write_usize(list.len());
+                for item in list.iter() {
+                    reflect_hash(item, hasher)?;
+                }
+                return Ok(());
+            }
+
+            match shape.ty {
+                Type::User(UserType::Struct(_)) => {
+                    for (_, field) in value.into_struct()?.fields() {
+                        reflect_hash(field, hasher)?;
+                    }
+                    Ok(())
+                }
+                Type::User(UserType::Enum(_)) => {
+                    let value = value.into_enum()?;
+                    let variant_index = value
+                        .variant_index()
+                        .map_err(|error| variant_error(shape, error))?;
+                    hasher.write_usize(variant_index);
+                    for (_, field) in value.fields() {
+                        reflect_hash(field, hasher)?;
+                    }
+                    Ok(())
+                }
+                _ => Err(ReflectError::MissingCharacteristic {
+                    shape,
+                    characteristic: Characteristic::Hash,
+                }),
+            }
+        }
+    }
+}
+
+/// Makes a deep copy of a value, like [`Clone`] would.
+///
+/// Uses the vtable of the shape when it has `clone_into`, and otherwise rebuilds options,
+/// results, lists, arrays, maps, `Box` and `Arc`, struct fields and enum variants one by one.
+///
+/// Fails when the value contains something that can't be cloned, like a scalar without
+/// `clone_into`.
+#[cfg(feature = "alloc")]
+pub fn reflect_clone<'facet, 'shape>(
+    value: Peek<'_, 'facet, 'shape>,
+) -> Result<HeapValue<'facet, 'shape>, ReflectError<'shape>> {
+    let mut partial = Partial::alloc_shape(value.shape())?;
+    clone_into(&mut partial, value)?;
+    partial.build()
+}
+
+#[cfg(feature = "alloc")]
+fn clone_into<'shape>(
+    partial: &mut Partial<'_, 'shape>,
+    value: Peek<'_, '_, 'shape>,
+) -> Result<(), ReflectError<'shape>> {
+    let shape = value.shape();
+    if let Some(clone_fn) = shape.vtable.sized().and_then(|v| (v.clone_into)()) {
+        let src = value.data().thin().ok_or(ReflectError::Unsized { shape })?;
+        partial.set_from_function(move |dst| {
+            // SAFETY: `src` points to a value of the shape the vtable belongs to, and `dst` has
+            // its layout
+            unsafe { clone_fn(src, dst) };
+            Ok(())
+        })?;
+        return Ok(());
+    }
+
+    match shape.def {
+        Def::Option(_) => match value.into_option()?.value() {
+            Some(inner) => {
+                partial.begin_some()?;
+                clone_into(partial, inner)?;
+                partial.end()?;
+            }
+            None => {
+                partial.set_default()?;
+            }
+        },
+        Def::Result(_) => {
+            let (is_ok, inner) = result_inner(value)?;
+            if is_ok {
+                partial.begin_ok()?;
+            } else {
+                partial.begin_err()?;
+            }
+            clone_into(partial, inner)?;
+            partial.end()?;
+        }
+        Def::List(_) => {
+            partial.begin_list()?;
+            for item in value.into_list_like()?.iter() {
+                partial.begin_list_item()?;
+                clone_into(partial, item)?;
+                partial.end()?;
+            }
+        }
+        Def::Array(_) => {
+            for (index, item) in value.into_list_like()?.iter().enumerate() {
+                partial.begin_nth_element(index)?;
+                clone_into(partial, item)?;
+                partial.end()?;
+            }
+        }
+        Def::Map(_) => {
+            partial.begin_map()?;
+            for (key, value) in value.into_map()?.iter() {
+                partial.begin_key()?;
+                clone_into(partial, key)?;
+                partial.end()?;
+                partial.begin_value()?;
+                clone_into(partial, value)?;
+                partial.end()?;
+            }
+        }
+        Def::SmartPointer(_) => {
+            let inner = borrow_inner(value)?;
+            partial.begin_smart_ptr()?;
+            clone_into(partial, inner)?;
+            partial.end()?;
+        }
+        _ => match shape.ty {
+            Type::User(UserType::Struct(struct_type)) => {
+                if struct_type.fields.is_empty() {
+                    // Nothing to copy, but the value still needs to be marked as initialized
+                    partial.set_from_function(|_| Ok(()))?;
+                }
+                for (index, (_, field)) in value.into_struct()?.fields().enumerate() {
+                    partial.begin_nth_field(index)?;
+                    clone_into(partial, field)?;
+                    partial.end()?;
+                }
+            }
+            Type::User(UserType::Enum(_)) => {
+                let value = value.into_enum()?;
+                let variant_index = value
+                    .variant_index()
+                    .map_err(|error| variant_error(shape, error))?;
+                partial.select_nth_variant(variant_index)?;
+                for (index, (_, field)) in value.fields().enumerate() {
+                    partial.begin_nth_field(index)?;
+                    clone_into(partial, field)?;
+                    partial.end()?;
+                }
+            }
+            _ => {
+                return Err(ReflectError::MissingCharacteristic {
+                    shape,
+                    characteristic: Characteristic::Clone,
+                });
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Makes the default value of a shape, like [`Default`] would.
+///
+/// Uses the vtable of the shape when it has `default_in_place`, and otherwise makes empty
+/// lists and maps, and fills arrays and struct fields with their own defaults. Fields with a
+/// `#[facet(default = ...)]` attribute use it.
+///
+/// Fails for enums and scalars without `default_in_place`, since there is no value to pick.
+#[cfg(feature = "alloc")]
+pub fn reflect_default<'facet, 'shape>(
+    shape: &'shape Shape<'shape>,
+) -> Result<HeapValue<'facet, 'shape>, ReflectError<'shape>> {
+    let mut partial = Partial::alloc_shape(shape)?;
+    default_into(&mut partial, shape)?;
+    partial.build()
+}
+
+#[cfg(feature = "alloc")]
+fn default_into<'shape>(
+    partial: &mut Partial<'_, 'shape>,
+    shape: &'shape Shape<'shape>,
+) -> Result<(), ReflectError<'shape>> {
+    if shape.is(Characteristic::Default) {
+        partial.set_default()?;
+        return Ok(());
+    }
+
+    match shape.def {
+        Def::List(_) => {
+            partial.begin_list()?;
+        }
+        Def::Map(_) => {
+            partial.begin_map()?;
+        }
+        Def::Array(def) => {
+            for index in 0..def.n {
+                partial.begin_nth_element(index)?;
+                default_into(partial, def.t)?;
+                partial.end()?;
+            }
+        }
+        _ => match shape.ty {
+            Type::User(UserType::Struct(struct_type)) => {
+                if struct_type.fields.is_empty() {
+                    // Nothing to fill, but the value still needs to be marked as initialized
+                    partial.set_from_function(|_| Ok(()))?;
+                }
+                for (index, field) in struct_type.fields.iter().enumerate() {
+                    partial.begin_nth_field(index)?;
+                    if let Some(default_fn) = field.vtable.default_fn {
+                        partial.set_field_default(default_fn)?;
+                    } else {
+                        default_into(partial, field.shape())?;
+                    }
+                    partial.end()?;
+                }
+            }
+            _ => {
+                return Err(ReflectError::MissingCharacteristic {
+                    shape,
+                    characteristic: Characteristic::Default,
+                });
+            }
+        },
+    }
+    Ok(())
+}
+
+fn borrow_inner<'mem, 'facet, 'shape>(
+    value: Peek<'mem, 'facet, 'shape>,
+) -> Result<Peek<'mem, 'facet, 'shape>, ReflectError<'shape>> {
+    let shape = value.shape();
+    value
+        .into_smart_pointer()?
+        .borrow_inner()
+        .ok_or(ReflectError::OperationFailed {
+            shape,
+            operation: "smart pointer does not allow borrowing its inner value",
+        })
+}
+
+/// The value of an `Ok` or the error of an `Err`, and whether it's an `Ok`.
+fn result_inner<'mem, 'facet, 'shape>(
+    value: Peek<'mem, 'facet, 'shape>,
+) -> Result<(bool, Peek<'mem, 'facet, 'shape>), ReflectError<'shape>> {
+    let result = value.into_result()?;
+    let inner = if result.is_ok() {
+        result.ok()
+    } else {
+        result.err()
+    };
+    // The vtable hands out the side that `is_ok` says is there
+    Ok((result.is_ok(), inner.unwrap()))
+}
+
+fn variant_error<'shape>(
+    shape: &'shape Shape<'shape>,
+    error: VariantError,
+) -> ReflectError<'shape> {
+    match error {
+        VariantError::OpaqueInternals => ReflectError::OperationFailed {
+            shape,
+            operation: "enum layout is opaque, cannot determine variant",
+        },
+        VariantError::Unsized => ReflectError::Unsized { shape },
+    }
+}
+
+/// 64-bit FNV-1a, to hash map entries on their own before combining them.
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
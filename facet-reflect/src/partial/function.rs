@@ -0,0 +1,32 @@
+use crate::{HeapValue, Partial, ReflectError, trace};
+use facet_core::{FunctionShape, PtrMut};
+
+/// Calls a function annotated with `#[facet_fn]` with the tuple of its arguments, for example
+/// one built with [`Partial`] or deserialized from some format, and returns what it returned.
+pub fn invoke<'facet, 'shape>(
+    function: &FunctionShape<'shape>,
+    mut args: HeapValue<'facet, 'shape>,
+) -> Result<HeapValue<'facet, 'shape>, ReflectError<'shape>> {
+    trace!("Invoking function {}", function.name);
+
+    if args.shape() != function.args_shape() {
+        return Err(ReflectError::WrongShape {
+            expected: function.args_shape(),
+            actual: args.shape(),
+        });
+    }
+
+    let mut ret = Partial::alloc_shape(function.return_shape())?;
+
+    // The function moves the arguments out, so only their memory is freed afterwards
+    let guard = args.guard.take().unwrap();
+    ret.set_from_function(|ptr| {
+        // SAFETY: the arguments have the shape the function takes, and `ptr` has the layout of
+        // the shape it returns, which `FunctionShapeBuilder::invoke` requires to match `invoke`
+        unsafe { (function.invoke())(PtrMut::new(guard.ptr), ptr) };
+        Ok(())
+    })?;
+    drop(guard);
+
+    ret.build()
+}
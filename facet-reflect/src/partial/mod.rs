@@ -140,6 +140,9 @@ mod heap_value;
 use alloc::vec::Vec;
 pub use heap_value::*;
 
+mod function;
+pub use function::*;
+
 use facet_core::{
     Def, EnumRepr, Facet, KnownSmartPointer, PtrConst, PtrMut, PtrUninit, Shape, Type, UserType,
     Variant,
@@ -199,6 +202,9 @@ enum FrameOwnership {
     Field,
     /// This frame's allocation is managed elsewhere (e.g., in MapInsertState)
     ManagedElsewhere,
+    /// This frame is a field the parent already counts as initialized (e.g. a map
+    /// reopened by `begin_nth_map_field`), so the parent drops its value
+    TrackedByParent,
 }
 
 struct Frame<'shape> {
@@ -276,6 +282,14 @@ enum Tracker<'shape> {
         /// Whether we're currently building the inner value
         building_inner: bool,
     },
+
+    /// Result being initialized with Ok(value) or Err(error)
+    Result {
+        /// Whether we're building the `Ok` value (as opposed to the `Err` one)
+        is_ok: bool,
+        /// Whether we're currently building the inner value
+        building_inner: bool,
+    },
 }
 
 impl<'shape> Frame<'shape> {
@@ -391,7 +405,7 @@ impl<'shape> Frame<'shape> {
                     Err(ReflectError::UninitializedValue { shape: self.shape })
                 }
             }
-            Tracker::Option { building_inner } => {
+            Tracker::Option { building_inner } | Tracker::Result { building_inner, .. } => {
                 if building_inner {
                     Err(ReflectError::UninitializedValue { shape: self.shape })
                 } else {
@@ -592,6 +606,20 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
             });
         }
 
+        // Don't allow overwriting when building a Result's inner value
+        if matches!(
+            frame.tracker,
+            Tracker::Result {
+                building_inner: true,
+                ..
+            }
+        ) {
+            return Err(ReflectError::OperationFailed {
+                shape: frame.shape,
+                operation: "Cannot overwrite while building Result inner value",
+            });
+        }
+
         // Call the function to initialize the value
         match f(frame.data) {
             Ok(()) => {
@@ -640,6 +668,20 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
             });
         }
 
+        // Don't allow overwriting when building a Result's inner value
+        if matches!(
+            frame.tracker,
+            Tracker::Result {
+                building_inner: true,
+                ..
+            }
+        ) {
+            return Err(ReflectError::OperationFailed {
+                shape: frame.shape,
+                operation: "Cannot overwrite while building Result inner value",
+            });
+        }
+
         // Parse the string value using the type's parse function
         let result = unsafe { parse_fn(s, frame.data) };
         match result {
@@ -1017,6 +1059,71 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         }
     }
 
+    /// Selects the nth field of a struct, which must be a map, to insert more entries into it
+    ///
+    /// Unlike [`Self::begin_nth_field`], a map that is already initialized keeps its entries,
+    /// so that entries can be added over several visits (this is how flattened maps collect
+    /// unknown fields). A map that isn't initialized yet starts out empty.
+    pub fn begin_nth_map_field(&mut self, idx: usize) -> Result<&mut Self, ReflectError<'shape>> {
+        self.require_active()?;
+        let frame = self.frames.last_mut().unwrap();
+        let Type::User(UserType::Struct(struct_type)) = frame.shape.ty else {
+            return Err(ReflectError::OperationFailed {
+                shape: frame.shape,
+                operation: "begin_nth_map_field can only be called on structs",
+            });
+        };
+        let Some(field) = struct_type.fields.get(idx) else {
+            return Err(ReflectError::OperationFailed {
+                shape: frame.shape,
+                operation: "field index out of bounds",
+            });
+        };
+        let Def::Map(map_def) = field.shape.def else {
+            return Err(ReflectError::OperationFailed {
+                shape: field.shape,
+                operation: "begin_nth_map_field can only be called on map fields",
+            });
+        };
+
+        if let Tracker::Uninit = frame.tracker {
+            frame.tracker = Tracker::Struct {
+                iset: ISet::new(struct_type.fields.len()),
+                current_child: None,
+            };
+        }
+        let Tracker::Struct {
+            iset,
+            current_child,
+        } = &mut frame.tracker
+        else {
+            return Err(ReflectError::OperationFailed {
+                shape: frame.shape,
+                operation: "begin_nth_map_field called on a struct that is not being built field by field",
+            });
+        };
+
+        let field_ptr = unsafe { frame.data.field_uninit_at(field.offset) };
+        if !iset.get(idx) {
+            unsafe {
+                (map_def.vtable.init_in_place_with_capacity_fn)(field_ptr, 0);
+            }
+            // The map stays owned by the struct while entries are added, so it is
+            // dropped with the struct even if an entry fails halfway
+            iset.set(idx);
+        }
+        *current_child = Some(idx);
+
+        let mut field_frame = Frame::new(field_ptr, field.shape, FrameOwnership::TrackedByParent);
+        field_frame.tracker = Tracker::Map {
+            is_initialized: true,
+            insert_state: MapInsertState::Idle,
+        };
+        self.frames.push(field_frame);
+
+        Ok(self)
+    }
+
     /// Selects the nth element of an array by index
     pub fn begin_nth_element(&mut self, idx: usize) -> Result<&mut Self, ReflectError<'shape>> {
         self.require_active()?;
@@ -1891,6 +1998,51 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
                     }
                 }
             }
+            Tracker::Result {
+                is_ok,
+                building_inner,
+            } => {
+                // We just popped the inner value frame for a Result's Ok or Err variant
+                if *building_inner {
+                    if let Def::Result(result_def) = parent_frame.shape.def {
+                        // Use the Result vtable to initialize Ok(value) or Err(error)
+                        let init_fn = if *is_ok {
+                            result_def.vtable.init_ok_fn
+                        } else {
+                            result_def.vtable.init_err_fn
+                        };
+
+                        // The popped frame contains the inner value
+                        let inner_value_ptr = unsafe { popped_frame.data.assume_init().as_const() };
+
+                        unsafe {
+                            init_fn(parent_frame.data, inner_value_ptr);
+                        }
+
+                        // Deallocate the inner value's memory since init_fn moved it
+                        if let FrameOwnership::Owned = popped_frame.ownership {
+                            if let Ok(layout) = popped_frame.shape.layout.sized_layout() {
+                                if layout.size() > 0 {
+                                    unsafe {
+                                        alloc::alloc::dealloc(
+                                            popped_frame.data.as_mut_byte_ptr(),
+                                            layout,
+                                        );
+                                    }
+                                }
+                            }
+                        }
+
+                        // Mark that we're no longer building the inner value
+                        *building_inner = false;
+                    } else {
+                        return Err(ReflectError::OperationFailed {
+                            shape: parent_frame.shape,
+                            operation: "Result frame without Result definition",
+                        });
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -2104,9 +2256,20 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
                     })
                 }
             }
+            Tracker::Result { building_inner, .. } => {
+                // For Results, index 0 represents the Ok or Err value
+                if index == 0 {
+                    Ok(!building_inner)
+                } else {
+                    Err(ReflectError::InvalidOperation {
+                        operation: "is_field_set",
+                        reason: "Result only has one field (index 0)",
+                    })
+                }
+            }
             _ => Err(ReflectError::InvalidOperation {
                 operation: "is_field_set",
-                reason: "Current frame is not a struct, enum variant, or option",
+                reason: "Current frame is not a struct, enum variant, option, or result",
             }),
         }
     }
@@ -2211,6 +2374,81 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         Ok(self)
     }
 
+    /// Begin building the Ok variant of a Result
+    pub fn begin_ok(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
+        self.begin_result_variant(true)
+    }
+
+    /// Begin building the Err variant of a Result
+    pub fn begin_err(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
+        self.begin_result_variant(false)
+    }
+
+    fn begin_result_variant(&mut self, is_ok: bool) -> Result<&mut Self, ReflectError<'shape>> {
+        self.require_active()?;
+        let frame = self.frames.last_mut().unwrap();
+
+        // Verify we're working with a Result
+        let result_def = match frame.shape.def {
+            Def::Result(def) => def,
+            _ => {
+                return Err(ReflectError::WasNotA {
+                    expected: "Result",
+                    actual: frame.shape,
+                });
+            }
+        };
+
+        // Drop any previous value, since we're about to pick a variant again
+        if matches!(
+            frame.tracker,
+            Tracker::Init
+                | Tracker::Result {
+                    building_inner: false,
+                    ..
+                }
+        ) {
+            if let Some(drop_fn) = frame.shape.vtable.sized().and_then(|v| (v.drop_in_place)()) {
+                unsafe { drop_fn(PtrMut::new(frame.data.as_mut_byte_ptr())) };
+            }
+        }
+
+        frame.tracker = Tracker::Result {
+            is_ok,
+            building_inner: true,
+        };
+
+        // Get the shape of the value or the error
+        let inner_shape = if is_ok {
+            result_def.t()
+        } else {
+            result_def.e()
+        };
+
+        // Allocate memory for the inner value
+        let inner_layout = inner_shape
+            .layout
+            .sized_layout()
+            .map_err(|_| ReflectError::Unsized { shape: inner_shape })?;
+
+        let inner_data = if inner_layout.size() == 0 {
+            // For ZST, use a non-null but unallocated pointer
+            PtrUninit::new(core::ptr::NonNull::<u8>::dangling().as_ptr())
+        } else {
+            let ptr = unsafe { alloc::alloc::alloc(inner_layout) };
+            if ptr.is_null() {
+                alloc::alloc::handle_alloc_error(inner_layout);
+            }
+            PtrUninit::new(ptr)
+        };
+
+        // Create a new frame for the inner value
+        let inner_frame = Frame::new(inner_data, inner_shape, FrameOwnership::Owned);
+        self.frames.push(inner_frame);
+
+        Ok(self)
+    }
+
     /// Begin building the inner value of a wrapper type
     pub fn begin_inner(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
         self.require_active()?;
@@ -2301,6 +2539,26 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         unsafe { self.set_shape(src_ptr.thin().unwrap(), src_shape) }
     }
 
+    /// Moves a fully built value into the current frame.
+    ///
+    /// Unlike [`Self::set_from_peek`], this takes ownership of the value: its heap
+    /// allocation is freed, but the value itself is not dropped.
+    pub fn set_from_heap_value(
+        &mut self,
+        mut value: HeapValue<'facet, 'shape>,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        self.require_active()?;
+
+        let src_ptr = PtrConst::new(value.guard.as_ref().unwrap().ptr);
+
+        // Safety: the heap value is fully initialized and matches its shape
+        unsafe { self.set_shape(src_ptr, value.shape)? };
+
+        // The value now lives in the current frame: free the heap allocation without dropping it
+        drop(value.guard.take());
+        Ok(self)
+    }
+
     /// Copy a field from a struct's default value (safe wrapper for deserialization)
     /// This method creates the Peek internally to avoid exposing unsafe code to callers
     pub fn set_field_from_default(
@@ -2520,6 +2778,12 @@ impl<'facet, 'shape, T> TypedPartial<'facet, 'shape, T> {
         Ok(self)
     }
 
+    /// Forwards begin_nth_map_field to the inner wip instance.
+    pub fn begin_nth_map_field(&mut self, idx: usize) -> Result<&mut Self, ReflectError<'shape>> {
+        self.inner.begin_nth_map_field(idx)?;
+        Ok(self)
+    }
+
     /// Forwards begin_nth_element to the inner wip instance.
     pub fn begin_nth_element(&mut self, idx: usize) -> Result<&mut Self, ReflectError<'shape>> {
         self.inner.begin_nth_element(idx)?;
@@ -2712,6 +2976,18 @@ impl<'facet, 'shape, T> TypedPartial<'facet, 'shape, T> {
         Ok(self)
     }
 
+    /// Forwards begin_ok to the inner wip instance.
+    pub fn begin_ok(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
+        self.inner.begin_ok()?;
+        Ok(self)
+    }
+
+    /// Forwards begin_err to the inner wip instance.
+    pub fn begin_err(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
+        self.inner.begin_err()?;
+        Ok(self)
+    }
+
     /// Forwards begin_inner to the inner wip instance.
     pub fn begin_inner(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
         self.inner.begin_inner()?;
@@ -2733,10 +3009,13 @@ impl<'facet, 'shape> Drop for Partial<'facet, 'shape> {
 
         // We need to properly drop all initialized fields
         while let Some(frame) = self.frames.pop() {
+            // The parent drops the value of such frames along with its other fields
+            let tracked_by_parent = matches!(frame.ownership, FrameOwnership::TrackedByParent);
             match &frame.tracker {
                 Tracker::Uninit => {
                     // Nothing was initialized, nothing to drop
                 }
+                Tracker::Init if tracked_by_parent => {}
                 Tracker::Init => {
                     // Fully initialized, drop it
                     if let Some(drop_fn) =
@@ -2824,7 +3103,7 @@ impl<'facet, 'shape> Drop for Partial<'facet, 'shape> {
                     insert_state,
                 } => {
                     // Drop the initialized Map
-                    if *is_initialized {
+                    if *is_initialized && !tracked_by_parent {
                         if let Some(drop_fn) =
                             frame.shape.vtable.sized().and_then(|v| (v.drop_in_place)())
                         {
@@ -2902,6 +3181,16 @@ impl<'facet, 'shape> Drop for Partial<'facet, 'shape> {
                         }
                     }
                 }
+                Tracker::Result { building_inner, .. } => {
+                    // Same as for Options: the Result is only initialized once its value is
+                    if !building_inner {
+                        if let Some(drop_fn) =
+                            frame.shape.vtable.sized().and_then(|v| (v.drop_in_place)())
+                        {
+                            unsafe { drop_fn(PtrMut::new(frame.data.as_mut_byte_ptr())) };
+                        }
+                    }
+                }
             }
 
             // Only deallocate if this frame owns the allocation
@@ -199,6 +199,11 @@ impl<'mem, 'facet, 'shape> Iterator for FieldsForSerializeIter<'mem, 'facet, 'sh
                         range: 0..1,
                         state: FieldIterState::FlattenedEnum { field, value: peek },
                     });
+                } else if peek.into_map().is_ok() {
+                    // The map's entries are written inline in the parent object, so
+                    // `field.name` is meaningless here: the keys come from the map
+                    field.flattened = true;
+                    return Some((field, peek));
                 } else {
                     // TODO: fail more gracefully
                     panic!("cannot flatten a {}", field.shape())
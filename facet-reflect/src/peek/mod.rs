@@ -24,8 +24,14 @@ pub use map::*;
 mod option;
 pub use option::*;
 
+mod result;
+pub use result::*;
+
 mod smartptr;
 pub use smartptr::*;
 
 mod tuple;
 pub use tuple::*;
+
+mod path;
+pub use path::*;
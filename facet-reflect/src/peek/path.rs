@@ -0,0 +1,539 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use facet_core::{Def, Shape, StructKind, Type, UserType};
+
+use crate::Peek;
+
+use super::{HasFields, PeekMap};
+
+/// A path expression that locates a value nested inside another one, like
+/// `servers[2].tls.cert_path` or `labels["env"]`.
+///
+/// Paths are made of:
+///
+///   * field names: `name`, `.name` (tuple fields are named `0`, `1`, ...)
+///   * indices into lists, arrays and tuples: `[2]`
+///   * map keys: `["env"]`, or `[env]` for keys without special characters. Keys are
+///     parsed with the key shape's `parse` function, so `[2]` also looks up `2` in a
+///     map with integer keys.
+///   * wildcards: `*` or `[*]`, which match every field, element or map value. They
+///     can only be resolved with [`Peek::at_path_all`].
+///
+/// `Option`s and smart pointers are transparent: `config.tls.cert` resolves through
+/// a `tls: Option<Box<Tls>>` field as long as it's `Some`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+/// One step of a [`Path`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A struct or enum variant field, by name
+    Field(String),
+    /// A list, array or tuple element, by index
+    Index(usize),
+    /// A map entry, by key
+    Key(String),
+    /// Every field, element or map value
+    Wildcard,
+}
+
+impl Path {
+    /// Returns the empty path, which resolves to the value itself
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Parses a path expression
+    pub fn parse(input: &str) -> Result<Self, PathParseError> {
+        PathParser { input, pos: 0 }.parse()
+    }
+
+    /// Returns the segments of this path
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Appends a segment to this path
+    pub fn push(&mut self, segment: PathSegment) {
+        self.segments.push(segment);
+    }
+
+    /// Returns this path with a segment appended
+    pub fn with(mut self, segment: PathSegment) -> Self {
+        self.push(segment);
+        self
+    }
+
+    /// Returns true if this path contains a wildcard
+    pub fn has_wildcard(&self) -> bool {
+        self.segments.contains(&PathSegment::Wildcard)
+    }
+
+    fn prefix(&self, len: usize) -> Path {
+        Path {
+            segments: self.segments[..len].to_vec(),
+        }
+    }
+}
+
+impl FromStr for Path {
+    type Err = PathParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if i == 0 => write!(f, "{name}")?,
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+                PathSegment::Key(key) => {
+                    write!(f, "[\"")?;
+                    for c in key.chars() {
+                        if c == '"' || c == '\\' {
+                            write!(f, "\\")?;
+                        }
+                        write!(f, "{c}")?;
+                    }
+                    write!(f, "\"]")?;
+                }
+                PathSegment::Wildcard => write!(f, "[*]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a path expression is malformed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathParseError {
+    /// Byte offset in the input at which parsing failed
+    pub position: usize,
+    /// What went wrong
+    pub reason: &'static str,
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid path at offset {}: {}",
+            self.position, self.reason
+        )
+    }
+}
+
+impl core::error::Error for PathParseError {}
+
+struct PathParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl PathParser<'_> {
+    fn parse(mut self) -> Result<Path, PathParseError> {
+        let mut path = Path::root();
+        while let Some(c) = self.peek() {
+            match c {
+                '.' => {
+                    self.pos += 1;
+                    path.push(self.field()?);
+                }
+                '[' => {
+                    self.pos += 1;
+                    path.push(self.bracket()?);
+                }
+                _ if path.segments.is_empty() => path.push(self.field()?),
+                _ => return Err(self.error("expected `.` or `[`")),
+            }
+        }
+        Ok(path)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn error(&self, reason: &'static str) -> PathParseError {
+        PathParseError {
+            position: self.pos,
+            reason,
+        }
+    }
+
+    fn field(&mut self) -> Result<PathSegment, PathParseError> {
+        if self.peek() == Some('*') {
+            self.pos += 1;
+            return Ok(PathSegment::Wildcard);
+        }
+        let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        if name.is_empty() {
+            return Err(self.error("expected a field name"));
+        }
+        Ok(PathSegment::Field(name.to_string()))
+    }
+
+    fn bracket(&mut self) -> Result<PathSegment, PathParseError> {
+        let segment = match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                PathSegment::Wildcard
+            }
+            Some('"') => {
+                self.pos += 1;
+                PathSegment::Key(self.quoted()?)
+            }
+            Some(_) => {
+                let start = self.pos;
+                let key = self.take_while(|c| c != ']');
+                if key.is_empty() {
+                    return Err(self.error("expected an index or a key"));
+                }
+                match key.parse() {
+                    Ok(index) => PathSegment::Index(index),
+                    Err(_) if key.contains(['[', '"', '.']) => {
+                        return Err(PathParseError {
+                            position: start,
+                            reason: "keys with special characters must be quoted",
+                        });
+                    }
+                    Err(_) => PathSegment::Key(key.to_string()),
+                }
+            }
+            None => return Err(self.error("unterminated `[`")),
+        };
+        if self.peek() != Some(']') {
+            return Err(self.error("expected `]`"));
+        }
+        self.pos += 1;
+        Ok(segment)
+    }
+
+    fn quoted(&mut self) -> Result<String, PathParseError> {
+        let mut out = String::new();
+        let mut chars = self.input[self.pos..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += offset + 1;
+                    return Ok(out);
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => out.push(escaped),
+                    None => break,
+                },
+                _ => out.push(c),
+            }
+        }
+        self.pos = self.input.len();
+        Err(self.error("unterminated string"))
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &str {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+}
+
+/// Error returned when a [`Path`] doesn't resolve against a value
+#[derive(Clone, PartialEq)]
+pub struct PathError<'shape> {
+    /// The part of the path that did resolve
+    pub resolved: Path,
+    /// The shape of the value the next segment was applied to
+    pub shape: &'shape Shape<'shape>,
+    /// What went wrong
+    pub kind: PathErrorKind,
+}
+
+/// The reason a [`Path`] didn't resolve, see [`PathError`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathErrorKind {
+    /// The struct or active enum variant has no field with that name
+    NoSuchField(String),
+    /// The index is past the end of the list, array or tuple
+    IndexOutOfBounds {
+        /// The index that was requested
+        index: usize,
+        /// The number of elements
+        len: usize,
+    },
+    /// The map has no entry for that key
+    NoSuchKey(String),
+    /// The key couldn't be parsed as the map's key type
+    InvalidKey(String),
+    /// The value has no fields
+    NotAStruct,
+    /// The value can't be indexed
+    NotIndexable,
+    /// The value is an `Option` that is `None`
+    NoneValue,
+    /// The value is a smart pointer whose pointee can't be accessed
+    OpaquePointer,
+    /// The path contains a wildcard, which needs [`Peek::at_path_all`]
+    Wildcard,
+}
+
+impl fmt::Display for PathErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathErrorKind::NoSuchField(name) => write!(f, "no field `{name}`"),
+            PathErrorKind::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} is out of bounds (length is {len})")
+            }
+            PathErrorKind::NoSuchKey(key) => write!(f, "no entry for key `{key}`"),
+            PathErrorKind::InvalidKey(key) => write!(f, "`{key}` is not a valid key"),
+            PathErrorKind::NotAStruct => write!(f, "value has no fields"),
+            PathErrorKind::NotIndexable => write!(f, "value cannot be indexed"),
+            PathErrorKind::NoneValue => write!(f, "value is None"),
+            PathErrorKind::OpaquePointer => write!(f, "pointee is not accessible"),
+            PathErrorKind::Wildcard => {
+                write!(f, "wildcards can only be resolved with `at_path_all`")
+            }
+        }
+    }
+}
+
+impl fmt::Display for PathError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.resolved.segments.is_empty() {
+            write!(f, "at root ({}): {}", self.shape, self.kind)
+        } else {
+            write!(f, "at `{}` ({}): {}", self.resolved, self.shape, self.kind)
+        }
+    }
+}
+
+impl fmt::Debug for PathError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl core::error::Error for PathError<'_> {}
+
+impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
+    /// Returns the value at the given path, see [`Path`]
+    pub fn at_path(self, path: &Path) -> Result<Peek<'mem, 'facet, 'shape>, PathError<'shape>> {
+        let mut value = self;
+        for (i, segment) in path.segments.iter().enumerate() {
+            value = step(value, segment).map_err(|(shape, kind)| PathError {
+                resolved: path.prefix(i),
+                shape,
+                kind,
+            })?;
+        }
+        Ok(value)
+    }
+
+    /// Returns an iterator over all values matching the given path, along with
+    /// their concrete path (with wildcards replaced by field names, indices or keys)
+    ///
+    /// Unlike [`Self::at_path`], branches that don't resolve are skipped rather than
+    /// reported as errors.
+    pub fn at_path_all(self, path: &Path) -> PathMatches<'mem, 'facet, 'shape> {
+        PathMatches {
+            segments: path.segments.clone(),
+            stack: alloc::vec![(Path::root(), self)],
+        }
+    }
+}
+
+/// Iterator over the values matching a [`Path`], see [`Peek::at_path_all`]
+pub struct PathMatches<'mem, 'facet, 'shape> {
+    segments: Vec<PathSegment>,
+    stack: Vec<(Path, Peek<'mem, 'facet, 'shape>)>,
+}
+
+impl<'mem, 'facet, 'shape> Iterator for PathMatches<'mem, 'facet, 'shape> {
+    type Item = (Path, Peek<'mem, 'facet, 'shape>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, value)) = self.stack.pop() {
+            let Some(segment) = self.segments.get(path.segments.len()) else {
+                return Some((path, value));
+            };
+            if *segment == PathSegment::Wildcard {
+                // Pushed in reverse, so that matches come out in order
+                let items = children(value);
+                for (child_segment, child) in items.into_iter().rev() {
+                    self.stack.push((path.clone().with(child_segment), child));
+                }
+            } else if let Ok(child) = step(value, segment) {
+                self.stack.push((path.with(segment.clone()), child));
+            }
+        }
+        None
+    }
+}
+
+/// Looks through `Option`s and smart pointers
+fn deref<'mem, 'facet, 'shape>(
+    mut value: Peek<'mem, 'facet, 'shape>,
+) -> Result<Peek<'mem, 'facet, 'shape>, (&'shape Shape<'shape>, PathErrorKind)> {
+    loop {
+        match value.shape.def {
+            Def::Option(_) => {
+                let option = value.into_option().unwrap();
+                value = option
+                    .value()
+                    .ok_or((value.shape, PathErrorKind::NoneValue))?;
+            }
+            Def::SmartPointer(_) => {
+                let pointer = value.into_smart_pointer().unwrap();
+                value = pointer
+                    .borrow_inner()
+                    .ok_or((value.shape, PathErrorKind::OpaquePointer))?;
+            }
+            _ => return Ok(value),
+        }
+    }
+}
+
+/// Applies a single (non-wildcard) segment
+fn step<'mem, 'facet, 'shape>(
+    value: Peek<'mem, 'facet, 'shape>,
+    segment: &PathSegment,
+) -> Result<Peek<'mem, 'facet, 'shape>, (&'shape Shape<'shape>, PathErrorKind)> {
+    let value = deref(value)?;
+    let shape = value.shape;
+    let result = match segment {
+        PathSegment::Field(name) => match shape.ty {
+            Type::User(UserType::Struct(_)) => value
+                .into_struct()
+                .unwrap()
+                .field_by_name(name)
+                .map_err(|_| PathErrorKind::NoSuchField(name.clone())),
+            Type::User(UserType::Enum(_)) => match value.into_enum().unwrap().field_by_name(name) {
+                Ok(Some(field)) => Ok(field),
+                _ => Err(PathErrorKind::NoSuchField(name.clone())),
+            },
+            _ => match value.into_map() {
+                // `labels.env` is the same as `labels["env"]`
+                Ok(map) => map_entry(map, name),
+                Err(_) => Err(PathErrorKind::NotAStruct),
+            },
+        },
+        PathSegment::Index(index) => {
+            if let Ok(list) = value.into_list_like() {
+                list.get(*index).ok_or(PathErrorKind::IndexOutOfBounds {
+                    index: *index,
+                    len: list.len(),
+                })
+            } else if let Ok(map) = value.into_map() {
+                map_entry(map, &index.to_string())
+            } else if let Type::User(UserType::Struct(ty)) = shape.ty {
+                match ty.kind {
+                    StructKind::Tuple | StructKind::TupleStruct => {
+                        value.into_struct().unwrap().field(*index).map_err(|_| {
+                            PathErrorKind::IndexOutOfBounds {
+                                index: *index,
+                                len: ty.fields.len(),
+                            }
+                        })
+                    }
+                    _ => Err(PathErrorKind::NotIndexable),
+                }
+            } else {
+                Err(PathErrorKind::NotIndexable)
+            }
+        }
+        PathSegment::Key(key) => match value.into_map() {
+            Ok(map) => map_entry(map, key),
+            Err(_) => Err(PathErrorKind::NotIndexable),
+        },
+        PathSegment::Wildcard => Err(PathErrorKind::Wildcard),
+    };
+    result.map_err(|kind| (shape, kind))
+}
+
+/// Looks up a map entry by the string form of its key
+fn map_entry<'mem, 'facet, 'shape>(
+    map: PeekMap<'mem, 'facet, 'shape>,
+    key: &str,
+) -> Result<Peek<'mem, 'facet, 'shape>, PathErrorKind> {
+    #[cfg(feature = "alloc")]
+    {
+        let key_shape = map.def().k();
+        if key_shape.is_from_str() {
+            let mut partial = crate::Partial::alloc_shape(key_shape)
+                .map_err(|_| PathErrorKind::InvalidKey(key.to_string()))?;
+            partial
+                .parse_from_str(key)
+                .map_err(|_| PathErrorKind::InvalidKey(key.to_string()))?;
+            let parsed = partial
+                .build()
+                .map_err(|_| PathErrorKind::InvalidKey(key.to_string()))?;
+            return map
+                .get_peek(parsed.peek())
+                .ok_or_else(|| PathErrorKind::NoSuchKey(key.to_string()));
+        }
+    }
+
+    // Keys that can't be parsed, like `&str`, are compared by their string value
+    map.iter()
+        .find(|(k, _)| k.as_str() == Some(key))
+        .map(|(_, v)| v)
+        .ok_or_else(|| PathErrorKind::NoSuchKey(key.to_string()))
+}
+
+/// All the direct children of a value, for wildcards
+fn children<'mem, 'facet, 'shape>(
+    value: Peek<'mem, 'facet, 'shape>,
+) -> Vec<(PathSegment, Peek<'mem, 'facet, 'shape>)> {
+    let Ok(value) = deref(value) else {
+        return Vec::new();
+    };
+
+    if let Ok(list) = value.into_list_like() {
+        return list
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (PathSegment::Index(i), item))
+            .collect();
+    }
+    if let Ok(map) = value.into_map() {
+        return map
+            .iter()
+            .map(|(k, v)| {
+                let key = match k.as_str() {
+                    Some(s) => s.to_string(),
+                    None => k.to_string(),
+                };
+                (PathSegment::Key(key), v)
+            })
+            .collect();
+    }
+    match value.shape.ty {
+        Type::User(UserType::Struct(_)) => value
+            .into_struct()
+            .unwrap()
+            .fields()
+            .map(|(field, v)| (PathSegment::Field(field.name.to_string()), v))
+            .collect(),
+        Type::User(UserType::Enum(_)) => {
+            let enum_ = value.into_enum().unwrap();
+            if enum_.active_variant().is_err() {
+                return Vec::new();
+            }
+            enum_
+                .fields()
+                .map(|(field, v)| (PathSegment::Field(field.name.to_string()), v))
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
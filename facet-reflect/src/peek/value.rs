@@ -451,6 +451,20 @@ impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
         }
     }
 
+    /// Tries to identify this value as a result
+    pub fn into_result(
+        self,
+    ) -> Result<super::PeekResult<'mem, 'facet, 'shape>, ReflectError<'shape>> {
+        if let Def::Result(def) = self.shape.def {
+            Ok(super::PeekResult { value: self, def })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "result",
+                actual: self.shape,
+            })
+        }
+    }
+
     /// Tries to identify this value as a tuple
     pub fn into_tuple(self) -> Result<PeekTuple<'mem, 'facet, 'shape>, ReflectError<'shape>> {
         if let Type::User(UserType::Struct(struct_type)) = self.shape.ty {
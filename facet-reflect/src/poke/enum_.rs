@@ -0,0 +1,149 @@
+use facet_core::{EnumType, Facet, FieldError, Variant};
+
+use crate::{PeekEnum, ReflectError, VariantError};
+
+use super::Poke;
+
+/// Lets you mutate an initialized enum: its active variant, and that variant's fields
+pub struct PokeEnum<'mem, 'facet, 'shape> {
+    /// The underlying value (discriminant and variant data)
+    pub(crate) value: Poke<'mem, 'facet, 'shape>,
+
+    /// The definition of the enum.
+    pub(crate) ty: EnumType<'shape>,
+}
+
+impl core::fmt::Debug for PokeEnum<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PokeEnum").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet, 'shape> PokeEnum<'mem, 'facet, 'shape> {
+    /// Returns the enum definition
+    #[inline(always)]
+    pub fn ty(&self) -> EnumType<'shape> {
+        self.ty
+    }
+
+    /// Returns the enum variants
+    #[inline(always)]
+    pub fn variants(&self) -> &'shape [Variant<'shape>] {
+        self.ty.variants
+    }
+
+    /// Returns a read-only view of the enum
+    #[inline]
+    pub fn as_peek_enum(&self) -> PeekEnum<'_, 'facet, 'shape> {
+        PeekEnum {
+            value: self.value.as_peek(),
+            ty: self.ty,
+        }
+    }
+
+    /// Returns the index of the active variant
+    #[inline]
+    pub fn variant_index(&self) -> Result<usize, VariantError> {
+        self.as_peek_enum().variant_index()
+    }
+
+    /// Returns the active variant
+    #[inline]
+    pub fn active_variant(&self) -> Result<&'shape Variant<'shape>, VariantError> {
+        self.as_peek_enum().active_variant()
+    }
+
+    /// Returns a mutable handle to a field of the active variant, by index
+    pub fn field(
+        &mut self,
+        index: usize,
+    ) -> Result<Option<Poke<'_, 'facet, 'shape>>, VariantError> {
+        let variant = self.active_variant()?;
+        let Some(field) = variant.data.fields.get(index) else {
+            return Ok(None);
+        };
+        Ok(Some(unsafe {
+            Poke::unchecked_new(self.value.data.field(field.offset), field.shape())
+        }))
+    }
+
+    /// Returns a mutable handle to a field of the active variant, by name
+    pub fn field_by_name(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<Poke<'_, 'facet, 'shape>>, VariantError> {
+        let variant = self.active_variant()?;
+        match variant.data.fields.iter().position(|f| f.name == name) {
+            Some(index) => self.field(index),
+            None => Ok(None),
+        }
+    }
+
+    /// Replaces a field of the active variant with `value`, dropping the previous value.
+    pub fn set_field<T: Facet<'facet>>(
+        &mut self,
+        index: usize,
+        value: T,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        let shape = self.value.shape;
+        let variant = self
+            .active_variant()
+            .map_err(|_| ReflectError::OperationFailed {
+                shape,
+                operation: "could not determine the active variant",
+            })?;
+        let bound = variant.data.fields.len();
+        match self.field(index) {
+            Ok(Some(mut field)) => {
+                field.set(value)?;
+            }
+            _ => {
+                return Err(ReflectError::FieldError {
+                    shape,
+                    field_error: FieldError::IndexOutOfBounds { index, bound },
+                });
+            }
+        }
+        Ok(self)
+    }
+
+    /// Switches the enum to the variant at the given index, dropping the previous value.
+    ///
+    /// All fields of the new variant are set to their default value, so they must
+    /// all implement `Default`. If they don't, the enum is left untouched.
+    #[cfg(feature = "alloc")]
+    pub fn set_variant(&mut self, index: usize) -> Result<&mut Self, ReflectError<'shape>> {
+        let shape = self.value.shape;
+        let Some(variant) = self.ty.variants.get(index) else {
+            return Err(ReflectError::NoSuchVariant { enum_type: self.ty });
+        };
+
+        // Build the new value on the side first, so that a failure doesn't leave
+        // us with a half-dropped enum.
+        let mut partial = crate::Partial::alloc_shape(shape)?;
+        partial.select_nth_variant(index)?;
+        for field_index in 0..variant.data.fields.len() {
+            partial.begin_nth_field(field_index)?.set_default()?.end()?;
+        }
+        let heap_value = partial.build()?;
+        self.value.replace_with_heap_value(heap_value)?;
+        Ok(self)
+    }
+
+    /// Switches the enum to the variant with the given name, dropping the previous value.
+    ///
+    /// See [`Self::set_variant`].
+    #[cfg(feature = "alloc")]
+    pub fn set_variant_named(&mut self, name: &str) -> Result<&mut Self, ReflectError<'shape>> {
+        let Some(index) = self.ty.variants.iter().position(|v| v.name == name) else {
+            return Err(ReflectError::NoSuchVariant { enum_type: self.ty });
+        };
+        self.set_variant(index)
+    }
+
+    /// Gives back the underlying [`Poke`]
+    #[inline(always)]
+    pub fn into_inner(self) -> Poke<'mem, 'facet, 'shape> {
+        self.value
+    }
+}
@@ -0,0 +1,83 @@
+use facet_core::{Facet, ListDef, PtrMut};
+
+use crate::{PeekList, ReflectError};
+
+use super::Poke;
+
+/// Lets you mutate an initialized list (implements mutating [`facet_core::ListVTable`] proxies)
+pub struct PokeList<'mem, 'facet, 'shape> {
+    pub(crate) value: Poke<'mem, 'facet, 'shape>,
+    pub(crate) def: ListDef<'shape>,
+}
+
+impl core::fmt::Debug for PokeList<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PokeList").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet, 'shape> PokeList<'mem, 'facet, 'shape> {
+    /// Def getter
+    pub fn def(&self) -> ListDef<'shape> {
+        self.def
+    }
+
+    /// Returns a read-only view of the list
+    #[inline]
+    pub fn as_peek_list(&self) -> PeekList<'_, 'facet, 'shape> {
+        PeekList {
+            value: self.value.as_peek(),
+            def: self.def,
+        }
+    }
+
+    /// Get the length of the list
+    pub fn len(&self) -> usize {
+        unsafe { (self.def.vtable.len)(self.value.data.as_const()) }
+    }
+
+    /// Returns true if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a mutable handle to the item at the specified index
+    ///
+    /// Returns `None` if the index is out of bounds, or if the list doesn't
+    /// support mutable access to its items.
+    pub fn get_mut(&mut self, index: usize) -> Option<Poke<'_, 'facet, 'shape>> {
+        let get_mut_fn = self.def.vtable.get_mut?;
+        let item = unsafe { get_mut_fn(self.value.data, index)? };
+        Some(unsafe { Poke::unchecked_new(item, self.def.t()) })
+    }
+
+    /// Appends an item to the end of the list
+    pub fn push<T: Facet<'facet>>(
+        &mut self,
+        mut item: T,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        if self.def.t() != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.t(),
+                actual: T::SHAPE,
+            });
+        }
+        let Some(push_fn) = self.def.vtable.push else {
+            return Err(ReflectError::OperationFailed {
+                shape: self.value.shape,
+                operation: "list does not support push",
+            });
+        };
+
+        unsafe { push_fn(self.value.data, PtrMut::new(&raw mut item)) };
+        // The list now owns the item
+        core::mem::forget(item);
+        Ok(self)
+    }
+
+    /// Gives back the underlying [`Poke`]
+    #[inline(always)]
+    pub fn into_inner(self) -> Poke<'mem, 'facet, 'shape> {
+        self.value
+    }
+}
@@ -0,0 +1,111 @@
+use facet_core::{Facet, MapDef, PtrConst, PtrMut};
+
+use crate::{PeekMap, ReflectError};
+
+use super::Poke;
+
+/// Lets you mutate an initialized map (implements mutating [`facet_core::MapVTable`] proxies)
+pub struct PokeMap<'mem, 'facet, 'shape> {
+    pub(crate) value: Poke<'mem, 'facet, 'shape>,
+    pub(crate) def: MapDef<'shape>,
+}
+
+impl core::fmt::Debug for PokeMap<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PokeMap").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet, 'shape> PokeMap<'mem, 'facet, 'shape> {
+    /// Def getter
+    pub fn def(&self) -> MapDef<'shape> {
+        self.def
+    }
+
+    /// Returns a read-only view of the map
+    #[inline]
+    pub fn as_peek_map(&self) -> PeekMap<'_, 'facet, 'shape> {
+        PeekMap {
+            value: self.value.as_peek(),
+            def: self.def,
+        }
+    }
+
+    /// Get the number of entries in the map
+    pub fn len(&self) -> usize {
+        unsafe { (self.def.vtable.len_fn)(self.value.data.as_const()) }
+    }
+
+    /// Returns true if the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check_key<K: Facet<'facet>>(&self) -> Result<(), ReflectError<'shape>> {
+        if self.def.k() != K::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.k(),
+                actual: K::SHAPE,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check if the map contains a key
+    pub fn contains_key<K: Facet<'facet>>(&self, key: &K) -> Result<bool, ReflectError<'shape>> {
+        self.check_key::<K>()?;
+        Ok(unsafe {
+            (self.def.vtable.contains_key_fn)(self.value.data.as_const(), PtrConst::new(key))
+        })
+    }
+
+    /// Returns a mutable handle to the value for the given key, if any
+    pub fn get_mut<K: Facet<'facet>>(
+        &mut self,
+        key: &K,
+    ) -> Result<Option<Poke<'_, 'facet, 'shape>>, ReflectError<'shape>> {
+        self.check_key::<K>()?;
+        let Some(get_value_mut_ptr_fn) = self.def.vtable.get_value_mut_ptr_fn else {
+            return Err(ReflectError::OperationFailed {
+                shape: self.value.shape,
+                operation: "map does not support mutable lookups",
+            });
+        };
+        let value_ptr = unsafe { get_value_mut_ptr_fn(self.value.data, PtrConst::new(key)) };
+        Ok(value_ptr.map(|ptr| unsafe { Poke::unchecked_new(ptr, self.def.v()) }))
+    }
+
+    /// Inserts a key-value pair into the map, replacing (and dropping) any previous
+    /// value for that key
+    pub fn insert<K: Facet<'facet>, V: Facet<'facet>>(
+        &mut self,
+        mut key: K,
+        mut value: V,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        self.check_key::<K>()?;
+        if self.def.v() != V::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.v(),
+                actual: V::SHAPE,
+            });
+        }
+
+        unsafe {
+            (self.def.vtable.insert_fn)(
+                self.value.data,
+                PtrMut::new(&raw mut key),
+                PtrMut::new(&raw mut value),
+            )
+        };
+        // The map now owns the key and the value
+        core::mem::forget(key);
+        core::mem::forget(value);
+        Ok(self)
+    }
+
+    /// Gives back the underlying [`Poke`]
+    #[inline(always)]
+    pub fn into_inner(self) -> Poke<'mem, 'facet, 'shape> {
+        self.value
+    }
+}
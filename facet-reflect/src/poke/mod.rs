@@ -0,0 +1,16 @@
+//! Allows mutating values that are already initialized
+
+mod value;
+pub use value::*;
+
+mod struct_;
+pub use struct_::*;
+
+mod enum_;
+pub use enum_::*;
+
+mod list;
+pub use list::*;
+
+mod map;
+pub use map::*;
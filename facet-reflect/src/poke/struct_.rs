@@ -0,0 +1,110 @@
+use facet_core::{Facet, FieldError, StructType};
+
+use crate::{Peek, PeekStruct, ReflectError};
+
+use super::Poke;
+
+/// Lets you mutate the fields of an initialized struct
+pub struct PokeStruct<'mem, 'facet, 'shape> {
+    /// the underlying value
+    pub(crate) value: Poke<'mem, 'facet, 'shape>,
+
+    /// the definition of the struct!
+    pub(crate) ty: StructType<'shape>,
+}
+
+impl core::fmt::Debug for PokeStruct<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PokeStruct").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet, 'shape> PokeStruct<'mem, 'facet, 'shape> {
+    /// Returns the struct definition
+    #[inline(always)]
+    pub fn ty(&self) -> &StructType<'shape> {
+        &self.ty
+    }
+
+    /// Returns the number of fields in this struct
+    #[inline(always)]
+    pub fn field_count(&self) -> usize {
+        self.ty.fields.len()
+    }
+
+    /// Returns a read-only view of the struct
+    #[inline]
+    pub fn as_peek_struct(&self) -> PeekStruct<'_, 'facet, 'shape> {
+        PeekStruct {
+            value: self.value.as_peek(),
+            ty: self.ty,
+        }
+    }
+
+    /// Returns a read-only view of the field at the given index
+    #[inline]
+    pub fn peek_field(&self, index: usize) -> Result<Peek<'_, 'facet, 'shape>, FieldError> {
+        self.as_peek_struct().field(index)
+    }
+
+    /// Returns a mutable handle to the field at the given index
+    #[inline]
+    pub fn field(&mut self, index: usize) -> Result<Poke<'_, 'facet, 'shape>, FieldError> {
+        self.ty
+            .fields
+            .get(index)
+            .map(|field| unsafe {
+                let field_data = self.value.data.field(field.offset);
+                Poke::unchecked_new(field_data, field.shape())
+            })
+            .ok_or(FieldError::IndexOutOfBounds {
+                index,
+                bound: self.ty.fields.len(),
+            })
+    }
+
+    /// Returns the index of the field with the given name
+    #[inline]
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.ty.fields.iter().position(|field| field.name == name)
+    }
+
+    /// Returns a mutable handle to the field with the given name
+    #[inline]
+    pub fn field_by_name(&mut self, name: &str) -> Result<Poke<'_, 'facet, 'shape>, FieldError> {
+        let index = self.field_index(name).ok_or(FieldError::NoSuchField)?;
+        self.field(index)
+    }
+
+    /// Replaces the field at the given index with `value`, dropping the previous value.
+    pub fn set_field<T: Facet<'facet>>(
+        &mut self,
+        index: usize,
+        value: T,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        let shape = self.value.shape;
+        self.field(index)
+            .map_err(|field_error| ReflectError::FieldError { shape, field_error })?
+            .set(value)?;
+        Ok(self)
+    }
+
+    /// Replaces the field with the given name with `value`, dropping the previous value.
+    pub fn set_field_by_name<T: Facet<'facet>>(
+        &mut self,
+        name: &str,
+        value: T,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        let index = self.field_index(name).ok_or(ReflectError::FieldError {
+            shape: self.value.shape,
+            field_error: FieldError::NoSuchField,
+        })?;
+        self.set_field(index, value)
+    }
+
+    /// Gives back the underlying [`Poke`]
+    #[inline(always)]
+    pub fn into_inner(self) -> Poke<'mem, 'facet, 'shape> {
+        self.value
+    }
+}
@@ -0,0 +1,221 @@
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use facet_core::Characteristic;
+use facet_core::{Def, Facet, PtrMut, Shape, Type, UserType};
+
+#[cfg(feature = "alloc")]
+use crate::HeapValue;
+use crate::{Peek, ReflectError};
+
+use super::{PokeEnum, PokeList, PokeMap, PokeStruct};
+
+/// Lets you mutate an initialized value (implements mutating [`facet_core::ValueVTable`] proxies)
+///
+/// Unlike [`crate::Partial`], which builds values from scratch, a `Poke` always points
+/// to a fully initialized value: every operation drops whatever it replaces, and leaves
+/// a valid value behind.
+pub struct Poke<'mem, 'facet, 'shape> {
+    /// Underlying data
+    pub(crate) data: PtrMut<'mem>,
+
+    /// Shape of the value
+    pub(crate) shape: &'shape Shape<'shape>,
+
+    invariant: PhantomData<fn(&'facet ()) -> &'facet ()>,
+}
+
+impl core::fmt::Debug for Poke<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Poke<{}>", self.shape)
+    }
+}
+
+impl<'mem, 'facet, 'shape> Poke<'mem, 'facet, 'shape> {
+    /// Creates a new `Poke` instance for a value of type `T`.
+    pub fn new<T: Facet<'facet>>(t: &'mem mut T) -> Self {
+        Self {
+            data: PtrMut::new(t),
+            shape: T::SHAPE,
+            invariant: PhantomData,
+        }
+    }
+
+    /// Creates a new `Poke` instance without checking the type.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because it doesn't check if the provided data
+    /// and shape are compatible. The caller must ensure that the data is valid
+    /// for the given shape, is initialized, and is not aliased for `'mem`.
+    pub unsafe fn unchecked_new(data: PtrMut<'mem>, shape: &'shape Shape<'shape>) -> Self {
+        Self {
+            data,
+            shape,
+            invariant: PhantomData,
+        }
+    }
+
+    /// Returns the shape of the value
+    #[inline(always)]
+    pub fn shape(&self) -> &'shape Shape<'shape> {
+        self.shape
+    }
+
+    /// Returns the underlying data pointer
+    #[inline(always)]
+    pub fn data(&self) -> PtrMut<'mem> {
+        self.data
+    }
+
+    /// Reborrows this `Poke` for a shorter lifetime
+    #[inline]
+    pub fn reborrow(&mut self) -> Poke<'_, 'facet, 'shape> {
+        Poke {
+            data: self.data,
+            shape: self.shape,
+            invariant: PhantomData,
+        }
+    }
+
+    /// Returns a read-only view of the value
+    #[inline]
+    pub fn as_peek(&self) -> Peek<'_, 'facet, 'shape> {
+        unsafe { Peek::unchecked_new(self.data.as_const(), self.shape) }
+    }
+
+    /// Read the value from memory into a Rust value.
+    #[inline]
+    pub fn get<T: Facet<'facet>>(&self) -> Result<&T, ReflectError<'shape>> {
+        if self.shape != T::SHAPE {
+            Err(ReflectError::WrongShape {
+                expected: self.shape,
+                actual: T::SHAPE,
+            })
+        } else {
+            Ok(unsafe { self.data.get::<T>() })
+        }
+    }
+
+    /// Borrow the value mutably as a Rust value.
+    #[inline]
+    pub fn get_mut<T: Facet<'facet>>(&mut self) -> Result<&mut T, ReflectError<'shape>> {
+        if self.shape != T::SHAPE {
+            Err(ReflectError::WrongShape {
+                expected: self.shape,
+                actual: T::SHAPE,
+            })
+        } else {
+            Ok(unsafe { self.data.as_mut::<T>() })
+        }
+    }
+
+    /// Replaces the value with `value`, dropping the previous one.
+    pub fn set<T: Facet<'facet>>(&mut self, value: T) -> Result<&mut Self, ReflectError<'shape>> {
+        if self.shape != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.shape,
+                actual: T::SHAPE,
+            });
+        }
+        unsafe { self.data.replace(value) };
+        Ok(self)
+    }
+
+    /// Replaces the value with its default, dropping the previous one.
+    ///
+    /// The value is left untouched if the shape doesn't implement `Default`.
+    #[cfg(feature = "alloc")]
+    pub fn set_default(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
+        let vtable = self
+            .shape
+            .vtable
+            .sized()
+            .ok_or(ReflectError::Unsized { shape: self.shape })?;
+        if (vtable.default_in_place)().is_none() {
+            return Err(ReflectError::MissingCharacteristic {
+                shape: self.shape,
+                characteristic: Characteristic::Default,
+            });
+        }
+
+        // Build the default on the side first, so the previous value is only dropped
+        // once its replacement exists.
+        let mut partial = crate::Partial::alloc_shape(self.shape)?;
+        partial.set_default()?;
+        let heap_value = partial.build()?;
+        self.replace_with_heap_value(heap_value)?;
+        Ok(self)
+    }
+
+    /// Drops the value and moves the one built in `heap_value` in its place.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn replace_with_heap_value(
+        &mut self,
+        mut heap_value: HeapValue<'facet, 'shape>,
+    ) -> Result<(), ReflectError<'shape>> {
+        let layout = self
+            .shape
+            .layout
+            .sized_layout()
+            .map_err(|_| ReflectError::Unsized { shape: self.shape })?;
+
+        unsafe {
+            if let Some(drop_fn) = self.shape.vtable.sized().and_then(|v| (v.drop_in_place)()) {
+                drop_fn(self.data);
+            }
+            let guard = heap_value.guard.take().unwrap();
+            core::ptr::copy_nonoverlapping(guard.ptr, self.data.as_mut_byte_ptr(), layout.size());
+            // The bytes now live in `self`: free the allocation without dropping them.
+            drop(guard);
+        }
+        Ok(())
+    }
+
+    /// Tries to identify this value as a struct
+    pub fn into_struct(self) -> Result<PokeStruct<'mem, 'facet, 'shape>, ReflectError<'shape>> {
+        if let Type::User(UserType::Struct(ty)) = self.shape.ty {
+            Ok(PokeStruct { value: self, ty })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "struct",
+                actual: self.shape,
+            })
+        }
+    }
+
+    /// Tries to identify this value as an enum
+    pub fn into_enum(self) -> Result<PokeEnum<'mem, 'facet, 'shape>, ReflectError<'shape>> {
+        if let Type::User(UserType::Enum(ty)) = self.shape.ty {
+            Ok(PokeEnum { value: self, ty })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "enum",
+                actual: self.shape,
+            })
+        }
+    }
+
+    /// Tries to identify this value as a list
+    pub fn into_list(self) -> Result<PokeList<'mem, 'facet, 'shape>, ReflectError<'shape>> {
+        if let Def::List(def) = self.shape.def {
+            Ok(PokeList { value: self, def })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "list",
+                actual: self.shape,
+            })
+        }
+    }
+
+    /// Tries to identify this value as a map
+    pub fn into_map(self) -> Result<PokeMap<'mem, 'facet, 'shape>, ReflectError<'shape>> {
+        if let Def::Map(def) = self.shape.def {
+            Ok(PokeMap { value: self, def })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "map",
+                actual: self.shape,
+            })
+        }
+    }
+}
@@ -1,2 +1,4 @@
+mod ops;
 mod partial;
 mod peek;
+mod poke;
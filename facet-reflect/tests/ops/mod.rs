@@ -0,0 +1,191 @@
+use core::hash::Hasher;
+use std::collections::HashMap;
+use std::hash::DefaultHasher;
+
+use facet::Facet;
+use facet_core::Characteristic;
+use facet_reflect::{Peek, ReflectError, reflect_clone, reflect_default, reflect_eq, reflect_hash};
+use facet_testhelpers::test;
+
+// None of these implement Clone, PartialEq, Hash or Default
+
+#[derive(Facet, Debug)]
+struct Plugin {
+    name: String,
+    tags: Vec<Tag>,
+    settings: HashMap<String, Tag>,
+    parent: Option<Box<Tag>>,
+    kind: Kind,
+}
+
+#[derive(Facet, Debug)]
+struct Tag {
+    label: String,
+    weight: u32,
+}
+
+#[derive(Facet, Debug)]
+#[repr(u8)]
+enum Kind {
+    Builtin,
+    External { path: String, priority: [Tag; 2] },
+}
+
+fn tag(label: &str, weight: u32) -> Tag {
+    Tag {
+        label: label.to_string(),
+        weight,
+    }
+}
+
+fn plugin() -> Plugin {
+    Plugin {
+        name: "lint".to_string(),
+        tags: vec![tag("fast", 1), tag("safe", 2)],
+        settings: HashMap::from([
+            ("level".to_string(), tag("warn", 3)),
+            ("scope".to_string(), tag("all", 4)),
+        ]),
+        parent: Some(Box::new(tag("base", 5))),
+        kind: Kind::External {
+            path: "/plugins/lint".to_string(),
+            priority: [tag("high", 6), tag("low", 7)],
+        },
+    }
+}
+
+fn hash_of(value: Peek<'_, '_, '_>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    reflect_hash(value, &mut hasher).unwrap();
+    hasher.finish()
+}
+
+#[test]
+fn test_reflect_eq() {
+    let a = plugin();
+    assert!(reflect_eq(Peek::new(&a), Peek::new(&plugin()))?);
+
+    let mut b = plugin();
+    b.tags.pop();
+    assert!(!reflect_eq(Peek::new(&a), Peek::new(&b))?);
+
+    let mut b = plugin();
+    b.settings.get_mut("scope").unwrap().weight = 40;
+    assert!(!reflect_eq(Peek::new(&a), Peek::new(&b))?);
+
+    let mut b = plugin();
+    b.parent = None;
+    assert!(!reflect_eq(Peek::new(&a), Peek::new(&b))?);
+
+    let mut b = plugin();
+    b.kind = Kind::Builtin;
+    assert!(!reflect_eq(Peek::new(&a), Peek::new(&b))?);
+
+    // Different shapes are never equal
+    assert!(!reflect_eq(Peek::new(&a), Peek::new(&a.tags[0]))?);
+}
+
+#[test]
+fn test_reflect_hash() {
+    assert_eq!(hash_of(Peek::new(&plugin())), hash_of(Peek::new(&plugin())));
+
+    let mut b = plugin();
+    b.tags[1].weight = 20;
+    assert_ne!(hash_of(Peek::new(&plugin())), hash_of(Peek::new(&b)));
+
+    let mut b = plugin();
+    b.kind = Kind::Builtin;
+    assert_ne!(hash_of(Peek::new(&plugin())), hash_of(Peek::new(&b)));
+}
+
+#[test]
+fn test_reflect_hash_ignores_map_order() {
+    let forward: HashMap<u32, Tag> = (0..32).map(|i| (i, tag("tag", i))).collect();
+    let backward: HashMap<u32, Tag> = (0..32).rev().map(|i| (i, tag("tag", i))).collect();
+    assert!(reflect_eq(Peek::new(&forward), Peek::new(&backward))?);
+    assert_eq!(hash_of(Peek::new(&forward)), hash_of(Peek::new(&backward)));
+}
+
+#[test]
+fn test_reflect_clone() {
+    let original = plugin();
+    let copy = reflect_clone(Peek::new(&original))?;
+    assert!(reflect_eq(Peek::new(&original), copy.peek())?);
+
+    let copy = copy.materialize::<Plugin>()?;
+    assert_eq!(copy.name, "lint");
+    assert_eq!(copy.tags[1].label, "safe");
+    assert_eq!(copy.settings["level"].weight, 3);
+    assert_eq!(copy.parent.unwrap().label, "base");
+    match copy.kind {
+        Kind::External { path, priority } => {
+            assert_eq!(path, "/plugins/lint");
+            assert_eq!(priority[1].label, "low");
+        }
+        Kind::Builtin => panic!("expected the External variant"),
+    }
+}
+
+#[test]
+fn test_reflect_clone_unit_variant() {
+    let copy = reflect_clone(Peek::new(&Kind::Builtin))?;
+    assert!(matches!(copy.materialize::<Kind>()?, Kind::Builtin));
+}
+
+#[test]
+fn test_reflect_ops_on_result() {
+    let ok: Result<Tag, Tag> = Ok(tag("fast", 1));
+    let err: Result<Tag, Tag> = Err(tag("fast", 1));
+    assert!(reflect_eq(
+        Peek::new(&ok),
+        Peek::new(&Ok::<Tag, Tag>(tag("fast", 1)))
+    )?);
+    assert!(!reflect_eq(Peek::new(&ok), Peek::new(&err))?);
+    assert_ne!(hash_of(Peek::new(&ok)), hash_of(Peek::new(&err)));
+
+    let copy = reflect_clone(Peek::new(&err))?;
+    assert!(reflect_eq(Peek::new(&err), copy.peek())?);
+    match copy.materialize::<Result<Tag, Tag>>()? {
+        Err(tag) => assert_eq!(tag.label, "fast"),
+        Ok(_) => panic!("expected an Err"),
+    }
+}
+
+#[test]
+fn test_reflect_default() {
+    #[derive(Facet, Debug)]
+    struct Settings {
+        name: String,
+        retries: u32,
+        #[facet(default = default_port())]
+        port: u16,
+        tags: Vec<Tag>,
+        by_name: HashMap<String, Tag>,
+        parent: Option<Tag>,
+        weights: [Tag; 2],
+    }
+
+    fn default_port() -> u16 {
+        8080
+    }
+
+    let settings = reflect_default(Settings::SHAPE)?.materialize::<Settings>()?;
+    assert_eq!(settings.name, "");
+    assert_eq!(settings.retries, 0);
+    assert_eq!(settings.port, 8080);
+    assert!(settings.tags.is_empty());
+    assert!(settings.by_name.is_empty());
+    assert!(settings.parent.is_none());
+    assert_eq!(settings.weights[1].weight, 0);
+}
+
+#[test]
+fn test_reflect_default_enum() {
+    assert!(matches!(
+        reflect_default(Kind::SHAPE),
+        Err(ReflectError::MissingCharacteristic {
+            characteristic: Characteristic::Default,
+            ..
+        })
+    ));
+}
@@ -1,6 +1,7 @@
-use facet_reflect::Partial;
+use facet_reflect::{Partial, ReflectError};
 use facet_testhelpers::test;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[test]
 fn wip_map_trivial() {
@@ -20,3 +21,101 @@ fn wip_map_trivial() {
         HashMap::from([("key".to_string(), "value".to_string())])
     );
 }
+
+#[test]
+fn wip_map_field_keeps_entries() {
+    #[derive(facet::Facet, Debug, PartialEq)]
+    struct Doc {
+        name: String,
+        extra: HashMap<String, u32>,
+    }
+
+    let mut partial = Partial::alloc::<Doc>()?;
+    for (key, value) in [("a", 1), ("b", 2)] {
+        partial.begin_nth_map_field(1)?;
+        partial.begin_key()?;
+        partial.set::<String>(key.into())?;
+        partial.end()?;
+        partial.begin_value()?;
+        partial.set::<u32>(value)?;
+        partial.end()?;
+        partial.end()?;
+
+        if key == "a" {
+            partial.begin_nth_field(0)?;
+            partial.set::<String>("doc".into())?;
+            partial.end()?;
+        }
+    }
+    let doc: Doc = *partial.build()?;
+
+    assert_eq!(
+        doc,
+        Doc {
+            name: "doc".to_string(),
+            extra: HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+        }
+    );
+}
+
+#[test]
+fn wip_map_field_starts_empty() {
+    #[derive(facet::Facet, Debug, PartialEq)]
+    struct Doc {
+        extra: HashMap<String, u32>,
+    }
+
+    let mut partial = Partial::alloc::<Doc>()?;
+    partial.begin_nth_map_field(0)?;
+    partial.end()?;
+    let doc: Doc = *partial.build()?;
+
+    assert_eq!(doc.extra, HashMap::new());
+}
+
+#[test]
+fn wip_map_field_dropped_mid_entry() {
+    #[derive(facet::Facet, Debug, PartialEq)]
+    struct Doc {
+        extra: HashMap<String, Arc<u32>>,
+    }
+
+    let value = Arc::new(1);
+    {
+        let mut partial = Partial::alloc::<Doc>()?;
+        partial.begin_nth_map_field(0)?;
+        partial.begin_key()?;
+        partial.set::<String>("a".into())?;
+        partial.end()?;
+        partial.begin_value()?;
+        partial.set(value.clone())?;
+        partial.end()?;
+        partial.end()?;
+
+        // The second entry fails halfway, and the partial is dropped
+        partial.begin_nth_map_field(0)?;
+        partial.begin_key()?;
+        partial.set::<String>("b".into())?;
+        partial.end()?;
+        partial.begin_value()?;
+        assert!(partial.set::<String>("oops".into()).is_err());
+    }
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn wip_map_field_on_initialized_struct() {
+    #[derive(facet::Facet, Debug, PartialEq)]
+    struct Doc {
+        extra: HashMap<String, u32>,
+    }
+
+    let mut partial = Partial::alloc::<Doc>()?;
+    partial.set(Doc {
+        extra: HashMap::new(),
+    })?;
+    assert!(matches!(
+        partial.begin_nth_map_field(0),
+        Err(ReflectError::OperationFailed { .. })
+    ));
+}
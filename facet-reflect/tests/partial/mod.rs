@@ -12,6 +12,7 @@ mod no_uninit;
 mod option_building;
 mod option_leak;
 mod put_vec_leak;
+mod result_building;
 mod struct_leak;
 mod tuples;
 mod variance;
@@ -0,0 +1,77 @@
+use facet_reflect::Partial;
+use facet_testhelpers::test;
+
+#[test]
+fn test_result_building_with_begin_ok() {
+    let mut wip = Partial::alloc::<Result<u32, String>>()?;
+    wip.begin_ok()?;
+    wip.set(42u32)?;
+    wip.end()?;
+
+    let result: Result<u32, String> = *wip.build()?;
+    assert_eq!(result, Ok(42));
+}
+
+#[test]
+fn test_result_building_with_begin_err() {
+    let mut wip = Partial::alloc::<Result<u32, String>>()?;
+    wip.begin_err()?;
+    wip.set("oops".to_string())?;
+    wip.end()?;
+
+    let result: Result<u32, String> = *wip.build()?;
+    assert_eq!(result, Err("oops".to_string()));
+}
+
+#[test]
+fn test_result_building_replaces_variant() {
+    let mut wip = Partial::alloc::<Result<String, String>>()?;
+    wip.begin_ok()?.set("first".to_string())?.end()?;
+    wip.begin_err()?.set("second".to_string())?.end()?;
+
+    let result: Result<String, String> = *wip.build()?;
+    assert_eq!(result, Err("second".to_string()));
+}
+
+#[test]
+fn test_result_building_nested() {
+    let mut wip = Partial::alloc::<Result<Option<String>, u8>>()?;
+    wip.begin_ok()?;
+    wip.begin_some()?;
+    wip.set("hello".to_string())?;
+    wip.end()?;
+    wip.end()?;
+
+    let result: Result<Option<String>, u8> = *wip.build()?;
+    assert_eq!(result, Ok(Some("hello".to_string())));
+}
+
+#[test]
+fn test_result_building_unfinished_inner_value() {
+    let mut wip = Partial::alloc::<Result<u32, String>>()?;
+    wip.begin_ok()?;
+    assert!(wip.build().is_err());
+}
+
+#[test]
+fn test_result_begin_ok_on_non_result() {
+    let mut wip = Partial::alloc::<Option<u32>>()?;
+    assert!(wip.begin_ok().is_err());
+}
+
+#[test]
+fn wip_result_testleak1() {
+    let mut wip = Partial::alloc::<Result<String, String>>()?;
+    wip.begin_err()?;
+    wip.set(String::from("Hello, world!"))?;
+    // Don't call end() or build() to test partial initialization
+}
+
+#[test]
+fn wip_result_testleak2() {
+    let mut wip = Partial::alloc::<Result<String, String>>()?;
+    wip.begin_ok()?;
+    wip.set(String::from("Hello, world!"))?;
+    wip.end()?;
+    // Don't call build() to test partial initialization
+}
@@ -6,7 +6,9 @@ mod list;
 mod list_like;
 mod map;
 mod option;
+mod path;
 mod reference;
+mod result;
 mod smartptr;
 mod struct_;
 mod value;
@@ -0,0 +1,250 @@
+use std::collections::{BTreeMap, HashMap};
+
+use facet::Facet;
+use facet_reflect::{Path, PathErrorKind, PathSegment, Peek};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+    labels: HashMap<String, String>,
+    ports: BTreeMap<u16, String>,
+}
+
+#[derive(Facet)]
+struct Server {
+    host: String,
+    tls: Option<Box<Tls>>,
+    weights: (u8, u8),
+}
+
+#[derive(Facet)]
+struct Tls {
+    cert_path: String,
+}
+
+fn config() -> Config {
+    Config {
+        name: "prod".to_string(),
+        servers: vec![
+            Server {
+                host: "a.example.com".to_string(),
+                tls: None,
+                weights: (1, 2),
+            },
+            Server {
+                host: "b.example.com".to_string(),
+                tls: Some(Box::new(Tls {
+                    cert_path: "/etc/b.pem".to_string(),
+                })),
+                weights: (3, 4),
+            },
+        ],
+        labels: [("env".to_string(), "production".to_string())]
+            .into_iter()
+            .collect(),
+        ports: [(80, "http".to_string()), (443, "https".to_string())]
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn get<'a>(value: &'a Config, path: &str) -> Peek<'a, 'static, 'static> {
+    Peek::new(value).at_path(&path.parse().unwrap()).unwrap()
+}
+
+#[test]
+fn parse_and_display() {
+    let path = Path::parse(r#"servers[2].tls.cert_path"#)?;
+    assert_eq!(
+        path.segments(),
+        &[
+            PathSegment::Field("servers".to_string()),
+            PathSegment::Index(2),
+            PathSegment::Field("tls".to_string()),
+            PathSegment::Field("cert_path".to_string()),
+        ]
+    );
+    assert_eq!(path.to_string(), "servers[2].tls.cert_path");
+
+    let path = Path::parse(r#"labels["a \"b\""][env].*[*]"#)?;
+    assert_eq!(
+        path.segments(),
+        &[
+            PathSegment::Field("labels".to_string()),
+            PathSegment::Key(r#"a "b""#.to_string()),
+            PathSegment::Key("env".to_string()),
+            PathSegment::Wildcard,
+            PathSegment::Wildcard,
+        ]
+    );
+    assert_eq!(path.to_string(), r#"labels["a \"b\""]["env"][*][*]"#);
+    assert!(path.has_wildcard());
+
+    assert_eq!(Path::parse("")?, Path::root());
+}
+
+#[test]
+fn parse_errors() {
+    let err = Path::parse("servers[2").unwrap_err();
+    assert_eq!(err.position, 9);
+
+    let err = Path::parse("servers..tls").unwrap_err();
+    assert_eq!(err.position, 8);
+
+    assert!(Path::parse(r#"labels["env]"#).is_err());
+    assert!(Path::parse("labels[]").is_err());
+    assert!(Path::parse("a b").is_err());
+}
+
+#[test]
+fn resolve_fields_and_indices() {
+    let config = config();
+
+    assert_eq!(get(&config, "name").get::<String>()?, "prod");
+    assert_eq!(
+        get(&config, "servers[0].host").get::<String>()?,
+        "a.example.com"
+    );
+    assert_eq!(get(&config, "servers[1].weights[1]").get::<u8>()?, &4);
+    assert_eq!(get(&config, "servers[1].weights.0").get::<u8>()?, &3);
+
+    let root = get(&config, "");
+    assert_eq!(root.shape(), Config::SHAPE);
+}
+
+#[test]
+fn resolve_through_option_and_box() {
+    let config = config();
+    assert_eq!(
+        get(&config, "servers[1].tls.cert_path").get::<String>()?,
+        "/etc/b.pem"
+    );
+
+    let err = Peek::new(&config)
+        .at_path(&"servers[0].tls.cert_path".parse()?)
+        .unwrap_err();
+    assert_eq!(err.kind, PathErrorKind::NoneValue);
+    assert_eq!(err.resolved.to_string(), "servers[0].tls");
+}
+
+#[test]
+fn resolve_map_keys() {
+    let config = config();
+    assert_eq!(
+        get(&config, r#"labels["env"]"#).get::<String>()?,
+        "production"
+    );
+    assert_eq!(get(&config, "labels.env").get::<String>()?, "production");
+    // Keys are parsed with the key shape
+    assert_eq!(get(&config, "ports[443]").get::<String>()?, "https");
+
+    let err = Peek::new(&config)
+        .at_path(&"labels.region".parse()?)
+        .unwrap_err();
+    assert_eq!(err.kind, PathErrorKind::NoSuchKey("region".to_string()));
+
+    let err = Peek::new(&config)
+        .at_path(&"ports[http]".parse()?)
+        .unwrap_err();
+    assert_eq!(err.kind, PathErrorKind::InvalidKey("http".to_string()));
+}
+
+#[test]
+fn resolve_str_keys() {
+    let mut map = HashMap::new();
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    let value = Peek::new(&map).at_path(&"b".parse()?).unwrap();
+    assert_eq!(value.get::<i32>()?, &2);
+}
+
+#[test]
+fn resolve_enum_fields() {
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Source {
+        File { path: String },
+        Inline(String),
+    }
+
+    let source = Source::File {
+        path: "a.toml".to_string(),
+    };
+    let value = Peek::new(&source).at_path(&"path".parse()?).unwrap();
+    assert_eq!(value.get::<String>()?, "a.toml");
+
+    let source = Source::Inline("x = 1".to_string());
+    let value = Peek::new(&source).at_path(&"0".parse()?).unwrap();
+    assert_eq!(value.get::<String>()?, "x = 1");
+
+    let err = Peek::new(&source).at_path(&"path".parse()?).unwrap_err();
+    assert_eq!(err.kind, PathErrorKind::NoSuchField("path".to_string()));
+}
+
+#[test]
+fn errors_are_precise() {
+    let config = config();
+    let peek = Peek::new(&config);
+
+    let err = peek.at_path(&"servers[5]".parse()?).unwrap_err();
+    assert_eq!(
+        err.kind,
+        PathErrorKind::IndexOutOfBounds { index: 5, len: 2 }
+    );
+    assert_eq!(err.resolved.to_string(), "servers");
+
+    let err = peek.at_path(&"servers[0].port".parse()?).unwrap_err();
+    assert_eq!(err.kind, PathErrorKind::NoSuchField("port".to_string()));
+    assert_eq!(err.shape, Server::SHAPE);
+    assert_eq!(err.to_string(), "at `servers[0]` (Server): no field `port`");
+
+    let err = peek.at_path(&"name[0]".parse()?).unwrap_err();
+    assert_eq!(err.kind, PathErrorKind::NotIndexable);
+
+    let err = peek.at_path(&"servers[*].host".parse()?).unwrap_err();
+    assert_eq!(err.kind, PathErrorKind::Wildcard);
+}
+
+#[test]
+fn wildcards() {
+    let config = config();
+    let peek = Peek::new(&config);
+
+    let hosts: Vec<_> = peek
+        .at_path_all(&"servers[*].host".parse()?)
+        .map(|(path, value)| (path.to_string(), value.get::<String>().unwrap().clone()))
+        .collect();
+    assert_eq!(
+        hosts,
+        [
+            ("servers[0].host".to_string(), "a.example.com".to_string()),
+            ("servers[1].host".to_string(), "b.example.com".to_string()),
+        ]
+    );
+
+    // Branches that don't resolve are skipped
+    let certs: Vec<_> = peek
+        .at_path_all(&"servers.*.tls.cert_path".parse()?)
+        .map(|(path, _)| path.to_string())
+        .collect();
+    assert_eq!(certs, ["servers[1].tls.cert_path"]);
+
+    let fields: Vec<_> = peek
+        .at_path_all(&"servers[0].*".parse()?)
+        .map(|(path, _)| path.to_string())
+        .collect();
+    assert_eq!(
+        fields,
+        ["servers[0].host", "servers[0].tls", "servers[0].weights"]
+    );
+
+    let ports: Vec<_> = peek
+        .at_path_all(&"ports[*]".parse()?)
+        .map(|(_, value)| value.get::<String>().unwrap().clone())
+        .collect();
+    assert_eq!(ports, ["http", "https"]);
+}
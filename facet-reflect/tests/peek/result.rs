@@ -0,0 +1,43 @@
+use facet_reflect::Peek;
+use facet_testhelpers::test;
+
+#[test]
+fn peek_result() {
+    // Test with an Ok value
+    let ok_value: Result<i32, String> = Ok(42);
+    let peek_value = Peek::new(&ok_value);
+
+    // Convert to result
+    let peek_result = peek_value
+        .into_result()
+        .expect("Should be convertible to result");
+
+    // Check the Ok variant methods
+    assert!(peek_result.is_ok());
+    assert!(!peek_result.is_err());
+    assert!(peek_result.err().is_none());
+
+    // Get the inner value
+    let inner_value = peek_result.ok().expect("Should have a value");
+    let value = inner_value.get::<i32>().unwrap();
+    assert_eq!(*value, 42);
+
+    // Test with an Err value
+    let err_value: Result<i32, String> = Err("nope".to_string());
+    let peek_value = Peek::new(&err_value);
+
+    // Convert to result
+    let peek_result = peek_value
+        .into_result()
+        .expect("Should be convertible to result");
+
+    // Check the Err variant methods
+    assert!(!peek_result.is_ok());
+    assert!(peek_result.is_err());
+    assert!(peek_result.ok().is_none());
+
+    // Get the inner error
+    let inner_error = peek_result.err().expect("Should have an error");
+    let error = inner_error.get::<String>().unwrap();
+    assert_eq!(error, "nope");
+}
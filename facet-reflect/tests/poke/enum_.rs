@@ -0,0 +1,66 @@
+use facet::Facet;
+use facet_reflect::{Poke, ReflectError};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Message {
+    Quit,
+    Write(String),
+    Move { x: i32, y: i32 },
+}
+
+#[test]
+fn poke_enum_set_variant() {
+    let mut value = Message::Write("hello".to_string());
+    let mut poke = Poke::new(&mut value).into_enum()?;
+    assert_eq!(poke.active_variant()?.name, "Write");
+
+    poke.set_variant_named("Move")?;
+    assert_eq!(poke.variant_index()?, 2);
+    poke.field_by_name("y")?.unwrap().set(5_i32)?;
+    assert!(poke.field_by_name("z")?.is_none());
+    assert_eq!(value, Message::Move { x: 0, y: 5 });
+
+    let mut poke = Poke::new(&mut value).into_enum()?;
+    poke.set_variant(0)?;
+    assert!(poke.field(0)?.is_none());
+    assert_eq!(value, Message::Quit);
+}
+
+#[test]
+fn poke_enum_set_field() {
+    let mut value = Message::Write("hello".to_string());
+    let mut poke = Poke::new(&mut value).into_enum()?;
+    poke.set_field(0, "bye".to_string())?;
+    assert!(matches!(
+        poke.set_field(1, "extra".to_string()),
+        Err(ReflectError::FieldError { .. })
+    ));
+    assert_eq!(value, Message::Write("bye".to_string()));
+}
+
+#[test]
+fn poke_enum_errors() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[allow(dead_code)]
+    struct NoDefault(u32);
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Holder {
+        Empty,
+        Full(NoDefault),
+    }
+
+    let mut value = Holder::Empty;
+    let mut poke = Poke::new(&mut value).into_enum()?;
+    assert!(matches!(
+        poke.set_variant_named("Missing"),
+        Err(ReflectError::NoSuchVariant { .. })
+    ));
+    assert!(poke.set_variant_named("Full").is_err());
+    assert_eq!(value, Holder::Empty);
+}
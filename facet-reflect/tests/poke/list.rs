@@ -0,0 +1,30 @@
+use facet_reflect::{Poke, ReflectError};
+use facet_testhelpers::test;
+
+#[test]
+fn poke_list_push_and_get_mut() {
+    let mut value = vec!["a".to_string()];
+    let mut poke = Poke::new(&mut value).into_list()?;
+    assert_eq!(poke.len(), 1);
+
+    poke.push("b".to_string())?.push("c".to_string())?;
+    assert_eq!(poke.len(), 3);
+    assert!(!poke.is_empty());
+
+    poke.get_mut(0).unwrap().set("z".to_string())?;
+    assert!(poke.get_mut(3).is_none());
+    assert_eq!(poke.as_peek_list().get(1).unwrap().get::<String>()?, "b");
+
+    assert_eq!(value, vec!["z", "b", "c"]);
+}
+
+#[test]
+fn poke_list_push_wrong_shape() {
+    let mut value: Vec<u8> = vec![];
+    let mut poke = Poke::new(&mut value).into_list()?;
+    assert!(matches!(
+        poke.push(1_u32),
+        Err(ReflectError::WrongShape { .. })
+    ));
+    assert!(poke.is_empty());
+}
@@ -0,0 +1,54 @@
+use facet_reflect::{Poke, ReflectError};
+use facet_testhelpers::test;
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn poke_map_insert_and_get_mut() {
+    let mut value: HashMap<String, u32> = HashMap::new();
+    let mut poke = Poke::new(&mut value).into_map()?;
+    assert!(poke.is_empty());
+
+    poke.insert("a".to_string(), 1_u32)?
+        .insert("b".to_string(), 2_u32)?;
+    assert_eq!(poke.len(), 2);
+    assert!(poke.contains_key(&"a".to_string())?);
+    assert!(!poke.contains_key(&"c".to_string())?);
+
+    // Inserting an existing key replaces its value
+    poke.insert("a".to_string(), 10_u32)?;
+    assert_eq!(poke.len(), 2);
+
+    poke.get_mut(&"b".to_string())?.unwrap().set(20_u32)?;
+    assert!(poke.get_mut(&"c".to_string())?.is_none());
+
+    assert_eq!(value.len(), 2);
+    assert_eq!(value["a"], 10);
+    assert_eq!(value["b"], 20);
+}
+
+#[test]
+fn poke_btreemap_get_mut() {
+    let mut value = BTreeMap::from([(1_u64, "one".to_string())]);
+    let mut poke = Poke::new(&mut value).into_map()?;
+
+    poke.get_mut(&1_u64)?.unwrap().set("uno".to_string())?;
+    assert!(poke.get_mut(&2_u64)?.is_none());
+    assert_eq!(value[&1], "uno");
+}
+
+#[test]
+fn poke_map_wrong_shape() {
+    let mut value: HashMap<String, u32> = HashMap::new();
+    let mut poke = Poke::new(&mut value).into_map()?;
+
+    assert!(matches!(
+        poke.insert(1_u32, 1_u32),
+        Err(ReflectError::WrongShape { .. })
+    ));
+    assert!(matches!(
+        poke.insert("a".to_string(), "b".to_string()),
+        Err(ReflectError::WrongShape { .. })
+    ));
+    assert!(poke.contains_key(&"a").is_err());
+    assert!(value.is_empty());
+}
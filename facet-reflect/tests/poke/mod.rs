@@ -0,0 +1,5 @@
+mod enum_;
+mod list;
+mod map;
+mod struct_;
+mod value;
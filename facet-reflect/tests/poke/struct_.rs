@@ -0,0 +1,78 @@
+use facet::Facet;
+use facet_reflect::{Poke, ReflectError};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Inner {
+    flag: bool,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Outer {
+    number: i32,
+    text: String,
+    inner: Inner,
+}
+
+fn outer() -> Outer {
+    Outer {
+        number: 1,
+        text: "one".to_string(),
+        inner: Inner { flag: false },
+    }
+}
+
+#[test]
+fn poke_struct_set_fields() {
+    let mut value = outer();
+    let mut poke = Poke::new(&mut value).into_struct()?;
+    assert_eq!(poke.field_count(), 3);
+
+    poke.set_field_by_name("number", 2_i32)?
+        .set_field(1, "two".to_string())?;
+    assert_eq!(*poke.peek_field(0)?.get::<i32>()?, 2);
+
+    poke.field_by_name("inner")?
+        .into_struct()?
+        .set_field_by_name("flag", true)?;
+
+    assert_eq!(
+        value,
+        Outer {
+            number: 2,
+            text: "two".to_string(),
+            inner: Inner { flag: true },
+        }
+    );
+}
+
+#[test]
+fn poke_struct_field_set_default() {
+    let mut value = outer();
+    let mut poke = Poke::new(&mut value).into_struct()?;
+    poke.field_by_name("text")?.set_default()?;
+    poke.field(0)?.set_default()?;
+    assert_eq!(value.text, "");
+    assert_eq!(value.number, 0);
+}
+
+#[test]
+fn poke_struct_errors() {
+    let mut value = outer();
+    let mut poke = Poke::new(&mut value).into_struct()?;
+
+    assert!(matches!(
+        poke.set_field_by_name("nope", 1_i32),
+        Err(ReflectError::FieldError { .. })
+    ));
+    assert!(matches!(
+        poke.set_field(7, 1_i32),
+        Err(ReflectError::FieldError { .. })
+    ));
+    assert!(matches!(
+        poke.set_field_by_name("number", "not a number".to_string()),
+        Err(ReflectError::WrongShape { .. })
+    ));
+
+    assert_eq!(value, outer());
+}
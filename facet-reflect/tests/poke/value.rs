@@ -0,0 +1,76 @@
+use facet_reflect::{Poke, ReflectError};
+use facet_testhelpers::test;
+
+#[test]
+fn poke_set_scalar() {
+    let mut value = 42_u32;
+    let mut poke = Poke::new(&mut value);
+    assert_eq!(*poke.get::<u32>()?, 42);
+
+    poke.set(1234_u32)?;
+    assert_eq!(*poke.get::<u32>()?, 1234);
+
+    *poke.get_mut::<u32>()? += 1;
+    assert_eq!(value, 1235);
+}
+
+#[test]
+fn poke_set_wrong_shape() {
+    let mut value = String::from("hello");
+    let mut poke = Poke::new(&mut value);
+
+    let err = poke.set(42_u64).unwrap_err();
+    assert!(matches!(err, ReflectError::WrongShape { .. }));
+    assert!(poke.get::<u64>().is_err());
+    assert_eq!(value, "hello");
+}
+
+#[test]
+fn poke_set_drops_previous_value() {
+    use std::rc::Rc;
+
+    let old = Rc::new(());
+    let mut value = Some(old.clone());
+    assert_eq!(Rc::strong_count(&old), 2);
+
+    Poke::new(&mut value).set(None::<Rc<()>>)?;
+    assert_eq!(Rc::strong_count(&old), 1);
+    assert!(value.is_none());
+}
+
+#[test]
+fn poke_set_default() {
+    let mut value = vec![1, 2, 3];
+    Poke::new(&mut value).set_default()?;
+    assert!(value.is_empty());
+
+    let mut value = String::from("not empty");
+    Poke::new(&mut value).set_default()?;
+    assert_eq!(value, "");
+}
+
+#[test]
+fn poke_set_default_drops_previous_value() {
+    use std::rc::Rc;
+
+    let old = Rc::new(());
+    let mut value = vec![old.clone(), old.clone()];
+    assert_eq!(Rc::strong_count(&old), 3);
+
+    Poke::new(&mut value).set_default()?;
+    assert_eq!(Rc::strong_count(&old), 1);
+    assert!(value.is_empty());
+}
+
+#[test]
+fn poke_into_wrong_kind() {
+    let mut value = 5_i32;
+    assert!(matches!(
+        Poke::new(&mut value).into_struct(),
+        Err(ReflectError::WasNotA { .. })
+    ));
+    assert!(matches!(
+        Poke::new(&mut value).into_list(),
+        Err(ReflectError::WasNotA { .. })
+    ));
+}
@@ -1,6 +1,8 @@
 use crate::Serializer;
 
+use alloc::string::String;
 use alloc::vec::Vec;
+use facet_core::Shape;
 
 struct DebugSerializer<W> {
     writer: W,
@@ -10,11 +12,15 @@ struct DebugSerializer<W> {
 #[derive(Debug)]
 enum DebugError {
     Fmt(core::fmt::Error),
+    Unsupported(String),
 }
 
 impl core::fmt::Display for DebugError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        core::fmt::Debug::fmt(self, f)
+        match self {
+            DebugError::Fmt(_) => core::fmt::Debug::fmt(self, f),
+            DebugError::Unsupported(reason) => f.write_str(reason),
+        }
     }
 }
 
@@ -228,6 +234,10 @@ where
         Ok(())
     }
 
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error {
+        DebugError::Unsupported(alloc::format!("{shape}: {reason}"))
+    }
+
     fn end_map(&mut self) -> Result<(), Self::Error> {
         self.need_comma.pop();
         write!(self.writer, "}}")?;
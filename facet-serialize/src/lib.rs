@@ -11,10 +11,12 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use facet_core::{
-    Def, Facet, Field, PointerType, ScalarAffinity, ShapeAttribute, StructKind, Type, UserType,
+    Def, Facet, Field, KnownSmartPointer, PointerType, ScalarAffinity, Shape, ShapeAttribute,
+    StructKind, Type, UserType, Variant,
 };
 use facet_reflect::{
-    FieldIter, FieldsForSerializeIter, HasFields, Peek, PeekListLikeIter, PeekMapIter, ScalarType,
+    FieldIter, FieldsForSerializeIter, HasFields, Peek, PeekEnum, PeekListLikeIter, PeekMapIter,
+    ScalarType, ValueId,
 };
 use log::{debug, trace};
 
@@ -95,6 +97,16 @@ pub trait Serializer<'shape> {
     /// * `name` - The field or key name to serialize.
     fn serialize_field_name(&mut self, name: &'shape str) -> Result<(), Self::Error>;
 
+    /// Serialize the name of a struct field, with access to the field itself.
+    ///
+    /// Formats that lay fields out differently depending on their attributes (like KDL,
+    /// where a field can be an argument, a property or a child node) can override this.
+    /// The default implementation forwards the field name to [`Self::serialize_field_name`].
+    #[inline(always)]
+    fn serialize_field(&mut self, field: &Field<'shape>) -> Result<(), Self::Error> {
+        self.serialize_field_name(field.name)
+    }
+
     /// Begin serializing an array/sequence-like value.
     ///
     /// # Arguments
@@ -109,6 +121,15 @@ pub trait Serializer<'shape> {
     /// * `len` - The number of entries, if known.
     fn start_map(&mut self, len: Option<usize>) -> Result<(), Self::Error>;
 
+    /// Build the error returned for a value the shape-driven traversal can't represent,
+    /// like a tuple variant of an internally tagged enum.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - The shape of the value being serialized.
+    /// * `reason` - Why the value can't be serialized.
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error;
+
     /// Serialize an unsigned 8-bit integer.
     #[inline(always)]
     fn serialize_u8(&mut self, value: u8) -> Result<(), Self::Error> {
@@ -219,6 +240,32 @@ pub trait Serializer<'shape> {
         let _ = discriminant;
         Ok(())
     }
+
+    /// Signal the start of a value behind a shared smart pointer, like `Rc` or `Arc`.
+    ///
+    /// `id` is the same for every pointer sharing the value. Returning `false` skips the value,
+    /// for formats that can refer back to a value they already wrote.
+    #[inline(always)]
+    fn start_shared(&mut self, id: ValueId<'shape>) -> Result<bool, Self::Error> {
+        let _ = id;
+        Ok(true)
+    }
+
+    /// Signal the end of a value started with `start_shared`.
+    #[inline(always)]
+    fn end_shared(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Give the format a chance to write a value its own way, before the generic logic does.
+    ///
+    /// Returning `true` means the value was written and is skipped by the generic logic, for
+    /// types a format has a native representation for (like MessagePack timestamps).
+    #[inline(always)]
+    fn serialize_custom(&mut self, value: Peek<'_, '_, 'shape>) -> Result<bool, Self::Error> {
+        let _ = value;
+        Ok(false)
+    }
 }
 
 // --- Iterative Serialization Logic ---
@@ -249,14 +296,19 @@ enum SerializeTask<'mem, 'facet, 'shape> {
         items: FieldIter<'mem, 'facet, 'shape>,
         first: bool,
     },
+    // The entries of a flattened map, written inline in the parent object
+    FlattenedMap {
+        entries: PeekMapIter<'mem, 'facet, 'shape>,
+    },
     // End markers
     EndObject,
     EndArray,
     EndMapKey,
     EndMapValue,
     EndField,
+    EndShared,
     // Field-related tasks
-    SerializeFieldName(&'shape str),
+    SerializeField(Field<'shape>),
     SerializeMapKey(Peek<'mem, 'facet, 'shape>),
     SerializeMapValue(Peek<'mem, 'facet, 'shape>),
 }
@@ -279,6 +331,10 @@ where
             SerializeTask::Value(mut cpeek, maybe_field) => {
                 debug!("Serializing a value, shape is {}", cpeek.shape());
 
+                if serializer.serialize_custom(cpeek)? {
+                    continue;
+                }
+
                 if cpeek
                     .shape()
                     .attributes
@@ -471,12 +527,36 @@ where
                             serializer.serialize_none()?;
                         }
                     }
+                    (Def::Result(_), _) => {
+                        // Externally tagged, like an enum: `{ "Ok": value }` or `{ "Err": error }`
+                        let result = cpeek.into_result().unwrap();
+                        let (discriminant, name, inner_peek) = match result.ok() {
+                            Some(ok) => (0, "Ok", ok),
+                            None => (1, "Err", result.err().unwrap()),
+                        };
+                        serializer.start_enum_variant(discriminant)?;
+                        serializer.start_object(Some(1))?;
+                        stack.push(SerializeTask::EndObject);
+
+                        stack.push(SerializeTask::EndField);
+                        serializer.serialize_field_name(name)?;
+                        stack.push(SerializeTask::Value(inner_peek, None));
+                    }
                     (Def::SmartPointer(_), _) => {
                         // For smart pointers, we need to borrow the inner value and serialize it
                         // This is similar to how transparent structs work - we serialize the inner value directly
 
                         let sp = cpeek.into_smart_pointer().unwrap();
                         if let Some(inner_peek) = sp.borrow_inner() {
+                            if matches!(
+                                sp.def().known,
+                                Some(KnownSmartPointer::Rc | KnownSmartPointer::Arc)
+                            ) {
+                                if !serializer.start_shared(inner_peek.id())? {
+                                    continue;
+                                }
+                                stack.push(SerializeTask::EndShared);
+                            }
                             // Push the inner value to be serialized
                             stack.push(SerializeTask::Value(inner_peek, None));
                         } else {
@@ -535,7 +615,7 @@ where
                             StructKind::Struct => {
                                 debug!("  Handling record struct");
                                 let peek_struct = cpeek.into_struct().unwrap();
-                                let fields = peek_struct.fields_for_serialize().count();
+                                let fields = object_len(peek_struct.fields_for_serialize());
                                 debug!("  Serializing {} fields as object", fields);
 
                                 stack.push(SerializeTask::Object {
@@ -554,6 +634,7 @@ where
                         }
                     }
                     (_, Type::User(UserType::Enum(_))) => {
+                        let enum_shape = cpeek.shape();
                         let peek_enum = cpeek.into_enum().unwrap();
                         let variant = peek_enum
                             .active_variant()
@@ -572,54 +653,99 @@ where
                         serializer.start_enum_variant(discriminant)?;
                         let flattened = maybe_field.map(|f| f.flattened).unwrap_or_default();
 
-                        if variant.data.fields.is_empty() {
-                            // Unit variant
-                            serializer.serialize_unit_variant(variant_index, variant.name)?;
-                        } else {
-                            if !flattened {
-                                // For now, treat all enum variants with data as objects
-                                serializer.start_object(Some(1))?;
+                        if flattened {
+                            // The variant name has already been emitted as a field name by
+                            // the parent, only the variant's data remains
+                            if variant.data.fields.is_empty() {
+                                serializer.serialize_unit_variant(variant_index, variant.name)?;
+                            } else {
+                                push_variant_data(peek_enum, variant, serializer, &mut stack)?;
+                            }
+                        } else if enum_shape.has_untagged_attr() {
+                            // Untagged: `data`
+                            if variant.data.fields.is_empty() {
+                                serializer.serialize_unit()?;
+                            } else {
+                                push_variant_data(peek_enum, variant, serializer, &mut stack)?;
+                            }
+                        } else if let Some(tag) = enum_shape.get_tag_attr() {
+                            if let Some(content) = enum_shape.get_content_attr() {
+                                // Adjacently tagged: `{ tag: "Variant", content: data }`
+                                let has_data = !variant.data.fields.is_empty();
+                                serializer.start_object(Some(if has_data { 2 } else { 1 }))?;
                                 stack.push(SerializeTask::EndObject);
 
-                                // Serialize variant name as field name
-                                serializer.serialize_field_name(variant.name)?;
-                            }
+                                serializer.serialize_field_name(tag)?;
+                                serializer.serialize_str(variant.name)?;
+                                serializer.end_field()?;
 
-                            if variant_is_newtype_like(variant) {
-                                // Newtype variant - serialize the inner value directly
-                                let fields = peek_enum.fields_for_serialize().collect::<Vec<_>>();
-                                let (field, field_peek) = fields[0];
-                                // TODO: error if `skip_serialize` is set?
-                                stack.push(SerializeTask::Value(field_peek, Some(field)));
-                            } else if variant.data.kind == StructKind::Tuple
-                                || variant.data.kind == StructKind::TupleStruct
-                            {
-                                // Tuple variant - serialize as array
-                                let fields = peek_enum.fields_for_serialize().count();
-                                serializer.start_array(Some(fields))?;
-                                stack.push(SerializeTask::EndArray);
-
-                                // Push fields in reverse order for tuple variant
-                                let fields_for_serialize =
-                                    peek_enum.fields_for_serialize().collect::<Vec<_>>();
-                                for (field, field_peek) in fields_for_serialize.into_iter().rev() {
-                                    stack.push(SerializeTask::Value(field_peek, Some(field)));
+                                if has_data {
+                                    stack.push(SerializeTask::EndField);
+                                    serializer.serialize_field_name(content)?;
+                                    push_variant_data(peek_enum, variant, serializer, &mut stack)?;
                                 }
                             } else {
-                                // Struct variant - serialize as object
-                                let fields = peek_enum.fields_for_serialize().count();
-                                serializer.start_object(Some(fields))?;
+                                // Internally tagged: `{ tag: "Variant", ...fields }`
+                                let fields: Vec<_> = if variant.data.fields.is_empty() {
+                                    Vec::new()
+                                } else if variant.data.kind == StructKind::Struct {
+                                    peek_enum.fields_for_serialize().collect()
+                                } else if variant_is_newtype_like(variant) {
+                                    // The inner value's fields are inlined next to the tag
+                                    let (_, inner) =
+                                        peek_enum.fields_for_serialize().next().unwrap();
+                                    match inner.into_struct() {
+                                        Ok(inner_struct) => {
+                                            inner_struct.fields_for_serialize().collect()
+                                        }
+                                        Err(_) => {
+                                            return Err(serializer.unsupported_shape(
+                                                enum_shape,
+                                                &alloc::format!(
+                                                    "internally tagged newtype variant {} must wrap a struct, not {}",
+                                                    variant.name,
+                                                    inner.shape()
+                                                ),
+                                            ));
+                                        }
+                                    }
+                                } else {
+                                    return Err(serializer.unsupported_shape(
+                                        enum_shape,
+                                        &alloc::format!(
+                                            "internally tagged enums can't contain tuple variant {}",
+                                            variant.name
+                                        ),
+                                    ));
+                                };
+
+                                serializer
+                                    .start_object(Some(object_len(fields.iter().copied()) + 1))?;
                                 stack.push(SerializeTask::EndObject);
 
-                                // Push fields in reverse order for struct variant
-                                let fields_for_serialize =
-                                    peek_enum.fields_for_serialize().collect::<Vec<_>>();
-                                for (field, field_peek) in fields_for_serialize.into_iter().rev() {
-                                    stack.push(SerializeTask::EndField);
-                                    stack.push(SerializeTask::Value(field_peek, Some(field)));
-                                    stack.push(SerializeTask::SerializeFieldName(field.name));
+                                serializer.serialize_field_name(tag)?;
+                                serializer.serialize_str(variant.name)?;
+                                serializer.end_field()?;
+
+                                // Push fields in reverse order
+                                for (field, field_peek) in fields.into_iter().rev() {
+                                    push_field(field, field_peek, &mut stack);
                                 }
                             }
+                        } else if variant.data.fields.is_empty() {
+                            // Unit variant
+                            serializer.serialize_unit_variant(variant_index, variant.name)?;
+                        } else {
+                            // Externally tagged: `{ "Variant": data }`
+                            serializer.start_object(Some(1))?;
+                            stack.push(SerializeTask::EndObject);
+
+                            // Serialize variant name as field name, and end it like any
+                            // other field so formats tracking a key path (toml, yaml,
+                            // urlencoded) leave the variant before the next sibling
+                            stack.push(SerializeTask::EndField);
+                            serializer.serialize_field_name(variant.name)?;
+                            push_variant_data(peek_enum, variant, serializer, &mut stack)?;
                         }
                     }
                     (_, Type::Pointer(pointer_type)) => {
@@ -675,9 +801,7 @@ where
                     first: false,
                     len,
                 });
-                stack.push(SerializeTask::EndField);
-                stack.push(SerializeTask::Value(value, Some(field)));
-                stack.push(SerializeTask::SerializeFieldName(field.name));
+                push_field(field, value, &mut stack);
             }
             SerializeTask::Array { mut items, first } => {
                 if first {
@@ -717,6 +841,15 @@ where
                 stack.push(SerializeTask::SerializeMapValue(value));
                 stack.push(SerializeTask::SerializeMapKey(key));
             }
+            SerializeTask::FlattenedMap { mut entries } => {
+                let Some((key, value)) = entries.next() else {
+                    continue;
+                };
+
+                stack.push(SerializeTask::FlattenedMap { entries });
+                stack.push(SerializeTask::SerializeMapValue(value));
+                stack.push(SerializeTask::SerializeMapKey(key));
+            }
             SerializeTask::TupleStruct {
                 mut items,
                 first,
@@ -756,8 +889,8 @@ where
             }
 
             // --- Field name and map key/value handling ---
-            SerializeTask::SerializeFieldName(name) => {
-                serializer.serialize_field_name(name)?;
+            SerializeTask::SerializeField(field) => {
+                serializer.serialize_field(&field)?;
             }
             SerializeTask::SerializeMapKey(key_peek) => {
                 stack.push(SerializeTask::EndMapKey);
@@ -786,6 +919,9 @@ where
             SerializeTask::EndField => {
                 serializer.end_field()?;
             }
+            SerializeTask::EndShared => {
+                serializer.end_shared()?;
+            }
         }
     }
 
@@ -793,6 +929,84 @@ where
     Ok(())
 }
 
+/// Serializes the data of a non-unit enum variant, without any tag: newtype variants
+/// become their inner value, tuple variants become arrays and struct variants become objects.
+fn push_variant_data<'mem, 'facet, 'shape, S>(
+    peek_enum: PeekEnum<'mem, 'facet, 'shape>,
+    variant: &'shape Variant<'shape>,
+    serializer: &mut S,
+    stack: &mut Vec<SerializeTask<'mem, 'facet, 'shape>>,
+) -> Result<(), S::Error>
+where
+    S: Serializer<'shape>,
+{
+    if variant_is_newtype_like(variant) {
+        // Newtype variant - serialize the inner value directly
+        let fields = peek_enum.fields_for_serialize().collect::<Vec<_>>();
+        let (field, field_peek) = fields[0];
+        // TODO: error if `skip_serialize` is set?
+        stack.push(SerializeTask::Value(field_peek, Some(field)));
+    } else if variant.data.kind == StructKind::Tuple || variant.data.kind == StructKind::TupleStruct
+    {
+        // Tuple variant - serialize as array
+        let fields = peek_enum.fields_for_serialize().count();
+        serializer.start_array(Some(fields))?;
+        stack.push(SerializeTask::EndArray);
+
+        // Push fields in reverse order for tuple variant
+        let fields_for_serialize = peek_enum.fields_for_serialize().collect::<Vec<_>>();
+        for (field, field_peek) in fields_for_serialize.into_iter().rev() {
+            stack.push(SerializeTask::Value(field_peek, Some(field)));
+        }
+    } else {
+        // Struct variant - serialize as object
+        let fields = object_len(peek_enum.fields_for_serialize());
+        serializer.start_object(Some(fields))?;
+        stack.push(SerializeTask::EndObject);
+
+        // Push fields in reverse order for struct variant
+        let fields_for_serialize = peek_enum.fields_for_serialize().collect::<Vec<_>>();
+        for (field, field_peek) in fields_for_serialize.into_iter().rev() {
+            push_field(field, field_peek, stack);
+        }
+    }
+    Ok(())
+}
+
+/// Pushes the tasks serializing one field of an object: its name and value, or, for a
+/// flattened map, each of its entries inline.
+fn push_field<'mem, 'facet, 'shape>(
+    field: Field<'shape>,
+    value: Peek<'mem, 'facet, 'shape>,
+    stack: &mut Vec<SerializeTask<'mem, 'facet, 'shape>>,
+) {
+    if field.flattened {
+        if let Ok(peek_map) = value.into_map() {
+            stack.push(SerializeTask::FlattenedMap {
+                entries: peek_map.iter(),
+            });
+            return;
+        }
+    }
+
+    stack.push(SerializeTask::EndField);
+    stack.push(SerializeTask::Value(value, Some(field)));
+    stack.push(SerializeTask::SerializeField(field));
+}
+
+/// Returns the number of entries in an object with the given fields, where a flattened
+/// map counts for as many entries as it has.
+fn object_len<'mem, 'facet, 'shape>(
+    fields: impl Iterator<Item = (Field<'shape>, Peek<'mem, 'facet, 'shape>)>,
+) -> usize {
+    fields
+        .map(|(field, value)| match value.into_map() {
+            Ok(peek_map) if field.flattened => peek_map.len(),
+            _ => 1,
+        })
+        .sum()
+}
+
 // --- Helper Trait for Ergonomics ---
 
 /// Extension trait to simplify calling the generic serializer.
@@ -5,6 +5,7 @@ use core::ops::Range;
 use alloc::{
     format,
     string::{String, ToString},
+    vec::Vec,
 };
 #[cfg(feature = "rich-diagnostics")]
 use ariadne::{Color, Label, Report, ReportKind, Source};
@@ -24,6 +25,8 @@ pub struct TomlDeError<'input, 'shape> {
     span: Option<Range<usize>>,
     /// Full Partial path.
     path: String,
+    /// Other errors found in the same pass.
+    others: Vec<TomlDeError<'input, 'shape>>,
 }
 
 impl<'input, 'shape> TomlDeError<'input, 'shape> {
@@ -39,9 +42,59 @@ impl<'input, 'shape> TomlDeError<'input, 'shape> {
             toml,
             span,
             path,
+            others: Vec::new(),
         }
     }
 
+    /// Combine errors found in the same pass into one, the first being the main error.
+    ///
+    /// # Panics
+    ///
+    /// When `errors` is empty.
+    pub(crate) fn from_errors(errors: Vec<Self>) -> Self {
+        let mut errors = errors.into_iter().flat_map(|mut error| {
+            let others = core::mem::take(&mut error.others);
+            core::iter::once(error).chain(others)
+        });
+        let mut error = errors.next().expect("at least one error");
+        error.others = errors.collect();
+        error
+    }
+
+    /// Replace the `root` of the paths by `path`, for errors found while deserializing a value
+    /// on its own.
+    pub(crate) fn rebase(mut self, root: &str, path: &str) -> Self {
+        let rebase = |error_path: &mut String| {
+            let rest = error_path.strip_prefix(root).unwrap_or_default();
+            *error_path = format!("{path}{rest}");
+        };
+        rebase(&mut self.path);
+        for error in &mut self.others {
+            rebase(&mut error.path);
+        }
+        self
+    }
+
+    /// Which part of the TOML source this error applies to.
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Path of the value this error applies to.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Other errors found in the same pass, in the order they were found.
+    pub fn others(&self) -> &[TomlDeError<'input, 'shape>] {
+        &self.others
+    }
+
+    /// This error followed by the others found in the same pass.
+    pub fn errors(&self) -> impl Iterator<Item = &TomlDeError<'input, 'shape>> {
+        core::iter::once(self).chain(&self.others)
+    }
+
     /// Message for this specific error.
     pub fn message(&self) -> String {
         match &self.kind {
@@ -82,6 +135,12 @@ impl<'input, 'shape> TomlDeError<'input, 'shape> {
             TomlDeErrorKind::ParseSingleValueAsMultipleFieldStruct => {
                 "Can't parse a single value as a struct with multiple fields".to_string()
             }
+            TomlDeErrorKind::NoMatchingVariant(shape) => {
+                format!("Data did not match any variant of untagged enum '{shape}'")
+            }
+            TomlDeErrorKind::UnknownField { field_name, shape } => {
+                format!("Unknown field '{field_name}' for '{shape}'")
+            }
         }
     }
 }
@@ -89,13 +148,30 @@ impl<'input, 'shape> TomlDeError<'input, 'shape> {
 #[cfg(not(feature = "rich-diagnostics"))]
 impl<'shape> core::fmt::Display for TomlDeError<'_, 'shape> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{} in path {}", self.message(), self.path)
+        for (index, error) in self.errors().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{} in path {}", error.message(), error.path)?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(feature = "rich-diagnostics")]
 impl<'shape> core::fmt::Display for TomlDeError<'_, 'shape> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for error in self.errors() {
+            error.write_report(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rich-diagnostics")]
+impl<'shape> TomlDeError<'_, 'shape> {
+    /// Write this error alone, with the TOML source around it.
+    fn write_report(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         // Don't print the TOML source if no span is set
         let Some(span) = &self.span else {
             return writeln!(f, "{} in path {}", self.message(), self.path);
@@ -176,4 +252,13 @@ pub enum TomlDeErrorKind<'shape> {
     ExpectedExactlyOneField,
     /// Tried parsing a single value as a struct with multiple fields.
     ParseSingleValueAsMultipleFieldStruct,
+    /// None of the variants of an untagged enum could be parsed from the value.
+    NoMatchingVariant(&'shape Shape<'shape>),
+    /// Found a TOML field that the struct doesn't have, and it denies unknown fields.
+    UnknownField {
+        /// Name of the TOML field.
+        field_name: String,
+        /// Struct that doesn't have the field.
+        shape: &'shape Shape<'shape>,
+    },
 }
@@ -8,14 +8,17 @@ mod to_scalar;
 
 use alloc::{
     borrow::Cow,
+    format,
     string::{String, ToString},
+    vec::Vec,
 };
+use core::ops::Range;
 pub use error::{TomlDeError, TomlDeErrorKind};
-use facet_core::{Characteristic, Def, Facet, FieldFlags, StructKind, Type, UserType};
+use facet_core::{Characteristic, Def, Facet, FieldFlags, Shape, StructKind, Type, UserType};
 use facet_reflect::{Partial, ReflectError, ScalarType};
 use log::trace;
 use owo_colors::OwoColorize;
-use toml_edit::{ImDocument, Item, TomlError};
+use toml_edit::{ImDocument, Item, Key, TomlError};
 
 macro_rules! reflect {
     ($wip:expr, $toml:expr, $span:expr, $($tt:tt)*) => {
@@ -64,12 +67,12 @@ pub fn from_str<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
     // Deserialize it with facet reflection
     deserialize_item(toml, partial.inner_mut(), docs.as_item())?;
 
-    // Build the result
+    // Build the result, which checks the invariants of the whole value
     let result = partial.build().map_err(|e| {
         TomlDeError::new(
             toml,
             TomlDeErrorKind::GenericReflect(e),
-            None,
+            docs.as_item().span(),
             "$".to_string(),
         )
     })?;
@@ -90,6 +93,11 @@ fn deserialize_item<'input, 'facet, 'shape>(
         return deserialize_as_option(toml, wip, item);
     }
 
+    // Result isn't an enum in the type system either, but it's represented like one
+    if let Def::Result(_) = wip.shape().def {
+        return deserialize_as_result(toml, wip, item);
+    }
+
     // First check the type system (Type)
     if let Type::User(UserType::Struct(struct_def)) = &wip.shape().ty {
         return deserialize_as_struct(toml, wip, struct_def, item);
@@ -161,51 +169,91 @@ fn deserialize_as_struct<'input, 'a, 'shape>(
         )
     })?;
 
+    let shape = wip.shape();
+    let path = wip.path();
+    let mut errors = Vec::new();
+    // After an error `wip` can't be used anymore, but the other fields are still checked for
+    // errors so they can all be reported at once
+    let mut failed = false;
+
     for field in def.fields {
-        reflect!(wip, toml, item.span(), begin_field(field.name));
+        let field_path = format!("{path}.{}", field.name);
 
         // Find the matching TOML field
-        let field_item = table.get(field.name);
-        match field_item {
-            Some(field_item) => deserialize_item(toml, wip, field_item)?,
+        match table.get(field.name) {
+            Some(field_item) if failed => {
+                errors.extend(check_item(toml, field.shape(), field_item, &field_path));
+            }
+            Some(field_item) => {
+                if let Err(error) = deserialize_field(toml, wip, field.name, field_item) {
+                    errors.push(error);
+                    failed = true;
+                }
+            }
             None => {
-                if let Def::Option(..) = field.shape().def {
+                let has_default = if let Def::Option(..) = field.shape().def {
                     // Default of `Option<T>` is `None`
-                    reflect!(wip, toml, item.span(), set_default());
+                    true
                 } else if field.flags.contains(FieldFlags::DEFAULT) {
                     // Handle the default - set_default internally handles custom default functions safely
                     if field.shape().is(Characteristic::Default)
                         || field.vtable.default_fn.is_some()
                     {
-                        reflect!(wip, toml, item.span(), set_default());
+                        true
                     } else {
                         // Throw an error when there's a "default" attribute but no implementation for the type
-                        return Err(TomlDeError::new(
+                        errors.push(TomlDeError::new(
                             toml,
                             TomlDeErrorKind::GenericReflect(
                                 ReflectError::DefaultAttrButNoDefaultImpl {
                                     shape: field.shape(),
                                 },
                             ),
-                            item.span(),
-                            wip.path(),
+                            header_span(toml, item),
+                            field_path,
                         ));
+                        continue;
                     }
-                } else if field.shape().is_type::<()>() {
-                    // Default of `()` is `()`
-                    reflect!(wip, toml, item.span(), set_default());
                 } else {
-                    return Err(TomlDeError::new(
+                    // Default of `()` is `()`
+                    field.shape().is_type::<()>()
+                };
+
+                if !has_default {
+                    errors.push(TomlDeError::new(
                         toml,
                         TomlDeErrorKind::ExpectedFieldWithName(field.name),
-                        item.span(),
-                        wip.path(),
+                        header_span(toml, item),
+                        field_path,
                     ));
+                } else if !failed {
+                    if let Err(error) = set_field_default(toml, wip, field.name, item) {
+                        errors.push(error);
+                        failed = true;
+                    }
                 }
             }
         }
+    }
 
-        reflect!(wip, toml, item.span(), end());
+    if shape.has_deny_unknown_fields_attr() {
+        for (key, _) in table.iter() {
+            if def.fields.iter().all(|field| field.name != key) {
+                errors.push(TomlDeError::new(
+                    toml,
+                    TomlDeErrorKind::UnknownField {
+                        field_name: key.to_string(),
+                        shape,
+                    },
+                    table.key(key).and_then(Key::span).or_else(|| item.span()),
+                    format!("{path}.{key}"),
+                ));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(TomlDeError::from_errors(errors));
     }
 
     trace!("Finished deserializing {}", "struct".blue());
@@ -213,6 +261,67 @@ fn deserialize_as_struct<'input, 'a, 'shape>(
     Ok(())
 }
 
+/// Deserialize the field of a struct named `name` from `item`.
+fn deserialize_field<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    name: &str,
+    item: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    reflect!(wip, toml, item.span(), begin_field(name));
+    deserialize_item(toml, wip, item)?;
+    reflect!(wip, toml, item.span(), end());
+
+    Ok(())
+}
+
+/// Set the field of a struct named `name` to its default, because it's missing from the `table`.
+fn set_field_default<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    name: &str,
+    table: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    reflect!(wip, toml, header_span(toml, table), begin_field(name));
+    reflect!(wip, toml, header_span(toml, table), set_default());
+    reflect!(wip, toml, header_span(toml, table), end());
+
+    Ok(())
+}
+
+/// Deserialize `item` as a value of `shape` on its own, only to find its errors.
+///
+/// This keeps finding errors after a first one, which leaves the `Partial` unusable.
+fn check_item<'input, 'shape>(
+    toml: &'input str,
+    shape: &'shape Shape<'shape>,
+    item: &Item,
+    path: &str,
+) -> Option<TomlDeError<'input, 'shape>> {
+    let mut partial = Partial::alloc_shape(shape).ok()?;
+    let root = partial.path();
+    let error = deserialize_item(toml, &mut partial, item).err()?;
+
+    Some(error.rebase(&root, path))
+}
+
+/// Span of the header of a table, to report what's missing from it.
+///
+/// Inline tables are on a single line so they're used whole, and the root table has no header
+/// so the start of the document is used.
+fn header_span(toml: &str, item: &Item) -> Option<Range<usize>> {
+    let span = item.span()?;
+    let source = toml.get(span.clone())?;
+    if source.starts_with('[') {
+        let end = source.find('\n').unwrap_or(source.len());
+        Some(span.start..span.start + source[..end].trim_end().len())
+    } else if source.starts_with('{') {
+        Some(span)
+    } else {
+        Some(span.start..span.start)
+    }
+}
+
 fn deserialize_as_enum<'input, 'a, 'shape>(
     toml: &'input str,
     wip: &mut Partial<'a, 'shape>,
@@ -224,6 +333,13 @@ fn deserialize_as_enum<'input, 'a, 'shape>(
         "enum".blue()
     );
 
+    if wip.shape().has_untagged_attr() {
+        return deserialize_as_untagged_enum(toml, wip, item);
+    }
+    if let Some(tag) = wip.shape().get_tag_attr() {
+        return deserialize_as_tagged_enum(toml, wip, tag, item);
+    }
+
     match item {
         Item::None => todo!(),
 
@@ -251,6 +367,7 @@ fn deserialize_as_enum<'input, 'a, 'shape>(
                             toml,
                             wip,
                             key,
+                            inline_table.key(key).and_then(Key::span),
                             // TODO: remove clone
                             &Item::Value(field.clone()),
                         );
@@ -277,7 +394,7 @@ fn deserialize_as_enum<'input, 'a, 'shape>(
                 )
             })?;
 
-            build_enum_from_variant_name(toml, wip, variant_name, item)?
+            build_enum_from_variant_name(toml, wip, variant_name, value.span(), item)?
         }
 
         Item::Table(table) => {
@@ -292,7 +409,13 @@ fn deserialize_as_enum<'input, 'a, 'shape>(
                         wip.path(),
                     ));
                 } else {
-                    build_enum_from_variant_name(toml, wip, key, field)?
+                    build_enum_from_variant_name(
+                        toml,
+                        wip,
+                        key,
+                        table.key(key).and_then(Key::span),
+                        field,
+                    )?
                 }
             } else {
                 return Err(TomlDeError::new(
@@ -316,13 +439,180 @@ fn build_enum_from_variant_name<'input, 'a, 'shape>(
     toml: &'input str,
     wip: &mut Partial<'a, 'shape>,
     variant_name: &str,
+    variant_span: Option<Range<usize>>,
     item: &Item,
 ) -> Result<(), TomlDeError<'input, 'shape>> {
-    // Select the variant
-    reflect!(wip, toml, item.span(), select_variant_named(variant_name));
+    // Select the variant, pointing at its name when it doesn't exist
+    reflect!(wip, toml, variant_span, select_variant_named(variant_name));
+
+    deserialize_variant_fields(toml, wip, item)
+}
+
+fn deserialize_as_tagged_enum<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    tag: &'shape str,
+    item: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    trace!("Reading variant name from key {}", tag.cyan().bold());
+
+    let table = item.as_table_like().ok_or_else(|| {
+        TomlDeError::new(
+            toml,
+            TomlDeErrorKind::ExpectedType {
+                expected: "table like structure",
+                got: item.type_name(),
+            },
+            item.span(),
+            wip.path(),
+        )
+    })?;
+
+    let tag_item = table.get(tag).ok_or_else(|| {
+        TomlDeError::new(
+            toml,
+            TomlDeErrorKind::ExpectedFieldWithName(tag),
+            header_span(toml, item),
+            wip.path(),
+        )
+    })?;
+    let variant_name = tag_item.as_str().ok_or_else(|| {
+        TomlDeError::new(
+            toml,
+            TomlDeErrorKind::ExpectedType {
+                expected: "string",
+                got: tag_item.type_name(),
+            },
+            tag_item.span(),
+            wip.path(),
+        )
+    })?;
+    reflect!(
+        wip,
+        toml,
+        tag_item.span(),
+        select_variant_named(variant_name)
+    );
 
     // Safe to unwrap because the variant got just selected
     let variant = wip.selected_variant().unwrap();
+    if variant.data.kind == StructKind::Unit {
+        return Ok(());
+    }
+
+    match wip.shape().get_content_attr() {
+        // Adjacently tagged, the data is in the content field
+        Some(content) => {
+            let content_item = table.get(content).ok_or_else(|| {
+                TomlDeError::new(
+                    toml,
+                    TomlDeErrorKind::ExpectedFieldWithName(content),
+                    item.span(),
+                    wip.path(),
+                )
+            })?;
+            deserialize_variant_data(toml, wip, content_item)
+        }
+        // Internally tagged, the data is next to the tag, which gets ignored
+        None => deserialize_variant_data(toml, wip, item),
+    }
+}
+
+fn deserialize_as_untagged_enum<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    item: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    let shape = wip.shape();
+    let Type::User(UserType::Enum(enum_def)) = shape.ty else {
+        unreachable!("untagged attribute on a non-enum shape");
+    };
+
+    for (index, variant) in enum_def.variants.iter().enumerate() {
+        // TOML has no null, so unit variants can't be represented
+        if variant.data.kind == StructKind::Unit {
+            continue;
+        }
+
+        trace!("Trying variant {}", variant.name.cyan().bold());
+
+        let Ok(mut attempt) = Partial::alloc_shape(shape) else {
+            continue;
+        };
+        if attempt.select_nth_variant(index).is_err()
+            || deserialize_variant_data(toml, &mut attempt, item).is_err()
+        {
+            continue;
+        }
+        if let Ok(value) = attempt.build() {
+            reflect!(wip, toml, item.span(), set_from_heap_value(value));
+            return Ok(());
+        }
+    }
+
+    Err(TomlDeError::new(
+        toml,
+        TomlDeErrorKind::NoMatchingVariant(shape),
+        item.span(),
+        wip.path(),
+    ))
+}
+
+/// Deserialize the data of the selected variant without any variant name around it, as used by
+/// tagged and untagged enums.
+fn deserialize_variant_data<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    item: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    // Safe to unwrap because the variant is always selected before
+    let variant = wip.selected_variant().unwrap();
+    let is_tuple =
+        variant.data.kind == StructKind::TupleStruct || variant.data.kind == StructKind::Tuple;
+
+    if is_tuple && variant.data.fields.len() == 1 {
+        // Newtype variants contain the inner value directly
+        reflect!(wip, toml, item.span(), begin_nth_field(0));
+        deserialize_item(toml, wip, item)?;
+        reflect!(wip, toml, item.span(), end());
+
+        return Ok(());
+    }
+
+    if let (true, Some(array)) = (is_tuple, item.as_array()) {
+        if array.len() != variant.data.fields.len() {
+            return Err(TomlDeError::new(
+                toml,
+                TomlDeErrorKind::ExpectedType {
+                    expected: "array with an element for each tuple field",
+                    got: "array of a different length",
+                },
+                item.span(),
+                wip.path(),
+            ));
+        }
+
+        for (index, value) in array.iter().enumerate() {
+            reflect!(wip, toml, item.span(), begin_nth_field(index));
+            // TODO: remove clone
+            deserialize_item(toml, wip, &Item::Value(value.clone()))?;
+            reflect!(wip, toml, item.span(), end());
+        }
+
+        return Ok(());
+    }
+
+    deserialize_variant_fields(toml, wip, item)
+}
+
+/// Deserialize the fields of the selected variant.
+fn deserialize_variant_fields<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    item: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    // Safe to unwrap because the variant is always selected before
+    let variant = wip.selected_variant().unwrap();
 
     if variant.data.kind == StructKind::Unit {
         // No need to do anything, we can just set the variant since it's a unit enum
@@ -395,6 +685,13 @@ fn deserialize_as_list<'input, 'a, 'shape>(
         "list".blue()
     );
 
+    // Used to keep finding errors in the other items after one
+    let path = wip.path();
+    let item_shape = match wip.shape().def {
+        Def::List(list_def) => Some(list_def.t()),
+        _ => None,
+    };
+
     // Check if this is an array of tables
     if let Some(array_of_tables) = item.as_array_of_tables() {
         // Handle array of tables
@@ -406,15 +703,11 @@ fn deserialize_as_list<'input, 'a, 'shape>(
         }
 
         // Loop over all tables in the array
-        for table in array_of_tables.iter() {
-            // Start the field
-            reflect!(wip, toml, table.span(), begin_list_item());
-
-            deserialize_item(toml, wip, &Item::Table(table.clone()))?;
-
-            // Finish the field
-            reflect!(wip, toml, table.span(), end());
-        }
+        let tables = array_of_tables
+            .iter()
+            // TODO: remove clone
+            .map(|table| Item::Table(table.clone()));
+        deserialize_list_items(toml, wip, tables, item_shape, &path)?;
 
         trace!("Finished deserializing {}", "array of tables".blue());
         return Ok(());
@@ -442,22 +735,61 @@ fn deserialize_as_list<'input, 'a, 'shape>(
     }
 
     // Loop over all items in the TOML list
-    for value in item.iter() {
-        // Start the field
-        reflect!(wip, toml, value.span(), begin_list_item());
+    let values = item
+        .iter()
+        // TODO: remove clone
+        .map(|value| Item::Value(value.clone()));
+    deserialize_list_items(toml, wip, values, item_shape, &path)?;
 
-        deserialize_item(
-            toml,
-            wip,
-            // TODO: remove clone
-            &Item::Value(value.clone()),
-        )?;
+    trace!("Finished deserializing {}", "list".blue());
+
+    Ok(())
+}
 
-        // Finish the field
-        reflect!(wip, toml, value.span(), end());
+/// Push every item to the list, reporting the errors of all items.
+fn deserialize_list_items<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    items: impl Iterator<Item = Item>,
+    item_shape: Option<&'shape Shape<'shape>>,
+    path: &str,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    let mut errors = Vec::new();
+
+    for (index, item) in items.enumerate() {
+        if !errors.is_empty() {
+            // After an error `wip` can't be used anymore, so only check the item for errors
+            errors.extend(
+                item_shape
+                    .and_then(|shape| check_item(toml, shape, &item, &format!("{path}[{index}]"))),
+            );
+            continue;
+        }
+
+        if let Err(error) = deserialize_list_item(toml, wip, &item) {
+            errors.push(error);
+        }
     }
 
-    trace!("Finished deserializing {}", "list".blue());
+    if !errors.is_empty() {
+        return Err(TomlDeError::from_errors(errors));
+    }
+
+    Ok(())
+}
+
+fn deserialize_list_item<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    item: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    // Start the field
+    reflect!(wip, toml, item.span(), begin_list_item());
+
+    deserialize_item(toml, wip, item)?;
+
+    // Finish the field
+    reflect!(wip, toml, item.span(), end());
 
     Ok(())
 }
@@ -496,8 +828,11 @@ fn deserialize_as_map<'input, 'a, 'shape>(
 
     // Loop over all items in the TOML list
     for (k, v) in table.iter() {
+        // Point at the key when it can't be used
+        let key_span = table.key(k).and_then(Key::span).or_else(|| item.span());
+
         // Start the key
-        reflect!(wip, toml, item.span(), begin_key());
+        reflect!(wip, toml, key_span.clone(), begin_key());
 
         trace!("Push {} {}", "key".cyan(), k.cyan().bold());
 
@@ -506,28 +841,33 @@ fn deserialize_as_map<'input, 'a, 'shape>(
             TomlDeError::new(
                 toml,
                 TomlDeErrorKind::UnrecognizedScalar(wip.shape()),
-                item.span(),
+                key_span.clone(),
                 wip.path(),
             )
         })? {
             ScalarType::String => {
-                reflect!(wip, toml, item.span(), set(k.to_string()));
+                reflect!(wip, toml, key_span.clone(), set(k.to_string()));
             }
             ScalarType::CowStr => {
-                reflect!(wip, toml, item.span(), set(Cow::Owned(k.to_string())));
+                reflect!(
+                    wip,
+                    toml,
+                    key_span.clone(),
+                    set(Cow::<str>::Owned(k.to_string()))
+                );
             }
             _ => {
                 return Err(TomlDeError::new(
                     toml,
                     TomlDeErrorKind::InvalidKey(wip.shape()),
-                    item.span(),
+                    key_span.clone(),
                     wip.path(),
                 ));
             }
         };
 
         // End the key frame
-        reflect!(wip, toml, item.span(), end());
+        reflect!(wip, toml, key_span, end());
 
         trace!("Push {}", "value".cyan());
 
@@ -571,6 +911,71 @@ fn deserialize_as_option<'input, 'a, 'shape>(
     Ok(())
 }
 
+fn deserialize_as_result<'input, 'a, 'shape>(
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
+    item: &Item,
+) -> Result<(), TomlDeError<'input, 'shape>> {
+    trace!(
+        "Deserializing {} as {}",
+        item.type_name().cyan(),
+        "result".blue()
+    );
+
+    // Like an externally tagged enum: a table with a single `Ok` or `Err` key
+    let table = item.as_table_like().ok_or_else(|| {
+        TomlDeError::new(
+            toml,
+            TomlDeErrorKind::ExpectedType {
+                expected: "table",
+                got: item.type_name(),
+            },
+            item.span(),
+            wip.path(),
+        )
+    })?;
+    let Some((key, inner)) = table.iter().next() else {
+        return Err(TomlDeError::new(
+            toml,
+            TomlDeErrorKind::ExpectedAtLeastOneField,
+            item.span(),
+            wip.path(),
+        ));
+    };
+    if table.len() > 1 {
+        return Err(TomlDeError::new(
+            toml,
+            TomlDeErrorKind::ExpectedExactlyOneField,
+            item.span(),
+            wip.path(),
+        ));
+    }
+
+    let key_span = table.key(key).and_then(Key::span);
+    match key {
+        "Ok" => reflect!(wip, toml, key_span, begin_ok()),
+        "Err" => reflect!(wip, toml, key_span, begin_err()),
+        _ => {
+            return Err(TomlDeError::new(
+                toml,
+                TomlDeErrorKind::UnknownField {
+                    field_name: key.to_string(),
+                    shape: wip.shape(),
+                },
+                key_span,
+                wip.path(),
+            ));
+        }
+    }
+
+    deserialize_item(toml, wip, inner)?;
+    reflect!(wip, toml, inner.span(), end());
+
+    trace!("Finished deserializing {}", "result".blue());
+
+    Ok(())
+}
+
 fn deserialize_as_smartpointer<'input, 'a, 'shape>(
     _toml: &'input str,
     _wip: &mut Partial<'a, 'shape>,
@@ -0,0 +1,202 @@
+//! Write the doc comments of Rust types into TOML documents.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use facet_core::{Def, Field, Shape, Type, UserType};
+use toml_edit::{Item, Table};
+
+use super::{TomlSerError, to_document};
+
+/// Serialize any `Facet` type to a TOML string, with doc comments.
+///
+/// The doc comment of each field is written as `#` comments above its key or table header, and
+/// the doc comment of a struct at the top of its table. This turns a default value into a
+/// sample configuration file that can't drift from the type.
+///
+/// ```
+/// use facet::Facet;
+///
+/// /// Settings of the server
+/// #[derive(Facet)]
+/// struct Config {
+///     /// Address to listen on
+///     address: String,
+///     /// Seconds before idle connections are closed
+///     timeout: u32,
+/// }
+///
+/// let config = Config {
+///     address: "localhost".to_string(),
+///     timeout: 30,
+/// };
+/// assert_eq!(
+///     facet_toml::to_string_documented(&config).unwrap(),
+///     r#"# Settings of the server
+///
+/// # Address to listen on
+/// address = "localhost"
+/// # Seconds before idle connections are closed
+/// timeout = 30
+/// "#
+/// );
+/// ```
+pub fn to_string_documented<'a, T: facet_core::Facet<'a>>(
+    value: &'a T,
+) -> Result<String, TomlSerError> {
+    let mut document = to_document(value)?;
+
+    let shape = skip_wrappers(T::SHAPE);
+    if !shape.doc.is_empty() {
+        // Separate the description of the whole file from the first key
+        document
+            .decor_mut()
+            .set_prefix(format!("{}\n", comment(shape.doc)));
+    }
+
+    let mut first_header = true;
+    document_table(
+        document.as_table_mut(),
+        Some(shape),
+        &[],
+        true,
+        &mut first_header,
+    );
+
+    Ok(document.to_string())
+}
+
+/// Write the comments of a table, its values and its subtables.
+///
+/// `first_header` tracks whether no header was written yet, in the order tables are written.
+fn document_table<'shape>(
+    table: &mut Table,
+    shape: Option<&'shape Shape<'shape>>,
+    field_doc: &[&str],
+    is_root: bool,
+    first_header: &mut bool,
+) {
+    let shape = shape.map(skip_wrappers);
+    let fields = shape.and_then(struct_fields).unwrap_or_default();
+    let shape_doc = match shape {
+        // The doc of the root goes at the top of the document
+        Some(shape) if !is_root => shape.doc,
+        _ => &[],
+    };
+    let has_values = table.iter().any(|(_, item)| item.is_value());
+
+    if !is_root {
+        let mut prefix = String::new();
+        prefix.push_str(&comment(field_doc));
+        if !has_values {
+            prefix.push_str(&comment(shape_doc));
+        }
+        if !prefix.is_empty() {
+            // Keep the blank line toml_edit writes between tables
+            if !*first_header {
+                prefix.insert(0, '\n');
+            }
+            table.decor_mut().set_prefix(prefix);
+        }
+        *first_header = false;
+    }
+
+    for field in fields {
+        if let Some((mut key, Item::Value(_))) = table.get_key_value_mut(field.name) {
+            if !field.doc.is_empty() {
+                key.leaf_decor_mut().set_prefix(comment(field.doc));
+            }
+        }
+    }
+
+    if has_values {
+        // The doc of a struct goes above the first key, under the table header
+        if !shape_doc.is_empty() {
+            let first_key = table
+                .iter()
+                .find(|(_, item)| item.is_value())
+                .map(|(key, _)| key.to_string());
+            if let Some(mut key) = first_key.and_then(|key| table.key_mut(&key)) {
+                let decor = key.leaf_decor_mut();
+                let field_comment = decor
+                    .prefix()
+                    .and_then(|prefix| prefix.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                decor.set_prefix(format!("{}{}", comment(shape_doc), field_comment));
+            }
+        }
+        *first_header = false;
+    }
+
+    let subtables: Vec<String> = table
+        .iter()
+        .filter(|(_, item)| item.is_table() || item.is_array_of_tables())
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in subtables {
+        let field = fields.iter().find(|field| field.name == key);
+        let (doc, shape) = match (field, shape.map(|shape| shape.def)) {
+            (Some(field), _) => (field.doc, Some(field.shape)),
+            // Values of maps have the doc of their type only
+            (None, Some(Def::Map(map_def))) => (&[][..], Some(map_def.v())),
+            _ => (&[][..], None),
+        };
+
+        match table.get_mut(&key) {
+            Some(Item::Table(subtable)) => {
+                document_table(subtable, shape, doc, false, first_header);
+            }
+            Some(Item::ArrayOfTables(array)) => {
+                let shape = shape.and_then(|shape| element_shape(skip_wrappers(shape)));
+                for (index, subtable) in array.iter_mut().enumerate() {
+                    // The doc of the field goes above the first table only
+                    let doc = if index == 0 { doc } else { &[] };
+                    document_table(subtable, shape, doc, false, first_header);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Format doc lines as TOML comments, which keep the space after `///`.
+fn comment(doc: &[&str]) -> String {
+    doc.iter().map(|line| format!("#{line}\n")).collect()
+}
+
+/// Get the type behind options, smart pointers and transparent wrappers.
+fn skip_wrappers<'shape>(mut shape: &'shape Shape<'shape>) -> &'shape Shape<'shape> {
+    loop {
+        shape = match shape.def {
+            Def::Option(option_def) => option_def.t,
+            Def::SmartPointer(pointer_def) => match pointer_def.pointee() {
+                Some(pointee) => pointee,
+                None => return shape,
+            },
+            _ => match shape.inner {
+                Some(inner) => inner(),
+                None => return shape,
+            },
+        };
+    }
+}
+
+fn struct_fields<'shape>(shape: &'shape Shape<'shape>) -> Option<&'shape [Field<'shape>]> {
+    match shape.ty {
+        Type::User(UserType::Struct(struct_type)) => Some(struct_type.fields),
+        _ => None,
+    }
+}
+
+fn element_shape<'shape>(shape: &'shape Shape<'shape>) -> Option<&'shape Shape<'shape>> {
+    match shape.def {
+        Def::List(list_def) => Some(list_def.t()),
+        Def::Array(array_def) => Some(array_def.t()),
+        Def::Slice(slice_def) => Some(slice_def.t),
+        _ => None,
+    }
+}
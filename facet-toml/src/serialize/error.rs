@@ -16,6 +16,15 @@ pub enum TomlSerError {
     UnsupportedByteArray,
     /// Invalid array of tables (expected structs)
     InvalidArrayOfTables,
+    /// The document to update isn't valid TOML.
+    InvalidDocument(toml_edit::TomlError),
+    /// The value has a shape that can't be written as TOML.
+    UnsupportedShape {
+        /// The shape of the value.
+        shape: alloc::string::String,
+        /// Why it can't be serialized.
+        reason: alloc::string::String,
+    },
 }
 
 impl core::fmt::Display for TomlSerError {
@@ -33,6 +42,12 @@ impl core::fmt::Display for TomlSerError {
             Self::InvalidArrayOfTables => {
                 write!(f, "Invalid array of tables: expected array of structs")
             }
+            Self::InvalidDocument(error) => {
+                write!(f, "Invalid TOML document: {error}")
+            }
+            Self::UnsupportedShape { shape, reason } => {
+                write!(f, "Cannot serialize {shape}: {reason}")
+            }
         }
     }
 }
@@ -4,7 +4,9 @@
 compile_error!("feature `alloc` is required");
 
 mod array_of_tables;
+mod docs;
 mod error;
+mod update;
 
 use alloc::{
     borrow::Cow,
@@ -14,11 +16,13 @@ use alloc::{
 use core::ops::{Deref, DerefMut};
 use owo_colors::OwoColorize;
 
+pub use docs::to_string_documented;
 pub use error::TomlSerError;
 use facet_reflect::HasFields;
 use facet_serialize::{Serialize, Serializer};
 use log::trace;
 use toml_edit::{DocumentMut, Item, Table, Value};
+pub use update::{to_string_preserving, update_document};
 
 /// Serializer for TOML values.
 pub struct TomlSerializer<'shape> {
@@ -179,9 +183,9 @@ impl<'shape> Serializer<'shape> for TomlSerializer<'shape> {
     fn serialize_unit_variant(
         &mut self,
         _variant_index: usize,
-        _variant_name: &'shape str,
+        variant_name: &'shape str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        self.write_value(variant_name)
     }
 
     fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
@@ -210,6 +214,17 @@ impl<'shape> Serializer<'shape> for TomlSerializer<'shape> {
         Ok(())
     }
 
+    fn unsupported_shape(
+        &mut self,
+        shape: &'shape facet_core::Shape<'shape>,
+        reason: &str,
+    ) -> Self::Error {
+        TomlSerError::UnsupportedShape {
+            shape: shape.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
     fn serialize_field_name(&mut self, name: &'shape str) -> Result<(), Self::Error> {
         self.push_key(name);
         trace!("Push field {}", self.key_stack);
@@ -295,6 +310,11 @@ impl core::fmt::Display for KeyStack<'_> {
 /// Serialize any `Facet` type to a TOML string.
 #[cfg(feature = "alloc")]
 pub fn to_string<'a, T: facet_core::Facet<'a>>(value: &'a T) -> Result<String, TomlSerError> {
+    Ok(to_document(value)?.to_string())
+}
+
+/// Serialize any `Facet` type to a new TOML document.
+fn to_document<'a, T: facet_core::Facet<'a>>(value: &'a T) -> Result<DocumentMut, TomlSerError> {
     // First peek at the value to understand its structure
     let peek = facet_reflect::Peek::new(value);
 
@@ -322,11 +342,11 @@ pub fn to_string<'a, T: facet_core::Facet<'a>>(value: &'a T) -> Result<String, T
             }
         }
 
-        Ok(serializer.into_string())
+        Ok(serializer.into_raw_document())
     } else {
         // Not a struct at root, use normal serialization
         let mut serializer = TomlSerializer::new();
         value.serialize(&mut serializer)?;
-        Ok(serializer.into_string())
+        Ok(serializer.into_raw_document())
     }
 }
@@ -0,0 +1,236 @@
+//! Write values into existing TOML documents, keeping their formatting.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Table, Value};
+
+use super::{TomlSerError, to_document};
+
+/// Update an existing TOML document so that it holds `value`.
+///
+/// Only what changed is rewritten: comments, whitespace, key order, the representation of
+/// unchanged values and whether tables are inline or standard are all kept. Keys the value
+/// doesn't have anymore are removed, and new keys are added after the existing ones.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Package {
+///     name: String,
+///     version: String,
+/// }
+///
+/// #[derive(Facet)]
+/// struct Manifest {
+///     package: Package,
+/// }
+///
+/// let mut document: toml_edit::DocumentMut = r#"
+/// # The package we ship
+/// [package]
+/// name = "demo"  # don't rename
+/// version = "0.1.0"
+/// "#
+/// .parse()
+/// .unwrap();
+///
+/// let manifest = Manifest {
+///     package: Package {
+///         name: "demo".to_string(),
+///         version: "0.2.0".to_string(),
+///     },
+/// };
+/// facet_toml::update_document(&mut document, &manifest).unwrap();
+///
+/// assert_eq!(
+///     document.to_string(),
+///     r#"
+/// # The package we ship
+/// [package]
+/// name = "demo"  # don't rename
+/// version = "0.2.0"
+/// "#
+/// );
+/// ```
+pub fn update_document<'a, T: facet_core::Facet<'a>>(
+    document: &mut DocumentMut,
+    value: &'a T,
+) -> Result<(), TomlSerError> {
+    let mut new_document = to_document(value)?;
+    update_table(
+        document.as_table_mut(),
+        core::mem::take(new_document.as_table_mut()),
+    );
+    Ok(())
+}
+
+/// Serialize any `Facet` type to a TOML string, keeping the formatting of the `original`
+/// document wherever the value didn't change. See [`update_document`].
+pub fn to_string_preserving<'a, T: facet_core::Facet<'a>>(
+    original: &str,
+    value: &'a T,
+) -> Result<String, TomlSerError> {
+    let mut document: DocumentMut = original.parse().map_err(TomlSerError::InvalidDocument)?;
+    update_document(&mut document, value)?;
+    Ok(document.to_string())
+}
+
+/// Write `new` over `item`, keeping the formatting of whatever didn't change.
+fn update_item(item: &mut Item, new: Item) {
+    let new = match new {
+        Item::Table(new_table) => match item {
+            Item::Table(table) => return update_table(table, new_table),
+            Item::Value(Value::InlineTable(table)) => {
+                return update_inline_table(table, new_table.into_inline_table());
+            }
+            _ => Item::Table(new_table),
+        },
+        Item::Value(new_value) => match (&mut *item, new_value) {
+            (Item::Value(value), new_value) => return update_value(value, new_value),
+            (Item::Table(table), Value::InlineTable(new_table)) => {
+                return update_table(table, new_table.into_table());
+            }
+            (_, new_value) => Item::Value(new_value),
+        },
+        Item::ArrayOfTables(new_array) => match item {
+            Item::ArrayOfTables(array) => return update_array_of_tables(array, new_array),
+            _ => Item::ArrayOfTables(new_array),
+        },
+        Item::None => Item::None,
+    };
+    *item = new;
+}
+
+fn update_table(table: &mut Table, new: Table) {
+    let removed: Vec<String> = table
+        .iter()
+        .filter(|(key, _)| !new.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in removed {
+        table.remove(&key);
+    }
+
+    for (key, new_item) in new {
+        // Keys of absent values, like `None`, are left empty by the serializer
+        if new_item.is_none() {
+            continue;
+        }
+        match table.get_mut(&key) {
+            Some(item) => update_item(item, new_item),
+            None => {
+                table.insert(&key, new_item);
+            }
+        }
+    }
+}
+
+fn update_inline_table(table: &mut InlineTable, new: InlineTable) {
+    let removed: Vec<String> = table
+        .iter()
+        .filter(|(key, _)| !new.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in removed {
+        table.remove(&key);
+    }
+
+    for (key, new_value) in new {
+        match table.get_mut(&key) {
+            Some(value) => update_value(value, new_value),
+            None => {
+                table.insert(key, new_value);
+            }
+        }
+    }
+}
+
+fn update_value(value: &mut Value, new: Value) {
+    match (value, new) {
+        (Value::InlineTable(table), Value::InlineTable(new_table)) => {
+            update_inline_table(table, new_table)
+        }
+        (Value::Array(array), Value::Array(new_array)) => update_array(array, new_array),
+        (value, new) => {
+            if !same_scalar(value, &new) {
+                // Keep the whitespace and comments around the value
+                let decor = value.decor().clone();
+                *value = new;
+                *value.decor_mut() = decor;
+            }
+        }
+    }
+}
+
+fn update_array(array: &mut Array, new: Array) {
+    let mut new_values = new.into_iter();
+    for index in 0..array.len() {
+        match new_values.next() {
+            Some(new_value) => update_value(array.get_mut(index).unwrap(), new_value),
+            None => {
+                while array.len() > index {
+                    array.remove(array.len() - 1);
+                }
+                break;
+            }
+        }
+    }
+
+    // Indent new items like the last one, so that multi-line arrays stay multi-line. Comments
+    // before the last item belong to it, so they aren't copied.
+    let indent = array.iter().last().map(|value| {
+        let prefix = value.decor().prefix().and_then(|prefix| prefix.as_str());
+        let prefix = prefix.unwrap_or_default();
+        match prefix.rfind('\n') {
+            Some(index) => prefix[index..].to_string(),
+            None => prefix.to_string(),
+        }
+    });
+    for mut new_value in new_values {
+        match &indent {
+            Some(indent) => {
+                new_value.decor_mut().set_prefix(indent.as_str());
+                new_value.decor_mut().set_suffix("");
+                array.push_formatted(new_value);
+            }
+            None => array.push(new_value),
+        }
+    }
+}
+
+fn update_array_of_tables(array: &mut ArrayOfTables, new: ArrayOfTables) {
+    let mut new_tables = new.into_iter();
+    for index in 0..array.len() {
+        match new_tables.next() {
+            Some(new_table) => update_table(array.get_mut(index).unwrap(), new_table),
+            None => {
+                while array.len() > index {
+                    array.remove(array.len() - 1);
+                }
+                break;
+            }
+        }
+    }
+
+    for new_table in new_tables {
+        array.push(new_table);
+    }
+}
+
+/// Whether two values are the same scalar, however they're written.
+fn same_scalar(value: &Value, other: &Value) -> bool {
+    match (value, other) {
+        (Value::String(value), Value::String(other)) => value.value() == other.value(),
+        (Value::Integer(value), Value::Integer(other)) => value.value() == other.value(),
+        (Value::Float(value), Value::Float(other)) => {
+            value.value().to_bits() == other.value().to_bits()
+        }
+        (Value::Boolean(value), Value::Boolean(other)) => value.value() == other.value(),
+        (Value::Datetime(value), Value::Datetime(other)) => value.value() == other.value(),
+        _ => false,
+    }
+}
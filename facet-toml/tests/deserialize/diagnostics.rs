@@ -0,0 +1,176 @@
+//! Tests for where errors point in the TOML source, and for reporting several errors at once.
+
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_toml::TomlDeErrorKind;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    name: String,
+    port: u16,
+    database: Database,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Database {
+    url: String,
+    pool_size: u32,
+}
+
+/// Part of the TOML source an error points at.
+fn source<'a>(toml: &'a str, span: Option<core::ops::Range<usize>>) -> &'a str {
+    &toml[span.expect("error has a span")]
+}
+
+#[test]
+fn test_type_mismatch_points_at_value() {
+    let toml = "name = \"demo\"\nport = \"eighty\"\n\n[database]\nurl = \"postgres://localhost\"\npool_size = 4\n";
+
+    let error = facet_toml::from_str::<Config>(toml).unwrap_err();
+    assert_eq!(source(toml, error.span()), "\"eighty\"");
+    assert!(error.others().is_empty());
+}
+
+#[test]
+fn test_missing_field_points_at_header() {
+    let toml = "name = \"demo\"\nport = 80\n\n[database]\nurl = \"postgres://localhost\"\n";
+
+    let error = facet_toml::from_str::<Config>(toml).unwrap_err();
+    assert_eq!(
+        error.kind,
+        TomlDeErrorKind::ExpectedFieldWithName("pool_size")
+    );
+    assert_eq!(source(toml, error.span()), "[database]");
+    assert_eq!(error.path(), "Config.database.pool_size");
+}
+
+#[test]
+fn test_missing_field_in_root() {
+    let toml = "name = \"demo\"\n\n[database]\nurl = \"postgres://localhost\"\npool_size = 4\n";
+
+    let error = facet_toml::from_str::<Config>(toml).unwrap_err();
+    assert_eq!(error.kind, TomlDeErrorKind::ExpectedFieldWithName("port"));
+    assert_eq!(error.span(), Some(0..0));
+    assert_eq!(error.path(), "Config.port");
+}
+
+#[test]
+fn test_unknown_field_points_at_key() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(deny_unknown_fields)]
+    struct Root {
+        value: i32,
+    }
+
+    let toml = "value = 1\nvalu = 2\n";
+
+    let error = facet_toml::from_str::<Root>(toml).unwrap_err();
+    assert!(matches!(
+        &error.kind,
+        TomlDeErrorKind::UnknownField { field_name, .. } if field_name == "valu"
+    ));
+    assert_eq!(source(toml, error.span()), "valu");
+
+    // Unknown fields are ignored by default
+    #[derive(Debug, Facet, PartialEq)]
+    struct Lenient {
+        value: i32,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Lenient>(toml).unwrap(),
+        Lenient { value: 1 }
+    );
+}
+
+#[test]
+fn test_unknown_variant_points_at_name() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: Level,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    enum Level {
+        Low,
+        High,
+    }
+
+    let toml = "value = \"Medium\"\n";
+    let error = facet_toml::from_str::<Root>(toml).unwrap_err();
+    assert_eq!(source(toml, error.span()), "\"Medium\"");
+
+    let toml = "[value.Medium]\n";
+    let error = facet_toml::from_str::<Root>(toml).unwrap_err();
+    assert_eq!(source(toml, error.span()), "Medium");
+}
+
+#[test]
+fn test_multiple_errors() {
+    let toml = "port = \"eighty\"\n\n[database]\nurl = 5\n";
+
+    let error = facet_toml::from_str::<Config>(toml).unwrap_err();
+    let errors: Vec<_> = error
+        .errors()
+        .map(|error| (error.path().to_string(), source(toml, error.span())))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("Config.name".to_string(), ""),
+            ("Config.port".to_string(), "\"eighty\""),
+            ("Config.database.url".to_string(), "5"),
+            ("Config.database.pool_size".to_string(), "[database]"),
+        ]
+    );
+    assert_eq!(error.others().len(), 3);
+
+    // Every error is part of the message
+    let message = error.to_string();
+    assert!(message.contains("pool_size"));
+    assert!(message.contains("Config.port"));
+}
+
+#[test]
+fn test_multiple_errors_in_list() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: Vec<i32>,
+    }
+
+    let toml = "values = [1, \"two\", 3, \"four\"]\n";
+
+    let error = facet_toml::from_str::<Root>(toml).unwrap_err();
+    let spans: Vec<_> = error
+        .errors()
+        .map(|error| source(toml, error.span()))
+        .collect();
+    assert_eq!(spans, ["\"two\"", "\"four\""]);
+}
+
+#[test]
+fn test_multiple_errors_in_array_of_tables() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        bin: Vec<Bin>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Bin {
+        name: String,
+    }
+
+    let toml = "[[bin]]\nname = 1\n\n[[bin]]\npath = \"main.rs\"\n";
+
+    let error = facet_toml::from_str::<Root>(toml).unwrap_err();
+    let spans: Vec<_> = error
+        .errors()
+        .map(|error| source(toml, error.span()))
+        .collect();
+    assert_eq!(spans, ["1", "[[bin]]"]);
+    assert_eq!(
+        error.others()[0].kind,
+        TomlDeErrorKind::ExpectedFieldWithName("name")
+    );
+}
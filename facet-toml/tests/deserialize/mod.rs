@@ -1,9 +1,11 @@
 mod basic;
+mod diagnostics;
 mod document;
 mod enum_;
 mod list;
 mod map;
 mod option;
+mod result;
 mod scalar;
 mod struct_;
 mod vec_struct;
@@ -0,0 +1,74 @@
+//! Tests for TOML values to results.
+
+use std::collections::VecDeque;
+
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_toml::TomlDeErrorKind;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Root {
+    value: Result<i32, String>,
+}
+
+#[test]
+fn test_result() {
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = { Ok = 1 }")?,
+        Root { value: Ok(1) },
+    );
+    assert_eq!(
+        facet_toml::from_str::<Root>("[value]\nErr = 'failed'")?,
+        Root {
+            value: Err("failed".to_string())
+        },
+    );
+}
+
+#[test]
+fn test_nested_result() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: Result<VecDeque<i32>, Option<String>>,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = { Ok = [1, 2] }")?,
+        Root {
+            value: Ok(VecDeque::from([1, 2]))
+        },
+    );
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = { Err = 'failed' }")?,
+        Root {
+            value: Err(Some("failed".to_string()))
+        },
+    );
+}
+
+#[test]
+fn test_invalid_result() {
+    assert!(matches!(
+        facet_toml::from_str::<Root>("value = { Fine = 1 }")
+            .unwrap_err()
+            .kind,
+        TomlDeErrorKind::UnknownField { field_name, .. } if field_name == "Fine"
+    ));
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = { Ok = 1, Err = 'failed' }")
+            .unwrap_err()
+            .kind,
+        TomlDeErrorKind::ExpectedExactlyOneField
+    );
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = {}").unwrap_err().kind,
+        TomlDeErrorKind::ExpectedAtLeastOneField
+    );
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = 1").unwrap_err().kind,
+        TomlDeErrorKind::ExpectedType {
+            expected: "table",
+            got: "integer"
+        }
+    );
+}
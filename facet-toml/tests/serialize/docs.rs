@@ -0,0 +1,156 @@
+//! Tests for writing doc comments as TOML comments.
+
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_testhelpers::test;
+
+/// Configuration of the service
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    /// Name shown in the logs
+    name: String,
+    /// Port to listen on.
+    ///
+    /// Ports below 1024 need extra permissions.
+    port: u16,
+    /// Settings of the database
+    database: Database,
+    /// Extra workers, by name
+    workers: BTreeMap<String, Worker>,
+    /// Optional features
+    features: Option<Vec<String>>,
+}
+
+/// Where data is stored
+#[derive(Debug, Facet, PartialEq)]
+struct Database {
+    /// Connection string
+    url: String,
+    pool_size: u32,
+}
+
+/// A background worker
+#[derive(Debug, Facet, PartialEq)]
+struct Worker {
+    /// How many run at once
+    concurrency: u32,
+}
+
+fn config() -> Config {
+    Config {
+        name: "demo".to_string(),
+        port: 8080,
+        database: Database {
+            url: "postgres://localhost".to_string(),
+            pool_size: 4,
+        },
+        workers: [("mailer".to_string(), Worker { concurrency: 2 })]
+            .into_iter()
+            .collect(),
+        features: None,
+    }
+}
+
+#[test]
+fn test_documented() {
+    assert_eq!(
+        facet_toml::to_string_documented(&config())?,
+        r#"# Configuration of the service
+
+# Name shown in the logs
+name = "demo"
+# Port to listen on.
+#
+# Ports below 1024 need extra permissions.
+port = 8080
+
+# Settings of the database
+[database]
+# Where data is stored
+# Connection string
+url = "postgres://localhost"
+pool_size = 4
+
+# Extra workers, by name
+[workers]
+
+[workers.mailer]
+# A background worker
+# How many run at once
+concurrency = 2
+"#
+    );
+}
+
+#[test]
+fn test_documented_round_trip() {
+    let serialized = facet_toml::to_string_documented(&config())?;
+    let deserialized: Config =
+        facet_toml::from_str(&serialized).map_err(|err| eyre::eyre!("{err}"))?;
+    assert_eq!(deserialized, config());
+}
+
+#[test]
+fn test_undocumented_type() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: i32,
+        table: Table,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Table {
+        value: i32,
+    }
+
+    let root = Root {
+        value: 1,
+        table: Table { value: 2 },
+    };
+    assert_eq!(
+        facet_toml::to_string_documented(&root)?,
+        facet_toml::to_string(&root)?
+    );
+}
+
+#[test]
+fn test_documented_array_of_tables() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        /// Binaries to build
+        bin: Vec<Bin>,
+    }
+
+    /// A binary target
+    #[derive(Debug, Facet, PartialEq)]
+    struct Bin {
+        /// Name of the executable
+        name: String,
+    }
+
+    let root = Root {
+        bin: vec![
+            Bin {
+                name: "first".to_string(),
+            },
+            Bin {
+                name: "second".to_string(),
+            },
+        ],
+    };
+    assert_eq!(
+        facet_toml::to_string_documented(&root)?,
+        r#"# Binaries to build
+[[bin]]
+# A binary target
+# Name of the executable
+name = "first"
+
+[[bin]]
+# A binary target
+# Name of the executable
+name = "second"
+"#
+    );
+}
@@ -4,6 +4,8 @@ use facet::Facet;
 use facet_testhelpers::test;
 use facet_toml::TomlDeErrorKind;
 
+use crate::assert_serialize;
+
 #[test]
 fn test_unit_only_enum() {
     #[derive(Debug, Facet, PartialEq)]
@@ -205,3 +207,98 @@ fn test_enum_root() {
     assert_eq!(facet_toml::from_str::<Root>("B = 2")?, Root::B(2));
     assert_eq!(facet_toml::from_str::<Root>("[C]")?, Root::C);
 }
+
+#[test]
+fn test_tagged_enums() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(tag = "type")]
+    #[repr(u8)]
+    enum Internal {
+        Empty,
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(tag = "t", content = "c")]
+    #[repr(u8)]
+    enum Adjacent {
+        Text(String),
+        Move(u32, u32),
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    enum Untagged {
+        Number(u64),
+        Name(String),
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        empty: Internal,
+        rect: Internal,
+        text: Adjacent,
+        moved: Adjacent,
+        number: Untagged,
+        name: Untagged,
+    }
+
+    assert_serialize!(
+        Root,
+        Root {
+            empty: Internal::Empty,
+            rect: Internal::Rect {
+                width: 1,
+                height: 2,
+            },
+            text: Adjacent::Text("hi".to_string()),
+            moved: Adjacent::Move(3, 4),
+            number: Untagged::Number(5),
+            name: Untagged::Name("six".to_string()),
+        },
+    );
+
+    assert_eq!(
+        facet_toml::from_str::<Internal>("width = 1\nheight = 2\ntype = 'Rect'")?,
+        Internal::Rect {
+            width: 1,
+            height: 2
+        },
+    );
+}
+
+#[test]
+fn test_externally_tagged_enum_output() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        a: Shape,
+        b: Shape,
+    }
+
+    assert_eq!(
+        facet_toml::to_string(&Root {
+            a: Shape::Circle(2),
+            b: Shape::Rect {
+                width: 1,
+                height: 2
+            },
+        })?,
+        "[a]\nCircle = 2\n\n[b]\n\n[b.Rect]\nwidth = 1\nheight = 2\n"
+    );
+    assert_eq!(
+        facet_toml::to_string(&Root {
+            a: Shape::Empty,
+            b: Shape::Circle(3),
+        })?,
+        "a = \"Empty\"\n\n[b]\nCircle = 3\n"
+    );
+}
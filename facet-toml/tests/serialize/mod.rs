@@ -1,10 +1,13 @@
 mod basic;
+mod docs;
 mod enum_;
 mod list;
 mod map;
 mod option;
+mod result;
 mod scalar;
 mod struct_;
+mod update;
 mod vec_struct;
 
 /// Assert that the TOML used to serialize a value can be used to deserialize the value too.
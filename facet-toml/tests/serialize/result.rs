@@ -0,0 +1,50 @@
+//! Tests for TOML values to results.
+
+use facet::Facet;
+use facet_testhelpers::test;
+
+use crate::assert_serialize;
+
+#[test]
+fn test_result() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: Result<i32, String>,
+    }
+
+    assert_serialize!(Root, Root { value: Ok(1) });
+    assert_serialize!(
+        Root,
+        Root {
+            value: Err("failed".to_string())
+        }
+    );
+}
+
+#[test]
+fn test_nested_result() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: Result<Inner, Option<u8>>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Inner {
+        name: String,
+    }
+
+    assert_serialize!(
+        Root,
+        Root {
+            value: Ok(Inner {
+                name: "inner".to_string()
+            })
+        }
+    );
+    assert_serialize!(
+        Root,
+        Root {
+            value: Err(Some(3))
+        }
+    );
+}
@@ -0,0 +1,223 @@
+//! Tests for updating existing TOML documents.
+
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Manifest {
+    package: Package,
+    dependencies: HashMap<String, Dependency>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Package {
+    name: String,
+    version: String,
+    description: Option<String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Dependency {
+    version: String,
+}
+
+fn manifest(version: &str, description: Option<&str>) -> Manifest {
+    Manifest {
+        package: Package {
+            name: "demo".to_string(),
+            version: version.to_string(),
+            description: description.map(str::to_string),
+        },
+        dependencies: [(
+            "facet".to_string(),
+            Dependency {
+                version: "0.27".to_string(),
+            },
+        )]
+        .into_iter()
+        .collect(),
+    }
+}
+
+#[test]
+fn test_unchanged_document() {
+    let original = r#"# Managed by hand, please keep the comments
+
+[package]
+version   =   "0.1.0" # bumped by the release script
+name = "demo"
+description = "A demo"
+
+[dependencies]
+facet = { version = "0.27" } # keep inline
+"#;
+
+    assert_eq!(
+        facet_toml::to_string_preserving(original, &manifest("0.1.0", Some("A demo")))?,
+        original
+    );
+}
+
+#[test]
+fn test_changed_values() {
+    let original = r#"# Managed by hand, please keep the comments
+
+[package]
+version   =   "0.1.0" # bumped by the release script
+name = "demo"
+description = "A demo"
+
+[dependencies]
+facet = { version = "0.27" } # keep inline
+"#;
+
+    assert_eq!(
+        facet_toml::to_string_preserving(original, &manifest("0.2.0", None))?,
+        r#"# Managed by hand, please keep the comments
+
+[package]
+version   =   "0.2.0" # bumped by the release script
+name = "demo"
+
+[dependencies]
+facet = { version = "0.27" } # keep inline
+"#
+    );
+}
+
+#[test]
+fn test_new_keys() {
+    let original = r#"[package]
+name = "demo" # the name
+version = "0.1.0"
+
+[dependencies.facet]
+version = "0.27"
+"#;
+
+    assert_eq!(
+        facet_toml::to_string_preserving(original, &manifest("0.1.0", Some("A demo")))?,
+        r#"[package]
+name = "demo" # the name
+version = "0.1.0"
+description = "A demo"
+
+[dependencies.facet]
+version = "0.27"
+"#
+    );
+}
+
+#[test]
+fn test_same_value_different_representation() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        mask: i64,
+        ratio: f64,
+        name: String,
+    }
+
+    let original = "mask = 0xff\nratio = 5e-1\nname = 'literal'\n";
+
+    assert_eq!(
+        facet_toml::to_string_preserving(
+            original,
+            &Root {
+                mask: 255,
+                ratio: 0.5,
+                name: "literal".to_string(),
+            }
+        )?,
+        original
+    );
+    assert_eq!(
+        facet_toml::to_string_preserving(
+            original,
+            &Root {
+                mask: 15,
+                ratio: 0.5,
+                name: "literal".to_string(),
+            }
+        )?,
+        "mask = 15\nratio = 5e-1\nname = 'literal'\n"
+    );
+}
+
+#[test]
+fn test_arrays() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: Vec<i32>,
+    }
+
+    let original = "values = [\n    1, # one\n    2,\n]\n";
+
+    assert_eq!(
+        facet_toml::to_string_preserving(
+            original,
+            &Root {
+                values: vec![1, 3, 4],
+            }
+        )?,
+        "values = [\n    1, # one\n    3,\n    4,\n]\n"
+    );
+    assert_eq!(
+        facet_toml::to_string_preserving(original, &Root { values: vec![1] })?,
+        "values = [\n    1,\n]\n"
+    );
+}
+
+#[test]
+fn test_array_of_tables() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        bin: Vec<Bin>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Bin {
+        name: String,
+    }
+
+    let original =
+        "# binaries\n[[bin]]\nname = \"first\" # the main one\n\n[[bin]]\nname = \"second\"\n";
+
+    assert_eq!(
+        facet_toml::to_string_preserving(
+            original,
+            &Root {
+                bin: vec![Bin {
+                    name: "first".to_string(),
+                }],
+            }
+        )?,
+        "# binaries\n[[bin]]\nname = \"first\" # the main one\n"
+    );
+}
+
+#[test]
+fn test_update_document() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: i32,
+    }
+
+    let mut document: toml_edit::DocumentMut = "value = 1 # the value\n".parse()?;
+    facet_toml::update_document(&mut document, &Root { value: 2 })?;
+    assert_eq!(document.to_string(), "value = 2 # the value\n");
+}
+
+#[test]
+fn test_invalid_document() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: i32,
+    }
+
+    assert!(matches!(
+        facet_toml::to_string_preserving("value = ", &Root { value: 1 }),
+        Err(facet_toml::TomlSerError::InvalidDocument(_))
+    ));
+}
@@ -0,0 +1,702 @@
+use std::borrow::Cow;
+
+use facet_core::{
+    Def, EnumType, Facet, Field, FieldFlags, ScalarAffinity, ShapeAttribute, StructKind, Type,
+    UserType, Variant,
+};
+use facet_reflect::Partial;
+use log::*;
+
+use crate::{ListStyle, UrlEncodedError, UrlEncodedOptions};
+
+/// Deserializes a URL encoded form data string into a value of type `T` that implements `Facet`.
+///
+/// This function supports parsing both flat structures and nested structures using the common
+/// bracket notation. For example, a form field like `user[name]` will be deserialized into
+/// a struct with a field named `user` that contains a field named `name`.
+///
+/// # Nested Structure Format
+///
+/// For nested structures, the library supports the standard bracket notation used in most web frameworks:
+/// - Simple nested objects: `object[field]=value`
+/// - Deeply nested objects: `object[field1][field2]=value`
+/// - Lists: `list=1&list=2`, `list[]=1&list[]=2` or `list[0]=1&list[1]=2`
+/// - Maps: `map[key]=value`
+///
+/// Missing `Option` fields are `None`, and missing lists and maps are empty.
+///
+/// # Basic Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_urlencoded::from_str;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct SearchParams {
+///     query: String,
+///     page: u64,
+/// }
+///
+/// let query_string = "query=rust+programming&page=2";
+///
+/// let params: SearchParams = from_str(query_string).expect("Failed to parse URL encoded data");
+/// assert_eq!(params, SearchParams { query: "rust programming".to_string(), page: 2 });
+/// ```
+///
+/// # Nested Structure Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_urlencoded::from_str;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct User {
+///     name: String,
+///     address: Address,
+/// }
+///
+/// let query_string = "name=John+Doe&address[street]=123+Main+St&address[city]=Anytown";
+///
+/// let user: User = from_str(query_string).expect("Failed to parse URL encoded data");
+/// assert_eq!(user, User {
+///     name: "John Doe".to_string(),
+///     address: Address {
+///         street: "123 Main St".to_string(),
+///         city: "Anytown".to_string(),
+///     },
+/// });
+/// ```
+pub fn from_str<'input: 'facet, 'facet, 'shape, T: Facet<'facet>>(
+    urlencoded: &'input str,
+) -> Result<T, UrlEncodedError<'shape>> {
+    from_str_with_options(urlencoded, UrlEncodedOptions::default())
+}
+
+/// Deserializes a URL encoded form data string into a value of type `T`, with the given
+/// options
+///
+/// ```
+/// use facet::Facet;
+/// use facet_urlencoded::{ListStyle, UrlEncodedOptions, from_str_with_options};
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct Filter {
+///     tags: Vec<String>,
+/// }
+///
+/// let options = UrlEncodedOptions::default().list_style(ListStyle::Comma);
+/// let filter: Filter = from_str_with_options("tags=rust,web", options).unwrap();
+/// assert_eq!(filter.tags, ["rust", "web"]);
+/// ```
+pub fn from_str_with_options<'input: 'facet, 'facet, 'shape, T: Facet<'facet>>(
+    urlencoded: &'input str,
+    options: UrlEncodedOptions,
+) -> Result<T, UrlEncodedError<'shape>> {
+    // Shapes of `T` are 'static, so the errors don't depend on how long the input lives
+    let mut partial = Partial::alloc_shape(T::SHAPE)?;
+    from_str_value(&mut partial, urlencoded, options)?;
+    let value = partial.build()?.materialize::<T>()?;
+    Ok(value)
+}
+
+/// Deserializes a URL encoded form data string into an heap-allocated value.
+///
+/// This is the lower-level function that works with `Partial` directly.
+fn from_str_value<'mem, 'shape>(
+    wip: &mut Partial<'mem, 'shape>,
+    urlencoded: &str,
+    options: UrlEncodedOptions,
+) -> Result<(), UrlEncodedError<'shape>> {
+    trace!("Starting URL encoded form data deserialization");
+
+    // Process the input into a nested structure
+    let root = Node::parse(urlencoded);
+
+    match (wip.shape().def, wip.shape().ty) {
+        (Def::Map(_), _) | (_, Type::User(UserType::Struct(_))) => {}
+        _ => {
+            error!("Unsupported root type");
+            return Err(UrlEncodedError::UnsupportedShape(
+                "Unsupported root type".to_string(),
+            ));
+        }
+    }
+
+    let deserializer = UrlEncodedDeserializer { options };
+    deserializer.deserialize_value(wip, &root, "")
+}
+
+/// The values of URL encoded form data, grouped by the path of their keys: the value of
+/// `a[b][0]=1` ends up in the `0` child of the `b` child of the `a` child of the root.
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// Values given for this exact key, still percent-encoded so that comma-joined lists
+    /// can be split: several for repeated keys like `a=1&a=2` or `a[]=1&a[]=2`
+    values: Vec<String>,
+    /// Nested keys, like `b` in `a[b]=1`, in the order they first appear
+    children: Vec<(String, Node)>,
+}
+
+impl Node {
+    fn parse(urlencoded: &str) -> Self {
+        let mut root = Node::default();
+        for pair in urlencoded.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let key = decode(key);
+            root.insert(&key_segments(&key), value.to_string());
+        }
+        root
+    }
+
+    fn leaf(raw_value: &str) -> Self {
+        Self {
+            values: vec![raw_value.to_string()],
+            children: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, segments: &[&str], raw_value: String) {
+        match segments {
+            // Empty brackets at the end of the key, like `a[]`, append to a list
+            [] | [""] => self.values.push(raw_value),
+            [first, rest @ ..] => {
+                let index = match self.children.iter().position(|(key, _)| key == first) {
+                    Some(index) => index,
+                    None => {
+                        self.children.push((first.to_string(), Node::default()));
+                        self.children.len() - 1
+                    }
+                };
+                self.children[index].1.insert(rest, raw_value);
+            }
+        }
+    }
+
+    fn child(&self, key: &str) -> Option<&Node> {
+        self.children
+            .iter()
+            .find(|(child_key, _)| child_key == key)
+            .map(|(_, child)| child)
+    }
+
+    /// The decoded value for this key, the last one if it's repeated
+    fn value(&self) -> Option<String> {
+        self.values.last().map(|raw| decode(raw).into_owned())
+    }
+}
+
+/// Splits a key like `a[b][]` into its segments, `a`, `b` and an empty one, or returns the
+/// whole key if it doesn't follow the bracket notation
+fn key_segments(key: &str) -> Vec<&str> {
+    if let Some(open_bracket) = key.find('[') {
+        let mut segments = vec![&key[..open_bracket]];
+        let mut rest = &key[open_bracket..];
+        while let Some(inner) = rest.strip_prefix('[') {
+            let Some(close_bracket) = inner.find(']') else {
+                break;
+            };
+            segments.push(&inner[..close_bracket]);
+            rest = &inner[(close_bracket + 1)..];
+        }
+        if rest.is_empty() {
+            return segments;
+        }
+    }
+    vec![key]
+}
+
+/// Decodes a percent-encoded key or value, where `+` stands for a space
+fn decode(raw: &str) -> Cow<'_, str> {
+    if raw.contains('+') {
+        let raw = raw.replace('+', " ");
+        Cow::Owned(
+            percent_encoding::percent_decode_str(&raw)
+                .decode_utf8_lossy()
+                .into_owned(),
+        )
+    } else {
+        percent_encoding::percent_decode_str(raw).decode_utf8_lossy()
+    }
+}
+
+/// Returns the key of the `name` child of the value at `key`, for error messages
+fn child_key(key: &str, name: &str) -> String {
+    if key.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}[{}]", key, name)
+    }
+}
+
+/// Returns whether a key is read by one of the fields, directly or through a flattened
+/// struct or enum
+fn is_field_key(fields: &[Field<'_>], key: &str) -> bool {
+    fields.iter().any(|field| {
+        if !field.flags.contains(FieldFlags::FLATTEN) {
+            return field.name == key;
+        }
+        match field.shape.ty {
+            Type::User(UserType::Struct(struct_type)) => is_field_key(struct_type.fields, key),
+            Type::User(UserType::Enum(enum_type)) => {
+                enum_type.variants.iter().any(|variant| variant.name == key)
+            }
+            _ => false,
+        }
+    })
+}
+
+struct UrlEncodedDeserializer {
+    options: UrlEncodedOptions,
+}
+
+impl UrlEncodedDeserializer {
+    /// Deserialize a value recursively using the nested values
+    fn deserialize_value<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        node: &Node,
+        key: &str,
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        let shape = wip.shape();
+        trace!("Deserializing {} at '{}'", shape, key);
+
+        match shape.def {
+            Def::Scalar(_) => return self.deserialize_scalar(wip, node, key),
+            Def::Option(option_def) => {
+                // Empty form fields stand for `None`, unless an empty string is a valid value
+                let is_empty =
+                    node.children.is_empty() && node.values.iter().all(|value| value.is_empty());
+                if is_empty && !option_def.t().is_type::<String>() {
+                    wip.set_default()?;
+                } else {
+                    wip.begin_some()?;
+                    self.deserialize_value(wip, node, key)?;
+                    wip.end()?;
+                }
+                return Ok(());
+            }
+            Def::List(_) => {
+                wip.begin_list()?;
+                for (item_key, item) in self.items(node, key) {
+                    wip.begin_list_item()?;
+                    self.deserialize_value(wip, &item, &item_key)?;
+                    wip.end()?;
+                }
+                return Ok(());
+            }
+            Def::Map(_) => {
+                wip.begin_map()?;
+                for (name, child) in &node.children {
+                    self.deserialize_map_entry(wip, name, child, &child_key(key, name))?;
+                }
+                return Ok(());
+            }
+            Def::SmartPointer(_) => {
+                wip.begin_smart_ptr()?;
+                self.deserialize_value(wip, node, key)?;
+                wip.end()?;
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        if shape.attributes.contains(&ShapeAttribute::Transparent) && shape.inner.is_some() {
+            wip.begin_inner()?;
+            self.deserialize_value(wip, node, key)?;
+            wip.end()?;
+            return Ok(());
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(struct_type)) if struct_type.kind == StructKind::Struct => {
+                trace!("Deserializing struct");
+                self.deserialize_fields(wip, node, key, struct_type.fields)?;
+                if shape.has_default_attr() {
+                    wip.fill_unset_fields_from_default()?;
+                }
+                trace!("Finished deserializing struct");
+                Ok(())
+            }
+            Type::User(UserType::Enum(enum_type)) => {
+                self.deserialize_enum(wip, node, key, &enum_type)
+            }
+            _ => {
+                error!("Unsupported shape: {}", shape);
+                Err(UrlEncodedError::UnsupportedShape(format!(
+                    "{} for field '{}'",
+                    shape, key
+                )))
+            }
+        }
+    }
+
+    /// Helper function to deserialize a scalar field
+    fn deserialize_scalar<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        node: &Node,
+        key: &str,
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        let Some(value) = node.value() else {
+            error!("Expected scalar field");
+            return Err(UrlEncodedError::UnsupportedShape(format!(
+                "Expected scalar for field '{}'",
+                key
+            )));
+        };
+        set_scalar(wip, value, key)
+    }
+
+    /// Deserializes one entry of a map, whose key is read from its text
+    fn deserialize_map_entry<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        name: &str,
+        node: &Node,
+        key: &str,
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        wip.begin_key()?;
+        if wip.shape().inner.is_some() {
+            wip.begin_inner()?;
+            set_scalar(wip, name.to_string(), key)?;
+            wip.end()?;
+        } else {
+            set_scalar(wip, name.to_string(), key)?;
+        }
+        wip.end()?;
+
+        wip.begin_value()?;
+        self.deserialize_value(wip, node, key)?;
+        wip.end()?;
+        Ok(())
+    }
+
+    /// Returns the items of a list, along with their keys for error messages.
+    ///
+    /// Children with an index as their key go at that index, and the values given for the
+    /// list's own key (split on commas with [`ListStyle::Comma`]) fill the other positions
+    /// in order: lists mixing scalars and structs are written that way.
+    fn items<'n>(&self, node: &'n Node, key: &str) -> Vec<(String, Cow<'n, Node>)> {
+        let mut values = Vec::new();
+        for raw_value in &node.values {
+            if self.options.get_list_style() == ListStyle::Comma {
+                if !raw_value.is_empty() {
+                    values.extend(raw_value.split(','));
+                }
+            } else {
+                values.push(raw_value.as_str());
+            }
+        }
+        let mut values = values.into_iter();
+
+        let mut indexed = node
+            .children
+            .iter()
+            .filter_map(|(index, child)| Some((index.parse::<usize>().ok()?, child)))
+            .collect::<Vec<_>>();
+        indexed.sort_by_key(|(index, _)| *index);
+        let mut indexed = indexed.into_iter().peekable();
+
+        let mut items = Vec::new();
+        loop {
+            let position = items.len();
+            if let Some((index, child)) = indexed.next_if(|(index, _)| *index <= position) {
+                items.push((child_key(key, &index.to_string()), Cow::Borrowed(child)));
+            } else if let Some(raw_value) = values.next() {
+                items.push((key.to_string(), Cow::Owned(Node::leaf(raw_value))));
+            } else if let Some((index, child)) = indexed.next() {
+                // Gaps in the indices are skipped
+                items.push((child_key(key, &index.to_string()), Cow::Borrowed(child)));
+            } else {
+                return items;
+            }
+        }
+    }
+
+    /// Deserializes the fields of a struct or struct variant, all of them read from the
+    /// children of `node`
+    fn deserialize_fields<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        node: &Node,
+        key: &str,
+        fields: &'shape [Field<'shape>],
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        let container_default = wip.shape().has_default_attr();
+
+        for (index, field) in fields.iter().enumerate() {
+            if field.flags.contains(FieldFlags::FLATTEN) {
+                wip.begin_nth_field(index)?;
+                self.deserialize_flattened(wip, node, key, field, fields)?;
+                wip.end()?;
+            } else if let Some(child) = node.child(field.name) {
+                wip.begin_nth_field(index)?;
+                self.deserialize_value(wip, child, &child_key(key, field.name))?;
+                wip.end()?;
+            } else if field.flags.contains(FieldFlags::DEFAULT) {
+                wip.begin_nth_field(index)?;
+                // Check for field-level default function first, then type-level default
+                if let Some(field_default_fn) = field.vtable.default_fn {
+                    wip.set_field_default(field_default_fn)?;
+                } else {
+                    wip.set_default()?;
+                }
+                wip.end()?;
+            } else if !container_default
+                && matches!(field.shape.def, Def::Option(_) | Def::List(_) | Def::Map(_))
+            {
+                // Absent options are `None`, and absent lists and maps are empty, which is
+                // also how they're written
+                wip.begin_nth_field(index)?;
+                wip.set_default()?;
+                wip.end()?;
+            } else {
+                // Left uninitialized, which is reported when the value is built
+                trace!("Missing field: {}", child_key(key, field.name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deserializes a flattened field, whose own fields (or variant, or map entries) are
+    /// next to its siblings rather than nested under its name
+    fn deserialize_flattened<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        node: &Node,
+        key: &str,
+        field: &Field<'shape>,
+        siblings: &'shape [Field<'shape>],
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        let shape = wip.shape();
+
+        if let Def::Map(_) = shape.def {
+            // Collects every key that none of the other fields read
+            wip.begin_map()?;
+            for (name, child) in &node.children {
+                if !is_field_key(siblings, name) {
+                    self.deserialize_map_entry(wip, name, child, &child_key(key, name))?;
+                }
+            }
+            return Ok(());
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(struct_type)) => {
+                self.deserialize_fields(wip, node, key, struct_type.fields)?;
+                if shape.has_default_attr() {
+                    wip.fill_unset_fields_from_default()?;
+                }
+                Ok(())
+            }
+            Type::User(UserType::Enum(enum_type)) => {
+                // The variant name is used as the key of its data
+                let Some((index, variant, child)) = enum_type
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, variant)| Some((index, variant, node.child(variant.name)?)))
+                else {
+                    return Err(UrlEncodedError::InvalidEnum(
+                        child_key(key, field.name),
+                        "no variant given".to_string(),
+                    ));
+                };
+                wip.select_nth_variant(index)?;
+                self.deserialize_variant_data(wip, child, &child_key(key, variant.name), variant)
+            }
+            _ => Err(UrlEncodedError::UnsupportedShape(format!(
+                "Cannot flatten {} for field '{}'",
+                shape,
+                child_key(key, field.name)
+            ))),
+        }
+    }
+
+    fn deserialize_enum<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        node: &Node,
+        key: &str,
+        enum_type: &EnumType<'shape>,
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        let shape = wip.shape();
+        trace!("Deserializing enum");
+
+        if shape.has_untagged_attr() {
+            return self.deserialize_untagged_enum(wip, node, key, enum_type);
+        }
+
+        let find_variant = |name: &str| {
+            enum_type
+                .variants
+                .iter()
+                .enumerate()
+                .find(|(_, variant)| variant.name == name)
+                .ok_or_else(|| {
+                    UrlEncodedError::InvalidEnum(
+                        key.to_string(),
+                        format!("unknown variant '{}'", name),
+                    )
+                })
+        };
+
+        if let Some(tag) = shape.get_tag_attr() {
+            let Some(name) = node.child(tag).and_then(Node::value) else {
+                return Err(UrlEncodedError::InvalidEnum(
+                    key.to_string(),
+                    format!("missing tag '{}'", tag),
+                ));
+            };
+            let (index, variant) = find_variant(&name)?;
+            wip.select_nth_variant(index)?;
+
+            return match shape.get_content_attr() {
+                // Adjacently tagged: `a[tag]=Variant&a[content]=data`
+                Some(content) => {
+                    let empty = Node::default();
+                    let data = node.child(content).unwrap_or(&empty);
+                    self.deserialize_variant_data(wip, data, &child_key(key, content), variant)
+                }
+                // Internally tagged: `a[tag]=Variant&a[field]=data`
+                None => self.deserialize_variant_data(wip, node, key, variant),
+            };
+        }
+
+        // Unit variants are written as their name: `a=Variant`
+        if let Some(name) = node.value() {
+            let (index, _) = find_variant(&name)?;
+            wip.select_nth_variant(index)?;
+            return Ok(());
+        }
+
+        // Other variants have their data under their name: `a[Variant]=data`
+        let Some((name, data)) = node.children.first() else {
+            return Err(UrlEncodedError::InvalidEnum(
+                key.to_string(),
+                "no variant given".to_string(),
+            ));
+        };
+        let (index, variant) = find_variant(name)?;
+        wip.select_nth_variant(index)?;
+        self.deserialize_variant_data(wip, data, &child_key(key, name), variant)
+    }
+
+    /// Deserializes an untagged enum by trying each variant in turn, until one matches.
+    fn deserialize_untagged_enum<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        node: &Node,
+        key: &str,
+        enum_type: &EnumType<'shape>,
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        for (index, variant) in enum_type.variants.iter().enumerate() {
+            trace!("Trying variant {}", variant.name);
+
+            // Unit variants are written as an empty value
+            if variant.data.kind == StructKind::Unit {
+                if node.children.is_empty() && node.value().as_deref() == Some("") {
+                    wip.select_nth_variant(index)?;
+                    return Ok(());
+                }
+                continue;
+            }
+
+            let mut attempt = Partial::alloc_shape(wip.shape())?;
+            attempt.select_nth_variant(index)?;
+            if self
+                .deserialize_variant_data(&mut attempt, node, key, variant)
+                .is_err()
+            {
+                continue;
+            }
+            if let Ok(value) = attempt.build() {
+                wip.set_from_heap_value(value)?;
+                return Ok(());
+            }
+        }
+
+        Err(UrlEncodedError::InvalidEnum(
+            key.to_string(),
+            format!("no variant of {} matches", wip.shape()),
+        ))
+    }
+
+    /// Deserializes the data of the selected variant, without any variant name around it.
+    fn deserialize_variant_data<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        node: &Node,
+        key: &str,
+        variant: &Variant<'shape>,
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        match variant.data.kind {
+            StructKind::Unit => Ok(()),
+            // Newtype variants contain the inner value directly
+            StructKind::Tuple | StructKind::TupleStruct if variant.data.fields.len() == 1 => {
+                wip.begin_nth_field(0)?;
+                self.deserialize_value(wip, node, key)?;
+                wip.end()?;
+                Ok(())
+            }
+            // Tuple variants are written like lists
+            StructKind::Tuple | StructKind::TupleStruct => {
+                let items = self.items(node, key);
+                if items.len() != variant.data.fields.len() {
+                    return Err(UrlEncodedError::InvalidEnum(
+                        key.to_string(),
+                        format!(
+                            "expected {} items for variant '{}', got {}",
+                            variant.data.fields.len(),
+                            variant.name,
+                            items.len()
+                        ),
+                    ));
+                }
+                for (index, (item_key, item)) in items.iter().enumerate() {
+                    wip.begin_nth_field(index)?;
+                    self.deserialize_value(wip, item, item_key)?;
+                    wip.end()?;
+                }
+                Ok(())
+            }
+            StructKind::Struct => self.deserialize_fields(wip, node, key, variant.data.fields),
+            _ => Err(UrlEncodedError::UnsupportedShape(format!(
+                "Unsupported kind for variant '{}'",
+                variant.name
+            ))),
+        }
+    }
+}
+
+/// Sets a scalar from its text, parsing it unless it's a string
+fn set_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: String,
+    key: &str,
+) -> Result<(), UrlEncodedError<'shape>> {
+    let shape = wip.shape();
+    if shape.is_type::<String>() {
+        wip.set(value)?;
+    } else if shape.is_type::<Cow<'_, str>>() {
+        wip.set(Cow::<str>::Owned(value))?;
+    } else if shape.vtable.sized().and_then(|v| (v.parse)()).is_none() {
+        warn!("facet-urlencoded: unsupported scalar type: {}", shape);
+        return Err(UrlEncodedError::UnsupportedType(format!("{}", shape)));
+    } else if wip.parse_from_str(&value).is_err() {
+        return Err(match shape.def {
+            Def::Scalar(sd) if matches!(sd.affinity, ScalarAffinity::Number(_)) => {
+                UrlEncodedError::InvalidNumber(key.to_string(), value)
+            }
+            _ => UrlEncodedError::InvalidValue(key.to_string(), value),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+/// How the items of a list are written as form data
+///
+/// Lists of structs, maps or other lists are always written with indices, like
+/// `a[0][name]=x`, since the other styles can't tell their items apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListStyle {
+    /// One pair per item, all with the same key: `a=1&a=2`
+    #[default]
+    Repeated,
+    /// One pair per item, with empty brackets after the key: `a[]=1&a[]=2`
+    Brackets,
+    /// One pair per item, with its index in brackets after the key: `a[0]=1&a[1]=2`
+    Indexed,
+    /// A single pair, with the items joined by commas: `a=1,2`
+    Comma,
+}
+
+/// Settings shared by the URL encoded form data reader and writer
+///
+/// ```
+/// use facet_urlencoded::{ListStyle, UrlEncodedOptions};
+///
+/// let options = UrlEncodedOptions::default().list_style(ListStyle::Brackets);
+/// assert_eq!(options.get_list_style(), ListStyle::Brackets);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UrlEncodedOptions {
+    list_style: ListStyle,
+}
+
+impl UrlEncodedOptions {
+    /// Sets how lists are written, [`ListStyle::Repeated`] by default
+    ///
+    /// When reading, repeated keys, empty brackets and indices are all accepted whatever
+    /// the style. Values are only split on commas with [`ListStyle::Comma`].
+    pub fn list_style(mut self, list_style: ListStyle) -> Self {
+        self.list_style = list_style;
+        self
+    }
+
+    /// How lists are written
+    pub fn get_list_style(&self) -> ListStyle {
+        self.list_style
+    }
+}
@@ -0,0 +1,367 @@
+use facet_core::{Facet, Shape};
+use facet_reflect::Peek;
+use facet_serialize::{Serializer, serialize_iterative};
+
+use crate::{ListStyle, UrlEncodedError, UrlEncodedOptions};
+
+/// Serializes a value into a URL encoded form data string.
+///
+/// The value must be a struct or a map. Nested values use the same bracket notation as
+/// [`from_str`](crate::from_str) reads: `user[name]=John`. `None` values are left out, and
+/// unit enum variants are written as their name.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_urlencoded::to_string;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>,
+///     address: Address,
+/// }
+///
+/// let user = User {
+///     name: "John Doe".to_string(),
+///     nickname: None,
+///     address: Address {
+///         street: "123 Main St".to_string(),
+///         city: "Anytown".to_string(),
+///     },
+/// };
+/// assert_eq!(
+///     to_string(&user).unwrap(),
+///     "name=John+Doe&address[street]=123+Main+St&address[city]=Anytown"
+/// );
+/// ```
+pub fn to_string<'facet, T: Facet<'facet>>(value: &T) -> Result<String, UrlEncodedError<'static>> {
+    to_string_with_options(value, UrlEncodedOptions::default())
+}
+
+/// Serializes a value into a URL encoded form data string, with the given options
+///
+/// ```
+/// use facet::Facet;
+/// use facet_urlencoded::{ListStyle, UrlEncodedOptions, to_string_with_options};
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct Filter {
+///     tags: Vec<String>,
+/// }
+///
+/// let filter = Filter { tags: vec!["rust".to_string(), "web".to_string()] };
+/// let options = UrlEncodedOptions::default().list_style(ListStyle::Indexed);
+/// assert_eq!(
+///     to_string_with_options(&filter, options).unwrap(),
+///     "tags[0]=rust&tags[1]=web"
+/// );
+/// ```
+pub fn to_string_with_options<'facet, T: Facet<'facet>>(
+    value: &T,
+    options: UrlEncodedOptions,
+) -> Result<String, UrlEncodedError<'static>> {
+    peek_to_string_with_options(Peek::new(value), options)
+}
+
+/// Serializes a `Peek` instance into a URL encoded form data string.
+pub fn peek_to_string<'shape>(
+    peek: Peek<'_, '_, 'shape>,
+) -> Result<String, UrlEncodedError<'shape>> {
+    peek_to_string_with_options(peek, UrlEncodedOptions::default())
+}
+
+/// Serializes a `Peek` instance into a URL encoded form data string, with the given options
+pub fn peek_to_string_with_options<'shape>(
+    peek: Peek<'_, '_, 'shape>,
+    options: UrlEncodedOptions,
+) -> Result<String, UrlEncodedError<'shape>> {
+    let mut serializer = UrlEncodedSerializer {
+        options,
+        output: String::new(),
+        path: Vec::new(),
+        containers: Vec::new(),
+        map_key: None,
+    };
+    serialize_iterative(peek, &mut serializer)?;
+    Ok(serializer.output)
+}
+
+/// Writes one `key=value` pair per scalar, its key being the path to the scalar in
+/// bracket notation
+struct UrlEncodedSerializer {
+    /// How lists are written
+    options: UrlEncodedOptions,
+
+    /// The pairs written so far
+    output: String,
+
+    /// The field names, map keys and list indices leading to the value being written
+    path: Vec<String>,
+
+    /// The objects, maps and lists being written, innermost last
+    containers: Vec<Container>,
+
+    /// The text of the map key being written, if any
+    map_key: Option<String>,
+}
+
+struct Container {
+    kind: ContainerKind,
+
+    /// Whether an index was added to the path for this container, as an item of a list
+    indexed: bool,
+}
+
+enum ContainerKind {
+    Object,
+    Map,
+    List {
+        /// The number of items written so far
+        len: usize,
+        /// The items written so far with [`ListStyle::Comma`], already percent-encoded
+        joined: Option<String>,
+    },
+}
+
+impl UrlEncodedSerializer {
+    /// Renders the current path as a key, like `user[address][city]`
+    fn key(&self) -> String {
+        let mut key = String::new();
+        for (index, segment) in self.path.iter().enumerate() {
+            if index > 0 {
+                key.push('[');
+            }
+            key.extend(form_urlencoded::byte_serialize(segment.as_bytes()));
+            if index > 0 {
+                key.push(']');
+            }
+        }
+        key
+    }
+
+    fn write_pair(&mut self, key: &str, encoded_value: &str) {
+        if !self.output.is_empty() {
+            self.output.push('&');
+        }
+        self.output.push_str(key);
+        self.output.push('=');
+        self.output.push_str(encoded_value);
+    }
+
+    fn write_scalar<'shape>(&mut self, value: &str) -> Result<(), UrlEncodedError<'shape>> {
+        if let Some(map_key) = &mut self.map_key {
+            map_key.push_str(value);
+            return Ok(());
+        }
+        if self.path.is_empty() {
+            return Err(UrlEncodedError::UnsupportedShape(
+                "Only structs and maps can be written as URL encoded form data".to_string(),
+            ));
+        }
+
+        let key = self.key();
+        let encoded_value = form_urlencoded::byte_serialize(value.as_bytes()).collect::<String>();
+        let list_style = self.options.get_list_style();
+        let Some(Container {
+            kind: ContainerKind::List { len, joined },
+            ..
+        }) = self.containers.last_mut()
+        else {
+            self.write_pair(&key, &encoded_value);
+            return Ok(());
+        };
+
+        let index = *len;
+        *len += 1;
+        match list_style {
+            ListStyle::Repeated => self.write_pair(&key, &encoded_value),
+            ListStyle::Brackets => self.write_pair(&format!("{}[]", key), &encoded_value),
+            ListStyle::Indexed => self.write_pair(&format!("{}[{}]", key, index), &encoded_value),
+            ListStyle::Comma => match joined {
+                Some(joined) => {
+                    joined.push(',');
+                    joined.push_str(&encoded_value);
+                }
+                None => *joined = Some(encoded_value),
+            },
+        }
+        Ok(())
+    }
+
+    fn start_container<'shape>(
+        &mut self,
+        kind: ContainerKind,
+    ) -> Result<(), UrlEncodedError<'shape>> {
+        if self.map_key.is_some() {
+            return Err(UrlEncodedError::UnsupportedShape(
+                "Map keys must be scalars".to_string(),
+            ));
+        }
+        if matches!(kind, ContainerKind::List { .. }) && self.path.is_empty() {
+            return Err(UrlEncodedError::UnsupportedShape(
+                "Only structs and maps can be written as URL encoded form data".to_string(),
+            ));
+        }
+
+        // Items of a list that aren't scalars always get an index, whatever the style
+        let mut indexed = false;
+        if let Some(Container {
+            kind: ContainerKind::List { len, .. },
+            ..
+        }) = self.containers.last_mut()
+        {
+            self.path.push(len.to_string());
+            *len += 1;
+            indexed = true;
+        }
+
+        self.containers.push(Container { kind, indexed });
+        Ok(())
+    }
+
+    fn end_container(&mut self) {
+        let Some(container) = self.containers.pop() else {
+            return;
+        };
+        if let ContainerKind::List {
+            joined: Some(joined),
+            ..
+        } = container.kind
+        {
+            let key = self.key();
+            self.write_pair(&key, &joined);
+        }
+        if container.indexed {
+            self.path.pop();
+        }
+    }
+}
+
+impl<'shape> Serializer<'shape> for UrlEncodedSerializer {
+    type Error = UrlEncodedError<'shape>;
+
+    fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.write_scalar(&value.to_string())
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.write_scalar(&value.to_string())
+    }
+
+    fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.write_scalar(&value.to_string())
+    }
+
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.write_scalar(&value.to_string())
+    }
+
+    fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.write_scalar(&value.to_string())
+    }
+
+    fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
+        self.write_scalar(&value.to_string())
+    }
+
+    fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.write_scalar(if value { "true" } else { "false" })
+    }
+
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.write_scalar(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.write_scalar(value)
+    }
+
+    fn serialize_bytes(&mut self, _value: &[u8]) -> Result<(), Self::Error> {
+        Err(UrlEncodedError::UnsupportedType(
+            "byte arrays can't be written as URL encoded form data".to_string(),
+        ))
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        // Absent keys are read back as `None`
+        Ok(())
+    }
+
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.write_scalar("")
+    }
+
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        variant_name: &'shape str,
+    ) -> Result<(), Self::Error> {
+        self.write_scalar(variant_name)
+    }
+
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.start_container(ContainerKind::Object)
+    }
+
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
+        Ok(())
+    }
+
+    fn serialize_field_name(&mut self, name: &'shape str) -> Result<(), Self::Error> {
+        self.path.push(name.to_string());
+        Ok(())
+    }
+
+    fn end_field(&mut self) -> Result<(), Self::Error> {
+        self.path.pop();
+        Ok(())
+    }
+
+    fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.start_container(ContainerKind::List {
+            len: 0,
+            joined: None,
+        })
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
+        Ok(())
+    }
+
+    fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.start_container(ContainerKind::Map)
+    }
+
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error {
+        UrlEncodedError::UnsupportedShape(format!("{shape}: {reason}"))
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
+        Ok(())
+    }
+
+    fn begin_map_key(&mut self) -> Result<(), Self::Error> {
+        self.map_key = Some(String::new());
+        Ok(())
+    }
+
+    fn end_map_key(&mut self) -> Result<(), Self::Error> {
+        let map_key = self.map_key.take().unwrap_or_default();
+        self.path.push(map_key);
+        Ok(())
+    }
+
+    fn end_map_value(&mut self) -> Result<(), Self::Error> {
+        self.path.pop();
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::from_str;
+use crate::{
+    ListStyle, UrlEncodedOptions, from_str, from_str_with_options, to_string,
+    to_string_with_options,
+};
 use facet::Facet;
 use facet_testhelpers::test;
 
@@ -119,6 +122,15 @@ fn test_invalid_number() {
     }
 }
 
+#[test]
+fn test_error_outlives_input() {
+    let err = {
+        let query_string = String::from("query=rust&page=not_a_number");
+        from_str::<SearchParams>(&query_string).unwrap_err()
+    };
+    assert!(matches!(err, crate::UrlEncodedError::InvalidNumber(..)));
+}
+
 #[test]
 fn test_nested_struct() {
     let query_string = "user[name]=John+Doe&user[age]=30&user[address][street]=123+Main+St&user[address][city]=Anytown&user[address][zip]=12345&product_id=ABC123&quantity=2";
@@ -216,3 +228,250 @@ fn test_deep_nesting() {
         }
     );
 }
+
+#[test]
+fn test_to_string_round_trip() {
+    let order = OrderForm {
+        product_id: "ABC123".to_string(),
+        quantity: 2,
+        user: User {
+            name: "John Doe".to_string(),
+            age: 30,
+            address: Address {
+                street: "123 Main St".to_string(),
+                city: "Anytown & Co".to_string(),
+                zip: "12345".to_string(),
+            },
+        },
+    };
+
+    let query_string = to_string(&order)?;
+    assert_eq!(
+        query_string,
+        "product_id=ABC123&quantity=2&user[name]=John+Doe&user[age]=30&user[address][street]=123+Main+St&user[address][city]=Anytown+%26+Co&user[address][zip]=12345"
+    );
+    assert_eq!(from_str::<OrderForm>(&query_string)?, order);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Filter {
+    tags: Vec<String>,
+    ids: Vec<u32>,
+}
+
+#[test]
+fn test_list_styles() {
+    let filter = Filter {
+        tags: vec!["rust".to_string(), "a,b".to_string()],
+        ids: vec![1, 2, 3],
+    };
+
+    for (style, expected) in [
+        (
+            ListStyle::Repeated,
+            "tags=rust&tags=a%2Cb&ids=1&ids=2&ids=3",
+        ),
+        (
+            ListStyle::Brackets,
+            "tags[]=rust&tags[]=a%2Cb&ids[]=1&ids[]=2&ids[]=3",
+        ),
+        (
+            ListStyle::Indexed,
+            "tags[0]=rust&tags[1]=a%2Cb&ids[0]=1&ids[1]=2&ids[2]=3",
+        ),
+        (ListStyle::Comma, "tags=rust,a%2Cb&ids=1,2,3"),
+    ] {
+        let options = UrlEncodedOptions::default().list_style(style);
+        let query_string = to_string_with_options(&filter, options)?;
+        assert_eq!(query_string, expected, "with {:?}", style);
+        assert_eq!(
+            from_str_with_options::<Filter>(&query_string, options)?,
+            filter,
+            "with {:?}",
+            style
+        );
+    }
+}
+
+#[test]
+fn test_lists_of_structs_and_empty_lists() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Item {
+        name: String,
+        count: u64,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Cart {
+        items: Vec<Item>,
+        coupons: Vec<String>,
+    }
+
+    let cart = Cart {
+        items: vec![
+            Item {
+                name: "apple".to_string(),
+                count: 3,
+            },
+            Item {
+                name: "pear".to_string(),
+                count: 1,
+            },
+        ],
+        coupons: vec![],
+    };
+
+    let query_string = to_string(&cart)?;
+    assert_eq!(
+        query_string,
+        "items[0][name]=apple&items[0][count]=3&items[1][name]=pear&items[1][count]=1"
+    );
+    assert_eq!(from_str::<Cart>(&query_string)?, cart);
+
+    // Indices may come in any order
+    let cart_from_str: Cart =
+        from_str("items[1][count]=1&items[1][name]=pear&items[0][name]=apple&items[0][count]=3")?;
+    assert_eq!(cart_from_str, cart);
+}
+
+#[test]
+fn test_options() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Profile {
+        name: String,
+        age: Option<u64>,
+        bio: Option<String>,
+        address: Option<Address>,
+    }
+
+    let profile = Profile {
+        name: "Jane".to_string(),
+        age: None,
+        bio: Some("".to_string()),
+        address: Some(Address {
+            street: "1 Elm St".to_string(),
+            city: "Springfield".to_string(),
+            zip: "54321".to_string(),
+        }),
+    };
+
+    let query_string = to_string(&profile)?;
+    assert_eq!(
+        query_string,
+        "name=Jane&bio=&address[street]=1+Elm+St&address[city]=Springfield&address[zip]=54321"
+    );
+    assert_eq!(from_str::<Profile>(&query_string)?, profile);
+
+    // Empty form fields are `None`, unless they're strings
+    let profile: Profile = from_str("name=Jane&age=")?;
+    assert_eq!(
+        profile,
+        Profile {
+            name: "Jane".to_string(),
+            age: None,
+            bio: None,
+            address: None,
+        }
+    );
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Active,
+    Suspended { reason: String, days: u32 },
+    Renamed(String),
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[facet(tag = "type")]
+#[allow(dead_code)]
+enum Payment {
+    Card { number: String },
+    Cash,
+}
+
+#[test]
+fn test_enums() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Account {
+        status: Status,
+        previous: Vec<Status>,
+        payment: Payment,
+    }
+
+    let account = Account {
+        status: Status::Suspended {
+            reason: "spam".to_string(),
+            days: 7,
+        },
+        previous: vec![Status::Active, Status::Renamed("bob".to_string())],
+        payment: Payment::Card {
+            number: "4242".to_string(),
+        },
+    };
+
+    let query_string = to_string(&account)?;
+    assert_eq!(
+        query_string,
+        "status[Suspended][reason]=spam&status[Suspended][days]=7&previous=Active&previous[1][Renamed]=bob&payment[type]=Card&payment[number]=4242"
+    );
+    assert_eq!(from_str::<Account>(&query_string)?, account);
+
+    let account: Account = from_str("status=Active&payment[type]=Cash")?;
+    assert_eq!(account.status, Status::Active);
+    assert_eq!(account.payment, Payment::Cash);
+
+    let err = from_str::<Account>("status=Deleted&payment[type]=Cash").unwrap_err();
+    match err {
+        crate::UrlEncodedError::InvalidEnum(field, _) => assert_eq!(field, "status"),
+        _ => panic!("Expected InvalidEnum error, got: {:?}", err),
+    }
+}
+
+#[test]
+fn test_flatten() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Pagination {
+        page: u64,
+        per_page: u64,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Search {
+        query: String,
+        #[facet(flatten)]
+        pagination: Pagination,
+        #[facet(flatten)]
+        status: Status,
+        #[facet(flatten)]
+        extra: std::collections::HashMap<String, String>,
+    }
+
+    let mut extra = std::collections::HashMap::new();
+    extra.insert("utm_source".to_string(), "newsletter".to_string());
+    let search = Search {
+        query: "rust".to_string(),
+        pagination: Pagination {
+            page: 2,
+            per_page: 20,
+        },
+        status: Status::Renamed("bob".to_string()),
+        extra,
+    };
+
+    let query_string = to_string(&search)?;
+    assert_eq!(
+        query_string,
+        "query=rust&page=2&per_page=20&Renamed=bob&utm_source=newsletter"
+    );
+    assert_eq!(from_str::<Search>(&query_string)?, search);
+}
+
+#[test]
+fn test_to_string_rejects_scalars() {
+    assert!(to_string(&42u64).is_err());
+    assert!(to_string(&vec![1u64, 2]).is_err());
+}
@@ -0,0 +1,505 @@
+use alloc::{borrow::Cow, string::String};
+
+use facet_core::{
+    Def, Facet, Field, FieldFlags, ShapeAttribute, StructKind, StructType, Type, UserType, Variant,
+};
+use facet_reflect::{Partial, ScalarType};
+
+use crate::{Map, Number, Value, ValueError};
+
+impl Value {
+    /// Builds a typed value out of this `Value`
+    ///
+    /// This accepts the same structure [`Value::from_peek`] produces: objects for
+    /// structs, arrays for tuples, and enums following their tagging attributes. Struct
+    /// fields that are absent fall back to their default if they have one, and to
+    /// `None` if they're optional. Numbers are converted to the target numeric type
+    /// when they fit, and strings are parsed for scalars that aren't strings.
+    pub fn deserialize_into<'facet, T: Facet<'facet>>(&self) -> Result<T, ValueError<'static>> {
+        // Shapes of `T` are 'static, so the errors don't depend on `'facet`
+        let mut partial = Partial::alloc_shape(T::SHAPE)?;
+        deserialize_value(&mut partial, self)?;
+        Ok(partial.build()?.materialize::<T>()?)
+    }
+}
+
+fn deserialize_value<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+
+    if shape.is_type::<Value>() {
+        wip.set(value.clone())?;
+        return Ok(());
+    }
+
+    if shape.attributes.contains(&ShapeAttribute::Transparent) {
+        wip.begin_inner()?;
+        deserialize_value(wip, value)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match shape.def {
+        Def::Scalar(_) => return deserialize_scalar(wip, value),
+        Def::List(_) => {
+            let items = elements(value).ok_or(ValueError::TypeMismatch {
+                expected: shape,
+                got: value.kind(),
+            })?;
+            wip.begin_list()?;
+            for item in items.iter() {
+                wip.begin_list_item()?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Array(_) => {
+            let items = elements(value).ok_or(ValueError::TypeMismatch {
+                expected: shape,
+                got: value.kind(),
+            })?;
+            for (index, item) in items.iter().enumerate() {
+                wip.begin_nth_element(index)?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Map(_) => return deserialize_map(wip, value),
+        Def::Option(_) => {
+            if value.is_null() {
+                wip.set_default()?;
+            } else {
+                wip.begin_some()?;
+                deserialize_value(wip, value)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::SmartPointer(_) => {
+            wip.begin_smart_ptr()?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => deserialize_struct(wip, sd, value),
+        Type::User(UserType::Enum(_)) => deserialize_enum(wip, value),
+        _ => Err(ValueError::Unsupported { shape }),
+    }
+}
+
+/// The elements of a list or array: byte strings count as arrays of numbers
+fn elements(value: &Value) -> Option<Cow<'_, [Value]>> {
+    match value {
+        Value::Array(items) => Some(Cow::Borrowed(items)),
+        Value::Bytes(bytes) => Some(Cow::Owned(bytes.iter().map(|&b| Value::from(b)).collect())),
+        _ => None,
+    }
+}
+
+fn deserialize_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+    let mismatch = || ValueError::TypeMismatch {
+        expected: shape,
+        got: value.kind(),
+    };
+
+    let Some(scalar) = ScalarType::try_from_shape(shape) else {
+        // Wrappers like `NonZero<T>` are built from their inner value
+        if shape.inner.is_some() {
+            wip.begin_inner()?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+            return Ok(());
+        }
+        let Value::String(s) = value else {
+            return Err(mismatch());
+        };
+        wip.parse_from_str(s)?;
+        return Ok(());
+    };
+
+    match (scalar, value) {
+        (ScalarType::Unit, Value::Null) => {
+            wip.set(())?;
+        }
+        (ScalarType::Bool, Value::Bool(b)) => {
+            wip.set(*b)?;
+        }
+        (ScalarType::String, Value::String(s)) => {
+            wip.set(s.clone())?;
+        }
+        (ScalarType::CowStr, Value::String(s)) => {
+            wip.set(Cow::<'facet, str>::Owned(s.clone()))?;
+        }
+        (ScalarType::Char, Value::String(s)) if s.chars().count() == 1 => {
+            wip.set(s.chars().next().unwrap())?;
+        }
+        (_, Value::Number(number)) => deserialize_number(wip, scalar, *number)?,
+        (_, Value::String(s)) => {
+            wip.parse_from_str(s)?;
+        }
+        _ => return Err(mismatch()),
+    }
+    Ok(())
+}
+
+fn deserialize_number<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    scalar: ScalarType,
+    number: Number,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+    let out_of_range = || ValueError::NumberOutOfRange { number, shape };
+
+    // Floats are accepted for integer types, as long as they have no fractional part
+    let integer = || match number {
+        Number::I64(n) => Ok(i128::from(n)),
+        Number::U64(n) => Ok(i128::from(n)),
+        Number::F64(n) => {
+            let i = n as i128;
+            if i as f64 == n {
+                Ok(i)
+            } else {
+                Err(out_of_range())
+            }
+        }
+    };
+
+    macro_rules! set_int {
+        ($ty:ty) => {{
+            let n = <$ty>::try_from(integer()?).map_err(|_| out_of_range())?;
+            wip.set(n)?;
+        }};
+    }
+
+    match scalar {
+        ScalarType::F32 => {
+            wip.set(number.as_f64() as f32)?;
+        }
+        ScalarType::F64 => {
+            wip.set(number.as_f64())?;
+        }
+        ScalarType::U8 => set_int!(u8),
+        ScalarType::U16 => set_int!(u16),
+        ScalarType::U32 => set_int!(u32),
+        ScalarType::U64 => set_int!(u64),
+        ScalarType::U128 => set_int!(u128),
+        ScalarType::USize => set_int!(usize),
+        ScalarType::I8 => set_int!(i8),
+        ScalarType::I16 => set_int!(i16),
+        ScalarType::I32 => set_int!(i32),
+        ScalarType::I64 => set_int!(i64),
+        ScalarType::I128 => set_int!(i128),
+        ScalarType::ISize => set_int!(isize),
+        _ => {
+            return Err(ValueError::TypeMismatch {
+                expected: shape,
+                got: "number",
+            });
+        }
+    }
+    Ok(())
+}
+
+fn deserialize_map<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    let Value::Object(map) = value else {
+        return Err(ValueError::TypeMismatch {
+            expected: wip.shape(),
+            got: value.kind(),
+        });
+    };
+
+    wip.begin_map()?;
+    for (key, value) in map {
+        deserialize_map_entry(wip, key, value)?;
+    }
+    Ok(())
+}
+
+fn deserialize_map_entry<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    key: &str,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    // Keys go through the same path as string values, so that they can be
+    // parsed into integers, newtypes and so on
+    wip.begin_key()?;
+    deserialize_value(wip, &Value::from(key))?;
+    wip.end()?;
+
+    wip.begin_value()?;
+    deserialize_value(wip, value)?;
+    wip.end()?;
+    Ok(())
+}
+
+fn deserialize_struct<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    sd: StructType<'shape>,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+
+    if sd.fields.is_empty() {
+        // A struct without fields is zero-sized: there's nothing to write
+        wip.set_from_function(|_| Ok(()))?;
+        return Ok(());
+    }
+
+    match (sd.kind, value) {
+        (StructKind::Struct, Value::Object(map)) => deserialize_fields(wip, sd.fields, map),
+        (StructKind::Tuple | StructKind::TupleStruct, Value::Array(items)) => {
+            for (index, item) in items.iter().enumerate() {
+                wip.begin_nth_field(index)?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+            Ok(())
+        }
+        _ => Err(ValueError::TypeMismatch {
+            expected: shape,
+            got: value.kind(),
+        }),
+    }
+}
+
+/// Sets the fields of a struct, or of the selected enum variant, from an object
+fn deserialize_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    map: &Map,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+
+    // A flattened map collects every key that isn't claimed by a field
+    let flattened_map = match shape.ty {
+        Type::User(UserType::Struct(_)) => fields.iter().position(|field| {
+            field.flags.contains(FieldFlags::FLATTEN) && matches!(field.shape.def, Def::Map(_))
+        }),
+        _ => None,
+    };
+
+    for (key, value) in map {
+        match (wip.field_index(key), flattened_map) {
+            (Some(index), _) => {
+                wip.begin_nth_field(index)?;
+                deserialize_value(wip, value)?;
+                wip.end()?;
+            }
+            (None, Some(index)) => {
+                wip.begin_nth_map_field(index)?;
+                deserialize_map_entry(wip, key, value)?;
+                wip.end()?;
+            }
+            (None, None) if shape.has_deny_unknown_fields_attr() => {
+                return Err(ValueError::UnknownField {
+                    shape,
+                    field: key.clone(),
+                });
+            }
+            (None, None) => {}
+        }
+    }
+
+    // Absent fields fall back to their default, or to `None` for options
+    for (index, field) in fields.iter().enumerate() {
+        if wip.is_field_set(index)? {
+            continue;
+        }
+        if Some(index) == flattened_map {
+            wip.begin_nth_map_field(index)?;
+            wip.end()?;
+        } else if field.flags.contains(FieldFlags::DEFAULT) {
+            wip.begin_nth_field(index)?;
+            if let Some(field_default_fn) = field.vtable.default_fn {
+                wip.set_field_default(field_default_fn)?;
+            } else {
+                wip.set_default()?;
+            }
+            wip.end()?;
+        } else if matches!(field.shape.def, Def::Option(_)) {
+            wip.begin_nth_field(index)?;
+            wip.set_default()?;
+            wip.end()?;
+        }
+    }
+    wip.fill_unset_fields_from_default()?;
+
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            return Err(ValueError::MissingField {
+                shape,
+                field: field.name,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn deserialize_enum<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+    let mismatch = || ValueError::TypeMismatch {
+        expected: shape,
+        got: value.kind(),
+    };
+
+    if shape.has_untagged_attr() {
+        return deserialize_untagged_enum(wip, value);
+    }
+
+    if let Some(tag) = shape.get_tag_attr() {
+        let Value::Object(map) = value else {
+            return Err(mismatch());
+        };
+        let name = match map.get(tag) {
+            Some(Value::String(name)) => name,
+            Some(other) => {
+                return Err(ValueError::TypeMismatch {
+                    expected: shape,
+                    got: other.kind(),
+                });
+            }
+            None => return Err(ValueError::MissingField { shape, field: tag }),
+        };
+        let variant = select_variant(wip, name)?;
+        if variant.data.fields.is_empty() {
+            return Ok(());
+        }
+
+        return if let Some(content) = shape.get_content_attr() {
+            // Adjacently tagged: the data lives under the content key
+            let data = map.get(content).ok_or(ValueError::MissingField {
+                shape,
+                field: content,
+            })?;
+            deserialize_variant_data(wip, variant, data)
+        } else {
+            // Internally tagged: the data is the object itself, minus the tag
+            let mut data = map.clone();
+            data.remove(tag);
+            deserialize_variant_data(wip, variant, &Value::Object(data))
+        };
+    }
+
+    match value {
+        // Unit variants are represented by their name
+        Value::String(name) => {
+            let variant = select_variant(wip, name)?;
+            if !variant.data.fields.is_empty() {
+                return Err(mismatch());
+            }
+            Ok(())
+        }
+        // Other variants are represented by an object with a single key
+        Value::Object(map) if map.len() == 1 => {
+            let (name, data) = map.iter().next().unwrap();
+            let variant = select_variant(wip, name)?;
+            deserialize_variant_data(wip, variant, data)
+        }
+        _ => Err(mismatch()),
+    }
+}
+
+fn deserialize_untagged_enum<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+    let Type::User(UserType::Enum(ed)) = shape.ty else {
+        return Err(ValueError::Unsupported { shape });
+    };
+
+    for (index, variant) in ed.variants.iter().enumerate() {
+        if variant.data.fields.is_empty() {
+            // Unit variants are represented as null
+            if value.is_null() {
+                wip.select_nth_variant(index)?;
+                return Ok(());
+            }
+            continue;
+        }
+
+        let mut attempt = Partial::alloc_shape(shape)?;
+        attempt.select_nth_variant(index)?;
+        if deserialize_variant_data(&mut attempt, variant, value).is_err() {
+            continue;
+        }
+        if let Ok(heap_value) = attempt.build() {
+            wip.set_from_heap_value(heap_value)?;
+            return Ok(());
+        }
+    }
+
+    Err(ValueError::NoSuchVariant { shape, name: None })
+}
+
+fn select_variant<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    name: &str,
+) -> Result<&'shape Variant<'shape>, ValueError<'shape>> {
+    let (index, variant) = wip
+        .find_variant(name)
+        .ok_or_else(|| ValueError::NoSuchVariant {
+            shape: wip.shape(),
+            name: Some(String::from(name)),
+        })?;
+    wip.select_nth_variant(index)?;
+    Ok(variant)
+}
+
+fn deserialize_variant_data<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    variant: &'shape Variant<'shape>,
+    value: &Value,
+) -> Result<(), ValueError<'shape>> {
+    let shape = wip.shape();
+    let mismatch = || ValueError::TypeMismatch {
+        expected: shape,
+        got: value.kind(),
+    };
+
+    match variant.data.kind {
+        StructKind::Unit => Ok(()),
+        StructKind::Tuple | StructKind::TupleStruct if variant.data.fields.len() == 1 => {
+            wip.begin_nth_field(0)?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+            Ok(())
+        }
+        StructKind::Tuple | StructKind::TupleStruct => {
+            let Value::Array(items) = value else {
+                return Err(mismatch());
+            };
+            for (index, item) in items.iter().enumerate() {
+                wip.begin_nth_field(index)?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+            Ok(())
+        }
+        StructKind::Struct => {
+            let Value::Object(map) = value else {
+                return Err(mismatch());
+            };
+            deserialize_fields(wip, variant.data.fields, map)
+        }
+        _ => Err(ValueError::Unsupported { shape }),
+    }
+}
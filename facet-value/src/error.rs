@@ -0,0 +1,96 @@
+use alloc::string::String;
+
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+use crate::Number;
+
+/// Errors that can occur when converting between a [`crate::Value`] and a typed value
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValueError<'shape> {
+    /// The shape can't be represented as a value, or built from one
+    Unsupported {
+        /// The shape that isn't supported
+        shape: &'shape Shape<'shape>,
+    },
+
+    /// The value's kind doesn't fit the target shape
+    TypeMismatch {
+        /// The shape we were building
+        expected: &'shape Shape<'shape>,
+        /// The kind of value we got instead, as returned by [`crate::Value::kind`]
+        got: &'static str,
+    },
+
+    /// A number doesn't fit in the target numeric type
+    NumberOutOfRange {
+        /// The number that doesn't fit
+        number: Number,
+        /// The numeric type it doesn't fit in
+        shape: &'shape Shape<'shape>,
+    },
+
+    /// An object is missing a field that has no default
+    MissingField {
+        /// The struct or enum being built
+        shape: &'shape Shape<'shape>,
+        /// The name of the missing field
+        field: &'shape str,
+    },
+
+    /// An object has a field the target type doesn't know about, and the type
+    /// denies unknown fields
+    UnknownField {
+        /// The struct or enum being built
+        shape: &'shape Shape<'shape>,
+        /// The name of the unknown field
+        field: String,
+    },
+
+    /// No variant of an enum matches the value
+    NoSuchVariant {
+        /// The enum being built
+        shape: &'shape Shape<'shape>,
+        /// The variant name we were given, if any
+        name: Option<String>,
+    },
+
+    /// An error occurred while reflecting on or building the value
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for ValueError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        ValueError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for ValueError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ValueError::Unsupported { shape } => {
+                write!(f, "Shape {shape} is not supported by Value")
+            }
+            ValueError::TypeMismatch { expected, got } => {
+                write!(f, "Expected a value for {expected}, got a {got}")
+            }
+            ValueError::NumberOutOfRange { number, shape } => {
+                write!(f, "Number {number:?} is out of range for {shape}")
+            }
+            ValueError::MissingField { shape, field } => {
+                write!(f, "Missing field '{field}' for {shape}")
+            }
+            ValueError::UnknownField { shape, field } => {
+                write!(f, "Unknown field '{field}' for {shape}")
+            }
+            ValueError::NoSuchVariant { shape, name } => match name {
+                Some(name) => write!(f, "No variant named '{name}' in {shape}"),
+                None => write!(f, "No variant of {shape} matches the value"),
+            },
+            ValueError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ValueError<'_> {}
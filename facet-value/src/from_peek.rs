@@ -0,0 +1,272 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use facet_core::{Def, Facet, ShapeAttribute, StructKind, Type, UserType, Variant};
+use facet_reflect::{FieldsForSerializeIter, HasFields, Peek, PeekEnum, ScalarType};
+
+use crate::{Map, Number, Value, ValueError};
+
+impl Value {
+    /// Converts a typed value into a `Value`
+    ///
+    /// This is a shorthand for [`Value::from_peek`] on a fresh [`Peek`].
+    pub fn from_facet<'a, T: Facet<'a>>(value: &'a T) -> Result<Value, ValueError<'static>> {
+        Value::from_peek(Peek::new(value))
+    }
+
+    /// Converts the value behind a [`Peek`] into a `Value`
+    ///
+    /// The result has the same structure the serializers would produce: structs become
+    /// objects (honoring renames, `skip_serializing_if` and flattening), enums follow
+    /// their tagging attributes, and `Vec<u8>` becomes [`Value::Bytes`]. Scalars that
+    /// aren't strings, booleans or numbers, like IP addresses, become strings through
+    /// their `Display` implementation.
+    pub fn from_peek<'shape>(peek: Peek<'_, '_, 'shape>) -> Result<Value, ValueError<'shape>> {
+        let mut peek = peek;
+        if peek
+            .shape()
+            .attributes
+            .contains(&ShapeAttribute::Transparent)
+        {
+            peek = peek
+                .into_struct()?
+                .field(0)
+                .map_err(|_| ValueError::Unsupported {
+                    shape: peek.shape(),
+                })?;
+        }
+        let peek = peek.innermost_peek();
+        let shape = peek.shape();
+
+        if let Ok(value) = peek.get::<Value>() {
+            return Ok(value.clone());
+        }
+
+        match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => scalar(peek),
+            (Def::List(_), _) if shape.is_type::<Vec<u8>>() => {
+                Ok(Value::Bytes(peek.get::<Vec<u8>>()?.clone()))
+            }
+            (Def::List(_) | Def::Array(_), _) => array(peek),
+            (Def::Map(_), _) => {
+                let peek_map = peek.into_map()?;
+                let mut map = Map::with_capacity(peek_map.len());
+                for (key, value) in peek_map.iter() {
+                    map.insert(map_key(key)?, Value::from_peek(value)?);
+                }
+                Ok(Value::Object(map))
+            }
+            (Def::Option(_), _) => match peek.into_option()?.value() {
+                Some(inner) => Value::from_peek(inner),
+                None => Ok(Value::Null),
+            },
+            (Def::SmartPointer(_), _) => match peek.into_smart_pointer()?.borrow_inner() {
+                Some(inner) => Value::from_peek(inner),
+                None => Err(ValueError::Unsupported { shape }),
+            },
+            (_, Type::User(UserType::Struct(sd))) => {
+                let peek_struct = peek.into_struct()?;
+                match sd.kind {
+                    StructKind::Unit => Ok(Value::Null),
+                    StructKind::Tuple => peek_struct
+                        .fields()
+                        .map(|(_, field)| Value::from_peek(field))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(Value::Array),
+                    StructKind::TupleStruct => peek_struct
+                        .fields_for_serialize()
+                        .map(|(_, field)| Value::from_peek(field))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(Value::Array),
+                    StructKind::Struct => object(peek_struct.fields_for_serialize()),
+                    _ => Err(ValueError::Unsupported { shape }),
+                }
+            }
+            (_, Type::User(UserType::Enum(_))) => enum_value(peek),
+            (_, Type::Pointer(_)) => {
+                if let Some(s) = peek.as_str() {
+                    Ok(Value::String(s.to_string()))
+                } else if let Some(bytes) = peek.as_bytes() {
+                    Ok(Value::Bytes(bytes.to_vec()))
+                } else if peek.into_list_like().is_ok() {
+                    array(peek)
+                } else {
+                    Err(ValueError::Unsupported { shape })
+                }
+            }
+            _ => Err(ValueError::Unsupported { shape }),
+        }
+    }
+}
+
+fn array<'shape>(peek: Peek<'_, '_, 'shape>) -> Result<Value, ValueError<'shape>> {
+    peek.into_list_like()?
+        .iter()
+        .map(Value::from_peek)
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
+fn scalar<'shape>(peek: Peek<'_, '_, 'shape>) -> Result<Value, ValueError<'shape>> {
+    let shape = peek.shape();
+    let value = match peek.scalar_type() {
+        Some(ScalarType::Unit) => Value::Null,
+        Some(ScalarType::Bool) => Value::Bool(*peek.get::<bool>()?),
+        Some(ScalarType::Char) => Value::String(peek.get::<char>()?.to_string()),
+        Some(ScalarType::Str) => Value::from(*peek.get::<&str>()?),
+        Some(ScalarType::String) => Value::from(peek.get::<String>()?.clone()),
+        Some(ScalarType::CowStr) => Value::from(peek.get::<Cow<'_, str>>()?.as_ref()),
+        Some(ScalarType::F32) => Value::from(*peek.get::<f32>()?),
+        Some(ScalarType::F64) => Value::from(*peek.get::<f64>()?),
+        Some(ScalarType::U8) => Value::from(*peek.get::<u8>()?),
+        Some(ScalarType::U16) => Value::from(*peek.get::<u16>()?),
+        Some(ScalarType::U32) => Value::from(*peek.get::<u32>()?),
+        Some(ScalarType::U64) => Value::from(*peek.get::<u64>()?),
+        Some(ScalarType::USize) => Value::from(*peek.get::<usize>()?),
+        Some(ScalarType::I8) => Value::from(*peek.get::<i8>()?),
+        Some(ScalarType::I16) => Value::from(*peek.get::<i16>()?),
+        Some(ScalarType::I32) => Value::from(*peek.get::<i32>()?),
+        Some(ScalarType::I64) => Value::from(*peek.get::<i64>()?),
+        Some(ScalarType::ISize) => Value::from(*peek.get::<isize>()?),
+        Some(ScalarType::U128) => {
+            let n = *peek.get::<u128>()?;
+            let n = u64::try_from(n).map_err(|_| ValueError::NumberOutOfRange {
+                number: Number::F64(n as f64),
+                shape,
+            })?;
+            Value::from(n)
+        }
+        Some(ScalarType::I128) => {
+            let n = *peek.get::<i128>()?;
+            let n = i64::try_from(n).map_err(|_| ValueError::NumberOutOfRange {
+                number: Number::F64(n as f64),
+                shape,
+            })?;
+            Value::from(n)
+        }
+        _ if shape.vtable.has_display() => Value::String(peek.to_string()),
+        _ => return Err(ValueError::Unsupported { shape }),
+    };
+    Ok(value)
+}
+
+/// Object keys are strings: string keys are used as-is, other scalars like integers
+/// go through their `Display` implementation.
+fn map_key<'shape>(key: Peek<'_, '_, 'shape>) -> Result<String, ValueError<'shape>> {
+    if let Some(s) = key.as_str() {
+        Ok(s.to_string())
+    } else if matches!(key.shape().def, Def::Scalar(_)) && key.shape().vtable.has_display() {
+        Ok(key.to_string())
+    } else {
+        Err(ValueError::Unsupported { shape: key.shape() })
+    }
+}
+
+fn object<'shape>(
+    fields: FieldsForSerializeIter<'_, '_, 'shape>,
+) -> Result<Value, ValueError<'shape>> {
+    let mut map = Map::new();
+    for (field, value) in fields {
+        if field.flattened {
+            if let Ok(peek_map) = value.into_map() {
+                // A flattened map's entries sit next to the other fields
+                for (key, value) in peek_map.iter() {
+                    map.insert(map_key(key)?, Value::from_peek(value)?);
+                }
+                continue;
+            }
+        }
+
+        let value = if field.flattened {
+            // A flattened enum: the field name is already the variant name
+            let peek_enum = value.into_enum()?;
+            let variant = active_variant(peek_enum)?;
+            if variant.data.fields.is_empty() {
+                Value::from(variant.name)
+            } else {
+                variant_data(peek_enum, variant)?
+            }
+        } else {
+            Value::from_peek(value)?
+        };
+        map.insert(field.name, value);
+    }
+    Ok(Value::Object(map))
+}
+
+fn enum_value<'shape>(peek: Peek<'_, '_, 'shape>) -> Result<Value, ValueError<'shape>> {
+    let shape = peek.shape();
+    let peek_enum = peek.into_enum()?;
+    let variant = active_variant(peek_enum)?;
+    let has_data = !variant.data.fields.is_empty();
+
+    if shape.has_untagged_attr() {
+        return if has_data {
+            variant_data(peek_enum, variant)
+        } else {
+            Ok(Value::Null)
+        };
+    }
+
+    let Some(tag) = shape.get_tag_attr() else {
+        // Externally tagged: `{ "Variant": data }`, or just `"Variant"` for unit variants
+        return if has_data {
+            Ok(Value::object([(
+                variant.name,
+                variant_data(peek_enum, variant)?,
+            )]))
+        } else {
+            Ok(Value::from(variant.name))
+        };
+    };
+
+    let mut map = Map::new();
+    map.insert(tag, Value::from(variant.name));
+    if let Some(content) = shape.get_content_attr() {
+        if has_data {
+            map.insert(content, variant_data(peek_enum, variant)?);
+        }
+    } else if has_data {
+        // Internally tagged: the variant's fields sit next to the tag
+        match variant_data(peek_enum, variant)? {
+            Value::Object(fields) => map.extend(fields),
+            _ => return Err(ValueError::Unsupported { shape }),
+        }
+    }
+    Ok(Value::Object(map))
+}
+
+/// The data of a non-unit variant, without its tag: newtype variants become their
+/// inner value, tuple variants become arrays and struct variants become objects.
+fn variant_data<'shape>(
+    peek_enum: PeekEnum<'_, '_, 'shape>,
+    variant: &'shape Variant<'shape>,
+) -> Result<Value, ValueError<'shape>> {
+    match variant.data.kind {
+        StructKind::Tuple if variant.data.fields.len() == 1 => {
+            match peek_enum.fields_for_serialize().next() {
+                Some((_, inner)) => Value::from_peek(inner),
+                None => Ok(Value::Null),
+            }
+        }
+        StructKind::Tuple | StructKind::TupleStruct => peek_enum
+            .fields_for_serialize()
+            .map(|(_, field)| Value::from_peek(field))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        _ => object(peek_enum.fields_for_serialize()),
+    }
+}
+
+fn active_variant<'shape>(
+    peek_enum: PeekEnum<'_, '_, 'shape>,
+) -> Result<&'shape Variant<'shape>, ValueError<'shape>> {
+    peek_enum
+        .active_variant()
+        .map_err(|_| ValueError::Unsupported {
+            shape: peek_enum.shape(),
+        })
+}
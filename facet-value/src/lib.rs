@@ -0,0 +1,16 @@
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+mod deserialize;
+mod error;
+mod from_peek;
+mod map;
+mod value;
+
+pub use error::*;
+pub use map::*;
+pub use value::*;
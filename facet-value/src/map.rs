@@ -0,0 +1,233 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{ops::Index, write};
+
+use facet_core::{
+    Def, Facet, IterVTable, MapDef, MapVTable, PtrConst, PtrMut, Shape, Type, UserType,
+    ValueVTable, value_vtable,
+};
+
+use crate::{Value, value::NULL};
+
+/// The entries of a [`Value::Object`], which keep their insertion order
+///
+/// Lookups are linear scans, which is faster than hashing for the small objects
+/// typically found in configuration files and payloads. Two maps are equal when they
+/// have the same entries, regardless of their order.
+#[derive(Clone, Debug, Default)]
+pub struct Map {
+    entries: Vec<(String, Value)>,
+}
+
+type MapIterator<'mem> = core::slice::Iter<'mem, (String, Value)>;
+type EntryRef<'mem> = fn(&'mem (String, Value)) -> (&'mem String, &'mem Value);
+
+impl Map {
+    /// Creates an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty map with room for `capacity` entries
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the map has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns true if the map has an entry for `key`
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Returns the value for `key`
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.position(key).map(|index| &self.entries[index].1)
+    }
+
+    /// Returns the value for `key` mutably
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.position(key).map(|index| &mut self.entries[index].1)
+    }
+
+    /// Inserts a value, returning the previous value for that key
+    ///
+    /// A new key goes at the end; an existing key keeps its position.
+    pub fn insert(&mut self, key: impl Into<String>, value: Value) -> Option<Value> {
+        let key = key.into();
+        match self.position(&key) {
+            Some(index) => Some(core::mem::replace(&mut self.entries[index].1, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the entry for `key` and returns its value, keeping the order of the
+    /// other entries
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let index = self.position(key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Returns an iterator over the entries, in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// Returns an iterator over the keys, in insertion order
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the values, in insertion order
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.entries.iter().position(|(k, _)| k == key)
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+/// Returns the value for a key, or [`Value::Null`] if the key is missing
+impl Index<&str> for Map {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, Value)> for Map {
+    fn from_iter<I: IntoIterator<Item = (K, Value)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Into<String>> Extend<(K, Value)> for Map {
+    fn extend<I: IntoIterator<Item = (K, Value)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = core::iter::Map<MapIterator<'a>, EntryRef<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let entry_ref: EntryRef<'a> = |(key, value)| (key, value);
+        self.entries.iter().map(entry_ref)
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (String, Value);
+    type IntoIter = alloc::vec::IntoIter<(String, Value)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+unsafe impl Facet<'_> for Map {
+    const VTABLE: &'static ValueVTable =
+        &const { value_vtable!(Map, |f, _opts| write!(f, "{}", Self::SHAPE.type_identifier)) };
+
+    const SHAPE: &'static Shape<'static> = &const {
+        Shape::builder_for_sized::<Self>()
+            .type_identifier("Map")
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Map(
+                MapDef::builder()
+                    .k(|| String::SHAPE)
+                    .v(|| Value::SHAPE)
+                    .vtable(
+                        &const {
+                            MapVTable::builder()
+                                .init_in_place_with_capacity(|uninit, capacity| unsafe {
+                                    uninit.put(Self::with_capacity(capacity))
+                                })
+                                .insert(|ptr, key, value| unsafe {
+                                    let map = ptr.as_mut::<Self>();
+                                    let k = key.read::<String>();
+                                    let v = value.read::<Value>();
+                                    map.insert(k, v);
+                                })
+                                .len(|ptr| unsafe {
+                                    let map = ptr.get::<Self>();
+                                    map.len()
+                                })
+                                .contains_key(|ptr, key| unsafe {
+                                    let map = ptr.get::<Self>();
+                                    map.contains_key(key.get::<String>())
+                                })
+                                .get_value_ptr(|ptr, key| unsafe {
+                                    let map = ptr.get::<Self>();
+                                    map.get(key.get::<String>())
+                                        .map(|v| PtrConst::new(v as *const _))
+                                })
+                                .get_value_mut_ptr(|ptr, key| unsafe {
+                                    let map = ptr.as_mut::<Self>();
+                                    map.get_mut(key.get::<String>())
+                                        .map(|v| PtrMut::new(v as *mut _))
+                                })
+                                .iter_vtable(
+                                    IterVTable::builder()
+                                        .init_with_value(|ptr| unsafe {
+                                            let map = ptr.get::<Self>();
+                                            let iter: MapIterator<'_> = map.entries.iter();
+                                            let state = Box::new(iter);
+                                            PtrMut::new(Box::into_raw(state) as *mut u8)
+                                        })
+                                        .next(|iter_ptr| unsafe {
+                                            let state = iter_ptr.as_mut::<MapIterator<'_>>();
+                                            state.next().map(|(key, value)| {
+                                                (PtrConst::new(key), PtrConst::new(value))
+                                            })
+                                        })
+                                        .next_back(|iter_ptr| unsafe {
+                                            let state = iter_ptr.as_mut::<MapIterator<'_>>();
+                                            state.next_back().map(|(key, value)| {
+                                                (PtrConst::new(key), PtrConst::new(value))
+                                            })
+                                        })
+                                        .dealloc(|iter_ptr| unsafe {
+                                            drop(Box::from_raw(
+                                                iter_ptr.as_ptr::<MapIterator<'_>>()
+                                                    as *mut MapIterator<'_>,
+                                            ))
+                                        })
+                                        .build(),
+                                )
+                                .build()
+                        },
+                    )
+                    .build(),
+            ))
+            .build()
+    };
+}
@@ -0,0 +1,346 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::ops::Index;
+
+use facet::Facet;
+
+use crate::Map;
+
+/// A dynamically-typed value, for data whose shape isn't known at compile time
+///
+/// `Value` is an untagged enum: formats read and write each variant as its bare data,
+/// and deserializers try the variants in declaration order until one fits. That order
+/// matters: strings come before booleans and numbers, so that `"true"` or `"42"` stay
+/// strings in formats that are lenient about scalar conversions.
+///
+/// Formats without a native byte string type (like JSON) write [`Value::Bytes`] as an
+/// array of numbers, which reads back as a [`Value::Array`].
+#[derive(Facet, Clone, Debug, Default, PartialEq)]
+#[facet(untagged)]
+#[repr(u8)]
+pub enum Value {
+    /// The absence of a value: `null` in JSON, `~` in YAML, `nil` in MessagePack
+    #[default]
+    Null,
+    /// A string
+    String(String),
+    /// A boolean
+    Bool(bool),
+    /// An integer or floating-point number
+    Number(Number),
+    /// An ordered list of values
+    Array(Vec<Value>),
+    /// A collection of values by string keys, in insertion order
+    Object(Map),
+    /// A byte string
+    Bytes(Vec<u8>),
+}
+
+/// A number, as stored in a [`Value`]
+///
+/// Integers are stored as [`Number::I64`] whenever they fit, and as [`Number::U64`]
+/// only above `i64::MAX`, so that the same number always compares equal no matter
+/// where it came from.
+#[derive(Facet, Clone, Copy, Debug, PartialEq)]
+#[facet(untagged)]
+#[repr(u8)]
+pub enum Number {
+    /// A signed integer, or an unsigned integer no larger than `i64::MAX`
+    I64(i64),
+    /// An unsigned integer larger than `i64::MAX`
+    U64(u64),
+    /// A floating-point number
+    F64(f64),
+}
+
+pub(crate) static NULL: Value = Value::Null;
+
+impl Value {
+    /// Creates an object from key-value pairs
+    pub fn object<K: Into<String>>(entries: impl IntoIterator<Item = (K, Value)>) -> Self {
+        Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    /// Creates an array from values
+    pub fn array(items: impl IntoIterator<Item = Value>) -> Self {
+        Value::Array(items.into_iter().collect())
+    }
+
+    /// Returns the name of this value's kind, for error messages
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::String(_) => "string",
+            Value::Bool(_) => "bool",
+            Value::Number(_) => "number",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+            Value::Bytes(_) => "bytes",
+        }
+    }
+
+    /// Returns true if this value is [`Value::Null`]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the string, if this value is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, if this value is a boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if this value is a number
+    pub fn as_number(&self) -> Option<Number> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as an `i64`, if this value is an integer that fits
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number()?.as_i64()
+    }
+
+    /// Returns the number as a `u64`, if this value is a non-negative integer
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_number()?.as_u64()
+    }
+
+    /// Returns the number as an `f64`, if this value is a number
+    pub fn as_f64(&self) -> Option<f64> {
+        Some(self.as_number()?.as_f64())
+    }
+
+    /// Returns the elements, if this value is an array
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements mutably, if this value is an array
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries, if this value is an object
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries mutably, if this value is an object
+    pub fn as_object_mut(&mut self) -> Option<&mut Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if this value is a byte string
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the value for a key, if this value is an object that has it
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?.get(key)
+    }
+
+    /// Returns the value for a key mutably, if this value is an object that has it
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.as_object_mut()?.get_mut(key)
+    }
+}
+
+/// Returns the value for a key, or [`Value::Null`] if this isn't an object or the key
+/// is missing
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+/// Returns the element at an index, or [`Value::Null`] if this isn't an array or the
+/// index is out of bounds
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        self.as_array()
+            .and_then(|items| items.get(index))
+            .unwrap_or(&NULL)
+    }
+}
+
+impl Number {
+    /// Returns the number as an `i64`, if it is an integer that fits
+    pub fn as_i64(self) -> Option<i64> {
+        match self {
+            Number::I64(n) => Some(n),
+            Number::U64(n) => i64::try_from(n).ok(),
+            Number::F64(_) => None,
+        }
+    }
+
+    /// Returns the number as a `u64`, if it is a non-negative integer
+    pub fn as_u64(self) -> Option<u64> {
+        match self {
+            Number::I64(n) => u64::try_from(n).ok(),
+            Number::U64(n) => Some(n),
+            Number::F64(_) => None,
+        }
+    }
+
+    /// Returns the number as an `f64`, which may lose precision for large integers
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Number::I64(n) => n as f64,
+            Number::U64(n) => n as f64,
+            Number::F64(n) => n,
+        }
+    }
+
+    /// Returns true if the number is a floating-point number
+    pub fn is_f64(self) -> bool {
+        matches!(self, Number::F64(_))
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(n: $ty) -> Self {
+                    Number::I64(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(n: $ty) -> Self {
+                    if n as u64 <= i64::MAX as u64 {
+                        Number::I64(n as i64)
+                    } else {
+                        Number::U64(n as u64)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, isize);
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+
+impl From<f32> for Number {
+    fn from(n: f32) -> Self {
+        Number::F64(n as f64)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(n: f64) -> Self {
+        Number::F64(n)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl From<Number> for Value {
+    fn from(n: Number) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.into())
+    }
+}
+
+impl From<Cow<'_, str>> for Value {
+    fn from(s: Cow<'_, str>) -> Self {
+        Value::String(s.into_owned())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(items: Vec<Value>) -> Self {
+        Value::Array(items)
+    }
+}
+
+impl From<Map> for Value {
+    fn from(map: Map) -> Self {
+        Value::Object(map)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl FromIterator<Value> for Value {
+    fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
+        Value::array(iter)
+    }
+}
@@ -0,0 +1,131 @@
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_value::Value;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Config {
+    name: String,
+    port: u16,
+    debug: bool,
+}
+
+fn sample() -> Value {
+    Value::object([
+        ("name", Value::from("db")),
+        ("port", Value::from(5432)),
+        ("ratio", Value::from(0.5)),
+        ("offset", Value::from(-7)),
+        ("debug", Value::from(false)),
+        ("tags", Value::array([Value::from("a"), Value::from("b")])),
+        ("nested", Value::object([("empty", Value::Null)])),
+    ])
+}
+
+/// The sample without its null entry, which YAML and TOML don't write
+fn sample_without_null() -> Value {
+    let mut value = sample();
+    value.as_object_mut().unwrap().remove("nested");
+    value
+}
+
+#[test]
+fn json() {
+    let value: Value = facet_json::from_str(
+        r#"{"name":"db","port":5432,"ratio":0.5,"offset":-7,"debug":false,"tags":["a","b"],"nested":{"empty":null}}"#,
+    )
+    .unwrap();
+    assert_eq!(value, sample());
+
+    let json = facet_json::to_string(&value);
+    let back: Value = facet_json::from_str(&json).unwrap();
+    assert_eq!(back, value);
+
+    // Numeric-looking strings stay strings
+    let value: Value = facet_json::from_str(r#"["42", "true", 42, true]"#).unwrap();
+    assert_eq!(
+        value,
+        Value::array([
+            Value::from("42"),
+            Value::from("true"),
+            Value::from(42),
+            Value::from(true),
+        ])
+    );
+}
+
+#[test]
+fn yaml() {
+    let value: Value = facet_yaml::from_str(
+        r#"
+name: db
+port: 5432
+ratio: 0.5
+offset: -7
+debug: false
+tags:
+  - a
+  - b
+nested:
+  empty: ~
+"#,
+    )
+    .unwrap();
+    assert_eq!(value, sample());
+
+    let value = sample_without_null();
+    let yaml = facet_yaml::to_string(&value).unwrap();
+    let back: Value = facet_yaml::from_str(&yaml).unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn toml() {
+    let value: Value = facet_toml::from_str(
+        r#"
+name = "db"
+port = 5432
+ratio = 0.5
+offset = -7
+debug = false
+tags = ["a", "b"]
+"#,
+    )
+    .unwrap();
+    assert_eq!(value, sample_without_null());
+
+    let toml = facet_toml::to_string(&value).unwrap();
+    let back: Value = facet_toml::from_str(&toml).unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn msgpack() {
+    let value = sample();
+    let bytes = facet_msgpack::to_vec(&value);
+    let back: Value = facet_msgpack::from_slice(&bytes).unwrap();
+    assert_eq!(back, value);
+
+    let value = Value::array([
+        Value::Bytes(vec![0, 1, 2]),
+        Value::from(u64::MAX),
+        Value::from(i64::MIN),
+    ]);
+    let bytes = facet_msgpack::to_vec(&value);
+    let back: Value = facet_msgpack::from_slice(&bytes).unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn typed_from_format_value() {
+    let value: Value =
+        facet_json::from_str(r#"{"name":"api","port":8080,"debug":true,"extra":[1]}"#).unwrap();
+    let config: Config = value.deserialize_into().unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "api".to_string(),
+            port: 8080,
+            debug: true,
+        }
+    );
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_value::{Map, Number, Value, ValueError};
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Server {
+    name: String,
+    port: u16,
+    #[facet(default)]
+    tags: Vec<String>,
+    weight: Option<f64>,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[facet(tag = "type")]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Message {
+    Ping,
+    Move { x: i32, y: i32 },
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[facet(deny_unknown_fields)]
+struct Strict {
+    a: u32,
+}
+
+#[test]
+fn accessors() {
+    let value = Value::object([
+        ("name", Value::from("db")),
+        ("ports", Value::array([Value::from(80), Value::from(443)])),
+        ("enabled", Value::from(true)),
+    ]);
+
+    assert_eq!(value["name"].as_str(), Some("db"));
+    assert_eq!(value["ports"][1].as_u64(), Some(443));
+    assert_eq!(value["enabled"].as_bool(), Some(true));
+    assert!(value["missing"].is_null());
+    assert!(value["ports"][7].is_null());
+    assert_eq!(value.kind(), "object");
+}
+
+#[test]
+fn numbers_normalize() {
+    assert_eq!(Value::from(5_u8), Value::from(5_i64));
+    assert_eq!(
+        Value::from(u64::MAX).as_number(),
+        Some(Number::U64(u64::MAX))
+    );
+    assert_eq!(Value::from(-3_i32).as_u64(), None);
+    assert_eq!(Value::from(1.5).as_i64(), None);
+    assert_eq!(Value::from(2_u32).as_f64(), Some(2.0));
+}
+
+#[test]
+fn map_keeps_insertion_order() {
+    let mut map: Map = [("b", Value::from(1)), ("a", Value::from(2))]
+        .into_iter()
+        .collect();
+    map.insert("b", Value::from(3));
+    map.insert("c", Value::from(4));
+
+    let keys: Vec<_> = map.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["b", "a", "c"]);
+    assert_eq!(map["b"], Value::from(3));
+    assert!(map["missing"].is_null());
+
+    assert_eq!(map.remove("a"), Some(Value::from(2)));
+    let keys: Vec<_> = map.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["b", "c"]);
+
+    let reversed: Map = [("c", Value::from(4)), ("b", Value::from(3))]
+        .into_iter()
+        .collect();
+    assert_eq!(map, reversed);
+}
+
+#[test]
+fn from_struct() {
+    let server = Server {
+        name: "db".to_string(),
+        port: 5432,
+        tags: vec!["primary".to_string()],
+        weight: None,
+    };
+    let value = Value::from_facet(&server).unwrap();
+    assert_eq!(
+        value,
+        Value::object([
+            ("name", Value::from("db")),
+            ("port", Value::from(5432)),
+            ("tags", Value::array([Value::from("primary")])),
+            ("weight", Value::Null),
+        ])
+    );
+}
+
+#[test]
+fn from_collections() {
+    let mut scores = HashMap::new();
+    scores.insert(7_u32, "seven".to_string());
+    let value = Value::from_facet(&scores).unwrap();
+    assert_eq!(value, Value::object([("7", Value::from("seven"))]));
+
+    let value = Value::from_facet(&vec![1_u8, 2, 3]).unwrap();
+    assert_eq!(value, Value::Bytes(vec![1, 2, 3]));
+
+    let value = Value::from_facet(&(1_i8, "two".to_string())).unwrap();
+    assert_eq!(value, Value::array([Value::from(1), Value::from("two")]));
+}
+
+#[test]
+fn from_enums() {
+    assert_eq!(
+        Value::from_facet(&Shape::Point).unwrap(),
+        Value::from("Point")
+    );
+    assert_eq!(
+        Value::from_facet(&Shape::Circle(1.5)).unwrap(),
+        Value::object([("Circle", Value::from(1.5))])
+    );
+    assert_eq!(
+        Value::from_facet(&Shape::Rect {
+            width: 2,
+            height: 3
+        })
+        .unwrap(),
+        Value::object([(
+            "Rect",
+            Value::object([("width", Value::from(2)), ("height", Value::from(3))])
+        )])
+    );
+    assert_eq!(
+        Value::from_facet(&Message::Move { x: 1, y: -1 }).unwrap(),
+        Value::object([
+            ("type", Value::from("Move")),
+            ("x", Value::from(1)),
+            ("y", Value::from(-1)),
+        ])
+    );
+}
+
+#[test]
+fn into_struct() {
+    let value = Value::object([
+        ("name", Value::from("cache")),
+        ("port", Value::from(6379)),
+        ("weight", Value::from(2)),
+    ]);
+    let server: Server = value.deserialize_into().unwrap();
+    assert_eq!(
+        server,
+        Server {
+            name: "cache".to_string(),
+            port: 6379,
+            tags: vec![],
+            weight: Some(2.0),
+        }
+    );
+}
+
+#[test]
+fn into_enums() {
+    let shape: Shape = Value::from("Point").deserialize_into().unwrap();
+    assert_eq!(shape, Shape::Point);
+
+    let shape: Shape = Value::object([("Circle", Value::from(0.5))])
+        .deserialize_into()
+        .unwrap();
+    assert_eq!(shape, Shape::Circle(0.5));
+
+    let message: Message = Value::object([
+        ("type", Value::from("Move")),
+        ("x", Value::from(3)),
+        ("y", Value::from(4)),
+    ])
+    .deserialize_into()
+    .unwrap();
+    assert_eq!(message, Message::Move { x: 3, y: 4 });
+}
+
+#[test]
+fn round_trip() {
+    let shapes = vec![
+        Shape::Point,
+        Shape::Circle(2.0),
+        Shape::Rect {
+            width: 4,
+            height: 5,
+        },
+    ];
+    let value = Value::from_facet(&shapes).unwrap();
+    let back: Vec<Shape> = value.deserialize_into().unwrap();
+    assert_eq!(back, shapes);
+
+    let value = Value::array([Value::Null, Value::from("x"), Value::Array(vec![])]);
+    let back: Value = Value::from_facet(&value)
+        .unwrap()
+        .deserialize_into()
+        .unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn errors() {
+    let err = Value::from(true).deserialize_into::<u32>().unwrap_err();
+    assert!(matches!(err, ValueError::TypeMismatch { got: "bool", .. }));
+
+    let err = Value::from(300).deserialize_into::<u8>().unwrap_err();
+    assert!(matches!(err, ValueError::NumberOutOfRange { .. }));
+
+    let err = Value::from(1.5).deserialize_into::<i32>().unwrap_err();
+    assert!(matches!(err, ValueError::NumberOutOfRange { .. }));
+
+    let err = Value::object([("port", Value::from(1))])
+        .deserialize_into::<Server>()
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ValueError::MissingField { field: "name", .. }
+    ));
+
+    let err = Value::object([("a", Value::from(1)), ("b", Value::from(2))])
+        .deserialize_into::<Strict>()
+        .unwrap_err();
+    assert!(matches!(err, ValueError::UnknownField { ref field, .. } if field == "b"));
+
+    let err = Value::from("Hexagon")
+        .deserialize_into::<Shape>()
+        .unwrap_err();
+    assert!(matches!(err, ValueError::NoSuchVariant { .. }));
+}
+
+#[test]
+fn flattened_map() {
+    use std::collections::BTreeMap;
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Labeled {
+        name: String,
+        #[facet(flatten)]
+        labels: BTreeMap<String, String>,
+    }
+
+    let labeled = Labeled {
+        name: "web".to_string(),
+        labels: BTreeMap::from([("tier".to_string(), "frontend".to_string())]),
+    };
+    let value = Value::from_facet(&labeled).unwrap();
+    assert_eq!(
+        value,
+        Value::object([
+            ("name", Value::from("web")),
+            ("tier", Value::from("frontend"))
+        ])
+    );
+    let back: Labeled = value.deserialize_into().unwrap();
+    assert_eq!(back, labeled);
+}
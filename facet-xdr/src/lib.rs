@@ -5,31 +5,61 @@
 use std::io::Write;
 
 use facet_core::{
-    Def, Facet, IntegerSize, NumberBits, ScalarAffinity, Signedness, StructKind, Type, UserType,
+    Def, Facet, Field, FieldAttribute, IntegerSize, NumberBits, ScalarAffinity, Shape, Signedness,
+    StructKind, Type, UserType,
 };
-use facet_reflect::{HeapValue, Partial, Peek};
+use facet_reflect::{HasFields, HeapValue, Partial, Peek};
 use facet_serialize::{Serializer, serialize_iterative};
 
+mod quadruple;
+
 /// Errors when serializing to XDR bytes
 #[derive(Debug)]
 pub enum XdrSerError {
     /// IO error
     Io(std::io::Error),
     /// Too many bytes for field
-    TooManyBytes,
+    TooManyBytes {
+        /// Position of this error in bytes
+        position: usize,
+    },
     /// Enum variant discriminant too large
-    TooManyVariants,
+    TooManyVariants {
+        /// Position of this error in bytes
+        position: usize,
+    },
+    /// Number too large for any XDR type
+    NumberOutOfRange {
+        /// Position of this error in bytes
+        position: usize,
+    },
     /// Unsupported type
-    UnsupportedType,
+    UnsupportedType {
+        /// Position of this error in bytes
+        position: usize,
+    },
 }
 
 impl core::fmt::Display for XdrSerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             XdrSerError::Io(error) => write!(f, "IO error: {}", error),
-            XdrSerError::TooManyBytes => write!(f, "Too many bytes for field"),
-            XdrSerError::TooManyVariants => write!(f, "Enum variant discriminant too large"),
-            XdrSerError::UnsupportedType => write!(f, "Unsupported type"),
+            XdrSerError::TooManyBytes { position } => {
+                write!(f, "Too many bytes for field at byte {}", position)
+            }
+            XdrSerError::TooManyVariants { position } => {
+                write!(
+                    f,
+                    "Enum variant discriminant too large at byte {}",
+                    position
+                )
+            }
+            XdrSerError::NumberOutOfRange { position } => {
+                write!(f, "Number too large for XDR at byte {}", position)
+            }
+            XdrSerError::UnsupportedType { position } => {
+                write!(f, "Unsupported type at byte {}", position)
+            }
         }
     }
 }
@@ -49,69 +79,116 @@ pub fn to_vec<'f, F: Facet<'f>>(value: &'f F) -> Result<Vec<u8>, XdrSerError> {
     let peek = Peek::new(value);
     let mut serializer = XdrSerializer {
         writer: &mut buffer,
+        pos: 0,
+        quadruple: false,
     };
     serialize_iterative(peek, &mut serializer)?;
     Ok(buffer)
 }
 
+/// Returns whether a field is marked with `#[facet(quadruple)]`, to go through XDR as a
+/// quadruple-precision float rather than a double
+fn is_quadruple(field: &Field<'_>) -> bool {
+    field
+        .attributes
+        .iter()
+        .any(|attr| matches!(attr, FieldAttribute::Arbitrary(content) if *content == "quadruple"))
+}
+
 struct XdrSerializer<'w, W: Write> {
     writer: &'w mut W,
+    /// Number of bytes written so far
+    pos: usize,
+    /// Whether the next value is a field marked with `#[facet(quadruple)]`
+    quadruple: bool,
+}
+
+impl<W: Write> XdrSerializer<'_, W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), XdrSerError> {
+        self.writer.write_all(bytes).map_err(XdrSerError::Io)?;
+        self.pos += bytes.len();
+        Ok(())
+    }
+
+    /// Writes opaque data without its length, padded with zeroes to a multiple of four bytes
+    fn write_padded(&mut self, bytes: &[u8]) -> Result<(), XdrSerError> {
+        self.write(bytes)?;
+        let pad_len = bytes.len() % 4;
+        if pad_len != 0 {
+            self.write(&[0u8; 3][..4 - pad_len])?;
+        }
+        Ok(())
+    }
+
+    /// Writes the discriminant of a union or enum, as an `int` or an `unsigned int`
+    fn write_discriminant(&mut self, discriminant: i64) -> Result<(), XdrSerError> {
+        if discriminant < i32::MIN as i64 || discriminant > u32::MAX as i64 {
+            return Err(XdrSerError::TooManyVariants { position: self.pos });
+        }
+        // Negative discriminants end up in two's complement, like an `int`
+        self.write(&(discriminant as u32).to_be_bytes())
+    }
 }
 
 impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
     type Error = XdrSerError;
 
+    fn serialize_u8(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.serialize_u32(value as u32)
+    }
+
+    fn serialize_u16(&mut self, value: u16) -> Result<(), Self::Error> {
+        self.serialize_u32(value as u32)
+    }
+
     fn serialize_u32(&mut self, value: u32) -> Result<(), Self::Error> {
-        self.writer
-            .write_all(&value.to_be_bytes())
-            .map_err(Self::Error::Io)
+        self.write(&value.to_be_bytes())
     }
 
     fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
-        self.writer
-            .write_all(&value.to_be_bytes())
-            .map_err(Self::Error::Io)
+        self.write(&value.to_be_bytes())
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        // Written as an unsigned hyper when it fits
+        let value = u64::try_from(value)
+            .map_err(|_| Self::Error::NumberOutOfRange { position: self.pos })?;
+        self.serialize_u64(value)
     }
 
-    fn serialize_u128(&mut self, _value: u128) -> Result<(), Self::Error> {
-        Err(Self::Error::UnsupportedType)
+    fn serialize_i8(&mut self, value: i8) -> Result<(), Self::Error> {
+        self.serialize_i32(value as i32)
+    }
+
+    fn serialize_i16(&mut self, value: i16) -> Result<(), Self::Error> {
+        self.serialize_i32(value as i32)
     }
 
     fn serialize_i32(&mut self, value: i32) -> Result<(), Self::Error> {
-        self.writer
-            .write_all(&value.to_be_bytes())
-            .map_err(Self::Error::Io)
+        self.write(&value.to_be_bytes())
     }
 
     fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
-        self.writer
-            .write_all(&value.to_be_bytes())
-            .map_err(Self::Error::Io)
+        self.write(&value.to_be_bytes())
     }
 
-    fn serialize_i128(&mut self, _value: i128) -> Result<(), Self::Error> {
-        Err(Self::Error::UnsupportedType)
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        // Written as a hyper when it fits
+        let value = i64::try_from(value)
+            .map_err(|_| Self::Error::NumberOutOfRange { position: self.pos })?;
+        self.serialize_i64(value)
     }
 
     fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
-        self.writer
-            .write_all(&value.to_be_bytes())
-            .map_err(Self::Error::Io)
+        self.write(&value.to_be_bytes())
     }
 
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
-        self.writer
-            .write_all(&value.to_be_bytes())
-            .map_err(Self::Error::Io)
+        self.write(&value.to_be_bytes())
     }
 
     fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
-        if value {
-            self.writer.write_all(&1u32.to_be_bytes())
-        } else {
-            self.writer.write_all(&0u32.to_be_bytes())
-        }
-        .map_err(Self::Error::Io)
+        self.serialize_u32(value as u32)
     }
 
     fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
@@ -125,19 +202,11 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
 
     fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
         if value.len() > u32::MAX as usize {
-            return Err(Self::Error::TooManyBytes);
+            return Err(Self::Error::TooManyBytes { position: self.pos });
         }
         let len = value.len() as u32;
-        self.writer
-            .write_all(&len.to_be_bytes())
-            .map_err(Self::Error::Io)?;
-        let pad_len = value.len() % 4;
-        self.writer.write_all(value).map_err(Self::Error::Io)?;
-        if pad_len != 0 {
-            let pad = vec![0u8; 4 - pad_len];
-            self.writer.write_all(&pad).map_err(Self::Error::Io)?;
-        }
-        Ok(())
+        self.write(&len.to_be_bytes())?;
+        self.write_padded(value)
     }
 
     fn serialize_none(&mut self) -> Result<(), Self::Error> {
@@ -160,6 +229,11 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
         Ok(())
     }
 
+    fn serialize_field(&mut self, field: &Field<'shape>) -> Result<(), Self::Error> {
+        self.quadruple = is_quadruple(field);
+        Ok(())
+    }
+
     fn serialize_field_name(&mut self, _name: &'shape str) -> Result<(), Self::Error> {
         Ok(())
     }
@@ -167,13 +241,11 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
     fn start_array(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
         if let Some(len) = len {
             if len > u32::MAX as usize {
-                return Err(Self::Error::TooManyBytes);
+                return Err(Self::Error::TooManyBytes { position: self.pos });
             }
-            self.writer
-                .write_all(&(len as u32).to_be_bytes())
-                .map_err(Self::Error::Io)
+            self.serialize_u32(len as u32)
         } else {
-            panic!("array length missing");
+            Err(Self::Error::UnsupportedType { position: self.pos })
         }
     }
 
@@ -181,13 +253,90 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
         Ok(())
     }
 
-    fn start_enum_variant(&mut self, discriminant: u64) -> Result<(), Self::Error> {
-        if discriminant > u32::MAX as u64 {
-            return Err(Self::Error::TooManyVariants);
+    fn unsupported_shape(&mut self, _shape: &'shape Shape<'shape>, _reason: &str) -> Self::Error {
+        XdrSerError::UnsupportedType { position: self.pos }
+    }
+
+    fn serialize_custom(&mut self, value: Peek<'_, '_, 'shape>) -> Result<bool, Self::Error> {
+        if core::mem::take(&mut self.quadruple) {
+            let Ok(value) = value.get::<f64>() else {
+                return Err(Self::Error::UnsupportedType { position: self.pos });
+            };
+            self.write(&quadruple::from_f64(*value).to_be_bytes())?;
+            return Ok(true);
+        }
+
+        match (value.shape().def, value.shape().ty) {
+            // Optional-data: a boolean, then the value if it's there
+            (Def::Option(_), _) => {
+                let option = value
+                    .into_option()
+                    .map_err(|_| Self::Error::UnsupportedType { position: self.pos })?;
+                match option.value() {
+                    Some(inner) => {
+                        self.serialize_bool(true)?;
+                        serialize_iterative(inner, self)?;
+                    }
+                    None => self.serialize_bool(false)?,
+                }
+                Ok(true)
+            }
+            // Fixed-length arrays and opaque data have no length prefix
+            (Def::Array(ad), _) => {
+                let items = value
+                    .into_list_like()
+                    .map_err(|_| Self::Error::UnsupportedType { position: self.pos })?;
+                if ad.t().is_type::<u8>() {
+                    let bytes = items
+                        .iter()
+                        .map(|p| p.get::<u8>().copied())
+                        .collect::<Result<Vec<u8>, _>>()
+                        .map_err(|_| Self::Error::UnsupportedType { position: self.pos })?;
+                    self.write_padded(&bytes)?;
+                } else {
+                    for item in items.iter() {
+                        serialize_iterative(item, self)?;
+                    }
+                }
+                Ok(true)
+            }
+            // Tuples are written like structs, one field after the other
+            (_, Type::User(UserType::Struct(sd)))
+                if matches!(sd.kind, StructKind::Tuple | StructKind::TupleStruct) =>
+            {
+                let peek_struct = value
+                    .into_struct()
+                    .map_err(|_| Self::Error::UnsupportedType { position: self.pos })?;
+                for (field, field_value) in peek_struct.fields_for_serialize() {
+                    self.quadruple = is_quadruple(&field);
+                    serialize_iterative(field_value, self)?;
+                }
+                Ok(true)
+            }
+            // Discriminated unions: the discriminant, then the fields of the variant
+            (_, Type::User(UserType::Enum(_))) => {
+                let peek_enum = value
+                    .into_enum()
+                    .map_err(|_| Self::Error::UnsupportedType { position: self.pos })?;
+                let variant = peek_enum
+                    .active_variant()
+                    .map_err(|_| Self::Error::UnsupportedType { position: self.pos })?;
+                let discriminant = match variant.discriminant {
+                    Some(discriminant) => discriminant,
+                    None => peek_enum
+                        .variant_index()
+                        .map_err(|_| Self::Error::UnsupportedType { position: self.pos })?
+                        as i64,
+                };
+                self.write_discriminant(discriminant)?;
+                for (field, field_value) in peek_enum.fields_for_serialize() {
+                    self.quadruple = is_quadruple(&field);
+                    serialize_iterative(field_value, self)?;
+                }
+                Ok(true)
+            }
+            _ => Ok(false),
         }
-        self.writer
-            .write_all(&(discriminant as u32).to_be_bytes())
-            .map_err(Self::Error::Io)
     }
 }
 
@@ -195,11 +344,20 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
 #[derive(Debug)]
 pub enum XdrDeserError {
     /// Unsupported numeric type
-    UnsupportedNumericType,
+    UnsupportedNumericType {
+        /// Position of this error in bytes
+        position: usize,
+    },
     /// Unsupported type
-    UnsupportedType,
+    UnsupportedType {
+        /// Position of this error in bytes
+        position: usize,
+    },
     /// Unexpected end of input
-    UnexpectedEof,
+    UnexpectedEof {
+        /// Position of this error in bytes
+        position: usize,
+    },
     /// Invalid boolean
     InvalidBoolean {
         /// Position of this error in bytes
@@ -222,15 +380,29 @@ pub enum XdrDeserError {
         /// Underlying UTF-8 error
         source: core::str::Utf8Error,
     },
+    /// Invalid character
+    InvalidChar {
+        /// Position of this error in bytes
+        position: usize,
+    },
+    /// Number out of range for the target type
+    NumberOutOfRange {
+        /// Position of this error in bytes
+        position: usize,
+    },
 }
 
 impl core::fmt::Display for XdrDeserError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            XdrDeserError::UnsupportedNumericType => write!(f, "Unsupported numeric type"),
-            XdrDeserError::UnsupportedType => write!(f, "Unsupported type"),
-            XdrDeserError::UnexpectedEof => {
-                write!(f, "Unexpected end of input")
+            XdrDeserError::UnsupportedNumericType { position } => {
+                write!(f, "Unsupported numeric type at byte {}", position)
+            }
+            XdrDeserError::UnsupportedType { position } => {
+                write!(f, "Unsupported type at byte {}", position)
+            }
+            XdrDeserError::UnexpectedEof { position } => {
+                write!(f, "Unexpected end of input at byte {}", position)
             }
             XdrDeserError::InvalidBoolean { position } => {
                 write!(f, "Invalid boolean at byte {}", position)
@@ -244,6 +416,12 @@ impl core::fmt::Display for XdrDeserError {
             XdrDeserError::InvalidString { position, .. } => {
                 write!(f, "Invalid string at byte {}", position)
             }
+            XdrDeserError::InvalidChar { position } => {
+                write!(f, "Invalid character at byte {}", position)
+            }
+            XdrDeserError::NumberOutOfRange { position } => {
+                write!(f, "Number out of range at byte {}", position)
+            }
         }
     }
 }
@@ -268,6 +446,7 @@ enum PopReason {
 enum DeserializeTask {
     Value,
     Field(usize),
+    Element(usize),
     ListItem,
     Pop(PopReason),
 }
@@ -276,58 +455,84 @@ struct XdrDeserializerStack<'input> {
     input: &'input [u8],
     pos: usize,
     stack: Vec<DeserializeTask>,
+    /// Whether the next value is a field marked with `#[facet(quadruple)]`
+    quadruple: bool,
 }
 
 impl<'shape, 'input> XdrDeserializerStack<'input> {
-    fn next_u32(&mut self) -> Result<u32, XdrDeserError> {
-        assert_eq!(self.pos % 4, 0);
-        if self.input[self.pos..].len() < 4 {
-            return Err(XdrDeserError::UnexpectedEof);
+    fn unsupported(&self) -> XdrDeserError {
+        XdrDeserError::UnsupportedType { position: self.pos }
+    }
+
+    /// Reads `len` bytes of opaque data and skips their padding
+    fn next_opaque(&mut self, len: usize) -> Result<&'input [u8], XdrDeserError> {
+        let padded_len = len.div_ceil(4) * 4;
+        if self.input.len() - self.pos < padded_len {
+            return Err(XdrDeserError::UnexpectedEof { position: self.pos });
         }
-        let bytes = &self.input[self.pos..self.pos + 4];
-        self.pos += 4;
+        let data = &self.input[self.pos..self.pos + len];
+        self.pos += padded_len;
+        Ok(data)
+    }
+
+    fn next_u32(&mut self) -> Result<u32, XdrDeserError> {
+        let bytes = self.next_opaque(4)?;
         Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
     }
 
     fn next_u64(&mut self) -> Result<u64, XdrDeserError> {
-        assert_eq!(self.pos % 4, 0);
-        if self.input[self.pos..].len() < 8 {
-            return Err(XdrDeserError::UnexpectedEof);
-        }
-        let bytes = &self.input[self.pos..self.pos + 8];
-        self.pos += 8;
+        let bytes = self.next_opaque(8)?;
         Ok(u64::from_be_bytes(bytes.try_into().unwrap()))
     }
 
-    fn next_data(&mut self, expected_len: Option<u32>) -> Result<&'input [u8], XdrDeserError> {
+    fn next_u128(&mut self) -> Result<u128, XdrDeserError> {
+        let bytes = self.next_opaque(16)?;
+        Ok(u128::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Reads an `int` or `unsigned int` and converts it to a narrower integer
+    fn next_narrow<T: TryFrom<i64>>(&mut self, signed: bool) -> Result<T, XdrDeserError> {
+        let position = self.pos;
+        let value = self.next_u32()?;
+        let value = if signed {
+            value as i32 as i64
+        } else {
+            value as i64
+        };
+        T::try_from(value).map_err(|_| XdrDeserError::NumberOutOfRange { position })
+    }
+
+    fn next_data(&mut self) -> Result<&'input [u8], XdrDeserError> {
         let len = self.next_u32()? as usize;
-        if let Some(expected_len) = expected_len {
-            assert_eq!(len, expected_len as usize);
-        }
-        self.pos += len;
-        let pad_len = len % 4;
-        let data = &self.input[self.pos - len..self.pos];
-        if pad_len != 0 {
-            self.pos += 4 - pad_len;
-        }
-        Ok(data)
+        self.next_opaque(len)
     }
 
     fn next<'f>(
         &mut self,
         mut wip: Partial<'f, 'shape>,
     ) -> Result<Partial<'f, 'shape>, XdrDeserError> {
+        if core::mem::take(&mut self.quadruple) {
+            if !wip.shape().is_type::<f64>() {
+                return Err(self.unsupported());
+            }
+            let position = self.pos;
+            let float = quadruple::to_f64(self.next_u128()?)
+                .ok_or(XdrDeserError::NumberOutOfRange { position })?;
+            wip.set(float).unwrap();
+            return Ok(wip);
+        }
+
         match (wip.shape().def, wip.shape().ty) {
             (Def::Scalar(sd), _) => match sd.affinity {
                 ScalarAffinity::Number(na) => match na.bits {
                     NumberBits::Integer { size, sign } => match (size, sign) {
                         (IntegerSize::Fixed(8), Signedness::Unsigned) => {
-                            let value = self.next_u32()? as u8;
+                            let value: u8 = self.next_narrow(false)?;
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(16), Signedness::Unsigned) => {
-                            let value = self.next_u32()? as u16;
+                            let value: u16 = self.next_narrow(false)?;
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
@@ -341,13 +546,19 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
+                        (IntegerSize::Fixed(128), Signedness::Unsigned) => {
+                            // Read from an unsigned hyper
+                            let value = self.next_u64()? as u128;
+                            wip.set(value).unwrap();
+                            Ok(wip)
+                        }
                         (IntegerSize::Fixed(8), Signedness::Signed) => {
-                            let value = self.next_u32()? as i8;
+                            let value: i8 = self.next_narrow(true)?;
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(16), Signedness::Signed) => {
-                            let value = self.next_u32()? as i16;
+                            let value: i16 = self.next_narrow(true)?;
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
@@ -361,19 +572,29 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
+                        (IntegerSize::Fixed(128), Signedness::Signed) => {
+                            // Read from a hyper
+                            let value = self.next_u64()? as i64 as i128;
+                            wip.set(value).unwrap();
+                            Ok(wip)
+                        }
                         (IntegerSize::PointerSized, Signedness::Unsigned) => {
                             // Handle usize - use 64-bit on most platforms
-                            let value = self.next_u64()? as usize;
+                            let position = self.pos;
+                            let value = usize::try_from(self.next_u64()?)
+                                .map_err(|_| XdrDeserError::NumberOutOfRange { position })?;
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
                         (IntegerSize::PointerSized, Signedness::Signed) => {
                             // Handle isize - use 64-bit on most platforms
-                            let value = self.next_u64()? as isize;
+                            let position = self.pos;
+                            let value = isize::try_from(self.next_u64()? as i64)
+                                .map_err(|_| XdrDeserError::NumberOutOfRange { position })?;
                             wip.set(value).unwrap();
                             Ok(wip)
                         }
-                        _ => Err(XdrDeserError::UnsupportedNumericType),
+                        _ => Err(XdrDeserError::UnsupportedNumericType { position: self.pos }),
                     },
                     NumberBits::Float {
                         sign_bits,
@@ -393,15 +614,16 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                             wip.set(float).unwrap();
                             Ok(wip)
                         } else {
-                            Err(XdrDeserError::UnsupportedNumericType)
+                            Err(XdrDeserError::UnsupportedNumericType { position: self.pos })
                         }
                     }
-                    _ => Err(XdrDeserError::UnsupportedNumericType),
+                    _ => Err(XdrDeserError::UnsupportedNumericType { position: self.pos }),
                 },
                 ScalarAffinity::String(_) => {
-                    let string = core::str::from_utf8(self.next_data(None)?).map_err(|e| {
+                    let position = self.pos;
+                    let string = core::str::from_utf8(self.next_data()?).map_err(|e| {
                         XdrDeserError::InvalidString {
-                            position: self.pos - 1,
+                            position,
                             source: e,
                         }
                     })?;
@@ -423,53 +645,49 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                 },
                 ScalarAffinity::Char(_) => {
                     let value = self.next_u32()?;
-                    wip.set(char::from_u32(value).unwrap()).unwrap();
+                    let value = char::from_u32(value).ok_or(XdrDeserError::InvalidChar {
+                        position: self.pos - 4,
+                    })?;
+                    wip.set(value).unwrap();
                     Ok(wip)
                 }
-                _ => Err(XdrDeserError::UnsupportedType),
+                _ => Err(self.unsupported()),
             },
             (Def::List(ld), _) => {
                 if ld.t().is_type::<u8>() {
-                    let data = self.next_data(None)?;
+                    let data = self.next_data()?;
                     wip.set(data.to_vec()).unwrap();
                     Ok(wip)
                 } else {
                     let len = self.next_u32()?;
-                    wip.begin_list().unwrap();
-                    if len == 0 {
-                        Ok(wip)
-                    } else {
-                        for _ in 0..len {
-                            self.stack.push(DeserializeTask::ListItem);
-                        }
-                        Ok(wip)
+                    wip.begin_list().map_err(|_| self.unsupported())?;
+                    for _ in 0..len {
+                        self.stack.push(DeserializeTask::ListItem);
                     }
+                    Ok(wip)
                 }
             }
+            // Fixed-length arrays and opaque data have no length prefix
             (Def::Array(ad), _) => {
-                let len = ad.n;
                 if ad.t().is_type::<u8>() {
-                    self.pos += len;
-                    let pad_len = len % 4;
-                    for byte in &self.input[self.pos - len..self.pos] {
-                        wip.begin_list_item().unwrap();
+                    let position = self.pos;
+                    let data = self.next_opaque(ad.n)?;
+                    for (index, byte) in data.iter().enumerate() {
+                        wip.begin_nth_element(index)
+                            .map_err(|_| XdrDeserError::UnsupportedType { position })?;
                         wip.set(*byte).unwrap();
                         wip.end().unwrap();
                     }
-                    if pad_len != 0 {
-                        self.pos += 4 - pad_len;
-                    }
-                    Ok(wip)
                 } else {
-                    for _ in 0..len {
-                        self.stack.push(DeserializeTask::ListItem);
+                    for index in (0..ad.n).rev() {
+                        self.stack.push(DeserializeTask::Element(index));
                     }
-                    Ok(wip)
                 }
+                Ok(wip)
             }
             (Def::Slice(sd), _) => {
                 if sd.t().is_type::<u8>() {
-                    let data = self.next_data(None)?;
+                    let data = self.next_data()?;
                     wip.set(data.to_vec()).unwrap();
                     Ok(wip)
                 } else {
@@ -480,15 +698,16 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                     Ok(wip)
                 }
             }
+            // Optional-data: a boolean, then the value if it's there
             (Def::Option(_), _) => match self.next_u32()? {
                 0 => {
-                    wip.set_default().unwrap();
+                    wip.set_default().map_err(|_| self.unsupported())?;
                     Ok(wip)
                 }
                 1 => {
+                    wip.begin_some().map_err(|_| self.unsupported())?;
                     self.stack.push(DeserializeTask::Pop(PopReason::Some));
                     self.stack.push(DeserializeTask::Value);
-                    wip.select_variant(1).unwrap();
                     Ok(wip)
                 }
                 _ => Err(XdrDeserError::InvalidOptional {
@@ -497,44 +716,46 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
             },
             (_, Type::User(ut)) => match ut {
                 UserType::Struct(st) => {
-                    if st.kind == StructKind::Tuple {
-                        // Handle tuple structs
-                        for _field in st.fields.iter() {
-                            self.stack.push(DeserializeTask::ListItem);
-                        }
-                        Ok(wip)
-                    } else {
-                        // Handle regular structs
-                        for (index, _field) in st.fields.iter().enumerate().rev() {
-                            if !wip.is_field_set(index).unwrap() {
-                                self.stack.push(DeserializeTask::Field(index));
-                            }
+                    // Structs, tuple structs and tuples are all read one field after the other
+                    for (index, _field) in st.fields.iter().enumerate().rev() {
+                        if !wip.is_field_set(index).unwrap() {
+                            self.stack.push(DeserializeTask::Field(index));
                         }
-                        Ok(wip)
                     }
+                    Ok(wip)
                 }
+                // Discriminated unions: the discriminant, then the fields of the variant
                 UserType::Enum(et) => {
                     let discriminant = self.next_u32()?;
-                    if let Some(variant) = et
+                    // The discriminant may be an `unsigned int` or an `int`
+                    let matches =
+                        |d: i64| d == discriminant as i64 || d == discriminant as i32 as i64;
+                    let found = et
                         .variants
                         .iter()
-                        .find(|v| v.discriminant == Some(discriminant as i64))
-                        .or(et.variants.get(discriminant as usize))
-                    {
-                        for (index, _field) in variant.data.fields.iter().enumerate().rev() {
-                            self.stack.push(DeserializeTask::Field(index));
-                        }
-                        wip.select_variant(discriminant as i64).unwrap();
-                        Ok(wip)
-                    } else {
-                        Err(XdrDeserError::InvalidVariant {
+                        .position(|v| v.discriminant.is_some_and(matches))
+                        .or_else(|| {
+                            let index = discriminant as usize;
+                            et.variants
+                                .get(index)
+                                .filter(|v| v.discriminant.is_none())
+                                .map(|_| index)
+                        });
+                    let Some(index) = found else {
+                        return Err(XdrDeserError::InvalidVariant {
                             position: self.pos - 4,
-                        })
+                        });
+                    };
+                    for field_index in (0..et.variants[index].data.fields.len()).rev() {
+                        self.stack.push(DeserializeTask::Field(field_index));
                     }
+                    wip.select_nth_variant(index)
+                        .map_err(|_| self.unsupported())?;
+                    Ok(wip)
                 }
-                _ => Err(XdrDeserError::UnsupportedType),
+                _ => Err(self.unsupported()),
             },
-            _ => Err(XdrDeserError::UnsupportedType),
+            _ => Err(self.unsupported()),
         }
     }
 }
@@ -551,6 +772,7 @@ pub fn deserialize_wip<'facet, 'shape>(
             DeserializeTask::Pop(PopReason::TopLevel),
             DeserializeTask::Value,
         ],
+        quadruple: false,
     };
 
     loop {
@@ -560,27 +782,44 @@ pub fn deserialize_wip<'facet, 'shape>(
         match runner.stack.pop() {
             Some(DeserializeTask::Pop(reason)) => {
                 if reason == PopReason::TopLevel {
-                    return Ok(wip.build().unwrap());
+                    return wip.build().map_err(|_| runner.unsupported());
                 } else {
-                    wip.end().unwrap();
+                    wip.end().map_err(|_| runner.unsupported())?;
                 }
             }
             Some(DeserializeTask::Value) => {
                 wip = runner.next(wip)?;
             }
             Some(DeserializeTask::Field(index)) => {
+                let field = match wip.shape().ty {
+                    Type::User(UserType::Struct(st)) => st.fields.get(index).copied(),
+                    Type::User(UserType::Enum(_)) => wip
+                        .selected_variant()
+                        .and_then(|v| v.data.fields.get(index).copied()),
+                    _ => None,
+                };
+                runner.quadruple = field.is_some_and(|f| is_quadruple(&f));
+                runner
+                    .stack
+                    .push(DeserializeTask::Pop(PopReason::ObjectOrListVal));
+                runner.stack.push(DeserializeTask::Value);
+                wip.begin_nth_field(index)
+                    .map_err(|_| runner.unsupported())?;
+            }
+            Some(DeserializeTask::Element(index)) => {
                 runner
                     .stack
                     .push(DeserializeTask::Pop(PopReason::ObjectOrListVal));
                 runner.stack.push(DeserializeTask::Value);
-                wip.begin_nth_field(index).unwrap();
+                wip.begin_nth_element(index)
+                    .map_err(|_| runner.unsupported())?;
             }
             Some(DeserializeTask::ListItem) => {
                 runner
                     .stack
                     .push(DeserializeTask::Pop(PopReason::ObjectOrListVal));
                 runner.stack.push(DeserializeTask::Value);
-                wip.begin_list_item().unwrap();
+                wip.begin_list_item().map_err(|_| runner.unsupported())?;
             }
             None => unreachable!("Instruction stack is empty"),
         }
@@ -0,0 +1,74 @@
+//! Conversions between `f64` and the bits of XDR quadruple-precision floats (IEEE 754
+//! binary128), which Rust has no stable type for.
+
+const F64_EXPONENT_BIAS: i64 = 1023;
+const F128_EXPONENT_BIAS: i64 = 16383;
+const F128_MANTISSA_BITS: u32 = 112;
+
+/// Returns the binary128 bits of `value`, which always represent it exactly
+pub(crate) fn from_f64(value: f64) -> u128 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 63) as u128) << 127;
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let mantissa = bits & ((1 << 52) - 1);
+
+    let (exponent, mantissa) = match (exponent, mantissa) {
+        (0, 0) => (0, 0),
+        // Infinities and NaNs
+        (0x7ff, _) => (0x7fff, (mantissa as u128) << 60),
+        // Subnormal: normalize it, binary128 has enough exponent range
+        (0, _) => {
+            let top_bit = 63 - mantissa.leading_zeros() as i64;
+            let exponent = top_bit - 1074 + F128_EXPONENT_BIAS;
+            let mantissa = (mantissa as u128 & !(1 << top_bit)) << (112 - top_bit);
+            (exponent as u128, mantissa)
+        }
+        _ => (
+            (exponent - F64_EXPONENT_BIAS + F128_EXPONENT_BIAS) as u128,
+            (mantissa as u128) << 60,
+        ),
+    };
+
+    sign | (exponent << F128_MANTISSA_BITS) | mantissa
+}
+
+/// Returns the `f64` with the value of the binary128 `bits`, or `None` if the value isn't
+/// representable exactly
+pub(crate) fn to_f64(bits: u128) -> Option<f64> {
+    let sign = ((bits >> 127) as u64) << 63;
+    let exponent = ((bits >> F128_MANTISSA_BITS) & 0x7fff) as i64;
+    let mantissa = bits & ((1 << F128_MANTISSA_BITS) - 1);
+
+    let f64_bits = match exponent {
+        0 if mantissa == 0 => 0,
+        // Subnormal binary128 values are far below the smallest f64
+        0 => return None,
+        0x7fff if mantissa == 0 => 0x7ff << 52,
+        // NaN: keep the top of the payload, and make sure it stays a NaN
+        0x7fff => match (mantissa >> 60) as u64 {
+            0 => (0x7ff << 52) | (1 << 51),
+            payload => (0x7ff << 52) | payload,
+        },
+        _ => {
+            let exponent = exponent - F128_EXPONENT_BIAS;
+            if (-1022..=1023).contains(&exponent) {
+                if mantissa & ((1 << 60) - 1) != 0 {
+                    return None;
+                }
+                (((exponent + F64_EXPONENT_BIAS) as u64) << 52) | (mantissa >> 60) as u64
+            } else if (-1074..-1022).contains(&exponent) {
+                // Subnormal f64: the implicit leading bit becomes part of the mantissa
+                let shift = (F128_MANTISSA_BITS as i64 - (exponent + 1074)) as u32;
+                let mantissa = mantissa | (1 << F128_MANTISSA_BITS);
+                if mantissa & ((1 << shift) - 1) != 0 {
+                    return None;
+                }
+                (mantissa >> shift) as u64
+            } else {
+                return None;
+            }
+        }
+    };
+
+    Some(f64::from_bits(sign | f64_bits))
+}
@@ -0,0 +1,198 @@
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_xdr::{XdrDeserError, XdrSerError, deserialize, to_vec};
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(i32)]
+enum Stat {
+    Ok { size: u64 } = 0,
+    NoEnt = 2,
+    Io = -5,
+}
+
+#[test]
+fn test_union_explicit_discriminants() {
+    let cases: [(Stat, &[u8]); 3] = [
+        (Stat::Ok { size: 7 }, &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7]),
+        (Stat::NoEnt, &[0, 0, 0, 2]),
+        (Stat::Io, &[0xff, 0xff, 0xff, 0xfb]),
+    ];
+    for (stat, bytes) in cases {
+        assert_eq!(to_vec(&stat)?, bytes);
+        assert_eq!(deserialize::<Stat>(bytes)?, stat);
+    }
+}
+
+#[test]
+fn test_union_unknown_discriminant() {
+    assert!(matches!(
+        deserialize::<Stat>(&[0, 0, 0, 1]),
+        Err(XdrDeserError::InvalidVariant { position: 0 })
+    ));
+}
+
+#[test]
+fn test_union_fields() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Pair(u32, u32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        a: Shape,
+        b: Shape,
+    }
+
+    let cases: [(Root, &[u8]); 2] = [
+        (
+            Root {
+                a: Shape::Circle(2),
+                b: Shape::Rect {
+                    width: 1,
+                    height: 2,
+                },
+            },
+            &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2],
+        ),
+        (
+            Root {
+                a: Shape::Empty,
+                b: Shape::Pair(1, 2),
+            },
+            &[0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2],
+        ),
+    ];
+    for (root, bytes) in cases {
+        assert_eq!(to_vec(&root)?, bytes);
+        assert_eq!(deserialize::<Root>(bytes)?, root);
+    }
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Optional {
+    present: Option<u32>,
+    absent: Option<u32>,
+}
+
+#[test]
+fn test_optional_data() {
+    let value = Optional {
+        present: Some(3),
+        absent: None,
+    };
+    let bytes = [0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 0];
+    assert_eq!(to_vec(&value)?, bytes);
+    assert_eq!(deserialize::<Optional>(&bytes)?, value);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Fixed {
+    handle: [u8; 5],
+    words: [u16; 2],
+}
+
+#[test]
+fn test_fixed_length_opaque_and_arrays() {
+    let value = Fixed {
+        handle: [1, 2, 3, 4, 5],
+        words: [6, 7],
+    };
+    let bytes = [1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 0, 6, 0, 0, 0, 7];
+    assert_eq!(to_vec(&value)?, bytes);
+    assert_eq!(deserialize::<Fixed>(&bytes)?, value);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Pair(u8, i16);
+
+#[derive(Debug, Facet, PartialEq)]
+struct Tuples {
+    pair: Pair,
+    tuple: (u32, bool),
+}
+
+#[test]
+fn test_tuples() {
+    let value = Tuples {
+        pair: Pair(1, -2),
+        tuple: (3, true),
+    };
+    let bytes = [0, 0, 0, 1, 0xff, 0xff, 0xff, 0xfe, 0, 0, 0, 3, 0, 0, 0, 1];
+    assert_eq!(to_vec(&value)?, bytes);
+    assert_eq!(deserialize::<Tuples>(&bytes)?, value);
+}
+
+#[test]
+fn test_narrow_integer_out_of_range() {
+    assert!(matches!(
+        deserialize::<Pair>(&[0, 0, 1, 0, 0, 0, 0, 0]),
+        Err(XdrDeserError::NumberOutOfRange { position: 0 })
+    ));
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Hypers {
+    unsigned: u128,
+    signed: i128,
+}
+
+#[test]
+fn test_hyper_128_bit() {
+    let value = Hypers {
+        unsigned: 1 << 40,
+        signed: -3,
+    };
+    let bytes = [
+        0, 0, 1, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfd,
+    ];
+    assert_eq!(to_vec(&value)?, bytes);
+    assert_eq!(deserialize::<Hypers>(&bytes)?, value);
+
+    let too_large = Hypers {
+        unsigned: 0,
+        signed: i128::MIN,
+    };
+    assert!(matches!(
+        to_vec(&too_large),
+        Err(XdrSerError::NumberOutOfRange { position: 8 })
+    ));
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Quadruple {
+    #[facet(quadruple)]
+    value: f64,
+    double: f64,
+}
+
+#[test]
+fn test_quadruple() {
+    let value = Quadruple {
+        value: -1.5,
+        double: 1.5,
+    };
+    let mut bytes = vec![0xbf, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    bytes.extend_from_slice(&1.5f64.to_be_bytes());
+    assert_eq!(to_vec(&value)?, bytes);
+    assert_eq!(deserialize::<Quadruple>(&bytes)?, value);
+
+    // More precision than a double has
+    bytes[15] = 1;
+    assert!(matches!(
+        deserialize::<Quadruple>(&bytes),
+        Err(XdrDeserError::NumberOutOfRange { position: 0 })
+    ));
+}
+
+#[test]
+fn test_error_position() {
+    assert!(matches!(
+        deserialize::<Fixed>(&[1, 2, 3, 4, 5, 0, 0, 0, 0, 0]),
+        Err(XdrDeserError::UnexpectedEof { position: 8 })
+    ));
+}
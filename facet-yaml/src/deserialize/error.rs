@@ -1,37 +1,218 @@
 //! Errors from parsing YAML documents.
 
-use alloc::{
-    format,
-    string::{String, ToString},
-};
+use super::Span;
+use alloc::{format, string::String};
+#[cfg(feature = "rich-diagnostics")]
+use ariadne::{Color, Config, IndexType, Label, Report, ReportKind, Source};
+use facet_core::Shape;
 use facet_reflect::ReflectError;
+use yaml_rust2::scanner::Marker;
 
-/// Any error
-#[derive(Debug, Clone)]
-pub struct AnyErr(pub String);
+/// Any error from deserializing YAML.
+pub struct YamlDeError<'input, 'shape> {
+    /// Type of error.
+    pub kind: YamlDeErrorKind<'shape>,
+    /// Reference to the YAML source.
+    #[cfg_attr(not(feature = "rich-diagnostics"), allow(dead_code))]
+    yaml: &'input str,
+    /// Which part of the YAML this error applies to.
+    span: Option<Span>,
+    /// Full Partial path.
+    path: String,
+}
+
+impl<'input, 'shape> YamlDeError<'input, 'shape> {
+    /// Create a new error.
+    pub fn new(
+        yaml: &'input str,
+        kind: YamlDeErrorKind<'shape>,
+        span: Option<Span>,
+        path: String,
+    ) -> Self {
+        Self {
+            kind,
+            yaml,
+            span,
+            path,
+        }
+    }
+
+    /// Where in the YAML source the error starts, if known.
+    ///
+    /// Lines are numbered from 1 and columns from 0, like `yaml-rust2` does.
+    pub fn marker(&self) -> Option<Marker> {
+        self.span.map(|span| span.start)
+    }
+
+    /// Which part of the YAML source this error applies to, if known.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Path of the value that failed to deserialize, like `$.servers[0].port`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Message for this specific error.
+    pub fn message(&self) -> String {
+        match &self.kind {
+            YamlDeErrorKind::Parse(message) => format!("YAML error: {message}"),
+            YamlDeErrorKind::ExpectedSingleDocument(count) => {
+                format!("Expected exactly one YAML document, got {count}")
+            }
+            YamlDeErrorKind::TypeMismatch { expected, got } => {
+                format!("Cannot convert {got} to {expected}")
+            }
+            YamlDeErrorKind::InvalidValue { shape, reason } => {
+                format!("Invalid value for {shape}: {reason}")
+            }
+            YamlDeErrorKind::InvalidKey { got } => {
+                format!("Expected a string key, got {got}")
+            }
+            YamlDeErrorKind::UnknownField { field_name, shape } => {
+                format!("Unknown field '{field_name}' for {shape}")
+            }
+            YamlDeErrorKind::MissingField { field_name, shape } => {
+                format!("Missing field '{field_name}' for {shape}")
+            }
+            YamlDeErrorKind::NoSuchVariant { name, enum_shape } => {
+                format!("No variant '{name}' in enum {enum_shape}")
+            }
+            YamlDeErrorKind::NoMatchingVariant(shape) => {
+                format!("Data did not match any variant of untagged enum {shape}")
+            }
+            YamlDeErrorKind::Unsupported(shape) => {
+                format!("Unsupported type: {shape}")
+            }
+            YamlDeErrorKind::Reflect(reflect_error) => {
+                format!("Reflection error: {reflect_error}")
+            }
+        }
+    }
+}
 
-impl core::fmt::Display for AnyErr {
+#[cfg(not(feature = "rich-diagnostics"))]
+impl core::fmt::Display for YamlDeError<'_, '_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.0)
+        match self.marker() {
+            Some(marker) => write!(
+                f,
+                "{} at line {} column {} in path {}",
+                self.message(),
+                marker.line(),
+                marker.col() + 1,
+                self.path
+            ),
+            None => write!(f, "{} in path {}", self.message(), self.path),
+        }
     }
 }
 
-impl core::error::Error for AnyErr {}
+#[cfg(feature = "rich-diagnostics")]
+impl core::fmt::Display for YamlDeError<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Don't print the YAML source if no span is set
+        let Some(span) = &self.span else {
+            return writeln!(f, "{} in path {}", self.message(), self.path);
+        };
+
+        let source_id = "yaml";
+
+        // Markers count characters, not bytes; empty spans still need something to point at
+        let start = span.start.index();
+        let end = span.end.index().max(start + 1);
+
+        let mut report = Report::build(ReportKind::Error, (source_id, start..end))
+            .with_config(Config::new().with_index_type(IndexType::Char))
+            .with_message(format!("Error at {}", self.path));
+
+        let label = Label::new((source_id, start..end))
+            .with_message(self.message())
+            .with_color(Color::Red);
+
+        report = report.with_label(label);
 
-impl From<String> for AnyErr {
-    fn from(s: String) -> Self {
-        Self(s)
+        let source = Source::from(self.yaml);
+
+        let mut writer = Vec::new();
+        if let Err(e) = report.finish().write((source_id, &source), &mut writer) {
+            return write!(f, "Error formatting with ariadne: {e}");
+        }
+
+        if let Ok(output) = String::from_utf8(writer) {
+            write!(f, "{}", output)
+        } else {
+            write!(f, "Error converting ariadne output to string")
+        }
     }
 }
 
-impl From<&str> for AnyErr {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
+impl core::error::Error for YamlDeError<'_, '_> {}
+
+impl core::fmt::Debug for YamlDeError<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
     }
 }
 
-impl<'shape> From<ReflectError<'shape>> for AnyErr {
-    fn from(value: ReflectError) -> Self {
-        Self(format!("Reflection error: {value}"))
+/// Type of error.
+#[derive(Debug, PartialEq)]
+pub enum YamlDeErrorKind<'shape> {
+    /// The YAML document could not be parsed.
+    Parse(String),
+    /// Expected a single YAML document, got none or several.
+    ExpectedSingleDocument(usize),
+    /// A YAML value can't be converted to the Rust type.
+    TypeMismatch {
+        /// Rust type that was expected.
+        expected: &'shape Shape<'shape>,
+        /// YAML type that we got.
+        got: &'static str,
+    },
+    /// A YAML value has the right type, but not a valid value for the Rust type.
+    InvalidValue {
+        /// Rust type the value was meant for.
+        shape: &'shape Shape<'shape>,
+        /// Explanation why it's invalid.
+        reason: String,
+    },
+    /// A key of a YAML hash is not a string.
+    InvalidKey {
+        /// YAML type of the key.
+        got: &'static str,
+    },
+    /// A key of a YAML hash doesn't match any field of the struct.
+    UnknownField {
+        /// Name of the field.
+        field_name: String,
+        /// Struct, or enum, that doesn't have the field.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A field of the struct has no key in the YAML hash, and no default.
+    MissingField {
+        /// Name of the field.
+        field_name: &'shape str,
+        /// Struct, or enum, the field belongs to.
+        shape: &'shape Shape<'shape>,
+    },
+    /// No variant of the enum has this name.
+    NoSuchVariant {
+        /// Name of the variant.
+        name: String,
+        /// Enum that doesn't have the variant.
+        enum_shape: &'shape Shape<'shape>,
+    },
+    /// None of the variants of an untagged enum could be parsed from the value.
+    NoMatchingVariant(&'shape Shape<'shape>),
+    /// Found a Rust type that we don't know how to handle.
+    Unsupported(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for YamlDeErrorKind<'shape> {
+    fn from(value: ReflectError<'shape>) -> Self {
+        Self::Reflect(value)
     }
 }
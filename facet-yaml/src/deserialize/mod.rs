@@ -4,71 +4,275 @@
 compile_error!("feature `alloc` is required");
 
 mod error;
+mod node;
 
 use alloc::{
     format,
     string::{String, ToString},
+    vec,
+    vec::Vec,
 };
-use error::AnyErr;
-use facet_core::{Characteristic, Def, Facet, FieldFlags, Type, UserType};
+use core::fmt::Display;
+pub use error::{YamlDeError, YamlDeErrorKind};
+use facet_core::{Def, Facet, Field, FieldFlags, StructKind, Type, UserType, Variant};
 use facet_reflect::Partial;
-use yaml_rust2::{Yaml, YamlLoader};
+pub use node::Span;
+use node::{MarkedYaml, MarkedYamlLoader, YamlData};
+use yaml_rust2::parser::Parser;
+
+macro_rules! reflect {
+    ($yaml:expr, $wip:expr, $node:expr, $($tt:tt)*) => {
+        match $wip.$($tt)* {
+            Ok(value) => value,
+            Err(e) => {
+                return Err(YamlDeError::new(
+                    $yaml,
+                    YamlDeErrorKind::Reflect(e),
+                    Some($node.span),
+                    $wip.path(),
+                ));
+            }
+        }
+    };
+}
 
 /// Deserializes a YAML string into a value of type `T` that implements `Facet`.
-pub fn from_str<'input: 'facet, 'facet, T: Facet<'facet>>(yaml: &'input str) -> Result<T, AnyErr> {
-    let mut typed_partial = Partial::alloc::<T>()?;
-    {
-        let wip = typed_partial.inner_mut();
-        from_str_value(wip, yaml)?;
+pub fn from_str<'input: 'facet, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    yaml: &'input str,
+) -> Result<T, YamlDeError<'input, 'shape>> {
+    let docs = load(yaml)?;
+    if docs.len() != 1 {
+        return Err(YamlDeError::new(
+            yaml,
+            YamlDeErrorKind::ExpectedSingleDocument(docs.len()),
+            docs.get(1).map(|doc| doc.span),
+            "$".to_string(),
+        ));
     }
-    let boxed_value = typed_partial.build().map_err(|e| AnyErr(e.to_string()))?;
+    from_document(yaml, &docs[0])
+}
+
+/// Deserializes a stream of YAML documents, separated by `---`, into values of type `T`.
+pub fn from_str_multi<'input: 'facet, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    yaml: &'input str,
+) -> Result<Vec<T>, YamlDeError<'input, 'shape>> {
+    load(yaml)?
+        .iter()
+        .map(|doc| from_document(yaml, doc))
+        .collect()
+}
+
+/// Parses the documents of a YAML stream, with aliases replaced by the nodes they refer to.
+fn load<'input, 'shape>(yaml: &'input str) -> Result<Vec<MarkedYaml>, YamlDeError<'input, 'shape>> {
+    let mut parser = Parser::new_from_str(yaml);
+    let mut loader = MarkedYamlLoader::default();
+    let docs = parser
+        .load(&mut loader, true)
+        .and_then(|()| loader.into_documents());
+    docs.map_err(|e| {
+        let marker = *e.marker();
+        YamlDeError::new(
+            yaml,
+            YamlDeErrorKind::Parse(e.info().to_string()),
+            Some(Span::new(marker, marker)),
+            "$".to_string(),
+        )
+    })
+}
+
+fn from_document<'input: 'facet, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    yaml: &'input str,
+    doc: &MarkedYaml,
+) -> Result<T, YamlDeError<'input, 'shape>> {
+    let mut typed_partial = Partial::alloc::<T>()
+        .map_err(|e| YamlDeError::new(yaml, YamlDeErrorKind::Reflect(e), None, "$".to_string()))?;
+    deserialize_value(yaml, typed_partial.inner_mut(), doc)?;
+    let boxed_value = typed_partial
+        .build()
+        .map_err(|e| YamlDeError::new(yaml, YamlDeErrorKind::Reflect(e), None, "$".to_string()))?;
     Ok(*boxed_value)
 }
 
-fn yaml_type(ty: &Yaml) -> &'static str {
-    match ty {
-        Yaml::Real(_) => "real number",
-        Yaml::Integer(_) => "integer",
-        Yaml::String(_) => "string",
-        Yaml::Boolean(_) => "boolean",
-        Yaml::Array(_) => "array",
-        Yaml::Hash(_) => "hash/map",
-        Yaml::Alias(_) => "alias",
-        Yaml::Null => "null",
-        Yaml::BadValue => "bad value",
+/// Builds an error about a YAML node.
+fn error<'input, 'shape>(
+    yaml: &'input str,
+    wip: &Partial<'_, 'shape>,
+    node: &MarkedYaml,
+    kind: YamlDeErrorKind<'shape>,
+) -> YamlDeError<'input, 'shape> {
+    YamlDeError::new(yaml, kind, Some(node.span), wip.path())
+}
+
+/// Builds an error about a YAML node that can't be converted to the type being deserialized.
+fn type_mismatch<'input, 'shape>(
+    yaml: &'input str,
+    wip: &Partial<'_, 'shape>,
+    node: &MarkedYaml,
+) -> YamlDeError<'input, 'shape> {
+    let kind = YamlDeErrorKind::TypeMismatch {
+        expected: wip.shape(),
+        got: yaml_type(node),
+    };
+    error(yaml, wip, node, kind)
+}
+
+fn yaml_type(node: &MarkedYaml) -> &'static str {
+    match &node.data {
+        YamlData::Real(_) => "real number",
+        YamlData::Integer(_) => "integer",
+        YamlData::String(_) => "string",
+        YamlData::Boolean(_) => "boolean",
+        YamlData::Array(_) => "array",
+        YamlData::Hash(_) => "hash/map",
+        YamlData::Null => "null",
+        YamlData::BadValue => "bad value",
+    }
+}
+
+fn as_str(node: &MarkedYaml) -> Option<&str> {
+    match &node.data {
+        YamlData::String(s) => Some(s),
+        _ => None,
+    }
+}
+
+/// Gets the key of a YAML hash entry, which must be a string.
+fn key_str<'input, 'shape, 'a>(
+    yaml: &'input str,
+    wip: &Partial<'_, 'shape>,
+    key: &'a MarkedYaml,
+) -> Result<&'a str, YamlDeError<'input, 'shape>> {
+    as_str(key).ok_or_else(|| {
+        let kind = YamlDeErrorKind::InvalidKey {
+            got: yaml_type(key),
+        };
+        error(yaml, wip, key, kind)
+    })
+}
+
+/// Finds the entry with a string key in a YAML hash.
+fn hash_entry<'a>(node: &'a MarkedYaml, key: &str) -> Option<(&'a MarkedYaml, &'a MarkedYaml)> {
+    hash_entries(node)
+        .into_iter()
+        .find(|(k, _)| as_str(k) == Some(key))
+}
+
+fn is_merge_key(key: &MarkedYaml) -> bool {
+    as_str(key) == Some("<<")
+}
+
+/// The entries of a YAML hash, including those brought in by `<<` merge keys.
+///
+/// Entries of the hash itself win over merged ones, and earlier merged hashes win over later ones.
+fn hash_entries(node: &MarkedYaml) -> Vec<(&MarkedYaml, &MarkedYaml)> {
+    let YamlData::Hash(hash) = &node.data else {
+        return Vec::new();
+    };
+    let mut entries: Vec<_> = hash.iter().filter(|(k, _)| !is_merge_key(k)).collect();
+    for (_, merged) in hash.iter().filter(|(k, _)| is_merge_key(k)) {
+        let sources: Vec<&MarkedYaml> = match &merged.data {
+            YamlData::Array(array) => array.iter().collect(),
+            _ => vec![merged],
+        };
+        for source in sources {
+            for (k, v) in hash_entries(source) {
+                if !entries.iter().any(|(existing, _)| existing.data == k.data) {
+                    entries.push((k, v));
+                }
+            }
+        }
     }
+    entries
 }
 
-fn yaml_to_u64(ty: &Yaml) -> Result<u64, AnyErr> {
-    match ty {
-        Yaml::Real(r) => r
+fn invalid_value<'input, 'shape>(
+    yaml: &'input str,
+    wip: &Partial<'_, 'shape>,
+    node: &MarkedYaml,
+    reason: impl Display,
+) -> YamlDeError<'input, 'shape> {
+    let kind = YamlDeErrorKind::InvalidValue {
+        shape: wip.shape(),
+        reason: reason.to_string(),
+    };
+    error(yaml, wip, node, kind)
+}
+
+fn yaml_to_u64<'input, 'shape>(
+    yaml: &'input str,
+    wip: &Partial<'_, 'shape>,
+    node: &MarkedYaml,
+) -> Result<u64, YamlDeError<'input, 'shape>> {
+    match &node.data {
+        YamlData::Real(r) => r
             .parse::<u64>()
-            .map_err(|_| AnyErr("Failed to parse real as u64".into())),
-        Yaml::Integer(i) => Ok(*i as u64),
-        Yaml::String(s) => s
+            .map_err(|_| invalid_value(yaml, wip, node, "failed to parse real as u64")),
+        YamlData::Integer(i) => Ok(*i as u64),
+        YamlData::String(s) => s
             .parse::<u64>()
-            .map_err(|_| AnyErr("Failed to parse string as u64".into())),
-        Yaml::Boolean(b) => Ok(if *b { 1 } else { 0 }),
-        _ => Err(AnyErr(format!("Cannot convert {} to u64", yaml_type(ty)))),
+            .map_err(|_| invalid_value(yaml, wip, node, "failed to parse string as u64")),
+        YamlData::Boolean(b) => Ok(if *b { 1 } else { 0 }),
+        _ => Err(type_mismatch(yaml, wip, node)),
     }
 }
 
-fn from_str_value<'facet, 'shape>(
-    wip: &mut Partial<'facet, 'shape>,
-    yaml: &str,
-) -> Result<(), AnyErr> {
-    let docs = YamlLoader::load_from_str(yaml).map_err(|e| e.to_string())?;
-    if docs.len() != 1 {
-        return Err("Expected exactly one YAML document".into());
+fn yaml_to_i64<'input, 'shape>(
+    yaml: &'input str,
+    wip: &Partial<'_, 'shape>,
+    node: &MarkedYaml,
+) -> Result<i64, YamlDeError<'input, 'shape>> {
+    match &node.data {
+        YamlData::Integer(i) => Ok(*i),
+        YamlData::Real(r) => r
+            .parse::<i64>()
+            .map_err(|_| invalid_value(yaml, wip, node, "failed to parse real as i64")),
+        YamlData::String(s) => s
+            .parse::<i64>()
+            .map_err(|_| invalid_value(yaml, wip, node, "failed to parse string as i64")),
+        YamlData::Boolean(b) => Ok(if *b { 1 } else { 0 }),
+        _ => Err(type_mismatch(yaml, wip, node)),
+    }
+}
+
+fn yaml_to_f64<'input, 'shape>(
+    yaml: &'input str,
+    wip: &Partial<'_, 'shape>,
+    node: &MarkedYaml,
+) -> Result<f64, YamlDeError<'input, 'shape>> {
+    match &node.data {
+        YamlData::Real(r) => r
+            .parse::<f64>()
+            .map_err(|_| invalid_value(yaml, wip, node, "failed to parse real as f64")),
+        YamlData::Integer(i) => Ok(*i as f64),
+        YamlData::String(s) => s
+            .parse::<f64>()
+            .map_err(|_| invalid_value(yaml, wip, node, "failed to parse string as f64")),
+        _ => Err(type_mismatch(yaml, wip, node)),
     }
-    deserialize_value(wip, &docs[0])?;
+}
+
+/// Sets an integer, after checking it fits in the target type.
+fn set_in_range<'input, 'facet, 'shape, T, V>(
+    yaml: &'input str,
+    wip: &mut Partial<'facet, 'shape>,
+    node: &MarkedYaml,
+    value: V,
+) -> Result<(), YamlDeError<'input, 'shape>>
+where
+    T: TryFrom<V> + Facet<'facet>,
+    V: Display + Copy,
+{
+    let value = T::try_from(value)
+        .map_err(|_| invalid_value(yaml, wip, node, format!("{value} is out of range")))?;
+    reflect!(yaml, wip, node, set(value));
     Ok(())
 }
 
-fn deserialize_value<'facet, 'shape>(
+fn deserialize_value<'input, 'facet, 'shape>(
+    yaml: &'input str,
     wip: &mut Partial<'facet, 'shape>,
-    value: &Yaml,
-) -> Result<(), AnyErr> {
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
     // Get the shape
     let shape = wip.shape();
     let innermost_shape = wip.innermost_shape();
@@ -94,76 +298,19 @@ fn deserialize_value<'facet, 'shape>(
         log::debug!("Handling facet(transparent) type");
 
         // For transparent types, push inner and deserialize as inner type
-        wip.begin_inner().map_err(|e| AnyErr(e.to_string()))?;
-        deserialize_value(wip, value)?;
-        wip.end().map_err(|e| AnyErr(e.to_string()))?;
+        reflect!(yaml, wip, value, begin_inner());
+        deserialize_value(yaml, wip, value)?;
+        reflect!(yaml, wip, value, end());
         return Ok(());
     }
 
     // First check the type system (Type)
     if let Type::User(UserType::Struct(sd)) = &shape.ty {
-        if let Yaml::Hash(hash) = value {
-            // Process all fields in the YAML map
-            for (k, v) in hash {
-                let k = k
-                    .as_str()
-                    .ok_or_else(|| AnyErr(format!("Expected string key, got: {}", yaml_type(k))))?;
-                let field_index = wip
-                    .field_index(k)
-                    .ok_or_else(|| AnyErr(format!("Field '{}' not found", k)))?;
-
-                #[cfg(feature = "log")]
-                log::debug!("Processing struct field '{}' (index: {})", k, field_index);
-
-                wip.begin_nth_field(field_index)
-                    .map_err(|e| AnyErr(format!("Field '{}' error: {}", k, e)))?;
-                deserialize_value(wip, v)?;
-                wip.end().map_err(|e| AnyErr(e.to_string()))?;
-            }
-
-            // Process any unset fields with defaults
-            for (index, field) in sd.fields.iter().enumerate() {
-                let is_set = wip.is_field_set(index).map_err(|e| AnyErr(e.to_string()))?;
-                if !is_set {
-                    // If field has default attribute, apply it
-                    if field.flags.contains(FieldFlags::DEFAULT) {
-                        #[cfg(feature = "log")]
-                        log::debug!("Setting default for field: {}", field.name);
-
-                        wip.begin_nth_field(index)
-                            .map_err(|e| AnyErr(e.to_string()))?;
-
-                        // Check for field-level default function first, then type-level default
-                        if let Some(field_default_fn) = field.vtable.default_fn {
-                            #[cfg(feature = "log")]
-                            log::debug!("Using field default function for field: {}", field.name);
-
-                            wip.set_field_default(field_default_fn)
-                                .map_err(|e| AnyErr(e.to_string()))?;
-                        } else if field.shape().is(Characteristic::Default) {
-                            #[cfg(feature = "log")]
-                            log::debug!("Using type Default for field: {}", field.name);
-
-                            wip.set_default().map_err(|e| AnyErr(e.to_string()))?;
-                        } else {
-                            return Err(AnyErr(format!(
-                                "Field '{}' has default attribute but its type doesn't implement Default and no default function provided",
-                                field.name
-                            )));
-                        }
-
-                        wip.end().map_err(|e| AnyErr(e.to_string()))?;
-                    }
-                }
-            }
+        return deserialize_fields(yaml, wip, sd.fields, value);
+    }
 
-            // Handle struct-level defaults using the safe API from facet-reflect
-            wip.fill_unset_fields_from_default()
-                .map_err(|e| AnyErr(e.to_string()))?;
-        } else {
-            return Err(AnyErr(format!("Expected a YAML hash, got: {:?}", value)));
-        }
-        return Ok(());
+    if let Type::User(UserType::Enum(_)) = &shape.ty {
+        return deserialize_enum(yaml, wip, value);
     }
 
     // Then check the def system (Def) using innermost_shape instead of shape
@@ -182,132 +329,48 @@ fn deserialize_value<'facet, 'shape>(
                 match num_affinity.bits {
                     NumberBits::Integer { size, sign } => match (size, sign) {
                         (IntegerSize::Fixed(bits), Signedness::Unsigned) => {
-                            let u = yaml_to_u64(value)?;
+                            let u = yaml_to_u64(yaml, wip, value)?;
                             match bits {
-                                8 => {
-                                    let val = u8::try_from(u).map_err(|_| {
-                                        AnyErr(format!("Value {} out of range for u8", u))
-                                    })?;
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                16 => {
-                                    let val = u16::try_from(u).map_err(|_| {
-                                        AnyErr(format!("Value {} out of range for u16", u))
-                                    })?;
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                32 => {
-                                    let val = u32::try_from(u).map_err(|_| {
-                                        AnyErr(format!("Value {} out of range for u32", u))
-                                    })?;
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                64 => {
-                                    wip.set(u).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                128 => {
-                                    let val = u128::from(u);
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
+                                8 => set_in_range::<u8, _>(yaml, wip, value, u)?,
+                                16 => set_in_range::<u16, _>(yaml, wip, value, u)?,
+                                32 => set_in_range::<u32, _>(yaml, wip, value, u)?,
+                                64 => set_in_range::<u64, _>(yaml, wip, value, u)?,
+                                128 => set_in_range::<u128, _>(yaml, wip, value, u)?,
                                 _ => {
-                                    return Err(AnyErr(format!(
-                                        "Unsupported fixed unsigned integer size: {}",
-                                        bits
-                                    )));
+                                    return Err(error(
+                                        yaml,
+                                        wip,
+                                        value,
+                                        YamlDeErrorKind::Unsupported(shape),
+                                    ));
                                 }
                             }
                         }
                         (IntegerSize::PointerSized, Signedness::Unsigned) => {
-                            let u = yaml_to_u64(value)?;
-                            let val = usize::try_from(u).map_err(|_| {
-                                AnyErr(format!("Value {} out of range for usize", u))
-                            })?;
-                            wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
+                            let u = yaml_to_u64(yaml, wip, value)?;
+                            set_in_range::<usize, _>(yaml, wip, value, u)?;
                         }
                         (IntegerSize::Fixed(bits), Signedness::Signed) => {
-                            let i = match value {
-                                Yaml::Integer(i) => *i,
-                                Yaml::Real(r) => r
-                                    .parse::<i64>()
-                                    .map_err(|_| AnyErr("Failed to parse real as i64".into()))?,
-                                Yaml::String(s) => s
-                                    .parse::<i64>()
-                                    .map_err(|_| AnyErr("Failed to parse string as i64".into()))?,
-                                Yaml::Boolean(b) => {
-                                    if *b {
-                                        1
-                                    } else {
-                                        0
-                                    }
-                                }
-                                _ => {
-                                    return Err(AnyErr(format!(
-                                        "Cannot convert {} to i64",
-                                        yaml_type(value)
-                                    )));
-                                }
-                            };
+                            let i = yaml_to_i64(yaml, wip, value)?;
                             match bits {
-                                8 => {
-                                    let val = i8::try_from(i).map_err(|_| {
-                                        AnyErr(format!("Value {} out of range for i8", i))
-                                    })?;
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                16 => {
-                                    let val = i16::try_from(i).map_err(|_| {
-                                        AnyErr(format!("Value {} out of range for i16", i))
-                                    })?;
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                32 => {
-                                    let val = i32::try_from(i).map_err(|_| {
-                                        AnyErr(format!("Value {} out of range for i32", i))
-                                    })?;
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                64 => {
-                                    wip.set(i).map_err(|e| AnyErr(e.to_string()))?;
-                                }
-                                128 => {
-                                    let val = i128::from(i);
-                                    wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
-                                }
+                                8 => set_in_range::<i8, _>(yaml, wip, value, i)?,
+                                16 => set_in_range::<i16, _>(yaml, wip, value, i)?,
+                                32 => set_in_range::<i32, _>(yaml, wip, value, i)?,
+                                64 => set_in_range::<i64, _>(yaml, wip, value, i)?,
+                                128 => set_in_range::<i128, _>(yaml, wip, value, i)?,
                                 _ => {
-                                    return Err(AnyErr(format!(
-                                        "Unsupported fixed signed integer size: {}",
-                                        bits
-                                    )));
+                                    return Err(error(
+                                        yaml,
+                                        wip,
+                                        value,
+                                        YamlDeErrorKind::Unsupported(shape),
+                                    ));
                                 }
                             }
                         }
                         (IntegerSize::PointerSized, Signedness::Signed) => {
-                            let i = match value {
-                                Yaml::Integer(i) => *i,
-                                Yaml::Real(r) => r
-                                    .parse::<i64>()
-                                    .map_err(|_| AnyErr("Failed to parse real as i64".into()))?,
-                                Yaml::String(s) => s
-                                    .parse::<i64>()
-                                    .map_err(|_| AnyErr("Failed to parse string as i64".into()))?,
-                                Yaml::Boolean(b) => {
-                                    if *b {
-                                        1
-                                    } else {
-                                        0
-                                    }
-                                }
-                                _ => {
-                                    return Err(AnyErr(format!(
-                                        "Cannot convert {} to i64",
-                                        yaml_type(value)
-                                    )));
-                                }
-                            };
-                            let val = isize::try_from(i).map_err(|_| {
-                                AnyErr(format!("Value {} out of range for isize", i))
-                            })?;
-                            wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
+                            let i = yaml_to_i64(yaml, wip, value)?;
+                            set_in_range::<isize, _>(yaml, wip, value, i)?;
                         }
                     },
                     NumberBits::Float {
@@ -317,73 +380,44 @@ fn deserialize_value<'facet, 'shape>(
                         has_explicit_first_mantissa_bit: _,
                     } => {
                         // Handle floating point numbers
-                        let f = match value {
-                            Yaml::Real(r) => r
-                                .parse::<f64>()
-                                .map_err(|_| AnyErr("Failed to parse real as f64".into()))?,
-                            Yaml::Integer(i) => *i as f64,
-                            Yaml::String(s) => s
-                                .parse::<f64>()
-                                .map_err(|_| AnyErr("Failed to parse string as f64".into()))?,
-                            _ => {
-                                return Err(AnyErr(format!(
-                                    "Cannot convert {} to f64",
-                                    yaml_type(value)
-                                )));
-                            }
-                        };
+                        let f = yaml_to_f64(yaml, wip, value)?;
                         // Determine float type based on mantissa bits (f32 has 23, f64 has 52)
                         if mantissa_bits <= 23 {
                             let val = f as f32;
-                            wip.set(val).map_err(|e| AnyErr(e.to_string()))?;
+                            reflect!(yaml, wip, value, set(val));
                         } else {
-                            wip.set(f).map_err(|e| AnyErr(e.to_string()))?;
+                            reflect!(yaml, wip, value, set(f));
                         }
                     }
-                    NumberBits::Fixed { .. } | NumberBits::Decimal { .. } => {
-                        return Err(AnyErr(
-                            "Fixed and decimal number types not supported in YAML deserializer"
-                                .into(),
-                        ));
-                    }
                     _ => {
-                        return Err(AnyErr(
-                            "Unsupported number type in YAML deserializer".into(),
-                        ));
+                        // Fixed and decimal number types aren't supported
+                        return Err(error(yaml, wip, value, YamlDeErrorKind::Unsupported(shape)));
                     }
                 }
             } else if innermost_shape.is_type::<bool>() {
                 // Handle boolean values
-                let b = match value {
-                    Yaml::Boolean(b) => *b,
-                    Yaml::Integer(i) => *i != 0,
-                    Yaml::String(s) => {
+                let b = match &value.data {
+                    YamlData::Boolean(b) => *b,
+                    YamlData::Integer(i) => *i != 0,
+                    YamlData::String(s) => {
                         let s = s.to_lowercase();
                         s == "true" || s == "yes" || s == "1"
                     }
-                    _ => {
-                        return Err(AnyErr(format!(
-                            "Cannot convert {} to bool",
-                            yaml_type(value)
-                        )));
-                    }
+                    _ => return Err(type_mismatch(yaml, wip, value)),
                 };
-                wip.set(b).map_err(|e| AnyErr(e.to_string()))?;
+                reflect!(yaml, wip, value, set(b));
             } else if innermost_shape.is_type::<String>() {
                 // For strings, set directly
-                let s = value
-                    .as_str()
-                    .ok_or_else(|| AnyErr(format!("Expected string, got: {}", yaml_type(value))))?
+                let s = as_str(value)
+                    .ok_or_else(|| type_mismatch(yaml, wip, value))?
                     .to_string();
-                wip.set(s).map_err(|e| AnyErr(e.to_string()))?;
+                reflect!(yaml, wip, value, set(s));
             } else {
                 // Try parse_from_str first for any scalar type that supports it
-                let s = value
-                    .as_str()
-                    .ok_or_else(|| AnyErr(format!("Expected string, got: {}", yaml_type(value))))?;
+                let s = as_str(value).ok_or_else(|| type_mismatch(yaml, wip, value))?;
                 if wip.parse_from_str(s).is_err() {
                     // If parsing fails, fall back to setting as String
-                    wip.set(s.to_string()).map_err(|e| AnyErr(e.to_string()))?;
+                    reflect!(yaml, wip, value, set(s.to_string()));
                 }
             }
         }
@@ -391,107 +425,389 @@ fn deserialize_value<'facet, 'shape>(
             #[cfg(feature = "log")]
             log::debug!("Processing list type");
 
-            deserialize_as_list(wip, value)?;
+            deserialize_as_list(yaml, wip, value)?;
         }
         Def::Map(_) => {
             #[cfg(feature = "log")]
             log::debug!("Processing map type");
 
-            deserialize_as_map(wip, value)?;
+            deserialize_as_map(yaml, wip, value)?;
+        }
+        Def::SmartPointer(_) => {
+            #[cfg(feature = "log")]
+            log::debug!("Processing smart pointer type");
+
+            reflect!(yaml, wip, value, begin_smart_ptr());
+            deserialize_value(yaml, wip, value)?;
+            reflect!(yaml, wip, value, end());
         }
         Def::Option(_) => {
             #[cfg(feature = "log")]
             log::debug!("Processing option type");
 
             // Handle Option<T>
-            if let Yaml::Null = value {
-                // Null maps to None - already handled by default
+            if let YamlData::Null = value.data {
+                // Null maps to None
+                reflect!(yaml, wip, value, set_default());
             } else {
                 // Non-null maps to Some(value)
-                wip.begin_some().map_err(|e| AnyErr(e.to_string()))?;
-                deserialize_value(wip, value)?;
-                wip.end().map_err(|e| AnyErr(e.to_string()))?;
+                reflect!(yaml, wip, value, begin_some());
+                deserialize_value(yaml, wip, value)?;
+                reflect!(yaml, wip, value, end());
             }
         }
+        Def::Result(_) => {
+            #[cfg(feature = "log")]
+            log::debug!("Processing result type");
+
+            deserialize_as_result(yaml, wip, value)?;
+        }
         // Enum has been moved to Type system
-        _ => return Err(AnyErr(format!("Unsupported type: {:?}", shape))),
+        _ => {
+            return Err(error(yaml, wip, value, YamlDeErrorKind::Unsupported(shape)));
+        }
     }
     Ok(())
 }
 
-fn deserialize_as_list<'facet, 'shape>(
+/// Deserializes a YAML hash into the fields of a struct, or of a struct variant.
+fn deserialize_fields<'input, 'facet, 'shape>(
+    yaml: &'input str,
     wip: &mut Partial<'facet, 'shape>,
-    value: &Yaml,
-) -> Result<(), AnyErr> {
+    fields: &'shape [Field<'shape>],
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
+    let shape = wip.shape();
+    if !matches!(value.data, YamlData::Hash(_)) {
+        return Err(type_mismatch(yaml, wip, value));
+    }
+
+    // Process all fields in the YAML map
+    for (k, v) in hash_entries(value) {
+        let key = key_str(yaml, wip, k)?;
+        let Some(field_index) = wip.field_index(key) else {
+            let kind = YamlDeErrorKind::UnknownField {
+                field_name: key.to_string(),
+                shape,
+            };
+            return Err(error(yaml, wip, k, kind));
+        };
+
+        #[cfg(feature = "log")]
+        log::debug!("Processing field '{}' (index: {})", key, field_index);
+
+        reflect!(yaml, wip, k, begin_nth_field(field_index));
+        deserialize_value(yaml, wip, v)?;
+        reflect!(yaml, wip, v, end());
+    }
+
+    // Absent fields fall back to their default, if they have one
+    for (index, field) in fields.iter().enumerate() {
+        if reflect!(yaml, wip, value, is_field_set(index))
+            || !field.flags.contains(FieldFlags::DEFAULT)
+        {
+            continue;
+        }
+
+        #[cfg(feature = "log")]
+        log::debug!("Setting default for field: {}", field.name);
+
+        reflect!(yaml, wip, value, begin_nth_field(index));
+        // Check for field-level default function first, then type-level default
+        if let Some(field_default_fn) = field.vtable.default_fn {
+            reflect!(yaml, wip, value, set_field_default(field_default_fn));
+        } else {
+            reflect!(yaml, wip, value, set_default());
+        }
+        reflect!(yaml, wip, value, end());
+    }
+
+    // Handle struct-level defaults using the safe API from facet-reflect
+    reflect!(yaml, wip, value, fill_unset_fields_from_default());
+
+    // Whatever is left was required
+    for (index, field) in fields.iter().enumerate() {
+        if !reflect!(yaml, wip, value, is_field_set(index)) {
+            let kind = YamlDeErrorKind::MissingField {
+                field_name: field.name,
+                shape,
+            };
+            return Err(error(yaml, wip, value, kind));
+        }
+    }
+    Ok(())
+}
+
+fn deserialize_as_list<'input, 'facet, 'shape>(
+    yaml: &'input str,
+    wip: &mut Partial<'facet, 'shape>,
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
     #[cfg(feature = "log")]
     log::debug!("deserialize_as_list: shape={}", wip.shape());
 
-    if let Yaml::Array(array) = value {
-        // Start the list
-        wip.begin_list().map_err(|e| AnyErr(e.to_string()))?;
+    let YamlData::Array(array) = &value.data else {
+        return Err(type_mismatch(yaml, wip, value));
+    };
 
-        // Handle empty list - just return without adding items
-        if array.is_empty() {
-            return Ok(());
+    // Start the list
+    reflect!(yaml, wip, value, begin_list());
+
+    // Process each element
+    for element in array.iter() {
+        #[cfg(feature = "log")]
+        log::debug!("Processing list element: {:?}", element);
+
+        // Push element
+        reflect!(yaml, wip, element, begin_list_item());
+        deserialize_value(yaml, wip, element)?;
+        reflect!(yaml, wip, element, end());
+    }
+
+    Ok(())
+}
+
+fn deserialize_as_map<'input, 'facet, 'shape>(
+    yaml: &'input str,
+    wip: &mut Partial<'facet, 'shape>,
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
+    if !matches!(value.data, YamlData::Hash(_)) {
+        return Err(type_mismatch(yaml, wip, value));
+    }
+
+    // Start the map
+    reflect!(yaml, wip, value, begin_map());
+
+    // Process each key-value pair
+    for (k, v) in hash_entries(value) {
+        // Get the key as a string
+        let key = key_str(yaml, wip, k)?;
+
+        // Push map key
+        reflect!(yaml, wip, k, begin_key());
+        reflect!(yaml, wip, k, set(key.to_string()));
+        reflect!(yaml, wip, k, end());
+
+        // Push map value
+        reflect!(yaml, wip, v, begin_value());
+        deserialize_value(yaml, wip, v)?;
+        reflect!(yaml, wip, v, end());
+    }
+
+    Ok(())
+}
+
+/// Results are represented like enum variants, by a hash with an `Ok` or an `Err` key.
+fn deserialize_as_result<'input, 'facet, 'shape>(
+    yaml: &'input str,
+    wip: &mut Partial<'facet, 'shape>,
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
+    let YamlData::Hash(hash) = &value.data else {
+        return Err(type_mismatch(yaml, wip, value));
+    };
+    if hash.len() != 1 {
+        return Err(type_mismatch(yaml, wip, value));
+    }
+
+    let (k, v) = hash.iter().next().unwrap();
+    match key_str(yaml, wip, k)? {
+        "Ok" => reflect!(yaml, wip, k, begin_ok()),
+        "Err" => reflect!(yaml, wip, k, begin_err()),
+        name => {
+            let kind = YamlDeErrorKind::NoSuchVariant {
+                name: name.to_string(),
+                enum_shape: wip.shape(),
+            };
+            return Err(error(yaml, wip, k, kind));
         }
+    };
+    deserialize_value(yaml, wip, v)?;
+    reflect!(yaml, wip, v, end());
+    Ok(())
+}
 
-        // Process each element
-        for element in array.iter() {
-            #[cfg(feature = "log")]
-            log::debug!("Processing list element: {:?}", element);
+fn deserialize_enum<'input, 'facet, 'shape>(
+    yaml: &'input str,
+    wip: &mut Partial<'facet, 'shape>,
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
+    let shape = wip.shape();
+
+    #[cfg(feature = "log")]
+    log::debug!("deserialize_enum: shape={}", shape);
+
+    if shape.has_untagged_attr() {
+        return deserialize_untagged_enum(yaml, wip, value);
+    }
 
-            // Push element
-            wip.begin_list_item().map_err(|e| AnyErr(e.to_string()))?;
-            deserialize_value(wip, element)?;
-            wip.end().map_err(|e| AnyErr(e.to_string()))?;
+    if let Some(tag) = shape.get_tag_attr() {
+        let YamlData::Hash(hash) = &value.data else {
+            return Err(type_mismatch(yaml, wip, value));
+        };
+        let missing = |wip: &Partial<'facet, 'shape>, field_name| {
+            let kind = YamlDeErrorKind::MissingField { field_name, shape };
+            error(yaml, wip, value, kind)
+        };
+        let (_, tag_value) = hash_entry(value, tag).ok_or_else(|| missing(wip, tag))?;
+        let variant_name = as_str(tag_value).ok_or_else(|| type_mismatch(yaml, wip, tag_value))?;
+        let variant = select_variant(yaml, wip, variant_name, tag_value)?;
+        if variant.data.fields.is_empty() {
+            return Ok(());
         }
 
-        Ok(())
-    } else {
-        Err(AnyErr(format!(
-            "Expected a YAML array, got: {}",
-            yaml_type(value)
-        )))
+        return if let Some(content) = shape.get_content_attr() {
+            // Adjacently tagged: the data lives under the content key
+            let (_, content_value) =
+                hash_entry(value, content).ok_or_else(|| missing(wip, content))?;
+            deserialize_variant_data(yaml, wip, variant, content_value)
+        } else {
+            // Internally tagged: the data is the hash itself, minus the tag
+            let data = hash
+                .iter()
+                .filter(|(k, _)| as_str(k) != Some(tag))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            let data = MarkedYaml {
+                span: value.span,
+                data: YamlData::Hash(data),
+            };
+            deserialize_variant_data(yaml, wip, variant, &data)
+        };
+    }
+
+    match &value.data {
+        // Unit variants are represented by their name
+        YamlData::String(name) => {
+            let variant = select_variant(yaml, wip, name, value)?;
+            if !variant.data.fields.is_empty() {
+                return Err(invalid_value(
+                    yaml,
+                    wip,
+                    value,
+                    format!("variant '{name}' is not a unit variant"),
+                ));
+            }
+            Ok(())
+        }
+        // Other variants are represented by a hash with a single key
+        YamlData::Hash(hash) if hash.len() == 1 => {
+            let (k, v) = hash.iter().next().unwrap();
+            let name = key_str(yaml, wip, k)?;
+            let variant = select_variant(yaml, wip, name, k)?;
+            deserialize_variant_data(yaml, wip, variant, v)
+        }
+        _ => Err(type_mismatch(yaml, wip, value)),
     }
 }
 
-fn deserialize_as_map<'facet, 'shape>(
+fn deserialize_untagged_enum<'input, 'facet, 'shape>(
+    yaml: &'input str,
     wip: &mut Partial<'facet, 'shape>,
-    value: &Yaml,
-) -> Result<(), AnyErr> {
-    if let Yaml::Hash(hash) = value {
-        // Start the map
-        wip.begin_map().map_err(|e| AnyErr(e.to_string()))?;
-
-        // Handle empty map
-        if hash.is_empty() {
-            return Ok(());
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
+    let shape = wip.shape();
+    let Type::User(UserType::Enum(ed)) = shape.ty else {
+        return Err(error(yaml, wip, value, YamlDeErrorKind::Unsupported(shape)));
+    };
+
+    for (index, variant) in ed.variants.iter().enumerate() {
+        #[cfg(feature = "log")]
+        log::debug!("Trying variant {}::{}", shape, variant.name);
+
+        if variant.data.fields.is_empty() {
+            // Unit variants are represented as null
+            if let YamlData::Null = value.data {
+                reflect!(yaml, wip, value, select_nth_variant(index));
+                return Ok(());
+            }
+            continue;
         }
 
-        // Process each key-value pair
-        for (k, v) in hash {
-            // Get the key as a string
-            let key_str = k
-                .as_str()
-                .ok_or_else(|| AnyErr(format!("Expected string key, got: {}", yaml_type(k))))?;
-
-            // Push map key
-            wip.begin_key().map_err(|e| AnyErr(e.to_string()))?;
-            wip.set(key_str.to_string())
-                .map_err(|e| AnyErr(e.to_string()))?;
-            wip.end().map_err(|e| AnyErr(e.to_string()))?;
-
-            // Push map value
-            wip.begin_value().map_err(|e| AnyErr(e.to_string()))?;
-            deserialize_value(wip, v)?;
-            wip.end().map_err(|e| AnyErr(e.to_string()))?;
+        let Ok(mut attempt) = Partial::alloc_shape(shape) else {
+            continue;
+        };
+        if attempt.select_nth_variant(index).is_err()
+            || deserialize_variant_data(yaml, &mut attempt, variant, value).is_err()
+        {
+            continue;
+        }
+        if let Ok(heap_value) = attempt.build() {
+            reflect!(yaml, wip, value, set_from_heap_value(heap_value));
+            return Ok(());
         }
+    }
 
-        Ok(())
-    } else {
-        Err(AnyErr(format!(
-            "Expected a YAML hash/map, got: {}",
-            yaml_type(value)
-        )))
+    Err(error(
+        yaml,
+        wip,
+        value,
+        YamlDeErrorKind::NoMatchingVariant(shape),
+    ))
+}
+
+fn select_variant<'input, 'facet, 'shape>(
+    yaml: &'input str,
+    wip: &mut Partial<'facet, 'shape>,
+    name: &str,
+    node: &MarkedYaml,
+) -> Result<&'shape Variant<'shape>, YamlDeError<'input, 'shape>> {
+    let Some((index, variant)) = wip.find_variant(name) else {
+        let kind = YamlDeErrorKind::NoSuchVariant {
+            name: name.to_string(),
+            enum_shape: wip.shape(),
+        };
+        return Err(error(yaml, wip, node, kind));
+    };
+    reflect!(yaml, wip, node, select_nth_variant(index));
+    Ok(variant)
+}
+
+fn deserialize_variant_data<'input, 'facet, 'shape>(
+    yaml: &'input str,
+    wip: &mut Partial<'facet, 'shape>,
+    variant: &'shape Variant<'shape>,
+    value: &MarkedYaml,
+) -> Result<(), YamlDeError<'input, 'shape>> {
+    match variant.data.kind {
+        StructKind::Unit => Ok(()),
+        StructKind::Tuple | StructKind::TupleStruct if variant.data.fields.len() == 1 => {
+            reflect!(yaml, wip, value, begin_nth_field(0));
+            deserialize_value(yaml, wip, value)?;
+            reflect!(yaml, wip, value, end());
+            Ok(())
+        }
+        StructKind::Tuple | StructKind::TupleStruct => {
+            let YamlData::Array(array) = &value.data else {
+                return Err(type_mismatch(yaml, wip, value));
+            };
+            if array.len() != variant.data.fields.len() {
+                return Err(invalid_value(
+                    yaml,
+                    wip,
+                    value,
+                    format!(
+                        "expected {} elements for tuple variant '{}', got {}",
+                        variant.data.fields.len(),
+                        variant.name,
+                        array.len()
+                    ),
+                ));
+            }
+            for (index, element) in array.iter().enumerate() {
+                reflect!(yaml, wip, element, begin_nth_field(index));
+                deserialize_value(yaml, wip, element)?;
+                reflect!(yaml, wip, element, end());
+            }
+            Ok(())
+        }
+        StructKind::Struct => deserialize_fields(yaml, wip, variant.data.fields, value),
+        _ => Err(error(
+            yaml,
+            wip,
+            value,
+            YamlDeErrorKind::Unsupported(wip.shape()),
+        )),
     }
 }
@@ -0,0 +1,289 @@
+//! YAML documents as trees of nodes that remember where they are in the source.
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{hash::Hasher, mem};
+use hashbrown::HashSet;
+use yaml_rust2::{
+    Event, Yaml,
+    parser::{MarkedEventReceiver, Tag},
+    scanner::{Marker, ScanError, TScalarStyle},
+};
+
+/// A part of the YAML source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    /// Where the part starts.
+    pub start: Marker,
+    /// Where the part ends. Scalars only know where they start, so this is the same as `start`
+    /// for them.
+    pub end: Marker,
+}
+
+impl Span {
+    /// Create a new span.
+    pub fn new(start: Marker, end: Marker) -> Self {
+        Self { start, end }
+    }
+}
+
+/// A YAML node and where it is in the source.
+#[derive(Clone, Debug)]
+pub(crate) struct MarkedYaml {
+    pub(crate) span: Span,
+    pub(crate) data: YamlData,
+}
+
+/// Nodes are equal when their data is, wherever they are.
+impl PartialEq for MarkedYaml {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl Eq for MarkedYaml {}
+
+impl core::hash::Hash for MarkedYaml {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl MarkedYaml {
+    /// How many nodes this one is made of, itself included.
+    fn node_count(&self) -> usize {
+        match &self.data {
+            YamlData::Array(array) => 1 + array.iter().map(Self::node_count).sum::<usize>(),
+            YamlData::Hash(hash) => {
+                1 + hash
+                    .iter()
+                    .map(|(key, value)| key.node_count() + value.node_count())
+                    .sum::<usize>()
+            }
+            _ => 1,
+        }
+    }
+}
+
+/// The data of a YAML node, like [`Yaml`] but with marked children.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum YamlData {
+    /// Kept as a string, like `yaml-rust2` does, so it can be parsed with the precision needed.
+    Real(String),
+    Integer(i64),
+    String(String),
+    Boolean(bool),
+    Array(Vec<MarkedYaml>),
+    Hash(Hash),
+    Null,
+    /// A scalar that doesn't match its tag, or an empty document.
+    BadValue,
+}
+
+impl From<Yaml> for YamlData {
+    fn from(yaml: Yaml) -> Self {
+        match yaml {
+            Yaml::Real(r) => YamlData::Real(r),
+            Yaml::Integer(i) => YamlData::Integer(i),
+            Yaml::String(s) => YamlData::String(s),
+            Yaml::Boolean(b) => YamlData::Boolean(b),
+            Yaml::Null => YamlData::Null,
+            // Scalars never load as collections or aliases
+            _ => YamlData::BadValue,
+        }
+    }
+}
+
+/// The entries of a YAML hash, in the order they appear in the source.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Hash(Vec<(MarkedYaml, MarkedYaml)>);
+
+impl Hash {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&MarkedYaml, &MarkedYaml)> {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl FromIterator<(MarkedYaml, MarkedYaml)> for Hash {
+    fn from_iter<I: IntoIterator<Item = (MarkedYaml, MarkedYaml)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// How many nodes aliases can expand to in a stream, so that nested aliases can't make memory
+/// grow exponentially ("billion laughs").
+const MAX_ALIAS_NODES: usize = 1 << 20;
+
+/// Builds [`MarkedYaml`] documents from parser events, the way `yaml_rust2::YamlLoader` builds
+/// [`Yaml`] ones. Aliases are replaced by the nodes they refer to.
+#[derive(Default)]
+pub(crate) struct MarkedYamlLoader {
+    docs: Vec<MarkedYaml>,
+    /// Collections being built, with their anchor ids
+    doc_stack: Vec<(MarkedYaml, usize)>,
+    /// Hashes being built, with the key waiting for its value and the keys already used
+    key_stack: Vec<(Option<MarkedYaml>, HashSet<MarkedYaml>)>,
+    /// Anchored nodes, with how many nodes they're made of
+    anchor_map: BTreeMap<usize, (MarkedYaml, usize)>,
+    /// How many nodes aliases expanded to so far
+    alias_nodes: usize,
+    error: Option<ScanError>,
+}
+
+impl MarkedYamlLoader {
+    /// The loaded documents, or the first error found while loading them.
+    pub(crate) fn into_documents(self) -> Result<Vec<MarkedYaml>, ScanError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.docs),
+        }
+    }
+
+    fn on_event_impl(&mut self, ev: Event, mark: Marker) -> Result<(), ScanError> {
+        match ev {
+            Event::DocumentStart | Event::Nothing | Event::StreamStart | Event::StreamEnd => {}
+            Event::DocumentEnd => match self.doc_stack.pop() {
+                Some((doc, _)) => self.docs.push(doc),
+                // An empty document
+                None => self.docs.push(MarkedYaml {
+                    span: Span::new(mark, mark),
+                    data: YamlData::BadValue,
+                }),
+            },
+            Event::SequenceStart(aid, _) => {
+                let node = MarkedYaml {
+                    span: Span::new(mark, mark),
+                    data: YamlData::Array(Vec::new()),
+                };
+                self.doc_stack.push((node, aid));
+            }
+            Event::MappingStart(aid, _) => {
+                let node = MarkedYaml {
+                    span: Span::new(mark, mark),
+                    data: YamlData::Hash(Hash::default()),
+                };
+                self.doc_stack.push((node, aid));
+                self.key_stack.push((None, HashSet::new()));
+            }
+            Event::SequenceEnd => self.end_collection(mark)?,
+            Event::MappingEnd => {
+                self.key_stack.pop();
+                self.end_collection(mark)?;
+            }
+            Event::Scalar(v, style, aid, tag) => {
+                let data = if style != TScalarStyle::Plain {
+                    YamlData::String(v)
+                } else if let Some(Tag { handle, suffix }) = tag {
+                    if handle == "tag:yaml.org,2002:" {
+                        match suffix.as_str() {
+                            "bool" => match v.as_str() {
+                                "true" | "True" | "TRUE" => YamlData::Boolean(true),
+                                "false" | "False" | "FALSE" => YamlData::Boolean(false),
+                                _ => YamlData::BadValue,
+                            },
+                            "int" => match v.parse::<i64>() {
+                                Ok(i) => YamlData::Integer(i),
+                                Err(_) => YamlData::BadValue,
+                            },
+                            "float" => match Yaml::from_str(&v) {
+                                Yaml::Real(_) | Yaml::Integer(_) => YamlData::Real(v),
+                                _ => YamlData::BadValue,
+                            },
+                            "null" => match v.as_str() {
+                                "~" | "null" => YamlData::Null,
+                                _ => YamlData::BadValue,
+                            },
+                            _ => YamlData::String(v),
+                        }
+                    } else {
+                        YamlData::String(v)
+                    }
+                } else {
+                    Yaml::from_str(&v).into()
+                };
+                let node = MarkedYaml {
+                    span: Span::new(mark, mark),
+                    data,
+                };
+                self.insert_new_node(node, aid, mark)?;
+            }
+            Event::Alias(id) => {
+                let data = match self.anchor_map.get(&id) {
+                    Some((node, count)) => {
+                        self.alias_nodes += count;
+                        if self.alias_nodes > MAX_ALIAS_NODES {
+                            return Err(ScanError::new(mark, "aliases expand to too many nodes"));
+                        }
+                        node.data.clone()
+                    }
+                    None => YamlData::BadValue,
+                };
+                let node = MarkedYaml {
+                    span: Span::new(mark, mark),
+                    data,
+                };
+                self.insert_new_node(node, 0, mark)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end_collection(&mut self, mark: Marker) -> Result<(), ScanError> {
+        let (mut node, aid) = self.doc_stack.pop().unwrap();
+        node.span.end = mark;
+        self.insert_new_node(node, aid, mark)
+    }
+
+    fn insert_new_node(
+        &mut self,
+        node: MarkedYaml,
+        aid: usize,
+        mark: Marker,
+    ) -> Result<(), ScanError> {
+        // Valid anchor ids start from 1
+        if aid > 0 {
+            self.anchor_map
+                .insert(aid, (node.clone(), node.node_count()));
+        }
+        let Some((parent, _)) = self.doc_stack.last_mut() else {
+            self.doc_stack.push((node, aid));
+            return Ok(());
+        };
+        match &mut parent.data {
+            YamlData::Array(array) => array.push(node),
+            YamlData::Hash(hash) => {
+                let (key, used_keys) = self.key_stack.last_mut().unwrap();
+                match mem::take(key) {
+                    // The node is a key
+                    None => *key = Some(node),
+                    // The node is the value of the pending key
+                    Some(key) => {
+                        if !used_keys.insert(key.clone()) {
+                            return Err(ScanError::new_string(
+                                mark,
+                                alloc::format!("{:?}: duplicated key in mapping", key.data),
+                            ));
+                        }
+                        hash.0.push((key, node));
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
+
+impl MarkedEventReceiver for MarkedYamlLoader {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.on_event_impl(ev, mark) {
+            self.error = Some(e);
+        }
+    }
+}
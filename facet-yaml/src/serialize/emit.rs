@@ -0,0 +1,264 @@
+//! Write YAML documents with anchors and aliases, or with doc comments, which `YamlEmitter`
+//! doesn't support.
+
+use alloc::{collections::BTreeMap, format, string::String};
+
+use facet_core::{Def, Field, Shape, Type, UserType};
+use yaml_rust2::{
+    EmitError, Yaml, YamlEmitter,
+    yaml::{Array, Hash},
+};
+
+/// Anchor or alias written before a node.
+#[derive(Clone, Copy)]
+pub(crate) enum Marker {
+    /// The node is written with this anchor.
+    Anchor(usize),
+    /// The node is written as an alias to this anchor instead.
+    Alias(usize),
+}
+
+/// Write a document in the same layout as `YamlEmitter`.
+///
+/// Nodes of the document found in `markers` get an anchor, or are replaced by an alias. When
+/// the `shape` of the document is known, doc comments of structs and their fields are written
+/// as comments.
+pub(crate) fn dump<'shape>(
+    output: &mut String,
+    document: &Yaml,
+    shape: Option<&'shape Shape<'shape>>,
+    markers: &BTreeMap<*const Yaml, Marker>,
+) -> Result<(), EmitError> {
+    let mut emitter = Emitter {
+        output,
+        level: -1,
+        markers,
+    };
+    emitter.output.push_str("---");
+    if emitter.anchor(document).is_some() {
+        emitter.emit_val(document, shape)
+    } else {
+        emitter.output.push('\n');
+        emitter.emit_node(document, shape)
+    }
+}
+
+fn anchor_name(anchor: usize) -> String {
+    format!("a{}", anchor + 1)
+}
+
+/// Get the type behind options, smart pointers and transparent wrappers.
+fn skip_wrappers<'shape>(mut shape: &'shape Shape<'shape>) -> &'shape Shape<'shape> {
+    loop {
+        shape = match shape.def {
+            Def::Option(option_def) => option_def.t,
+            Def::SmartPointer(pointer_def) => match pointer_def.pointee() {
+                Some(pointee) => pointee,
+                None => return shape,
+            },
+            _ => match shape.inner {
+                Some(inner) => inner(),
+                None => return shape,
+            },
+        };
+    }
+}
+
+fn struct_fields<'shape>(shape: &'shape Shape<'shape>) -> &'shape [Field<'shape>] {
+    match shape.ty {
+        Type::User(UserType::Struct(struct_type)) => struct_type.fields,
+        _ => &[],
+    }
+}
+
+fn element_shape<'shape>(shape: &'shape Shape<'shape>) -> Option<&'shape Shape<'shape>> {
+    match shape.def {
+        Def::List(list_def) => Some(list_def.t()),
+        Def::Array(array_def) => Some(array_def.t()),
+        Def::Slice(slice_def) => Some(slice_def.t),
+        _ => None,
+    }
+}
+
+struct Emitter<'a> {
+    output: &'a mut String,
+    level: isize,
+    markers: &'a BTreeMap<*const Yaml, Marker>,
+}
+
+impl Emitter<'_> {
+    fn write_indent(&mut self) {
+        for _ in 0..self.level.max(0) {
+            self.output.push_str("  ");
+        }
+    }
+
+    fn marker(&self, node: &Yaml) -> Option<Marker> {
+        self.markers.get(&(node as *const Yaml)).copied()
+    }
+
+    fn anchor(&self, node: &Yaml) -> Option<usize> {
+        match self.marker(node)? {
+            Marker::Anchor(anchor) => Some(anchor),
+            Marker::Alias(_) => None,
+        }
+    }
+
+    fn write_anchor(&mut self, anchor: usize) {
+        self.output.push_str(" &");
+        self.output.push_str(&anchor_name(anchor));
+    }
+
+    /// Write doc lines as comments, each followed by the indentation of the next line.
+    fn write_doc(&mut self, doc: &[&str]) {
+        for line in doc {
+            // The space after `///` is kept in the line
+            self.output.push('#');
+            self.output.push_str(line);
+            self.output.push('\n');
+            self.write_indent();
+        }
+    }
+
+    fn emit_node<'shape>(
+        &mut self,
+        node: &Yaml,
+        shape: Option<&'shape Shape<'shape>>,
+    ) -> Result<(), EmitError> {
+        if let Some(Marker::Alias(anchor)) = self.marker(node) {
+            self.output.push('*');
+            self.output.push_str(&anchor_name(anchor));
+            return Ok(());
+        }
+        let shape = shape.map(skip_wrappers);
+        match node {
+            Yaml::Array(array) => self.emit_array(array, shape.and_then(element_shape)),
+            Yaml::Hash(hash) => self.emit_hash(hash, shape),
+            scalar => {
+                // Let `YamlEmitter` decide how to quote scalars
+                let mut text = String::new();
+                YamlEmitter::new(&mut text).dump(scalar)?;
+                self.output
+                    .push_str(text.trim_start_matches("---").trim_start());
+                Ok(())
+            }
+        }
+    }
+
+    fn emit_array<'shape>(
+        &mut self,
+        array: &Array,
+        shape: Option<&'shape Shape<'shape>>,
+    ) -> Result<(), EmitError> {
+        if array.is_empty() {
+            self.output.push_str("[]");
+            return Ok(());
+        }
+        self.level += 1;
+        for (index, item) in array.iter().enumerate() {
+            if index > 0 {
+                self.output.push('\n');
+                self.write_indent();
+            }
+            self.output.push('-');
+            self.emit_inline_val(item, shape)?;
+        }
+        self.level -= 1;
+        Ok(())
+    }
+
+    fn emit_hash<'shape>(
+        &mut self,
+        hash: &Hash,
+        shape: Option<&'shape Shape<'shape>>,
+    ) -> Result<(), EmitError> {
+        if hash.is_empty() {
+            self.output.push_str("{}");
+            return Ok(());
+        }
+        self.level += 1;
+
+        let fields = shape.map(struct_fields).unwrap_or_default();
+        let values_shape = match shape.map(|shape| shape.def) {
+            Some(Def::Map(map_def)) => Some(map_def.v()),
+            _ => None,
+        };
+        if let Some(shape) = shape {
+            self.write_doc(shape.doc);
+        }
+
+        for (index, (key, value)) in hash.iter().enumerate() {
+            if index > 0 {
+                self.output.push('\n');
+                self.write_indent();
+            }
+            let field = match key {
+                Yaml::String(key) => fields.iter().find(|field| field.name == key.as_str()),
+                _ => None,
+            };
+            let value_shape = match field {
+                Some(field) => {
+                    self.write_doc(field.doc);
+                    Some(field.shape)
+                }
+                None => values_shape,
+            };
+            self.emit_node(key, None)?;
+            self.output.push(':');
+            self.emit_val(value, value_shape)?;
+        }
+        self.level -= 1;
+        Ok(())
+    }
+
+    /// Write an array item, which starts on the line of its `-` like `YamlEmitter`'s compact
+    /// mode does.
+    fn emit_inline_val<'shape>(
+        &mut self,
+        value: &Yaml,
+        shape: Option<&'shape Shape<'shape>>,
+    ) -> Result<(), EmitError> {
+        if self.anchor(value).is_some() {
+            return self.emit_val(value, shape);
+        }
+        self.output.push(' ');
+        self.emit_node(value, shape)
+    }
+
+    /// Write a hash value, or an anchored value. Non-empty collections start on the next line,
+    /// unless they're aliases.
+    fn emit_val<'shape>(
+        &mut self,
+        value: &Yaml,
+        shape: Option<&'shape Shape<'shape>>,
+    ) -> Result<(), EmitError> {
+        match self.marker(value) {
+            Some(Marker::Anchor(anchor)) => self.write_anchor(anchor),
+            Some(Marker::Alias(_)) => {
+                self.output.push(' ');
+                return self.emit_node(value, shape);
+            }
+            None => {}
+        }
+        match value {
+            Yaml::Array(array) if !array.is_empty() => {
+                self.output.push('\n');
+                self.level += 1;
+                self.write_indent();
+                self.level -= 1;
+                self.emit_node(value, shape)
+            }
+            Yaml::Hash(hash) if !hash.is_empty() => {
+                self.output.push('\n');
+                self.level += 1;
+                self.write_indent();
+                self.level -= 1;
+                self.emit_node(value, shape)
+            }
+            _ => {
+                self.output.push(' ');
+                self.emit_node(value, shape)
+            }
+        }
+    }
+}
@@ -14,6 +14,13 @@ pub enum YamlSerError {
     },
     /// YAML doesn't support byte arrays.
     UnsupportedByteArray,
+    /// The value has a shape that can't be written as YAML.
+    UnsupportedShape {
+        /// The shape of the value.
+        shape: alloc::string::String,
+        /// Why it can't be serialized.
+        reason: alloc::string::String,
+    },
 }
 
 impl core::fmt::Display for YamlSerError {
@@ -28,6 +35,9 @@ impl core::fmt::Display for YamlSerError {
             Self::UnsupportedByteArray => {
                 write!(f, "YAML doesn't support byte arrays")
             }
+            Self::UnsupportedShape { shape, reason } => {
+                write!(f, "Cannot serialize {shape}: {reason}")
+            }
         }
     }
 }
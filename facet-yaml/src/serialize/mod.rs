@@ -3,17 +3,22 @@
 #[cfg(not(feature = "alloc"))]
 compile_error!("feature `alloc` is required");
 
+mod emit;
 mod error;
 
 use core::borrow::Borrow as _;
 
 use alloc::{
     borrow::Cow,
+    collections::BTreeMap,
     string::{String, ToString as _},
     vec::Vec,
 };
 
+use emit::Marker;
 pub use error::YamlSerError;
+use facet_core::Shape;
+use facet_reflect::ValueId;
 use facet_serialize::{Serialize, Serializer};
 use yaml_rust2::{
     Yaml, YamlEmitter,
@@ -28,6 +33,10 @@ pub struct YamlSerializer<'shape> {
     yaml: Yaml,
     /// What type the current item is.
     current: KeyOrValue,
+    /// Whether values shared through `Rc` or `Arc` are written once, then referred to by alias.
+    anchors: bool,
+    /// Shared values written so far, and where.
+    shared: Vec<Shared<'shape>>,
 }
 
 impl<'shape> YamlSerializer<'shape> {
@@ -37,23 +46,86 @@ impl<'shape> YamlSerializer<'shape> {
             key_stack: Vec::new(),
             yaml: Yaml::BadValue,
             current: KeyOrValue::Value,
+            anchors: false,
+            shared: Vec::new(),
         }
     }
 
+    /// Write values shared through `Rc` or `Arc` once, with an anchor, and refer to them with
+    /// aliases afterwards. Values that only appear once don't get an anchor.
+    pub fn with_anchors(mut self) -> Self {
+        self.anchors = true;
+        self
+    }
+
     /// Get the output serialized YAML document.
+    ///
+    /// Shared values are written in full everywhere they appear, anchors and aliases only exist
+    /// in the output of [`Self::into_string`].
     pub fn into_raw_document(self) -> Yaml {
         self.yaml
     }
 
     /// Get the output serialized YAML string.
     pub fn into_string(self) -> String {
+        self.write(None)
+    }
+
+    /// Get the output serialized YAML string, with the doc comments of `shape` and its fields as
+    /// comments when it's given.
+    fn write<'doc>(self, shape: Option<&'doc Shape<'doc>>) -> String {
         let mut output = String::new();
-        let mut emitter = YamlEmitter::new(&mut output);
-        emitter.dump(&self.yaml).unwrap();
+        let markers = self.markers();
+        if markers.is_empty() && shape.is_none() {
+            let mut emitter = YamlEmitter::new(&mut output);
+            emitter.dump(&self.yaml).unwrap();
+        } else {
+            // `YamlEmitter` can't write anchors or comments
+            emit::dump(&mut output, &self.yaml, shape, &markers).unwrap();
+        }
 
         output
     }
 
+    /// Anchors and aliases of the shared values written more than once, by the node they mark.
+    ///
+    /// Anchors are numbered in the order their values first appear.
+    fn markers(&self) -> BTreeMap<*const Yaml, Marker> {
+        let mut markers = BTreeMap::new();
+        let repeated = self
+            .shared
+            .iter()
+            .filter(|shared| !shared.aliases.is_empty())
+            // A `None` in a hash isn't written at all, so there's nothing to anchor
+            .filter_map(|shared| Some((resolve(&self.yaml, &shared.path)?, shared)));
+        for (anchor, (node, shared)) in repeated.enumerate() {
+            markers.insert(node as *const Yaml, Marker::Anchor(anchor));
+            for alias in &shared.aliases {
+                if let Some(node) = resolve(&self.yaml, alias) {
+                    markers.insert(node as *const Yaml, Marker::Alias(anchor));
+                }
+            }
+        }
+
+        markers
+    }
+
+    /// Get the path to the value about to be written.
+    fn current_path(&mut self) -> Vec<PathSegment<'shape>> {
+        let mut path: Vec<_> = self
+            .key_stack
+            .iter()
+            .cloned()
+            .map(PathSegment::Key)
+            .collect();
+        // Values are pushed at the end of arrays
+        if let Some(array) = self.current_mut().as_mut_vec() {
+            path.push(PathSegment::Index(array.len()));
+        }
+
+        path
+    }
+
     /// Write a value depending on the context.
     fn write_value(&mut self, value: Yaml) -> Result<(), YamlSerError> {
         match self.current {
@@ -227,9 +299,9 @@ impl<'shape> Serializer<'shape> for YamlSerializer<'shape> {
     fn serialize_unit_variant(
         &mut self,
         _variant_index: usize,
-        _variant_name: &'shape str,
+        variant_name: &'shape str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        self.write_value(Yaml::String(variant_name.to_string()))
     }
 
     fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
@@ -250,6 +322,13 @@ impl<'shape> Serializer<'shape> for YamlSerializer<'shape> {
         Ok(())
     }
 
+    fn unsupported_shape(&mut self, shape: &'shape Shape<'shape>, reason: &str) -> Self::Error {
+        YamlSerError::UnsupportedShape {
+            shape: shape.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
     fn serialize_field_name(&mut self, name: &'shape str) -> Result<(), Self::Error> {
         self.push_key(Cow::Borrowed(name), "field");
 
@@ -279,6 +358,32 @@ impl<'shape> Serializer<'shape> for YamlSerializer<'shape> {
 
         Ok(())
     }
+
+    fn start_shared(&mut self, id: ValueId<'shape>) -> Result<bool, Self::Error> {
+        // Keys can't be anchored, they're plain strings
+        if !self.anchors || self.current == KeyOrValue::Key {
+            return Ok(true);
+        }
+
+        let path = self.current_path();
+        // Copy the value if it's been written already, the copy becomes an alias when emitted
+        if let Some(shared) = self.shared.iter_mut().find(|shared| shared.id == id) {
+            let value = resolve(&self.yaml, &shared.path)
+                .cloned()
+                .unwrap_or(Yaml::Null);
+            shared.aliases.push(path);
+            self.write_value(value)?;
+            return Ok(false);
+        }
+
+        self.shared.push(Shared {
+            id,
+            path,
+            aliases: Vec::new(),
+        });
+
+        Ok(true)
+    }
 }
 
 /// What type the current item is.
@@ -290,6 +395,29 @@ enum KeyOrValue {
     Value,
 }
 
+/// A value shared through `Rc` or `Arc`.
+struct Shared<'shape> {
+    id: ValueId<'shape>,
+    /// Where the value is written first.
+    path: Vec<PathSegment<'shape>>,
+    /// Where the value is written again.
+    aliases: Vec<Vec<PathSegment<'shape>>>,
+}
+
+/// Step from a collection to one of its items.
+enum PathSegment<'shape> {
+    Key(Cow<'shape, str>),
+    Index(usize),
+}
+
+/// Get the node at the end of a path, unless it has been removed.
+fn resolve<'yaml>(yaml: &'yaml Yaml, path: &[PathSegment<'_>]) -> Option<&'yaml Yaml> {
+    path.iter().try_fold(yaml, |item, segment| match segment {
+        PathSegment::Key(key) => item.as_hash()?.get(&Yaml::String(key.to_string())),
+        PathSegment::Index(index) => item.as_vec()?.get(*index),
+    })
+}
+
 /// Serialize any `Facet` type to a YAML string.
 #[cfg(feature = "alloc")]
 pub fn to_string<'a, T: facet_core::Facet<'a>>(value: &'a T) -> Result<String, YamlSerError> {
@@ -299,6 +427,68 @@ pub fn to_string<'a, T: facet_core::Facet<'a>>(value: &'a T) -> Result<String, Y
     Ok(serializer.into_string())
 }
 
+/// Serialize any `Facet` type to a YAML string, writing values shared through `Rc` or `Arc` once
+/// with an anchor and referring to them with aliases afterwards.
+#[cfg(feature = "alloc")]
+pub fn to_string_with_anchors<'a, T: facet_core::Facet<'a>>(
+    value: &'a T,
+) -> Result<String, YamlSerError> {
+    let mut serializer = YamlSerializer::new().with_anchors();
+    value.serialize(&mut serializer)?;
+
+    Ok(serializer.into_string())
+}
+
+/// Serialize any `Facet` type to a YAML string, with doc comments.
+///
+/// The doc comment of each field is written as `#` comments above its key, and the doc comment
+/// of a struct at the top of its mapping. This turns a default value into a sample configuration
+/// file that can't drift from the type.
+///
+/// ```
+/// use facet::Facet;
+///
+/// /// Settings of the server
+/// #[derive(Facet)]
+/// struct Config {
+///     /// Address to listen on
+///     address: String,
+///     /// Seconds before idle connections are closed
+///     timeout: u32,
+/// }
+///
+/// let config = Config {
+///     address: "localhost".to_string(),
+///     timeout: 30,
+/// };
+/// assert_eq!(
+///     facet_yaml::to_string_documented(&config).unwrap(),
+///     "---\n# Settings of the server\n# Address to listen on\naddress: localhost\n# Seconds before idle connections are closed\ntimeout: 30"
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_string_documented<'a, T: facet_core::Facet<'a>>(
+    value: &'a T,
+) -> Result<String, YamlSerError> {
+    let mut serializer = YamlSerializer::new();
+    value.serialize(&mut serializer)?;
+
+    Ok(serializer.write(Some(T::SHAPE)))
+}
+
+/// Serialize values of any `Facet` type to a YAML stream, one document per value.
+#[cfg(feature = "alloc")]
+pub fn to_string_multi<'a, T: facet_core::Facet<'a>>(
+    values: &'a [T],
+) -> Result<String, YamlSerError> {
+    let documents = values
+        .iter()
+        .map(to_string)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(documents.join("\n"))
+}
+
 /// Static type name for a YAML type.
 fn type_name(yaml: &Yaml) -> &'static str {
     match yaml {
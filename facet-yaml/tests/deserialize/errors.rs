@@ -0,0 +1,98 @@
+//! Tests for error kinds and locations.
+
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_yaml::YamlDeErrorKind;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Server {
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn test_invalid_yaml() {
+    let err = facet_yaml::from_str::<Server>("name: [db").unwrap_err();
+    assert!(matches!(err.kind, YamlDeErrorKind::Parse(_)));
+    assert!(err.marker().is_some());
+}
+
+#[test]
+fn test_duplicated_key() {
+    let err = facet_yaml::from_str::<Server>("name: db\nport: 80\nname: api").unwrap_err();
+    assert!(
+        matches!(&err.kind, YamlDeErrorKind::Parse(message) if message.contains("duplicated key")),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_type_mismatch() {
+    let err = facet_yaml::from_str::<Server>("name: db\nport: [1, 2]\n").unwrap_err();
+    assert!(matches!(
+        err.kind,
+        YamlDeErrorKind::TypeMismatch { expected, got: "array" } if expected.is_type::<u16>()
+    ));
+    let marker = err.marker().unwrap();
+    assert_eq!((marker.line(), marker.col()), (2, 6));
+}
+
+#[test]
+fn test_out_of_range() {
+    let err = facet_yaml::from_str::<Server>("name: db\nport: 70000\n").unwrap_err();
+    assert!(matches!(err.kind, YamlDeErrorKind::InvalidValue { .. }));
+    assert_eq!(err.marker().unwrap().line(), 2);
+}
+
+#[test]
+fn test_unknown_field() {
+    let err = facet_yaml::from_str::<Server>("name: db\nprot: 5432\n").unwrap_err();
+    assert!(matches!(
+        &err.kind,
+        YamlDeErrorKind::UnknownField { field_name, .. } if field_name == "prot"
+    ));
+    let marker = err.marker().unwrap();
+    assert_eq!((marker.line(), marker.col()), (2, 0));
+}
+
+#[test]
+fn test_missing_field() {
+    let err = facet_yaml::from_str::<Server>("name: db\n").unwrap_err();
+    assert!(matches!(
+        err.kind,
+        YamlDeErrorKind::MissingField {
+            field_name: "port",
+            ..
+        }
+    ));
+    assert_eq!(err.marker().unwrap().line(), 1);
+}
+
+#[test]
+fn test_nested_error_location() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Config {
+        servers: Vec<Server>,
+    }
+
+    let yaml = "servers:\n  - name: db\n    port: 5432\n  - name: cache\n    port: {}\n";
+    let err = facet_yaml::from_str::<Config>(yaml).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        YamlDeErrorKind::TypeMismatch {
+            got: "hash/map",
+            ..
+        }
+    ));
+    let marker = err.marker().unwrap();
+    assert_eq!((marker.line(), marker.col()), (5, 10));
+}
+
+#[test]
+fn test_unknown_result_variant() {
+    let err = facet_yaml::from_str::<Result<u16, String>>("Fine: 5432\n").unwrap_err();
+    assert!(matches!(
+        &err.kind,
+        YamlDeErrorKind::NoSuchVariant { name, .. } if name == "Fine"
+    ));
+}
@@ -74,3 +74,23 @@ fn test_deserialize_nested_lists() {
     let nested: Vec<Vec<u64>> = facet_yaml::from_str(yaml)?;
     assert_eq!(nested, vec![vec![1, 2], vec![3, 4]]);
 }
+
+#[test]
+fn test_deserialize_result_list() {
+    let yaml = r#"
+        - Ok: 1
+        - Err: false
+        - Ok: 2
+    "#;
+
+    let results: Vec<Result<u8, bool>> = facet_yaml::from_str(yaml)?;
+    assert_eq!(results, vec![Ok(1), Err(false), Ok(2)]);
+}
+
+#[test]
+fn test_deserialize_vec_deque() {
+    let yaml = r#"[1, 2, 3]"#;
+
+    let numbers: std::collections::VecDeque<u64> = facet_yaml::from_str(yaml)?;
+    assert_eq!(numbers, [1, 2, 3]);
+}
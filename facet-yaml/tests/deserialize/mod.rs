@@ -1,7 +1,9 @@
 mod datetime;
 mod default;
+mod errors;
 mod from_str;
 mod ip_test;
 mod lists;
 mod maps;
+mod multi;
 mod transparent;
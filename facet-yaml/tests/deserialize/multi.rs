@@ -0,0 +1,156 @@
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_yaml::YamlDeErrorKind;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Resource {
+    kind: String,
+    name: String,
+}
+
+#[test]
+fn test_from_str_multi() {
+    let yaml = r#"
+kind: Service
+name: web
+---
+kind: Deployment
+name: web
+"#;
+
+    let resources: Vec<Resource> = facet_yaml::from_str_multi(yaml)?;
+    assert_eq!(
+        resources,
+        vec![
+            Resource {
+                kind: "Service".to_string(),
+                name: "web".to_string(),
+            },
+            Resource {
+                kind: "Deployment".to_string(),
+                name: "web".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_from_str_rejects_multiple_documents() {
+    let yaml = "kind: Service\nname: web\n---\nkind: Deployment\nname: web\n";
+
+    let err = facet_yaml::from_str::<Resource>(yaml).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        YamlDeErrorKind::ExpectedSingleDocument(2)
+    ));
+    assert_eq!(err.marker().unwrap().line(), 4);
+}
+
+#[test]
+fn test_anchors_and_aliases() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Limits {
+        cpu: String,
+        memory: String,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Containers {
+        app: Limits,
+        sidecar: Limits,
+    }
+
+    let yaml = r#"
+app: &limits
+  cpu: 500m
+  memory: 1Gi
+sidecar: *limits
+"#;
+
+    let containers: Containers = facet_yaml::from_str(yaml)?;
+    assert_eq!(containers.app, containers.sidecar);
+    assert_eq!(containers.sidecar.memory, "1Gi");
+}
+
+#[test]
+fn test_nested_aliases_are_capped() {
+    // Each level aliases the previous one ten times, so the last one expands to 10^9 nodes
+    let mut yaml = String::from("l0: &l0 [lol]\n");
+    for level in 1..10 {
+        let previous = level - 1;
+        let aliases = vec![format!("*l{previous}"); 10].join(", ");
+        yaml.push_str(&format!("l{level}: &l{level} [{aliases}]\n"));
+    }
+
+    let err = facet_yaml::from_str::<Vec<String>>(&yaml).unwrap_err();
+    assert!(
+        matches!(&err.kind, YamlDeErrorKind::Parse(message) if message.contains("too many nodes")),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_merge_keys() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Labels {
+        app: String,
+        tier: String,
+        team: String,
+    }
+
+    let yaml = r#"
+defaults: &defaults
+  app: shop
+  tier: backend
+labels:
+  - <<: *defaults
+    team: payments
+  - <<: *defaults
+    tier: frontend
+    team: web
+"#;
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        defaults: std::collections::HashMap<String, String>,
+        labels: Vec<Labels>,
+    }
+
+    let root: Root = facet_yaml::from_str(yaml)?;
+    assert_eq!(
+        root.labels,
+        vec![
+            Labels {
+                app: "shop".to_string(),
+                tier: "backend".to_string(),
+                team: "payments".to_string(),
+            },
+            Labels {
+                app: "shop".to_string(),
+                tier: "frontend".to_string(),
+                team: "web".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_shared_pointers() {
+    use std::sync::Arc;
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        first: Arc<Resource>,
+        second: Arc<Resource>,
+    }
+
+    let yaml = r#"
+first: &web
+  kind: Service
+  name: web
+second: *web
+"#;
+
+    let root: Root = facet_yaml::from_str(yaml)?;
+    assert_eq!(root.first, root.second);
+}
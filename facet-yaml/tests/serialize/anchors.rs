@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use facet::Facet;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Limits {
+    cpu: String,
+    memory: u64,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Containers {
+    app: Arc<Limits>,
+    sidecar: Arc<Limits>,
+}
+
+#[test]
+fn test_serialize_multi() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let values = [
+        Limits {
+            cpu: "500m".to_string(),
+            memory: 1024,
+        },
+        Limits {
+            cpu: "1".to_string(),
+            memory: 2048,
+        },
+    ];
+
+    let yaml = facet_yaml::to_string_multi(&values)?;
+    assert_eq!(
+        yaml,
+        "---\ncpu: 500m\nmemory: 1024\n---\ncpu: \"1\"\nmemory: 2048"
+    );
+
+    let deserialized: Vec<Limits> = facet_yaml::from_str_multi(&yaml).unwrap();
+    assert_eq!(deserialized, values);
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_shared_without_anchors() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let limits = Arc::new(Limits {
+        cpu: "500m".to_string(),
+        memory: 1024,
+    });
+    let containers = Containers {
+        app: limits.clone(),
+        sidecar: limits,
+    };
+
+    let yaml = facet_yaml::to_string(&containers)?;
+    assert_eq!(
+        yaml,
+        "---\napp:\n  cpu: 500m\n  memory: 1024\nsidecar:\n  cpu: 500m\n  memory: 1024"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_shared_with_anchors() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let limits = Arc::new(Limits {
+        cpu: "500m".to_string(),
+        memory: 1024,
+    });
+    let containers = Containers {
+        app: limits.clone(),
+        sidecar: limits,
+    };
+
+    let yaml = facet_yaml::to_string_with_anchors(&containers)?;
+    assert_eq!(
+        yaml,
+        "---\napp: &a1\n  cpu: 500m\n  memory: 1024\nsidecar: *a1"
+    );
+
+    let deserialized: Containers = facet_yaml::from_str(&yaml).unwrap();
+    assert_eq!(deserialized, containers);
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_distinct_values_are_not_anchored() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let containers = Containers {
+        app: Arc::new(Limits {
+            cpu: "500m".to_string(),
+            memory: 1024,
+        }),
+        sidecar: Arc::new(Limits {
+            cpu: "500m".to_string(),
+            memory: 1024,
+        }),
+    };
+
+    let yaml = facet_yaml::to_string_with_anchors(&containers)?;
+    assert_eq!(
+        yaml,
+        "---\napp:\n  cpu: 500m\n  memory: 1024\nsidecar:\n  cpu: 500m\n  memory: 1024"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_shared_scalars_in_list() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        names: Vec<Arc<String>>,
+    }
+
+    let name = Arc::new("web".to_string());
+    let root = Root {
+        names: vec![name.clone(), Arc::new("db".to_string()), name],
+    };
+
+    let yaml = facet_yaml::to_string_with_anchors(&root)?;
+    assert_eq!(yaml, "---\nnames:\n  - &a1 web\n  - db\n  - *a1");
+
+    let deserialized: Root = facet_yaml::from_str(&yaml).unwrap();
+    assert_eq!(deserialized, root);
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use eyre::Result;
+use facet::Facet;
+
+/// Configuration of the service
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    /// Name shown in the logs
+    name: String,
+    /// Settings of the database
+    database: Database,
+    /// Background workers
+    workers: Vec<Worker>,
+    /// Labels, by name
+    labels: BTreeMap<String, String>,
+}
+
+/// Where data is stored
+#[derive(Debug, Facet, PartialEq)]
+struct Database {
+    /// Connection string.
+    ///
+    /// Credentials are read from the environment.
+    url: String,
+    pool_size: u32,
+}
+
+/// A background worker
+#[derive(Debug, Facet, PartialEq)]
+struct Worker {
+    /// What it works on
+    queue: String,
+}
+
+#[test]
+fn test_serialize_documented() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let config = Config {
+        name: "demo".to_string(),
+        database: Database {
+            url: "postgres://localhost".to_string(),
+            pool_size: 4,
+        },
+        workers: vec![
+            Worker {
+                queue: "mail".to_string(),
+            },
+            Worker {
+                queue: "billing".to_string(),
+            },
+        ],
+        labels: [("team".to_string(), "core".to_string())]
+            .into_iter()
+            .collect(),
+    };
+
+    let yaml = facet_yaml::to_string_documented(&config)?;
+    assert_eq!(
+        yaml,
+        r#"---
+# Configuration of the service
+# Name shown in the logs
+name: demo
+# Settings of the database
+database:
+  # Where data is stored
+  # Connection string.
+  #
+  # Credentials are read from the environment.
+  url: "postgres://localhost"
+  pool_size: 4
+# Background workers
+workers:
+  - # A background worker
+    # What it works on
+    queue: mail
+  - # A background worker
+    # What it works on
+    queue: billing
+# Labels, by name
+labels:
+  team: core"#
+    );
+
+    let deserialized: Config = facet_yaml::from_str(&yaml).unwrap();
+    assert_eq!(deserialized, config);
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_undocumented_type() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Limits {
+        cpu: String,
+        memory: u64,
+    }
+
+    let limits = Limits {
+        cpu: "500m".to_string(),
+        memory: 1024,
+    };
+    assert_eq!(
+        facet_yaml::to_string_documented(&limits)?,
+        facet_yaml::to_string(&limits)?
+    );
+
+    Ok(())
+}
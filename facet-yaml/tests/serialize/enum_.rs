@@ -0,0 +1,137 @@
+use facet::Facet;
+use facet_testhelpers::test;
+
+use crate::assert_serialize;
+
+#[test]
+fn test_externally_tagged_enum() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        empty: Shape,
+        circle: Shape,
+        rect: Shape,
+    }
+
+    assert_serialize!(
+        Root,
+        Root {
+            empty: Shape::Empty,
+            circle: Shape::Circle(2),
+            rect: Shape::Rect {
+                width: 1,
+                height: 2
+            },
+        },
+    );
+}
+
+#[test]
+fn test_externally_tagged_enum_output() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Pair(u32, u32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        a: Shape,
+        b: Shape,
+    }
+
+    assert_eq!(
+        facet_yaml::to_string(&Root {
+            a: Shape::Circle(2),
+            b: Shape::Rect {
+                width: 1,
+                height: 2
+            },
+        })?,
+        "---\na:\n  Circle: 2\nb:\n  Rect:\n    width: 1\n    height: 2"
+    );
+    assert_eq!(
+        facet_yaml::to_string(&Root {
+            a: Shape::Empty,
+            b: Shape::Pair(1, 2),
+        })?,
+        "---\na: Empty\nb:\n  Pair:\n    - 1\n    - 2"
+    );
+}
+
+#[test]
+fn test_internally_tagged_enum() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(tag = "type")]
+    #[repr(u8)]
+    enum Shape {
+        Empty,
+        Rect { width: u32, height: u32 },
+    }
+
+    assert_serialize!(Shape, Shape::Empty);
+    assert_serialize!(
+        Shape,
+        Shape::Rect {
+            width: 1,
+            height: 2
+        },
+    );
+
+    assert_eq!(
+        facet_yaml::from_str::<Shape>("width: 3\nheight: 4\ntype: Rect")?,
+        Shape::Rect {
+            width: 3,
+            height: 4
+        },
+    );
+}
+
+#[test]
+fn test_adjacently_tagged_enum() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(tag = "t", content = "c")]
+    #[repr(u8)]
+    enum Message {
+        Ping,
+        Text(String),
+        Move(u32, u32),
+    }
+
+    assert_serialize!(Message, Message::Ping);
+    assert_serialize!(Message, Message::Text("hi".to_string()));
+    assert_serialize!(Message, Message::Move(1, 2));
+}
+
+#[test]
+fn test_untagged_enum() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    enum Id {
+        Number(u64),
+        Name(String),
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        ids: Vec<Id>,
+    }
+
+    assert_serialize!(
+        Root,
+        Root {
+            ids: vec![Id::Number(1), Id::Name("two".to_string())],
+        },
+    );
+}
@@ -1,6 +1,10 @@
+mod anchors;
 mod basic;
+mod docs;
+mod enum_;
 mod list;
 mod map;
+mod result;
 mod scalar;
 mod struct_;
 
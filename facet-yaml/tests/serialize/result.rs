@@ -0,0 +1,31 @@
+//! Tests for YAML values to results.
+
+use eyre::Result;
+use facet::Facet;
+
+use crate::assert_serialize;
+
+#[test]
+fn test_result() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: core::result::Result<i32, String>,
+    }
+
+    assert_serialize!(Root, Root { value: Ok(1) });
+    assert_serialize!(
+        Root,
+        Root {
+            value: Err("out of range".to_string())
+        }
+    );
+
+    assert_eq!(
+        facet_yaml::to_string(&Root { value: Ok(1) })?,
+        "---\nvalue:\n  Ok: 1"
+    );
+
+    Ok(())
+}
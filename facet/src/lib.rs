@@ -51,6 +51,12 @@ pub use facet_core::*;
 ///
 /// * `invariants = ".."` Called when doing `Partial::build`. **TODO**
 ///
+/// * `tag = ".."` On enums: store the variant name under this key, next to the variant's fields (internally tagged). Variants must be unit, struct, or newtype variants wrapping a struct.
+///
+/// * `tag = "..", content = ".."` On enums: store the variant name under the `tag` key and the variant's data under the `content` key (adjacently tagged).
+///
+/// * `untagged` On enums: serialize only the variant's data, and try each variant in turn when deserializing.
+///
 /// # Field Attributes
 ///
 /// ```rust
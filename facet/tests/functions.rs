@@ -1,5 +1,5 @@
 #[cfg(feature = "function")]
-use facet::{facet_fn, fn_shape};
+use facet::{Facet, facet_fn, fn_shape};
 
 #[cfg(feature = "function")]
 #[test]
@@ -319,3 +319,86 @@ fn function_without_doc_comments() {
     // Test no documentation is captured
     assert!(shape.documentation.is_empty());
 }
+
+#[cfg(feature = "function")]
+#[test]
+fn function_shape_types() {
+    #[facet_fn]
+    fn repeat(text: String, times: usize) -> Vec<String> {
+        vec![text; times]
+    }
+
+    let shape = fn_shape!(repeat);
+    assert_eq!(shape.param_shapes.len(), 2);
+    assert_eq!(shape.param_shapes[0], String::SHAPE);
+    assert_eq!(shape.param_shapes[1], usize::SHAPE);
+    assert_eq!(shape.args_shape(), <(String, usize)>::SHAPE);
+    assert_eq!(shape.return_shape(), <Vec<String>>::SHAPE);
+}
+
+#[cfg(feature = "function")]
+#[test]
+fn function_shape_types_without_parameters_or_return_type() {
+    #[facet_fn]
+    fn nothing() {}
+
+    let shape = fn_shape!(nothing);
+    assert!(shape.param_shapes.is_empty());
+    assert_eq!(shape.args_shape(), <()>::SHAPE);
+    assert_eq!(shape.return_shape(), <()>::SHAPE);
+}
+
+#[cfg(feature = "function")]
+#[test]
+fn generic_function_shape_types() {
+    #[facet_fn]
+    fn first<T>(items: Vec<T>) -> Option<T> {
+        items.into_iter().next()
+    }
+
+    let shape = fn_shape!(first<u8>);
+    assert_eq!(shape.param_shapes, &[<Vec<u8>>::SHAPE]);
+    assert_eq!(shape.return_shape(), <Option<u8>>::SHAPE);
+}
+
+#[cfg(feature = "function")]
+#[test]
+fn invoke_function() {
+    use facet_reflect::{Partial, invoke};
+
+    #[facet_fn]
+    fn greet(greeting: &'static str, name: String) -> String {
+        format!("{greeting}, {name}!")
+    }
+
+    let shape = fn_shape!(greet);
+    let mut args = Partial::alloc_shape(shape.args_shape()).unwrap();
+    args.set_nth_field(0, "Hello")
+        .unwrap()
+        .set_nth_field(1, "World".to_string())
+        .unwrap();
+
+    let result = invoke(&shape, args.build().unwrap()).unwrap();
+    assert_eq!(result.materialize::<String>().unwrap(), "Hello, World!");
+}
+
+#[cfg(feature = "function")]
+#[test]
+fn invoke_function_with_wrong_arguments() {
+    use facet_reflect::{Partial, ReflectError, invoke};
+
+    #[facet_fn]
+    fn double(x: i32) -> i32 {
+        x * 2
+    }
+
+    let shape = fn_shape!(double);
+    let mut args = Partial::alloc::<(i64,)>().unwrap();
+    args.set_nth_field(0, 1i64).unwrap();
+    let args = args.inner_mut().build().unwrap();
+
+    assert!(matches!(
+        invoke(&shape, args),
+        Err(ReflectError::WrongShape { .. })
+    ));
+}
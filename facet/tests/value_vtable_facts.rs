@@ -1236,6 +1236,68 @@ fn test_vecs() {
     );
 }
 
+#[test]
+fn test_vec_deques() {
+    use std::collections::VecDeque;
+
+    // VecDeque<i32> implements Debug, PartialEq, but not Ord
+    check_facts(
+        &VecDeque::from([1, 2, 3]),
+        &VecDeque::from([1, 2, 3]),
+        FactBuilder::new()
+            .debug()
+            .partial_eq_and(true)
+            .default()
+            .clone()
+            .build(),
+        TypedMarkerTraits::new()
+            .eq()
+            .send()
+            .sync()
+            .unpin()
+            .unwind_safe()
+            .ref_unwind_safe(),
+    );
+}
+
+#[test]
+fn test_results() {
+    // Result<i32, String> implements Debug, PartialEq, but not Default
+    check_facts(
+        &Ok::<i32, String>(1),
+        &Err::<i32, String>("error".to_string()),
+        FactBuilder::new()
+            .debug()
+            .partial_eq_and(false)
+            .clone()
+            .build(),
+        TypedMarkerTraits::new()
+            .eq()
+            .send()
+            .sync()
+            .unpin()
+            .unwind_safe()
+            .ref_unwind_safe(),
+    );
+
+    check_facts(
+        &Ok::<i32, String>(1),
+        &Ok::<i32, String>(1),
+        FactBuilder::new()
+            .debug()
+            .partial_eq_and(true)
+            .clone()
+            .build(),
+        TypedMarkerTraits::new()
+            .eq()
+            .send()
+            .sync()
+            .unpin()
+            .unwind_safe()
+            .ref_unwind_safe(),
+    );
+}
+
 #[test]
 fn test_hashmaps() {
     use std::collections::HashMap;